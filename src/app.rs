@@ -0,0 +1,535 @@
+//! The `graphql-codegen-rust` commands.
+//!
+//! [`run`] executes a parsed [`Cli`], creating generators through the given
+//! [`GeneratorRegistry`]. A binary that registers its own generators can call it
+//! to get the full command-line tool, with `--generator` able to pick them.
+
+use crate::cli::{Cli, Commands};
+use crate::config::Config;
+use crate::generator::{self, GeneratorRegistry};
+use crate::logger::Logger;
+use crate::parser::{self, GraphQLParser};
+use crate::{validation, watch};
+
+use fs_err as fs;
+
+/// Runs a parsed command line, creating generators from `registry`
+///
+/// A failure is logged as an error, so it follows `--log-format` and reaches
+/// `--log-file`, before being returned.
+pub async fn run(cli: Cli, registry: &GeneratorRegistry) -> anyhow::Result<()> {
+    let logger = Logger::new(cli.verbose, cli.quiet, cli.log_format);
+    let logger = match &cli.log_file {
+        Some(log_file) => match logger.with_file(log_file) {
+            Ok(logger) => logger,
+            Err(error) => {
+                let error = anyhow::Error::from(error);
+                Logger::new(cli.verbose, cli.quiet, cli.log_format).error(&format!("{:#}", error));
+                return Err(error);
+            }
+        },
+        None => logger,
+    };
+
+    let result = run_command(cli.command, &logger, registry).await;
+    if let Err(error) = &result {
+        logger.error(&format!("{:#}", error));
+    }
+    result
+}
+
+/// Runs one command, logging its progress to `logger`
+async fn run_command(
+    command: Option<Commands>,
+    logger: &Logger,
+    registry: &GeneratorRegistry,
+) -> anyhow::Result<()> {
+    match command {
+        Some(Commands::Init {
+            url,
+            orm,
+            db,
+            output,
+            headers,
+            auth,
+            ca_cert,
+        }) => {
+            logger.info("Initializing GraphQL codegen...");
+            logger.debug(&format!("URL: {}", url));
+            logger.debug(&format!("ORM: {:?}", orm));
+            logger.debug(&format!("Database: {:?}", db));
+            logger.debug(&format!("Output directory: {:?}", output));
+
+            // Create output directory
+            logger.trace("Creating output directory...");
+            fs::create_dir_all(&output)?;
+
+            // Create config
+            let config = Config::from(&Commands::Init {
+                url,
+                orm,
+                db,
+                output,
+                headers,
+                auth: auth.clone(),
+                ca_cert,
+            });
+
+            // The config only refers to --bearer/--basic credentials, and an
+            // --env-token is never saved; this request uses the real values
+            let mut request_headers = config.headers.clone();
+            auth.apply(&mut request_headers)?;
+
+            // Fetch and parse schema
+            logger.info("Fetching GraphQL schema via introspection...");
+            let parser = GraphQLParser::for_config(&config)?;
+            let (schema, warnings) = parser
+                .parse_from_introspection(&config.url, &request_headers)
+                .await?;
+            for warning in warnings {
+                logger.warning(&warning);
+            }
+
+            // Save config
+            let config_path = Config::config_path(&config.output_dir);
+            logger.trace(&format!("Saving config to: {:?}", config_path));
+            config.save_to_file(&config_path)?;
+
+            // Generate code
+            logger.info("Generating Rust code...");
+            let generator = registry.create(config.orm.name())?;
+            generate_all_code(&schema, &config, &*generator, logger).await?;
+
+            logger.success("Initialization complete!");
+            logger.info(&format!("Config saved to: {:?}", config_path));
+            if let Some(var) = auth.config_env_var() {
+                logger.info(&format!(
+                    "The config reads the Authorization credentials from ${{{}}}; set it before running generate",
+                    var
+                ));
+            }
+        }
+        Some(Commands::Generate {
+            config,
+            output,
+            auth,
+            incremental,
+            only_changed_tables,
+            types,
+            exclude_types,
+            dump_introspection,
+            introspection_file,
+            ca_cert,
+            introspection_depth,
+            introspection_method,
+            continue_on_error,
+            check,
+            schema_only,
+            force,
+            generator,
+        }) => {
+            logger.info("Generating code...");
+
+            let mut config = load_config(config, logger)?;
+
+            // Override output if specified
+            if let Some(output_dir) = output {
+                logger.debug(&format!("Overriding output directory: {:?}", output_dir));
+                config.output_dir = output_dir;
+            }
+
+            auth.apply(&mut config.headers)?;
+
+            if incremental {
+                logger.debug("Incremental mode enabled");
+                config.incremental = true;
+            }
+
+            if only_changed_tables {
+                logger.debug("Only generating migrations for changed tables");
+                config.only_changed_tables = true;
+            }
+
+            if schema_only {
+                logger.debug("Only generating the schema file");
+                config.schema_only = true;
+            }
+
+            if force {
+                logger.debug("Generating over files not generated by this tool");
+                config.force = true;
+            }
+
+            if continue_on_error {
+                logger.debug("Skipping types that fail to generate");
+                config.continue_on_error = true;
+            }
+
+            // Override type filters if specified
+            if !types.is_empty() {
+                logger.debug(&format!("Only generating types: {}", types.join(", ")));
+                config.include_types = types;
+            }
+            if !exclude_types.is_empty() {
+                logger.debug(&format!("Excluding types: {}", exclude_types.join(", ")));
+                config.exclude_types = exclude_types;
+            }
+            if dump_introspection.is_some() {
+                config.dump_introspection = dump_introspection;
+            }
+            if introspection_file.is_some() {
+                config.introspection_file = introspection_file;
+            }
+            if ca_cert.is_some() {
+                config.ca_cert = ca_cert;
+            }
+            if let Some(depth) = introspection_depth {
+                config.introspection.depth = depth;
+            }
+            if let Some(method) = introspection_method {
+                config.introspection.method = method;
+            }
+
+            let schema = load_schema(&config, logger, true).await?;
+
+            let generator = match generator {
+                Some(name) => {
+                    logger.debug(&format!("Using generator: {}", name));
+                    registry.create(&name)?
+                }
+                None => registry.create(config.orm.name())?,
+            };
+            if check {
+                logger.info("Checking generated code is up to date...");
+                let changed = crate::check_all_code(&schema, &config, &*generator).await?;
+                if !changed.is_empty() {
+                    for path in &changed {
+                        logger.error(&format!("Out of date: {}", path.display()));
+                    }
+                    return Err(anyhow::anyhow!(
+                        "{} generated file{} out of date in {:?}",
+                        changed.len(),
+                        if changed.len() == 1 { " is" } else { "s are" },
+                        config.output_dir
+                    ));
+                }
+                logger.success("Generated code is up to date");
+                return Ok(());
+            }
+
+            // Generate code
+            logger.info("Generating Rust code...");
+            generate_all_code(&schema, &config, &*generator, logger).await?;
+
+            logger.success("Code generation complete!");
+        }
+        Some(Commands::ValidateSchema { config }) => {
+            logger.info("Validating schema...");
+
+            let config = load_config(config, logger)?;
+
+            let schema = load_schema(&config, logger, true).await?;
+
+            let diagnostics = validation::validate_schema(&schema, &config);
+            let error_count = report_diagnostics(logger, &diagnostics);
+            if error_count > 0 {
+                return Err(anyhow::anyhow!(
+                    "Schema validation found {} error{}",
+                    error_count,
+                    if error_count == 1 { "" } else { "s" }
+                ));
+            }
+
+            if diagnostics.is_empty() {
+                logger.success("Schema can be generated without loss");
+            } else {
+                logger.success(&format!(
+                    "Schema validation complete with {} finding{}",
+                    diagnostics.len(),
+                    if diagnostics.len() == 1 { "" } else { "s" }
+                ));
+            }
+        }
+        Some(Commands::Validate { config }) => {
+            logger.info("Validating config and schema...");
+
+            let config = load_config(config, logger)?;
+
+            // Nothing is written, not even a dump
+            let schema = load_schema(&config, logger, false).await?;
+
+            // Narrow to the types that would be generated, which also checks the
+            // filters; table name collisions are reported with the diagnostics
+            let schema = generator::narrow_schema(&schema, &config)?;
+            let relationships = generator::detect_relationships(&schema);
+            logger.info(&format!(
+                "{} types, {} enums, {} scalars, {} relationships",
+                schema.types.len(),
+                schema.enums.len(),
+                schema.scalars.len(),
+                relationships.values().map(Vec::len).sum::<usize>()
+            ));
+
+            for scalar in validation::unmapped_scalars(&schema, &config) {
+                logger.warning(&format!(
+                    "Scalar '{}' has no type mapping and falls back to String",
+                    scalar
+                ));
+            }
+
+            // Generated code referencing these fields may break when they're removed
+            for line in validation::deprecation_report(&schema) {
+                logger.warning(&line);
+            }
+
+            let diagnostics = validation::validate_schema(&schema, &config);
+            let error_count = report_diagnostics(logger, &diagnostics);
+            if error_count > 0 {
+                return Err(anyhow::anyhow!(
+                    "Validation found {} error{}",
+                    error_count,
+                    if error_count == 1 { "" } else { "s" }
+                ));
+            }
+
+            logger.success("Config and schema are valid");
+        }
+        Some(Commands::Diff { old, new }) => {
+            let parser = GraphQLParser::new();
+            logger.debug(&format!("Comparing {:?} with {:?}", old, new));
+            let (old_schema, old_warnings) = parser.parse_from_schema_file(&old)?;
+            let (new_schema, new_warnings) = parser.parse_from_schema_file(&new)?;
+            for warning in old_warnings.iter().chain(&new_warnings) {
+                logger.warning(warning);
+            }
+            let changes = parser::diff::diff_schemas(&old_schema, &new_schema);
+            if changes.is_empty() {
+                logger.success("The schemas are identical");
+                return Ok(());
+            }
+
+            for change in &changes {
+                if change.breaking {
+                    logger.warning(&change.to_string());
+                } else {
+                    logger.note(&change.to_string());
+                }
+            }
+
+            let breaking = changes.iter().filter(|c| c.breaking).count();
+            if breaking > 0 {
+                return Err(anyhow::anyhow!(
+                    "Found {} breaking change{} out of {}",
+                    breaking,
+                    if breaking == 1 { "" } else { "s" },
+                    changes.len()
+                ));
+            }
+            logger.success(&format!(
+                "Found {} change{}, none breaking",
+                changes.len(),
+                if changes.len() == 1 { "" } else { "s" }
+            ));
+        }
+        Some(Commands::Watch { config, interval }) => {
+            let config = load_config(config, logger)?;
+            if interval == 0 {
+                return Err(anyhow::anyhow!("--interval must be at least 1 second"));
+            }
+
+            let generator = registry.create(config.orm.name())?;
+            let mut watcher = watch::SchemaWatcher::new();
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval));
+            // A slow poll delays the next one instead of bunching them up
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            logger.success(&format!(
+                "Watching the schema every {}s, press Ctrl-C to stop",
+                interval
+            ));
+
+            loop {
+                ticker.tick().await;
+                logger.trace("Polling the schema...");
+                let schema = match watcher.observe(load_schema(&config, logger, true).await) {
+                    watch::WatchEvent::Initial(schema) => {
+                        logger.info("Generating Rust code...");
+                        schema
+                    }
+                    watch::WatchEvent::Changed(schema) => {
+                        logger.info("Schema changed, regenerating Rust code...");
+                        schema
+                    }
+                    watch::WatchEvent::Unchanged => {
+                        logger.trace("Schema unchanged");
+                        continue;
+                    }
+                    watch::WatchEvent::Failed(e) => {
+                        // The server may be restarting; keep polling
+                        logger.warning(&format!("Failed to fetch the schema: {}", e));
+                        continue;
+                    }
+                };
+
+                match generate_all_code(&schema, &config, &*generator, logger).await {
+                    Ok(()) => logger.success(&format!(
+                        "Regenerated code at {}",
+                        chrono::Local::now().format("%H:%M:%S")
+                    )),
+                    Err(e) => logger.error(&format!("Code generation failed: {}", e)),
+                }
+            }
+        }
+        None => {
+            // Default behavior: generate from auto-detected config
+            logger.info("Generating code from auto-detected config...");
+
+            let config = load_config(None, logger)?;
+
+            let schema = load_schema(&config, logger, true).await?;
+
+            // Generate code
+            logger.info("Generating Rust code...");
+            let generator = registry.create(config.orm.name())?;
+            generate_all_code(&schema, &config, &*generator, logger).await?;
+
+            logger.success("Code generation complete!");
+        }
+    }
+
+    Ok(())
+}
+
+/// Load the config file at `path`, or the auto-detected one when not given
+fn load_config(path: Option<std::path::PathBuf>, logger: &Logger) -> anyhow::Result<Config> {
+    let config_path = if let Some(path) = path {
+        logger.debug(&format!("Using specified config: {:?}", path));
+        path
+    } else {
+        logger.trace("Auto-detecting config file...");
+        Config::auto_detect_config()?
+    };
+
+    logger.debug(&format!("Loading config from: {:?}", config_path));
+    Config::from_file(&config_path)
+}
+
+/// Read the schema from the configured introspection file, or fetch it from
+/// the url, writing the response to `dump_introspection` when `dump` is set
+async fn load_schema(
+    config: &Config,
+    logger: &Logger,
+    dump: bool,
+) -> anyhow::Result<parser::ParsedSchema> {
+    let parser = GraphQLParser::for_config(config)?;
+    if let Some(path) = &config.introspection_file {
+        logger.info(&format!("Reading GraphQL schema from {:?}...", path));
+        let (schema, warnings) = parser.parse_from_introspection_file(path)?;
+        for warning in warnings {
+            logger.warning(&warning);
+        }
+        return Ok(schema);
+    }
+    if config.url.trim().is_empty() {
+        return Err(anyhow::anyhow!(
+            "No schema source configured\n\nSet `url` to a GraphQL endpoint, or `introspection_file` to a saved introspection response."
+        ));
+    }
+
+    logger.info("Fetching GraphQL schema via introspection...");
+    let dump_path = config.dump_introspection.as_deref().filter(|_| dump);
+    let (schema, warnings) = parser
+        .parse_from_introspection_with_dump(&config.url, &config.headers, dump_path)
+        .await?;
+    for warning in warnings {
+        logger.warning(&warning);
+    }
+    if let Some(path) = dump_path {
+        logger.debug(&format!("Saved introspection response to: {:?}", path));
+    }
+    Ok(schema)
+}
+
+/// Print validation diagnostics and return how many are errors
+fn report_diagnostics(logger: &Logger, diagnostics: &[validation::Diagnostic]) -> usize {
+    for diagnostic in diagnostics {
+        match diagnostic.severity {
+            validation::Severity::Error => logger.error(&diagnostic.to_string()),
+            validation::Severity::Warning => logger.warning(&diagnostic.to_string()),
+            validation::Severity::Info => logger.note(&diagnostic.to_string()),
+        }
+    }
+
+    diagnostics
+        .iter()
+        .filter(|d| d.severity == validation::Severity::Error)
+        .count()
+}
+
+/// Run the library's generation pipeline and log what it did
+async fn generate_all_code(
+    schema: &parser::ParsedSchema,
+    config: &Config,
+    generator: &dyn generator::CodeGenerator,
+    logger: &Logger,
+) -> anyhow::Result<()> {
+    let summary = crate::generate_all_code(schema, config, generator).await?;
+
+    for path in &summary.foreign_files {
+        logger.warning(&format!(
+            "{} was not generated by graphql-codegen-rust",
+            path.display()
+        ));
+    }
+
+    for (type_name, error) in &summary.skipped_types {
+        logger.warning(&format!("Skipped type '{}': {}", type_name, error));
+    }
+    for scalar in &summary.fallback_scalars {
+        logger.warning(&format!(
+            "Scalar '{}' has no type mapping and falls back to String",
+            scalar
+        ));
+    }
+    let entity_count = summary.entity_files.len();
+    for (index, path) in summary.entity_files.iter().enumerate() {
+        let filename = path.file_name().unwrap_or_default().to_string_lossy();
+        if summary.unchanged_files.contains(path) {
+            logger.trace(&format!("Skipping unchanged entity: {}", filename));
+        } else {
+            logger.debug(&format!(
+                "Generating entity {}/{}: {}",
+                index + 1,
+                entity_count,
+                filename
+            ));
+        }
+    }
+    if config.incremental {
+        logger.info(&format!(
+            "Generated {} entity files ({} unchanged)",
+            entity_count - summary.unchanged_files.len(),
+            summary.unchanged_files.len()
+        ));
+    } else if entity_count > 0 {
+        logger.info(&format!("Generated {} entity files", entity_count));
+    }
+    for path in &summary.written_files {
+        if !summary.entity_files.contains(path) {
+            logger.info(&format!("Generated {}", path.display()));
+        }
+    }
+    for path in &summary.removed_files {
+        logger.info(&format!("Removed stale {}", path.display()));
+    }
+
+    let mut required_crates: Vec<_> = summary.required_crates.into_iter().collect();
+    if !required_crates.is_empty() {
+        required_crates.sort();
+        logger.info(&format!(
+            "Add these dependencies: {}",
+            required_crates.join(", ")
+        ));
+    }
+
+    Ok(())
+}
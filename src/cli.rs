@@ -1,6 +1,11 @@
-use clap::{Parser, Subcommand};
+use base64::Engine;
+use clap::{Args, Parser, Subcommand};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
+use crate::config::IntrospectionMethod;
+use crate::logger::LogFormat;
+
 #[derive(Parser)]
 #[command(name = "graphql-codegen-rust")]
 #[command(version = env!("CARGO_PKG_VERSION"))]
@@ -12,6 +17,18 @@ pub struct Cli {
     /// Increase verbosity level (-v, -vv, -vvv)
     #[arg(short, long, action = clap::ArgAction::Count)]
     pub verbose: u8,
+
+    /// Only print errors
+    #[arg(short, long, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Format of log messages
+    #[arg(long, value_enum, default_value = "text")]
+    pub log_format: LogFormat,
+
+    /// Also write log messages to this file
+    #[arg(long)]
+    pub log_file: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -37,6 +54,13 @@ pub enum Commands {
         /// Additional headers for GraphQL requests (key:value pairs)
         #[arg(short = 'H', long, value_parser = parse_header)]
         headers: Vec<(String, String)>,
+
+        #[command(flatten)]
+        auth: AuthArgs,
+
+        /// Extra PEM root certificate to trust for introspection (saved to the config)
+        #[arg(long, value_name = "PATH")]
+        ca_cert: Option<PathBuf>,
     },
 
     /// Generate code from existing configuration
@@ -48,6 +72,103 @@ pub enum Commands {
         /// Output directory (overrides config)
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        #[command(flatten)]
+        auth: AuthArgs,
+
+        /// Only rewrite entity files whose types changed since the last run
+        #[arg(long, alias = "since")]
+        incremental: bool,
+
+        /// Only emit migrations for tables changed since the last run
+        #[arg(long)]
+        only_changed_tables: bool,
+
+        /// Only generate these types (repeatable or comma-separated, overrides config)
+        #[arg(long, value_name = "TYPE", value_delimiter = ',')]
+        types: Vec<String>,
+
+        /// Skip these types (repeatable or comma-separated, overrides config)
+        #[arg(long, value_name = "TYPE", value_delimiter = ',')]
+        exclude_types: Vec<String>,
+
+        /// Write the raw introspection response JSON to this file before parsing
+        #[arg(long, value_name = "PATH")]
+        dump_introspection: Option<PathBuf>,
+
+        /// Read the schema from this introspection JSON file instead of the url (overrides config)
+        #[arg(long, value_name = "PATH")]
+        introspection_file: Option<PathBuf>,
+
+        /// Extra PEM root certificate to trust for introspection (overrides config)
+        #[arg(long, value_name = "PATH")]
+        ca_cert: Option<PathBuf>,
+
+        /// Levels of `ofType` unrolled by the introspection query (overrides config)
+        #[arg(long, value_name = "N")]
+        introspection_depth: Option<usize>,
+
+        /// HTTP method of the introspection request, for gateways that only allow GET (overrides config)
+        #[arg(long, value_name = "METHOD")]
+        introspection_method: Option<IntrospectionMethod>,
+
+        /// Skip types whose generation fails instead of aborting
+        #[arg(long)]
+        continue_on_error: bool,
+
+        /// Fail if the generated code differs from the output directory, without writing it
+        #[arg(long)]
+        check: bool,
+
+        /// Only generate the schema file, skipping entities, client and migrations
+        #[arg(long)]
+        schema_only: bool,
+
+        /// Generate even if the output directory has files not generated by this tool,
+        /// removing stale generated files
+        #[arg(long)]
+        force: bool,
+
+        /// Registered generator to use instead of the config's ORM, e.g. `plain`
+        #[arg(long, value_name = "NAME")]
+        generator: Option<String>,
+    },
+
+    /// Check the schema for constructs the generators can't faithfully represent
+    ValidateSchema {
+        /// Config file path (auto-detects codegen.yml or TOML)
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+    },
+
+    /// Check the config and schema and report problems without writing any files
+    Validate {
+        /// Config file path (auto-detects codegen.yml or TOML)
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+    },
+
+    /// Compare two schemas and report added, removed and changed types, fields and enums
+    ///
+    /// Exits with an error when a change is breaking, such as a removed field or a
+    /// field whose type changed.
+    Diff {
+        /// The schema before the change, as SDL or an introspection JSON file
+        old: PathBuf,
+
+        /// The schema after the change, as SDL or an introspection JSON file
+        new: PathBuf,
+    },
+
+    /// Poll the schema and regenerate code whenever it changes
+    Watch {
+        /// Config file path (auto-detects codegen.yml or TOML)
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Seconds between schema polls
+        #[arg(short, long, value_name = "SECONDS", default_value_t = 2)]
+        interval: u64,
     },
 }
 
@@ -56,6 +177,7 @@ pub enum Commands {
 /// Each ORM generates different code structures optimized for their respective ecosystems:
 /// - **Diesel**: Mature, compile-time SQL safety, macro-heavy approach
 /// - **Sea-ORM**: Async-first, runtime SQL building, entity relationships
+/// - **Plain**: Serde structs and migration SQL only, for use without an ORM
 #[derive(
     Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, clap::ValueEnum, Default,
 )]
@@ -68,6 +190,21 @@ pub enum OrmType {
     /// Generates Sea-ORM Entity models, ActiveModel structs, and migration files.
     /// Best for async applications with complex relationships and runtime flexibility.
     SeaOrm,
+
+    /// Generates plain serde structs and raw SQL migrations, with no ORM dependency.
+    /// Best for rusqlite, sqlx or hand-written queries.
+    Plain,
+}
+
+impl OrmType {
+    /// Name of the ORM as accepted by `--orm` and the generator registry
+    pub fn name(&self) -> &'static str {
+        match self {
+            OrmType::Diesel => "diesel",
+            OrmType::SeaOrm => "sea-orm",
+            OrmType::Plain => "plain",
+        }
+    }
 }
 
 /// Supported database backends.
@@ -94,6 +231,84 @@ pub enum DatabaseType {
     Mysql,
 }
 
+/// Shortcuts for the `Authorization` header; at most one may be given.
+#[derive(Args, Debug, Clone, Default, PartialEq, Eq)]
+#[group(multiple = false)]
+pub struct AuthArgs {
+    /// Bearer token sent as `Authorization: Bearer <TOKEN>`
+    #[arg(long, value_name = "TOKEN")]
+    pub bearer: Option<String>,
+
+    /// Credentials sent as HTTP basic auth
+    #[arg(long, value_name = "USER:PASS")]
+    pub basic: Option<String>,
+
+    /// Environment variable holding a bearer token, read at generation time
+    /// and never written to the config file
+    #[arg(long, value_name = "VARNAME")]
+    pub env_token: Option<String>,
+}
+
+impl AuthArgs {
+    /// Environment variable the config written by `init` reads the `--bearer`
+    /// token or the base64 `--basic` credentials from, if either was given
+    pub fn config_env_var(&self) -> Option<&'static str> {
+        if self.bearer.is_some() {
+            Some("API_TOKEN")
+        } else if self.basic.is_some() {
+            Some("API_BASIC_AUTH")
+        } else {
+            None
+        }
+    }
+
+    /// `Authorization` value to save in the config for `--bearer` or `--basic`.
+    ///
+    /// Refers to [`config_env_var`](Self::config_env_var) instead of holding the
+    /// secret, since the config file is usually committed.
+    pub fn config_header(&self) -> Option<String> {
+        let var = self.config_env_var()?;
+        let scheme = if self.bearer.is_some() {
+            "Bearer"
+        } else {
+            "Basic"
+        };
+        Some(format!("{} ${{{}}}", scheme, var))
+    }
+
+    /// `Authorization` value from `--bearer` or `--basic`
+    pub fn credentials_header(&self) -> Option<String> {
+        if let Some(token) = &self.bearer {
+            return Some(format!("Bearer {}", token));
+        }
+        self.basic.as_ref().map(|credentials| {
+            format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(credentials)
+            )
+        })
+    }
+
+    /// Set the `Authorization` header from whichever flag was given
+    pub fn apply(&self, headers: &mut HashMap<String, String>) -> anyhow::Result<()> {
+        let value = match &self.env_token {
+            Some(var) => {
+                let token = std::env::var(var).map_err(|_| {
+                    anyhow::anyhow!("Environment variable '{}' for --env-token is not set", var)
+                })?;
+                Some(format!("Bearer {}", token))
+            }
+            None => self.credentials_header(),
+        };
+        if let Some(value) = value {
+            // Replace any configured Authorization header, whatever its casing
+            headers.retain(|key, _| !key.eq_ignore_ascii_case("authorization"));
+            headers.insert("Authorization".to_string(), value);
+        }
+        Ok(())
+    }
+}
+
 /// Parses a header string in "key:value" format for CLI arguments.
 ///
 /// Used internally by clap to validate and parse header arguments.
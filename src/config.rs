@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use fs_err as fs;
 
 use crate::cli::{DatabaseType, OrmType};
+use crate::parser::{ParsedField, ParsedType};
 
 /// YAML configuration format compatible with GraphQL Code Generator
 #[cfg(feature = "yaml-codegen-config")]
@@ -52,15 +53,172 @@ pub struct RustCodegenConfig {
     /// Custom scalar mappings
     #[serde(default)]
     pub scalar_mappings: HashMap<String, String>,
+    /// VARCHAR lengths for String fields, keyed by `Type.field`
+    #[serde(default)]
+    pub string_length_mappings: HashMap<String, u32>,
+    /// Raw SQL column types, keyed by `Type.field`
+    #[serde(default)]
+    pub sql_type_overrides: HashMap<String, String>,
+    /// Fields with a UNIQUE constraint, as `Type.field`
+    #[serde(default)]
+    pub unique_fields: Vec<String>,
+    /// Fields with a secondary index, as `Type.field`
+    #[serde(default)]
+    pub indexed_fields: Vec<String>,
+    /// Index every foreign key column
+    #[serde(default = "default_true")]
+    pub auto_fk_index: bool,
+    /// Primary key fields of types keyed by several columns
+    #[serde(default)]
+    pub composite_keys: HashMap<String, Vec<String>>,
+    /// Column defaults in GraphQL notation, keyed by `Type.field`
+    #[serde(default)]
+    pub default_values: HashMap<String, String>,
+    /// Fields stored as a JSON column instead of a relation, as `Type.field`
+    #[serde(default)]
+    pub embed_as_json: Vec<String>,
+    /// Leave the generation timestamp out of file banners
+    #[serde(default)]
+    pub deterministic_output: bool,
+    /// Schema file path relative to the output directory
+    #[serde(default)]
+    pub schema_file_path: Option<PathBuf>,
+    /// Entity directory relative to the output directory
+    #[serde(default)]
+    pub entities_dir: Option<PathBuf>,
+    /// Migration directory relative to the output directory
+    #[serde(default)]
+    pub migrations_dir: Option<PathBuf>,
+    /// Generate a `src/lib.rs` declaring the modules, with a prelude
+    #[serde(default = "default_true")]
+    pub generate_lib_rs: bool,
+    /// Generate a `Cargo.toml` for the output directory
+    #[serde(default)]
+    pub generate_cargo_toml: bool,
+    /// Fail instead of mapping unknown custom scalars to `String`
+    #[serde(default)]
+    pub strict_scalars: bool,
+    /// Generate Diesel `AsChangeset` structs for partial updates
+    #[serde(default = "default_true")]
+    pub generate_changesets: bool,
+    /// Store enums in native PostgreSQL enum types
+    #[serde(default)]
+    pub postgres_native_enums: bool,
+    /// Pluralize table names, leaving struct names singular
+    #[serde(default)]
+    pub pluralize_tables: bool,
+    /// Generate even if the output directory holds files this tool didn't
+    /// write, removing stale generated files
+    #[serde(default)]
+    pub force: bool,
     /// Table naming convention
     #[serde(default)]
     pub table_naming: TableNamingConvention,
+    /// Storage type for GraphQL ID values
+    #[serde(default, alias = "id_type")]
+    pub id_mapping: IdMapping,
+    /// Database-side default for UUID primary keys
+    #[serde(default)]
+    pub pk_default: PkDefault,
+    /// Add `AUTOINCREMENT` to SQLite integer primary keys
+    #[serde(default)]
+    pub sqlite_autoincrement: bool,
+    /// Crate providing the built-in date/time scalar types
+    #[serde(default)]
+    pub datetime_crate: DateTimeCrate,
+    /// How enum columns are stored
+    #[serde(default)]
+    pub enum_storage: EnumStorage,
+    /// Prefix for enum variants whose GraphQL value starts with a digit
+    #[serde(default = "default_enum_prefix")]
+    pub prefix_enum_values: String,
     /// Generate migrations
     #[serde(default = "default_true")]
     pub generate_migrations: bool,
     /// Generate entities
     #[serde(default = "default_true")]
     pub generate_entities: bool,
+    /// Only generate the schema file
+    #[serde(default)]
+    pub schema_only: bool,
+    /// Write schema, entities and enums to one `include!`-able file
+    #[serde(default)]
+    pub single_file: bool,
+    /// Only rewrite entity files that changed since the last run
+    #[serde(default)]
+    pub incremental: bool,
+    /// Only emit migrations for tables changed since the last run
+    #[serde(default)]
+    pub only_changed_tables: bool,
+    /// Run generated Rust files through rustfmt
+    #[serde(default = "default_true")]
+    pub format_output: bool,
+    /// Generate a typed client for the root query and mutation fields
+    #[serde(default)]
+    pub generate_client: bool,
+    /// Generate a `Paginated<T>` wrapper and per-entity `find_paginated` helpers
+    #[serde(default)]
+    pub generate_pagination: bool,
+    /// Generate `create`/`find_by_id`/`update`/`delete` helpers per entity
+    #[serde(default)]
+    pub generate_crud: bool,
+    /// Generate a sea-query `Iden` enum for each Sea-ORM table
+    #[serde(default)]
+    pub generate_iden: bool,
+    /// Add a round-trip test stub module to each entity
+    #[serde(default)]
+    pub generate_tests: bool,
+    /// Leave deprecated enum values out of generated enums
+    #[serde(default)]
+    pub skip_deprecated: bool,
+    /// Map GraphQL `Int` to `i64`/`BIGINT` instead of `i32`/`INTEGER`
+    #[serde(default)]
+    pub int_as_i64: bool,
+    /// Serialize Sea-ORM models with the original GraphQL field names
+    #[serde(default)]
+    pub preserve_graphql_names: bool,
+    /// Gate each entity module behind an `entity_<name>` feature
+    #[serde(default)]
+    pub feature_per_entity: bool,
+    /// Explicit feature names for entity modules, keyed by GraphQL type name
+    #[serde(default)]
+    pub feature_map: HashMap<String, String>,
+    /// Only generate these types (all types when empty)
+    #[serde(default)]
+    pub include_types: Vec<String>,
+    /// Never generate these types
+    #[serde(default)]
+    pub exclude_types: Vec<String>,
+    /// Generate entities and tables for the root Query/Mutation/Subscription types
+    #[serde(default)]
+    pub generate_root_types: bool,
+    /// Regexes for type names that never become tables (Relay pagination types by default)
+    #[serde(default)]
+    pub skip_patterns: Option<Vec<String>>,
+    /// Skip types whose generation fails instead of aborting
+    #[serde(default)]
+    pub continue_on_error: bool,
+    /// Line endings of generated files
+    #[serde(default)]
+    pub line_endings: LineEndings,
+    /// URI schemes of secret references to resolve in the url and headers
+    #[serde(default)]
+    pub secret_schemes: Vec<String>,
+    /// Write the raw introspection response JSON to this file
+    #[serde(default)]
+    pub dump_introspection: Option<PathBuf>,
+    /// Read the schema from this introspection JSON file instead of the url
+    #[serde(default)]
+    pub introspection_file: Option<PathBuf>,
+    /// Extra PEM root certificate trusted for introspection requests
+    #[serde(default)]
+    pub ca_cert: Option<PathBuf>,
+    /// How the introspection query is sent
+    #[serde(default)]
+    pub introspection: IntrospectionRequest,
+    /// Sea-ORM migration output style
+    #[serde(default)]
+    pub sea_orm_migration_style: SeaOrmMigrationStyle,
 }
 
 #[cfg(feature = "yaml-codegen-config")]
@@ -87,9 +245,61 @@ impl Default for RustCodegenConfig {
             output_dir: default_output(),
             type_mappings: HashMap::new(),
             scalar_mappings: HashMap::new(),
+            string_length_mappings: HashMap::new(),
+            sql_type_overrides: HashMap::new(),
+            unique_fields: Vec::new(),
+            indexed_fields: Vec::new(),
+            auto_fk_index: true,
+            composite_keys: HashMap::new(),
+            default_values: HashMap::new(),
+            embed_as_json: Vec::new(),
+            deterministic_output: false,
+            schema_file_path: None,
+            entities_dir: None,
+            migrations_dir: None,
+            generate_lib_rs: true,
+            generate_cargo_toml: false,
+            strict_scalars: false,
+            generate_changesets: true,
+            postgres_native_enums: false,
+            pluralize_tables: false,
+            force: false,
             table_naming: TableNamingConvention::default(),
+            id_mapping: IdMapping::default(),
+            pk_default: PkDefault::default(),
+            sqlite_autoincrement: false,
+            datetime_crate: DateTimeCrate::default(),
+            enum_storage: EnumStorage::default(),
+            prefix_enum_values: default_enum_prefix(),
             generate_migrations: true,
             generate_entities: true,
+            schema_only: false,
+            single_file: false,
+            incremental: false,
+            only_changed_tables: false,
+            format_output: true,
+            generate_client: false,
+            generate_pagination: false,
+            generate_crud: false,
+            generate_iden: false,
+            generate_tests: false,
+            skip_deprecated: false,
+            int_as_i64: false,
+            preserve_graphql_names: false,
+            feature_per_entity: false,
+            feature_map: HashMap::new(),
+            include_types: Vec::new(),
+            exclude_types: Vec::new(),
+            generate_root_types: false,
+            skip_patterns: None,
+            continue_on_error: false,
+            line_endings: LineEndings::default(),
+            secret_schemes: Vec::new(),
+            dump_introspection: None,
+            introspection_file: None,
+            ca_cert: None,
+            introspection: IntrospectionRequest::default(),
+            sea_orm_migration_style: SeaOrmMigrationStyle::default(),
         }
     }
 }
@@ -158,7 +368,7 @@ impl Default for RustCodegenConfig {
 ///     ..Default::default()
 /// };
 /// ```
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     /// URL of the GraphQL endpoint that supports introspection.
     ///
@@ -169,6 +379,9 @@ pub struct Config {
     /// - `"https://api.github.com/graphql"` (GitHub's public API)
     /// - `"https://api.example.com/graphql"` (your custom API)
     /// - `"http://localhost:4000/graphql"` (local development)
+    ///
+    /// May be empty when `introspection_file` is set.
+    #[serde(default)]
     pub url: String,
 
     /// ORM framework to generate code for.
@@ -176,6 +389,7 @@ pub struct Config {
     /// Determines the structure and style of generated code:
     /// - `OrmType::Diesel`: Generates table schemas and Queryable structs
     /// - `OrmType::SeaOrm`: Generates Entity models and ActiveModel structs
+    /// - `OrmType::Plain`: Generates serde structs with no ORM dependency
     pub orm: OrmType,
 
     /// Target database backend.
@@ -218,8 +432,8 @@ pub struct Config {
     /// - Domain-specific types
     /// - Third-party library types
     ///
-    /// If a GraphQL type is not found in this map, default mappings are used
-    /// based on the database type and built-in GraphQL scalars.
+    /// If a GraphQL type is not found in this map, `scalar_mappings` is tried,
+    /// then the built-in mappings for the database type and GraphQL scalars.
     ///
     /// # Examples
     /// ```toml
@@ -234,7 +448,8 @@ pub struct Config {
     /// Custom scalar type mappings for GraphQL scalars.
     ///
     /// Similar to `type_mappings` but specifically for GraphQL scalar types.
-    /// These are applied before the built-in scalar mappings.
+    /// These are applied before the built-in scalar mappings, and a scalar
+    /// in both maps takes its `type_mappings` entry.
     ///
     /// # Examples
     /// ```toml
@@ -245,6 +460,247 @@ pub struct Config {
     #[serde(default)]
     pub scalar_mappings: HashMap<String, String>,
 
+    /// Maximum lengths for String fields, keyed by `Type.field`.
+    ///
+    /// Mapped fields become `VARCHAR(n)` columns on MySQL and PostgreSQL so they
+    /// can be indexed; other String fields stay `TEXT`. A `@maxLength(n)`
+    /// directive on the SDL field has the same effect, with this map taking
+    /// precedence. SQLite ignores the length.
+    ///
+    /// # Examples
+    /// ```toml
+    /// [string_length_mappings]
+    /// "User.email" = 255
+    /// ```
+    ///
+    /// Default: `{}`
+    #[serde(default)]
+    pub string_length_mappings: HashMap<String, u32>,
+
+    /// SQL column types for individual fields, keyed by `Type.field`.
+    ///
+    /// The type is written into migrations as-is and takes precedence over
+    /// `string_length_mappings` and the scalar and type mappings. Entity field
+    /// types are unaffected.
+    ///
+    /// # Examples
+    /// ```toml
+    /// [sql_type_overrides]
+    /// "User.code" = "VARCHAR(8)"
+    /// ```
+    ///
+    /// Default: `{}`
+    #[serde(default)]
+    pub sql_type_overrides: HashMap<String, String>,
+
+    /// Fields that get a `UNIQUE` constraint, as `Type.field`.
+    ///
+    /// Fields carrying an SDL `@unique` directive are always unique; this list
+    /// covers schemas without directives, such as introspected ones.
+    ///
+    /// # Examples
+    /// ```toml
+    /// unique_fields = ["User.email", "Account.slug"]
+    /// ```
+    ///
+    /// Default: `[]`
+    #[serde(default)]
+    pub unique_fields: Vec<String>,
+
+    /// Fields that get a secondary index, as `Type.field`.
+    ///
+    /// Works like `unique_fields` for the SDL `@index` directive. Each index is
+    /// named `idx_<table>_<column>`, shortened with a hash suffix when it
+    /// exceeds the database's identifier limit.
+    ///
+    /// # Examples
+    /// ```toml
+    /// indexed_fields = ["Post.publishedAt"]
+    /// ```
+    ///
+    /// Default: `[]`
+    #[serde(default)]
+    pub indexed_fields: Vec<String>,
+
+    /// Whether Diesel migrations index every foreign key column.
+    ///
+    /// Turn this off for tables whose foreign keys are rarely queried; columns
+    /// listed in `indexed_fields` or marked `@index` are still indexed.
+    ///
+    /// Default: `true`
+    #[serde(default = "default_true")]
+    pub auto_fk_index: bool,
+
+    /// Primary keys made of several fields, keyed by GraphQL type name.
+    ///
+    /// Tables for these types get a `PRIMARY KEY (a, b)` constraint instead of
+    /// a single key column, as join tables and legacy schemas need. In SDL
+    /// schemas, marking two or more fields `@id` does the same.
+    ///
+    /// # Examples
+    /// ```toml
+    /// [composite_keys]
+    /// PostTag = ["postId", "tagId"]
+    /// ```
+    ///
+    /// Default: `{}`
+    #[serde(default)]
+    pub composite_keys: HashMap<String, Vec<String>>,
+
+    /// Column defaults for fields without one in the schema, keyed by `Type.field`.
+    ///
+    /// Values use GraphQL notation (`false`, `0`, `"draft"`); bare words such as
+    /// enum values are taken as strings. Schema defaults come from an SDL
+    /// `@default(value: ...)` directive or the field's default in the type's
+    /// `Create<Type>Input` or `<Type>Input`, and take precedence.
+    ///
+    /// # Examples
+    /// ```toml
+    /// [default_values]
+    /// "User.active" = "true"
+    /// "Post.status" = "DRAFT"
+    /// ```
+    ///
+    /// Default: `{}`
+    #[serde(default)]
+    pub default_values: HashMap<String, String>,
+
+    /// Fields stored in a single JSON column instead of as a relation, as `Type.field`.
+    ///
+    /// Suits object fields that are only ever read along with their parent,
+    /// such as an address. Embedded fields become `serde_json::Value`, stored as
+    /// `JSONB` on PostgreSQL, `JSON` on MySQL and `TEXT` on SQLite. Lists are
+    /// stored as one JSON array. Fields typed as a `JSON` or `JSONB` scalar are
+    /// stored the same way without being listed.
+    ///
+    /// # Examples
+    /// ```toml
+    /// embed_as_json = ["User.address", "Order.lineItems"]
+    /// ```
+    ///
+    /// Default: `[]`
+    #[serde(default)]
+    pub embed_as_json: Vec<String>,
+
+    /// Leave the generation timestamp out of the banner on generated files.
+    ///
+    /// Every generated file starts with a `@generated ... do not edit` banner
+    /// naming the generator version and, by default, when it was generated.
+    /// Enable this so repeated runs on the same schema produce byte-identical
+    /// files, which keeps diffs of committed generated code clean.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub deterministic_output: bool,
+
+    /// Path of the schema file, relative to `output_dir`.
+    ///
+    /// Unset, Diesel writes `src/schema.rs` and Sea-ORM a root `mod.rs`. Plain
+    /// structs have no schema file, so this is ignored for them.
+    ///
+    /// Default: `None`
+    #[serde(default)]
+    pub schema_file_path: Option<PathBuf>,
+
+    /// Directory of the entity files, relative to `output_dir`.
+    ///
+    /// Diesel and plain structs also write the entities' `mod.rs` here.
+    ///
+    /// Default: `None` (`src/entities`)
+    #[serde(default)]
+    pub entities_dir: Option<PathBuf>,
+
+    /// Directory of the migrations, relative to `output_dir`.
+    ///
+    /// SQL migrations go in one subdirectory each and Sea-ORM's Rust
+    /// migrations in its `src` subdirectory.
+    ///
+    /// Default: `None` (`migrations`)
+    #[serde(default)]
+    pub migrations_dir: Option<PathBuf>,
+
+    /// Whether to generate a `src/lib.rs` crate entrypoint.
+    ///
+    /// It declares `pub mod entities;` (and Diesel's `pub mod schema;`) and
+    /// re-exports the generated types from a `prelude` module. Sea-ORM also
+    /// gets a `src/entities/mod.rs` declaring its entity files.
+    ///
+    /// Default: `true`
+    #[serde(default = "default_true")]
+    pub generate_lib_rs: bool,
+
+    /// Whether to generate a `Cargo.toml` in `output_dir`.
+    ///
+    /// Lists the ORM with the feature for `db` and whichever of `chrono`,
+    /// `time`, `uuid` and `serde_json` the generated types use, so the output
+    /// builds as a crate of its own. Leave it off when the output sits inside
+    /// an existing crate.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub generate_cargo_toml: bool,
+
+    /// Whether custom scalars without a Rust type are an error.
+    ///
+    /// A custom scalar used by a generated field that isn't in `type_mappings`
+    /// or `scalar_mappings` and has no built-in mapping (date/time, 64-bit
+    /// integer and JSON scalars) falls back to `String` with a warning. Turn
+    /// this on to make that fallback a generation error instead.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub strict_scalars: bool,
+
+    /// Whether Diesel entities get an `AsChangeset` struct for updates.
+    ///
+    /// `UpdateUser` holds every non-key field as an `Option`, and `None`
+    /// fields are left untouched by `diesel::update(...).set(...)`. Has no
+    /// effect on the other ORMs.
+    ///
+    /// Default: `true`
+    #[serde(default = "default_true")]
+    pub generate_changesets: bool,
+
+    /// Whether PostgreSQL enum columns use native enum types.
+    ///
+    /// Migrations start with a `create_enum_types` migration (named
+    /// `00000000000000_create_enum_types` for Diesel and
+    /// `m0000_create_enum_types` for Sea-ORM, so it sorts first) running
+    /// `CREATE TYPE role AS ENUM ('ADMIN', 'USER')` for each enum, and enum
+    /// columns use those types instead of `TEXT`. Diesel's schema gets the
+    /// matching `sql_types` and Sea-ORM enums use `db_type = "Enum"`. Only
+    /// applies to PostgreSQL with `EnumStorage::Text`.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub postgres_native_enums: bool,
+
+    /// Whether table names are pluralized.
+    ///
+    /// Only table identifiers change: a `User` type keeps its `User` struct
+    /// and `user` module but maps to a `users` table, in migrations, Diesel's
+    /// `table!` and `#[diesel(table_name = users)]`, and Sea-ORM's
+    /// `table_name`. Pluralization follows regular English endings
+    /// (`Category` → `categories`, `Address` → `addresses`).
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub pluralize_tables: bool,
+
+    /// Whether to generate into an output directory holding files this tool
+    /// didn't write.
+    ///
+    /// Without it, generation stops when the output directory contains Rust or
+    /// SQL files lacking the `@generated` banner, since they could be
+    /// overwritten. With it, those files are reported, and besides the entity
+    /// files of deleted types, which are always removed, any generated file
+    /// the schema no longer produces is removed; files without the banner are
+    /// never removed.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub force: bool,
+
     /// Naming convention for database tables and columns.
     ///
     /// Controls how GraphQL type/field names are converted to database identifiers.
@@ -255,6 +711,87 @@ pub struct Config {
     #[serde(default)]
     pub table_naming: TableNamingConvention,
 
+    /// How GraphQL `ID` values are stored in the database.
+    ///
+    /// Applies to `ID` fields, foreign keys and the implicit `id` primary key:
+    /// - `IdMapping::Auto`: Per-database default (INTEGER on SQLite, UUID on PostgreSQL,
+    ///   INT UNSIGNED on MySQL)
+    /// - `IdMapping::Integer`: Auto-incrementing integer keys
+    /// - `IdMapping::String`: Opaque text keys, never auto-incremented
+    /// - `IdMapping::Uuid`: UUID keys
+    ///
+    /// Also accepted as `id_type`.
+    ///
+    /// Default: `IdMapping::Auto`
+    #[serde(default, alias = "id_type")]
+    pub id_mapping: IdMapping,
+
+    /// How the database generates UUID primary key values.
+    ///
+    /// Integer keys always auto-increment; this only affects UUID keys:
+    /// - `PkDefault::Auto`: `gen_random_uuid()` on PostgreSQL, no default elsewhere
+    /// - `PkDefault::GenRandomUuid`: `gen_random_uuid()` on PostgreSQL (emits
+    ///   `CREATE EXTENSION IF NOT EXISTS pgcrypto`, needed before PostgreSQL 13),
+    ///   `(UUID())` on MySQL
+    /// - `PkDefault::UuidGenerateV4`: `uuid_generate_v4()` on PostgreSQL (emits
+    ///   `CREATE EXTENSION IF NOT EXISTS "uuid-ossp"`), `(UUID())` on MySQL
+    ///
+    /// SQLite has no UUID function, so its keys never get a default.
+    ///
+    /// Default: `PkDefault::Auto`
+    #[serde(default)]
+    pub pk_default: PkDefault,
+
+    /// Whether SQLite integer primary keys are declared `INTEGER PRIMARY KEY AUTOINCREMENT`.
+    ///
+    /// Without it they are plain `INTEGER PRIMARY KEY` columns, which alias the
+    /// rowid and are still assigned automatically, but may reuse the ids of
+    /// deleted rows. `AUTOINCREMENT` guarantees ids are never reused at the cost
+    /// of an extra `sqlite_sequence` lookup per insert. Applies to the implicit
+    /// `id` column and to detected `ID` primary keys alike; other databases are
+    /// unaffected.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub sqlite_autoincrement: bool,
+
+    /// Crate providing the Rust types for the `DateTime`, `Date` and `Time` scalars.
+    ///
+    /// - `DateTimeCrate::Chrono`: `chrono::DateTime<chrono::Utc>`, `chrono::NaiveDate`,
+    ///   `chrono::NaiveTime`
+    /// - `DateTimeCrate::Time`: `time::OffsetDateTime`, `time::Date`, `time::Time`
+    ///
+    /// `DateTime` takes the zone-aware type above on PostgreSQL (`TIMESTAMPTZ`)
+    /// and the naive `chrono::NaiveDateTime` or `time::PrimitiveDateTime` on
+    /// SQLite (`TIMESTAMP`) and MySQL (`DATETIME`).
+    ///
+    /// Explicit `type_mappings` or `scalar_mappings` for these scalars take
+    /// precedence.
+    ///
+    /// Default: `DateTimeCrate::Chrono`
+    #[serde(default)]
+    pub datetime_crate: DateTimeCrate,
+
+    /// How enum values are stored in the database.
+    ///
+    /// - `EnumStorage::Text`: the variant name in a text column
+    /// - `EnumStorage::Int`: an integer column with `#[repr(i32)]` enums and a
+    ///   `TryFrom<i32>` impl. Variants take their discriminant from an
+    ///   `@value(int: N)` directive, otherwise the previous value plus one.
+    ///
+    /// Default: `EnumStorage::Text`
+    #[serde(default)]
+    pub enum_storage: EnumStorage,
+
+    /// Prefix for enum variants whose GraphQL value starts with a digit.
+    ///
+    /// Such values, e.g. `2FA`, aren't valid Rust identifiers, so the variant
+    /// becomes `V2FA` while Sea-ORM's `string_value` keeps the original value.
+    ///
+    /// Default: `"V"`
+    #[serde(default = "default_enum_prefix")]
+    pub prefix_enum_values: String,
+
     /// Whether to generate database migration files.
     ///
     /// When enabled, creates SQL migration files in the `migrations/` directory
@@ -274,12 +811,470 @@ pub struct Config {
     /// Default: `true`
     #[serde(default = "default_true")]
     pub generate_entities: bool,
+
+    /// Whether to generate only the schema file.
+    ///
+    /// Writes Diesel's `src/schema.rs` or Sea-ORM's `mod.rs` and skips entities,
+    /// module files, the client and migrations, for projects with hand-written
+    /// models.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub schema_only: bool,
+
+    /// Whether to write the generated code as a single self-contained file.
+    ///
+    /// Tables, entities and enums go into `src/generated.rs` instead of the
+    /// schema, entity and module files, with all imports inlined so the file
+    /// can be pulled into a module of your own with `include!`. Diesel output
+    /// is flat; Sea-ORM entities become inline modules that refer to each
+    /// other as siblings. Migrations and the client are written as usual.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub single_file: bool,
+
+    /// Whether to skip rewriting entity files that haven't changed.
+    ///
+    /// When enabled, a manifest of per-type content hashes is kept in the output
+    /// directory and only entity files whose generated code differs from the
+    /// previous run are rewritten. The schema and module files are always written.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub incremental: bool,
+
+    /// Whether to emit migrations only for tables changed since the last run.
+    ///
+    /// The migration SQL of every table is kept in the output directory's
+    /// manifest. New tables get their create migration, changed tables an
+    /// `ALTER TABLE` migration adding and dropping columns, and removed tables
+    /// one that drops them, leaving the existing migration history untouched.
+    /// Other table changes, such as a column changing type, fail generation
+    /// rather than recreate the table and lose its rows. Requires SQL
+    /// migrations, so Sea-ORM needs `sea_orm_migration_style: Raw`.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub only_changed_tables: bool,
+
+    /// Whether to format generated Rust files with `rustfmt`.
+    ///
+    /// Generated code is assembled without regard for formatting, so running it
+    /// through `rustfmt` avoids churn when the output is committed. Formatting is
+    /// best-effort: if `rustfmt` is not installed the code is written as-is.
+    ///
+    /// Default: `true`
+    #[serde(default = "default_true")]
+    pub format_output: bool,
+
+    /// Whether to generate a typed GraphQL client alongside the entities.
+    ///
+    /// When enabled, `src/client.rs` contains one `async fn` per root query and
+    /// mutation field, sending the operation to `url` and deserializing the
+    /// response into generated structs. The client sends the configured
+    /// `headers` by name only, reading each value at runtime from a
+    /// `GRAPHQL_<HEADER>` environment variable (`GRAPHQL_AUTHORIZATION`), so no
+    /// credentials end up in the generated file. Only fields whose arguments are
+    /// all scalars or enums are supported for now.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub generate_client: bool,
+
+    /// Whether to generate pagination helpers for Sea-ORM entities.
+    ///
+    /// Adds a generic `Paginated<T>` struct to `mod.rs` and a
+    /// `find_paginated(db, page, per_page)` function on each entity, built on
+    /// Sea-ORM's `paginate`. The generated code needs the Sea-ORM runtime, so
+    /// this is ignored for Diesel.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub generate_pagination: bool,
+
+    /// Whether to generate CRUD helpers for Sea-ORM entities.
+    ///
+    /// Adds `async fn create`, `find_by_id`, `update` and `delete` to each
+    /// entity module, built on the `ActiveModel` and `Entity` APIs, with the
+    /// key parameter typed like the entity's primary key. Ignored for Diesel.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub generate_crud: bool,
+
+    /// Whether to generate sea-query identifiers for Sea-ORM tables.
+    ///
+    /// Adds a `<Type>Iden` enum implementing `sea_query::Iden` to each entity,
+    /// with a `Table` variant for the table name and one variant per column,
+    /// for building dynamic queries with typed identifiers. Ignored for Diesel.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub generate_iden: bool,
+
+    /// Whether to add a test scaffolding module to each entity.
+    ///
+    /// Appends a `#[cfg(test)] mod tests` with an ignored round-trip test that
+    /// opens an in-memory SQLite database, inserts a row and selects it back,
+    /// as a starting point for the entity's own tests. The stub needs the
+    /// ORM's SQLite support as a dev-dependency (`diesel` with the `sqlite`
+    /// feature, or `sea-orm` with `sqlx-sqlite` plus `tokio`), and the new row's
+    /// fields are left for the user to fill in.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub generate_tests: bool,
+
+    /// Whether to leave deprecated enum values out of generated enums.
+    ///
+    /// Deprecated values (`isDeprecated` in introspection, `@deprecated` in
+    /// SDL) are generated as `#[deprecated]` variants, with the deprecation
+    /// reason as the note. When enabled they are dropped instead; rows still
+    /// holding such a value will then fail to load.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub skip_deprecated: bool,
+
+    /// Whether GraphQL `Int` fields are stored as 64-bit integers.
+    ///
+    /// `Int` is 32-bit by spec and maps to `i32`/`INTEGER` by default. Some APIs
+    /// return wider values as `Int` anyway; with this set such fields become
+    /// `i64`, Diesel `BigInt` and `BIGINT` columns. The `Long` and `BigInt`
+    /// custom scalars always map to these types unless given their own mapping.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub int_as_i64: bool,
+
+    /// Whether Sea-ORM models serialize with the original GraphQL field names.
+    ///
+    /// Struct fields are snake_case (`authorId` becomes `author_id`), so JSON
+    /// from the API doesn't deserialize into the models as-is. When enabled,
+    /// fields whose Rust name differs get `#[serde(rename = "authorId")]`.
+    /// Ignored for Diesel, whose models don't derive serde traits.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub preserve_graphql_names: bool,
+
+    /// Whether to gate every entity module behind its own Cargo feature.
+    ///
+    /// When enabled, each module declaration in the generated `mod.rs` is
+    /// prefixed with `#[cfg(feature = "entity_<snake_case_name>")]` so large
+    /// generated crates can compile only the entities they need.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub feature_per_entity: bool,
+
+    /// Explicit Cargo feature names for entity modules.
+    ///
+    /// Maps GraphQL type or enum names to the feature that gates their module.
+    /// Entries take precedence over `feature_per_entity` naming.
+    ///
+    /// # Examples
+    /// ```toml
+    /// [feature_map]
+    /// "User" = "accounts"
+    /// "Invoice" = "billing"
+    /// ```
+    #[serde(default)]
+    pub feature_map: HashMap<String, String>,
+
+    /// GraphQL type and enum names to generate; everything else is skipped.
+    ///
+    /// An empty list generates every type. Enums used by a listed type are
+    /// generated with it. Fields referencing a skipped type are dropped along
+    /// with the type itself, so no migration or relation points at a table
+    /// that isn't generated.
+    ///
+    /// Default: `[]`
+    #[serde(default)]
+    pub include_types: Vec<String>,
+
+    /// GraphQL type and enum names to skip, applied after `include_types`.
+    ///
+    /// Default: `[]`
+    #[serde(default)]
+    pub exclude_types: Vec<String>,
+
+    /// Whether the root operation types become entities and tables.
+    ///
+    /// `Query`, `Mutation` and `Subscription` (or the roots named in the
+    /// schema) describe the API rather than stored data, so they are skipped
+    /// unless this is enabled.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub generate_root_types: bool,
+
+    /// Regular expressions for type names that never become entities or tables.
+    ///
+    /// Each pattern must match the whole type name. When unset, Relay-style
+    /// pagination types are skipped: `PageInfo`, `.*Connection$` and `.*Edge$`.
+    /// Set an empty list to generate every type.
+    ///
+    /// Default: `None` (the Relay pagination patterns)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_patterns: Option<Vec<String>>,
+
+    /// Skip types whose generation fails or panics instead of aborting the run.
+    ///
+    /// When generating the schema fails, each type is generated on its own to
+    /// find the failing ones, which are left out together with fields
+    /// referencing them. The remaining types are generated as usual and the
+    /// skipped ones are listed in the `GenerationSummary`.
+    ///
+    /// Default: `false`
+    #[serde(default)]
+    pub continue_on_error: bool,
+
+    /// Line endings used in generated files.
+    ///
+    /// - `LineEndings::Lf`: `\n` everywhere, so output is identical across platforms
+    /// - `LineEndings::Crlf`: `\r\n`, for CRLF-normalizing tooling
+    /// - `LineEndings::Native`: `\r\n` on Windows, `\n` elsewhere
+    ///
+    /// Default: `LineEndings::Lf`
+    #[serde(default)]
+    pub line_endings: LineEndings,
+
+    /// URI schemes identifying secret references in `url` and `headers`.
+    ///
+    /// Values such as `vault://secret/api#token` whose scheme is listed here are
+    /// passed to the `SecretResolver` set on the `CodeGenerator` before
+    /// introspection. The default resolver leaves them unchanged.
+    ///
+    /// # Examples
+    /// ```toml
+    /// secret_schemes = ["vault", "aws-sm"]
+    ///
+    /// [headers]
+    /// "Authorization" = "Bearer vault://secret/api#token"
+    /// ```
+    ///
+    /// Default: `[]`
+    #[serde(default)]
+    pub secret_schemes: Vec<String>,
+
+    /// File receiving the raw introspection response JSON before parsing.
+    ///
+    /// Useful for debugging server-specific quirks or attaching the schema to a
+    /// bug report. Only the response body is written; request headers are not.
+    ///
+    /// Default: `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dump_introspection: Option<PathBuf>,
+
+    /// Introspection response JSON file to read the schema from.
+    ///
+    /// When set, no request is sent and `url` may be left empty, so generation
+    /// works offline or in CI from a committed schema. Expects a full
+    /// introspection response, such as a file written by `dump_introspection`.
+    ///
+    /// Default: `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub introspection_file: Option<PathBuf>,
+
+    /// PEM file with an extra root certificate for introspection requests.
+    ///
+    /// Trusted in addition to the system roots, for gateways served with a
+    /// certificate from an internal CA. Proxies are taken from the
+    /// `HTTP_PROXY`, `HTTPS_PROXY` and `NO_PROXY` environment variables.
+    ///
+    /// Default: `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<PathBuf>,
+
+    /// How the introspection query is sent to the server.
+    ///
+    /// By default it is POSTed as JSON with `operationName: "IntrospectionQuery"`
+    /// and empty `variables`, which gateways rejecting anonymous operations accept.
+    /// Use `method: Get` for servers that only allow introspection over GET,
+    /// and `use_apq: true` for servers requiring automatic persisted queries.
+    ///
+    /// # Examples
+    /// ```toml
+    /// [introspection]
+    /// method = "Get"
+    /// operation_name = ""  # Omit operationName
+    /// send_variables = false
+    /// ```
+    #[serde(default)]
+    pub introspection: IntrospectionRequest,
+
+    /// How Sea-ORM migrations are emitted.
+    ///
+    /// - `SeaOrmMigrationStyle::Rust`: `migrations/src/m0001_create_<table>_table.rs` files,
+    ///   numbered so referenced tables come first, implementing `MigrationTrait` with `SchemaManager`, plus a `Migrator` in
+    ///   `migrations/src/lib.rs`
+    /// - `SeaOrmMigrationStyle::Raw`: `up.sql`/`down.sql` pairs like Diesel
+    ///
+    /// Ignored for Diesel. Default: `SeaOrmMigrationStyle::Rust`
+    #[serde(default)]
+    pub sea_orm_migration_style: SeaOrmMigrationStyle,
+}
+
+/// Every setting at its documented default, matching a config file that
+/// leaves them out
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            orm: OrmType::default(),
+            db: DatabaseType::default(),
+            output_dir: PathBuf::from("./generated"),
+            headers: HashMap::new(),
+            type_mappings: HashMap::new(),
+            scalar_mappings: HashMap::new(),
+            string_length_mappings: HashMap::new(),
+            sql_type_overrides: HashMap::new(),
+            unique_fields: Vec::new(),
+            indexed_fields: Vec::new(),
+            auto_fk_index: true,
+            composite_keys: HashMap::new(),
+            default_values: HashMap::new(),
+            embed_as_json: Vec::new(),
+            deterministic_output: false,
+            schema_file_path: None,
+            entities_dir: None,
+            migrations_dir: None,
+            generate_lib_rs: true,
+            generate_cargo_toml: false,
+            strict_scalars: false,
+            generate_changesets: true,
+            postgres_native_enums: false,
+            pluralize_tables: false,
+            force: false,
+            table_naming: TableNamingConvention::default(),
+            id_mapping: IdMapping::default(),
+            pk_default: PkDefault::default(),
+            sqlite_autoincrement: false,
+            datetime_crate: DateTimeCrate::default(),
+            enum_storage: EnumStorage::default(),
+            prefix_enum_values: default_enum_prefix(),
+            generate_migrations: true,
+            generate_entities: true,
+            schema_only: false,
+            single_file: false,
+            incremental: false,
+            only_changed_tables: false,
+            format_output: true,
+            generate_client: false,
+            generate_pagination: false,
+            generate_crud: false,
+            generate_iden: false,
+            generate_tests: false,
+            skip_deprecated: false,
+            int_as_i64: false,
+            preserve_graphql_names: false,
+            feature_per_entity: false,
+            feature_map: HashMap::new(),
+            include_types: Vec::new(),
+            exclude_types: Vec::new(),
+            generate_root_types: false,
+            skip_patterns: None,
+            continue_on_error: false,
+            line_endings: LineEndings::default(),
+            secret_schemes: Vec::new(),
+            dump_introspection: None,
+            introspection_file: None,
+            ca_cert: None,
+            introspection: IntrospectionRequest::default(),
+            sea_orm_migration_style: SeaOrmMigrationStyle::default(),
+        }
+    }
+}
+
+/// Type name patterns skipped when `skip_patterns` is unset
+pub const DEFAULT_SKIP_PATTERNS: &[&str] = &["PageInfo", ".*Connection$", ".*Edge$"];
+
+/// Replace `${VAR}` and `$VAR` with environment variable values; `$$` is a literal `$`
+fn interpolate_env_vars(value: &str) -> anyhow::Result<String> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        let (name, remainder) = if let Some(braced) = rest.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .ok_or_else(|| anyhow::anyhow!("unterminated '${{' in '{}'", value))?;
+            (&braced[..end], &braced[end + 1..])
+        } else if let Some(remainder) = rest.strip_prefix('$') {
+            result.push('$');
+            rest = remainder;
+            continue;
+        } else {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        };
+
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            // Not a variable reference, keep the `$` as written
+            result.push('$');
+            continue;
+        }
+        let resolved = std::env::var(name)
+            .map_err(|_| anyhow::anyhow!("environment variable '{}' is not set", name))?;
+        result.push_str(&resolved);
+        rest = remainder;
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Check that a GraphQL endpoint URL is absolute `http`/`https` with a host
+pub fn validate_endpoint_url(url: &str) -> anyhow::Result<()> {
+    let url = url.trim();
+    let help = "Expected a full endpoint URL such as https://api.example.com/graphql";
+    if url.is_empty() {
+        return Err(anyhow::anyhow!("GraphQL endpoint url is empty\n\n{}", help));
+    }
+
+    if !url.contains("://") {
+        return Err(anyhow::anyhow!(
+            "Invalid GraphQL endpoint url '{}': missing http:// or https://\n\n{}, e.g. https://{}",
+            url,
+            help,
+            url
+        ));
+    }
+
+    let parsed = reqwest::Url::parse(url).map_err(|e| {
+        anyhow::anyhow!("Invalid GraphQL endpoint url '{}': {}\n\n{}", url, e, help)
+    })?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(anyhow::anyhow!(
+            "Invalid GraphQL endpoint url '{}': unsupported scheme '{}', use http or https\n\n{}",
+            url,
+            parsed.scheme(),
+            help
+        ));
+    }
+    if parsed.host_str().is_none_or(str::is_empty) {
+        return Err(anyhow::anyhow!(
+            "Invalid GraphQL endpoint url '{}': missing host\n\n{}",
+            url,
+            help
+        ));
+    }
+    Ok(())
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_enum_prefix() -> String {
+    "V".to_string()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub enum TableNamingConvention {
     /// Convert GraphQL type names to snake_case (default)
@@ -291,7 +1286,364 @@ pub enum TableNamingConvention {
     PascalCase,
 }
 
+/// Storage type for GraphQL `ID` values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum IdMapping {
+    /// Use the database's default ID type
+    #[default]
+    #[serde(alias = "auto")]
+    Auto,
+    /// Store IDs as auto-incrementing integers
+    #[serde(alias = "integer")]
+    Integer,
+    /// Store IDs as opaque strings, e.g. Relay global IDs
+    #[serde(alias = "string")]
+    String,
+    /// Store IDs as UUIDs
+    #[serde(alias = "uuid")]
+    Uuid,
+}
+
+/// Database-side generator for UUID primary keys.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum PkDefault {
+    /// Use the database's built-in default, without extensions
+    #[default]
+    Auto,
+    /// PostgreSQL `gen_random_uuid()` from pgcrypto
+    GenRandomUuid,
+    /// PostgreSQL `uuid_generate_v4()` from uuid-ossp
+    UuidGenerateV4,
+}
+
+/// Crate used for built-in date/time scalar types.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DateTimeCrate {
+    /// `chrono` types such as `chrono::DateTime<chrono::Utc>`
+    #[default]
+    Chrono,
+    /// `time` types such as `time::OffsetDateTime`
+    Time,
+}
+
+/// Storage representation for enum columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum EnumStorage {
+    /// Variant names in a text column
+    #[default]
+    Text,
+    /// Integer discriminants in an integer column
+    Int,
+}
+
+/// Envelope and HTTP method of the introspection request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntrospectionRequest {
+    /// HTTP method used to send the query
+    #[serde(default)]
+    pub method: IntrospectionMethod,
+    /// Operation name of the query and its `operationName`; empty for an
+    /// anonymous query
+    #[serde(default = "default_operation_name")]
+    pub operation_name: String,
+    /// Whether to send an empty `variables` object
+    #[serde(default = "default_true")]
+    pub send_variables: bool,
+    /// Levels of `ofType` the query unrolls, bounding how deeply list and
+    /// non-null wrappers can nest around a named type
+    #[serde(default = "default_introspection_depth")]
+    pub depth: usize,
+    /// Send the query as an automatic persisted query, by its SHA-256 hash
+    /// first and in full only if the server doesn't know it yet
+    #[serde(default)]
+    pub use_apq: bool,
+}
+
+impl Default for IntrospectionRequest {
+    fn default() -> Self {
+        Self {
+            method: IntrospectionMethod::default(),
+            operation_name: default_operation_name(),
+            send_variables: true,
+            depth: default_introspection_depth(),
+            use_apq: false,
+        }
+    }
+}
+
+fn default_operation_name() -> String {
+    "IntrospectionQuery".to_string()
+}
+
+fn default_introspection_depth() -> usize {
+    7
+}
+
+/// HTTP method for the introspection request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, clap::ValueEnum)]
+pub enum IntrospectionMethod {
+    /// JSON body in a POST request
+    #[default]
+    #[serde(alias = "post")]
+    Post,
+    /// `query`, `operationName` and `variables` URL parameters in a GET request
+    #[serde(alias = "get")]
+    Get,
+}
+
+/// Line ending style for generated files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum LineEndings {
+    /// Unix `\n`
+    #[default]
+    Lf,
+    /// Windows `\r\n`
+    Crlf,
+    /// The platform's convention
+    Native,
+}
+
+impl LineEndings {
+    /// Convert generated content, which uses `\n`, to this style
+    pub fn apply(&self, content: &str) -> String {
+        let crlf = match self {
+            LineEndings::Lf => false,
+            LineEndings::Crlf => true,
+            LineEndings::Native => cfg!(windows),
+        };
+        let content = content.replace("\r\n", "\n");
+        if crlf {
+            content.replace('\n', "\r\n")
+        } else {
+            content
+        }
+    }
+}
+
+/// Fluent builder for [`Config`], created with [`Config::builder`].
+///
+/// Unset options take the same defaults as a config file, from
+/// [`Config::default`].
+#[derive(Debug, Clone, Default)]
+pub struct ConfigBuilder {
+    config: Config,
+}
+
+impl ConfigBuilder {
+    /// GraphQL endpoint URL (required)
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.config.url = url.into();
+        self
+    }
+
+    /// ORM to generate code for
+    pub fn orm(mut self, orm: OrmType) -> Self {
+        self.config.orm = orm;
+        self
+    }
+
+    /// Target database backend
+    pub fn db(mut self, db: DatabaseType) -> Self {
+        self.config.db = db;
+        self
+    }
+
+    /// Directory generated code is written to
+    pub fn output_dir(mut self, output_dir: impl Into<PathBuf>) -> Self {
+        self.config.output_dir = output_dir.into();
+        self
+    }
+
+    /// Add an HTTP header sent with introspection requests
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.config.headers.insert(name.into(), value.into());
+        self
+    }
+
+    /// Map a GraphQL type to a Rust type
+    pub fn type_mapping(
+        mut self,
+        graphql_type: impl Into<String>,
+        rust_type: impl Into<String>,
+    ) -> Self {
+        self.config
+            .type_mappings
+            .insert(graphql_type.into(), rust_type.into());
+        self
+    }
+
+    /// Map a GraphQL scalar to a Rust type
+    pub fn scalar_mapping(
+        mut self,
+        scalar: impl Into<String>,
+        rust_type: impl Into<String>,
+    ) -> Self {
+        self.config
+            .scalar_mappings
+            .insert(scalar.into(), rust_type.into());
+        self
+    }
+
+    /// Read the schema from an introspection JSON file instead of the url
+    pub fn introspection_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.introspection_file = Some(path.into());
+        self
+    }
+
+    /// Naming convention for tables and columns
+    pub fn table_naming(mut self, table_naming: TableNamingConvention) -> Self {
+        self.config.table_naming = table_naming;
+        self
+    }
+
+    /// Finish the config, checking that required options are set
+    pub fn build(self) -> anyhow::Result<Config> {
+        if self.config.url.trim().is_empty() && self.config.introspection_file.is_none() {
+            return Err(anyhow::anyhow!(
+                "Config url is required\n\nSet it with Config::builder().url(\"https://api.example.com/graphql\"), or read a saved schema with .introspection_file(\"schema.json\")."
+            ));
+        }
+        self.config.check_url()
+    }
+}
+
+/// Output style for Sea-ORM migrations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SeaOrmMigrationStyle {
+    /// Raw `up.sql`/`down.sql` files
+    Raw,
+    /// Rust migration crate using `SchemaManager` table builders
+    #[default]
+    Rust,
+}
+
 impl Config {
+    /// Start building a config programmatically
+    ///
+    /// ```rust
+    /// use graphql_codegen_rust::Config;
+    /// use graphql_codegen_rust::cli::{DatabaseType, OrmType};
+    ///
+    /// let config = Config::builder()
+    ///     .url("https://api.example.com/graphql")
+    ///     .orm(OrmType::SeaOrm)
+    ///     .db(DatabaseType::Postgres)
+    ///     .header("Authorization", "Bearer token123")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(config.output_dir.to_str(), Some("./generated"));
+    /// ```
+    pub fn builder() -> ConfigBuilder {
+        ConfigBuilder::default()
+    }
+
+    /// Schema file path relative to `output_dir`, or `None` when the ORM has none
+    pub fn schema_file(&self) -> Option<String> {
+        let default = match self.orm {
+            OrmType::Diesel => "src/schema.rs",
+            OrmType::SeaOrm => "mod.rs",
+            OrmType::Plain => return None,
+        };
+        Some(match &self.schema_file_path {
+            Some(path) => relative_path(path, ""),
+            None => default.to_string(),
+        })
+    }
+
+    /// Path of an entity file relative to `output_dir`
+    pub fn entity_file(&self, filename: &str) -> String {
+        let dir = self.entities_dir.as_deref();
+        relative_path(dir.unwrap_or(Path::new("src/entities")), filename)
+    }
+
+    /// Path of a migration file, given relative to the migration directory,
+    /// relative to `output_dir`
+    pub fn migration_file(&self, path: &str) -> String {
+        let dir = self.migrations_dir.as_deref();
+        relative_path(dir.unwrap_or(Path::new("migrations")), path)
+    }
+
+    /// Path of a module wiring file from [`CodeGenerator::generate_module_files`],
+    /// moving files of the default entity directory into `entities_dir`
+    ///
+    /// [`CodeGenerator::generate_module_files`]: crate::generator::CodeGenerator::generate_module_files
+    pub fn module_file(&self, path: &str) -> String {
+        match path.strip_prefix("src/entities/") {
+            Some(filename) => self.entity_file(filename),
+            None => path.to_string(),
+        }
+    }
+
+    /// Maximum length of a String field, from `string_length_mappings` or a
+    /// `@maxLength` directive
+    pub fn string_length(&self, type_name: &str, field: &ParsedField) -> Option<u32> {
+        self.string_length_mappings
+            .get(&format!("{}.{}", type_name, field.name))
+            .copied()
+            .or(field.max_length)
+    }
+
+    /// SQL column type override for a field, from `sql_type_overrides`
+    pub fn sql_type_override(&self, type_name: &str, field: &ParsedField) -> Option<&str> {
+        self.sql_type_overrides
+            .get(&format!("{}.{}", type_name, field.name))
+            .map(String::as_str)
+    }
+
+    /// Whether a field is unique, from `unique_fields` or a `@unique` directive
+    pub fn is_unique(&self, type_name: &str, field: &ParsedField) -> bool {
+        field.has_directive("unique")
+            || self
+                .unique_fields
+                .iter()
+                .any(|f| *f == format!("{}.{}", type_name, field.name))
+    }
+
+    /// Fields of a type's composite primary key, from `composite_keys` or two
+    /// or more `@id` directives; `None` for single-column keys
+    pub fn composite_key(&self, type_name: &str, parsed_type: &ParsedType) -> Option<Vec<String>> {
+        let fields = match self.composite_keys.get(type_name) {
+            Some(fields) => fields.clone(),
+            None => parsed_type
+                .fields
+                .iter()
+                .filter(|f| f.has_directive("id"))
+                .map(|f| f.name.clone())
+                .collect(),
+        };
+        (fields.len() > 1).then_some(fields)
+    }
+
+    /// Column default of a field in GraphQL notation, from the schema or
+    /// `default_values`
+    pub fn default_value<'a>(&'a self, type_name: &str, field: &'a ParsedField) -> Option<&'a str> {
+        field.default_value.as_deref().or_else(|| {
+            self.default_values
+                .get(&format!("{}.{}", type_name, field.name))
+                .map(String::as_str)
+        })
+    }
+
+    /// Whether a field is indexed, from `indexed_fields` or an `@index` directive
+    pub fn is_indexed(&self, type_name: &str, field: &ParsedField) -> bool {
+        field.has_directive("index")
+            || self
+                .indexed_fields
+                .iter()
+                .any(|f| *f == format!("{}.{}", type_name, field.name))
+    }
+
+    /// Type name patterns to skip, falling back to [`DEFAULT_SKIP_PATTERNS`]
+    pub fn skip_patterns(&self) -> Vec<String> {
+        match &self.skip_patterns {
+            Some(patterns) => patterns.clone(),
+            None => DEFAULT_SKIP_PATTERNS
+                .iter()
+                .map(|p| p.to_string())
+                .collect(),
+        }
+    }
+
     /// Load config from a file (auto-detects YAML or TOML)
     pub fn from_file(path: &PathBuf) -> anyhow::Result<Self> {
         let contents = fs::read_to_string(path).map_err(|e| {
@@ -321,6 +1673,20 @@ impl Config {
         } else {
             Self::from_toml_str(&contents)
         }
+        .and_then(Config::check_url)
+    }
+
+    /// Validate the endpoint url unless the schema comes from a file or the
+    /// url is a secret reference resolved later
+    fn check_url(self) -> anyhow::Result<Self> {
+        let is_secret = self
+            .url
+            .split_once("://")
+            .is_some_and(|(scheme, _)| self.secret_schemes.iter().any(|s| s == scheme));
+        if self.introspection_file.is_none() && !is_secret {
+            validate_endpoint_url(&self.url)?;
+        }
+        Ok(self)
     }
 
     /// Load config from TOML string
@@ -331,7 +1697,7 @@ impl Config {
                 e
             )
         })?;
-        Ok(config)
+        config.interpolate_env()
     }
 
     /// Load config from YAML string
@@ -353,7 +1719,7 @@ impl Config {
         // Use rust_codegen section if present, otherwise defaults
         let rust_config = yaml_config.rust_codegen.unwrap_or_default();
 
-        Ok(Config {
+        Config {
             url,
             orm: rust_config.orm,
             db: rust_config.db,
@@ -361,10 +1727,74 @@ impl Config {
             headers,
             type_mappings: rust_config.type_mappings,
             scalar_mappings: rust_config.scalar_mappings,
+            string_length_mappings: rust_config.string_length_mappings,
+            sql_type_overrides: rust_config.sql_type_overrides,
+            unique_fields: rust_config.unique_fields,
+            indexed_fields: rust_config.indexed_fields,
+            auto_fk_index: rust_config.auto_fk_index,
+            composite_keys: rust_config.composite_keys,
+            default_values: rust_config.default_values,
+            embed_as_json: rust_config.embed_as_json,
+            deterministic_output: rust_config.deterministic_output,
+            schema_file_path: rust_config.schema_file_path,
+            entities_dir: rust_config.entities_dir,
+            migrations_dir: rust_config.migrations_dir,
+            generate_lib_rs: rust_config.generate_lib_rs,
+            generate_cargo_toml: rust_config.generate_cargo_toml,
+            strict_scalars: rust_config.strict_scalars,
+            generate_changesets: rust_config.generate_changesets,
+            postgres_native_enums: rust_config.postgres_native_enums,
+            pluralize_tables: rust_config.pluralize_tables,
+            force: rust_config.force,
             table_naming: rust_config.table_naming,
+            id_mapping: rust_config.id_mapping,
+            pk_default: rust_config.pk_default,
+            sqlite_autoincrement: rust_config.sqlite_autoincrement,
+            datetime_crate: rust_config.datetime_crate,
+            enum_storage: rust_config.enum_storage,
+            prefix_enum_values: rust_config.prefix_enum_values,
             generate_migrations: rust_config.generate_migrations,
             generate_entities: rust_config.generate_entities,
-        })
+            schema_only: rust_config.schema_only,
+            single_file: rust_config.single_file,
+            incremental: rust_config.incremental,
+            only_changed_tables: rust_config.only_changed_tables,
+            format_output: rust_config.format_output,
+            generate_client: rust_config.generate_client,
+            generate_pagination: rust_config.generate_pagination,
+            generate_crud: rust_config.generate_crud,
+            generate_iden: rust_config.generate_iden,
+            generate_tests: rust_config.generate_tests,
+            skip_deprecated: rust_config.skip_deprecated,
+            int_as_i64: rust_config.int_as_i64,
+            preserve_graphql_names: rust_config.preserve_graphql_names,
+            feature_per_entity: rust_config.feature_per_entity,
+            feature_map: rust_config.feature_map,
+            include_types: rust_config.include_types,
+            exclude_types: rust_config.exclude_types,
+            generate_root_types: rust_config.generate_root_types,
+            skip_patterns: rust_config.skip_patterns,
+            continue_on_error: rust_config.continue_on_error,
+            line_endings: rust_config.line_endings,
+            secret_schemes: rust_config.secret_schemes,
+            dump_introspection: rust_config.dump_introspection,
+            introspection_file: rust_config.introspection_file,
+            ca_cert: rust_config.ca_cert,
+            introspection: rust_config.introspection,
+            sea_orm_migration_style: rust_config.sea_orm_migration_style,
+        }
+        .interpolate_env()
+    }
+
+    /// Expand `${VAR}` and `$VAR` references in the url and header values
+    fn interpolate_env(mut self) -> anyhow::Result<Self> {
+        self.url = interpolate_env_vars(&self.url)
+            .map_err(|e| anyhow::anyhow!("Failed to expand url: {}", e))?;
+        for (name, value) in self.headers.iter_mut() {
+            *value = interpolate_env_vars(value)
+                .map_err(|e| anyhow::anyhow!("Failed to expand header '{}': {}", name, e))?;
+        }
+        Ok(self)
     }
 
     /// Save config to a TOML file
@@ -414,8 +1844,13 @@ impl From<&crate::cli::Commands> for Config {
                 db,
                 output,
                 headers,
+                auth,
+                ca_cert,
             } => {
-                let headers_map = headers.iter().cloned().collect();
+                let mut headers_map: HashMap<String, String> = headers.iter().cloned().collect();
+                if let Some(value) = auth.config_header() {
+                    headers_map.insert("Authorization".to_string(), value);
+                }
 
                 Config {
                     url: url.clone(),
@@ -425,12 +1860,75 @@ impl From<&crate::cli::Commands> for Config {
                     headers: headers_map,
                     type_mappings: HashMap::new(),
                     scalar_mappings: HashMap::new(),
+                    string_length_mappings: HashMap::new(),
+                    sql_type_overrides: HashMap::new(),
+                    unique_fields: Vec::new(),
+                    indexed_fields: Vec::new(),
+                    auto_fk_index: true,
+                    composite_keys: HashMap::new(),
+                    default_values: HashMap::new(),
+                    embed_as_json: Vec::new(),
+                    deterministic_output: false,
+                    schema_file_path: None,
+                    entities_dir: None,
+                    migrations_dir: None,
+                    generate_lib_rs: true,
+                    generate_cargo_toml: false,
+                    strict_scalars: false,
+                    generate_changesets: true,
+                    postgres_native_enums: false,
+                    pluralize_tables: false,
+                    force: false,
                     table_naming: TableNamingConvention::default(),
+                    id_mapping: IdMapping::default(),
+                    pk_default: PkDefault::default(),
+                    sqlite_autoincrement: false,
+                    datetime_crate: DateTimeCrate::default(),
+                    enum_storage: EnumStorage::default(),
+                    prefix_enum_values: default_enum_prefix(),
                     generate_migrations: true,
                     generate_entities: true,
+                    schema_only: false,
+                    single_file: false,
+                    incremental: false,
+                    only_changed_tables: false,
+                    format_output: true,
+                    generate_client: false,
+                    generate_pagination: false,
+                    generate_crud: false,
+                    generate_iden: false,
+                    generate_tests: false,
+                    skip_deprecated: false,
+                    int_as_i64: false,
+                    preserve_graphql_names: false,
+                    feature_per_entity: false,
+                    feature_map: HashMap::new(),
+                    include_types: Vec::new(),
+                    exclude_types: Vec::new(),
+                    generate_root_types: false,
+                    skip_patterns: None,
+                    continue_on_error: false,
+                    line_endings: LineEndings::default(),
+                    secret_schemes: Vec::new(),
+                    dump_introspection: None,
+                    introspection_file: None,
+                    ca_cert: ca_cert.clone(),
+                    introspection: IntrospectionRequest::default(),
+                    sea_orm_migration_style: SeaOrmMigrationStyle::default(),
                 }
             }
             _ => unreachable!("Config can only be created from Init command"),
         }
     }
 }
+
+/// `dir` joined with `rest` as a `/` separated relative path
+fn relative_path(dir: &Path, rest: &str) -> String {
+    let dir = dir.to_string_lossy().replace('\\', "/");
+    let dir = dir.trim_start_matches("./").trim_end_matches('/');
+    match (dir.is_empty(), rest.is_empty()) {
+        (true, _) => rest.to_string(),
+        (false, true) => dir.to_string(),
+        (false, false) => format!("{}/{}", dir, rest),
+    }
+}
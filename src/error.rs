@@ -0,0 +1,53 @@
+//! Error type returned by the public generation entry points.
+//!
+//! Internals use [`anyhow`] for context-rich messages; [`CodegenError`] wraps
+//! those errors in a variant per pipeline stage so callers can match on what
+//! failed while the messages stay the same.
+
+/// Failure kinds of [`CodeGenerator::generate_from_config`](crate::CodeGenerator::generate_from_config)
+/// and [`generate_from_config_file`](crate::generate_from_config_file).
+#[derive(Debug, thiserror::Error)]
+pub enum CodegenError {
+    /// The GraphQL endpoint couldn't be reached
+    #[error(transparent)]
+    Network(anyhow::Error),
+
+    /// The endpoint answered, but not with a usable introspection result
+    #[error(transparent)]
+    Introspection(anyhow::Error),
+
+    /// The introspected schema couldn't be converted for generation
+    #[error(transparent)]
+    Parse(anyhow::Error),
+
+    /// The configuration file or a configured value is invalid
+    #[error(transparent)]
+    Config(anyhow::Error),
+
+    /// Reading or writing files failed
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// Code generation failed
+    #[error(transparent)]
+    Generation(#[from] anyhow::Error),
+}
+
+impl CodegenError {
+    /// Classify an introspection request failure; transport errors are `Network`
+    pub(crate) fn introspection(error: anyhow::Error) -> Self {
+        if error.downcast_ref::<reqwest::Error>().is_some() {
+            Self::Network(error)
+        } else {
+            Self::Introspection(error)
+        }
+    }
+
+    /// Classify a generation failure, keeping file system errors distinct
+    pub(crate) fn generation(error: anyhow::Error) -> Self {
+        match error.downcast::<std::io::Error>() {
+            Ok(error) => Self::Io(error),
+            Err(error) => Self::Generation(error),
+        }
+    }
+}
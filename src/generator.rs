@@ -1,10 +1,19 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
 use crate::cli::{DatabaseType, OrmType};
-use crate::config::Config;
-use crate::parser::{ParsedField, ParsedSchema};
+use crate::config::{
+    Config, DateTimeCrate, EnumStorage, IdMapping, PkDefault, SeaOrmMigrationStyle,
+};
+use crate::manifest::{Manifest, TableSnapshot};
+use crate::parser::{ParsedEnum, ParsedField, ParsedSchema, ParsedType};
 
+pub mod cargo;
+pub mod client;
 pub mod diesel;
+pub mod plain;
 pub mod sea_orm;
 
 pub trait CodeGenerator {
@@ -19,20 +28,1215 @@ pub trait CodeGenerator {
         schema: &ParsedSchema,
         config: &Config,
     ) -> anyhow::Result<Vec<MigrationFile>>;
+
+    /// Crate root registering the Rust `migrations`, written to
+    /// `migrations/src/lib.rs`, or `None` when the migrations need none.
+    /// It isn't a migration itself.
+    fn generate_migrator(
+        &self,
+        _migrations: &[MigrationFile],
+        _config: &Config,
+    ) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+
+    /// Module files wiring the generated code together, as `(path, code)` pairs
+    /// with paths relative to the output directory
+    fn generate_module_files(
+        &self,
+        _schema: &ParsedSchema,
+        _config: &Config,
+    ) -> anyhow::Result<Vec<(String, String)>> {
+        Ok(Vec::new())
+    }
+
+    /// Tables, entities and enums as one self-contained file with no `use super::`
+    /// imports, suitable for `include!`
+    fn generate_single_file(
+        &self,
+        _schema: &ParsedSchema,
+        _config: &Config,
+    ) -> anyhow::Result<String> {
+        anyhow::bail!("This generator does not support single-file output")
+    }
 }
 
 #[derive(Debug)]
-pub struct MigrationFile {
-    pub name: String,
-    pub up_sql: String,
-    pub down_sql: String,
+pub enum MigrationFile {
+    /// SQL migration written to `migrations/<name>/up.sql` and `down.sql`
+    Sql {
+        name: String,
+        up_sql: String,
+        down_sql: String,
+    },
+    /// Rust source written to `migrations/src/<name>.rs`
+    Rust { name: String, code: String },
+}
+
+impl MigrationFile {
+    pub fn name(&self) -> &str {
+        match self {
+            MigrationFile::Sql { name, .. } | MigrationFile::Rust { name, .. } => name,
+        }
+    }
+}
+
+/// Concrete storage type for GraphQL `ID` values, resolved from the config's `id_mapping`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdType {
+    Integer,
+    String,
+    Uuid,
+}
+
+impl IdType {
+    /// Resolve the effective ID type for a database and ID mapping
+    pub fn resolve(db_type: &DatabaseType, id_mapping: &IdMapping) -> Self {
+        match id_mapping {
+            IdMapping::Auto => match db_type {
+                DatabaseType::Sqlite | DatabaseType::Mysql => IdType::Integer,
+                DatabaseType::Postgres => IdType::Uuid,
+            },
+            IdMapping::Integer => IdType::Integer,
+            IdMapping::String => IdType::String,
+            IdMapping::Uuid => IdType::Uuid,
+        }
+    }
+
+    /// Rust type used for ID values
+    pub fn rust_type(&self, db_type: &DatabaseType) -> &'static str {
+        match self {
+            IdType::Integer => match db_type {
+                DatabaseType::Sqlite | DatabaseType::Postgres => "i32",
+                DatabaseType::Mysql => "u32",
+            },
+            IdType::String => "String",
+            IdType::Uuid => "uuid::Uuid",
+        }
+    }
+
+    /// Diesel column type used for ID values
+    pub fn diesel_type(&self, db_type: &DatabaseType) -> &'static str {
+        match self {
+            IdType::Integer => match db_type {
+                DatabaseType::Sqlite | DatabaseType::Postgres => "Integer",
+                DatabaseType::Mysql => "Unsigned<Integer>",
+            },
+            IdType::String => "Text",
+            IdType::Uuid => match db_type {
+                DatabaseType::Postgres => "Uuid",
+                DatabaseType::Sqlite | DatabaseType::Mysql => "Text",
+            },
+        }
+    }
+
+    /// SQL column type used for ID values
+    pub fn sql_type(&self, db_type: &DatabaseType) -> &'static str {
+        match self {
+            IdType::Integer => match db_type {
+                DatabaseType::Sqlite | DatabaseType::Postgres => "INTEGER",
+                DatabaseType::Mysql => "INT UNSIGNED",
+            },
+            IdType::String => match db_type {
+                DatabaseType::Sqlite | DatabaseType::Postgres => "TEXT",
+                // MySQL cannot index TEXT columns without a prefix length
+                DatabaseType::Mysql => "VARCHAR(191)",
+            },
+            IdType::Uuid => match db_type {
+                DatabaseType::Postgres => "UUID",
+                DatabaseType::Sqlite => "TEXT",
+                DatabaseType::Mysql => "CHAR(36)",
+            },
+        }
+    }
+
+    /// Whether the database assigns primary key values of this type
+    pub fn is_auto_increment(&self) -> bool {
+        matches!(self, IdType::Integer)
+    }
+
+    /// SQL expression the database uses to generate key values, if any
+    pub fn default_sql(
+        &self,
+        db_type: &DatabaseType,
+        pk_default: &PkDefault,
+    ) -> Option<&'static str> {
+        match (self, db_type, pk_default) {
+            (IdType::Uuid, DatabaseType::Postgres, PkDefault::UuidGenerateV4) => {
+                Some("uuid_generate_v4()")
+            }
+            (IdType::Uuid, DatabaseType::Postgres, _) => Some("gen_random_uuid()"),
+            (IdType::Uuid, DatabaseType::Mysql, PkDefault::Auto) => None,
+            (IdType::Uuid, DatabaseType::Mysql, _) => Some("(UUID())"),
+            _ => None,
+        }
+    }
+
+    /// Statement enabling the extension that provides `default_sql`, if one is needed
+    pub fn extension_sql(
+        &self,
+        db_type: &DatabaseType,
+        pk_default: &PkDefault,
+    ) -> Option<&'static str> {
+        match (self, db_type, pk_default) {
+            (IdType::Uuid, DatabaseType::Postgres, PkDefault::GenRandomUuid) => {
+                Some("CREATE EXTENSION IF NOT EXISTS pgcrypto;")
+            }
+            (IdType::Uuid, DatabaseType::Postgres, PkDefault::UuidGenerateV4) => {
+                Some("CREATE EXTENSION IF NOT EXISTS \"uuid-ossp\";")
+            }
+            _ => None,
+        }
+    }
+
+    /// SQL type and constraints for a primary key column of this type
+    ///
+    /// SQLite integer keys only get `AUTOINCREMENT` when `sqlite_autoincrement`
+    /// is set; a plain `INTEGER PRIMARY KEY` already aliases the rowid.
+    pub fn primary_key_sql(
+        &self,
+        db_type: &DatabaseType,
+        pk_default: &PkDefault,
+        sqlite_autoincrement: bool,
+    ) -> String {
+        match (self, db_type) {
+            (IdType::Integer, DatabaseType::Sqlite) if sqlite_autoincrement => {
+                "INTEGER PRIMARY KEY AUTOINCREMENT".to_string()
+            }
+            (IdType::Integer, DatabaseType::Sqlite) => "INTEGER PRIMARY KEY".to_string(),
+            (IdType::Integer, DatabaseType::Postgres) => "SERIAL PRIMARY KEY".to_string(),
+            (IdType::Integer, DatabaseType::Mysql) => {
+                "INT UNSIGNED PRIMARY KEY AUTO_INCREMENT".to_string()
+            }
+            _ => match self.default_sql(db_type, pk_default) {
+                Some(default) => {
+                    format!("{} PRIMARY KEY DEFAULT {}", self.sql_type(db_type), default)
+                }
+                None => format!("{} PRIMARY KEY", self.sql_type(db_type)),
+            },
+        }
+    }
+}
+
+/// Custom scalars used by fields of `schema` that have no mapping and fall
+/// back to `String`, sorted by name
+pub fn fallback_scalars(schema: &ParsedSchema, config: &Config) -> Vec<String> {
+    crate::validation::unmapped_scalars(schema, config)
+        .into_iter()
+        .filter(|scalar| {
+            schema.types.values().any(|parsed_type| {
+                parsed_type.fields.iter().any(|field| {
+                    matches!(&field.field_type, crate::parser::FieldType::Scalar(name) if name == scalar)
+                })
+            })
+        })
+        .collect()
+}
+
+/// Narrow a parsed schema to the types that should become entities and tables.
+///
+/// Applies the `include_types`/`exclude_types` filters, drops types matching
+/// `skip_patterns` and, unless `generate_root_types` is set, drops the root
+/// operation types. Roots come from the schema itself, falling back to `Query`,
+/// `Mutation` and `Subscription` when the schema doesn't declare any. With
+/// `skip_deprecated`, deprecated enum values are dropped too. Fields listed in
+/// `embed_as_json` become `JSON` scalar fields stored in a single column;
+/// entries for filtered-out types are ignored.
+/// Fails when two of the remaining types map to the same table name, two
+/// values of an enum to the same variant, or a type is named like a Rust
+/// prelude type such as `String`, and with
+/// `strict_scalars` when a field's custom scalar has no Rust type.
+pub fn prepare_schema(schema: &ParsedSchema, config: &Config) -> anyhow::Result<ParsedSchema> {
+    let schema = narrow_schema(schema, config)?;
+
+    // Colliding types would overwrite each other's table and entity file
+    let collisions = crate::validation::find_table_collisions(&schema, config);
+    if !collisions.is_empty() {
+        let details: Vec<String> = collisions
+            .iter()
+            .map(|(table_name, type_names)| {
+                format!("'{}' from {}", table_name, type_names.join(", "))
+            })
+            .collect();
+        anyhow::bail!(
+            "Multiple types map to the same table name: {}\nRename them or exclude all but one with exclude_types",
+            details.join("; ")
+        );
+    }
+
+    // Colliding values would become duplicate variants
+    let collisions = crate::validation::find_variant_collisions(&schema, config);
+    if !collisions.is_empty() {
+        let details: Vec<String> = collisions
+            .iter()
+            .map(|(enum_name, variant, values)| {
+                format!("{} {} as '{}'", enum_name, values.join(" and "), variant)
+            })
+            .collect();
+        anyhow::bail!(
+            "Enum values map to the same Rust variant: {}\nRename them in the schema or exclude the enum with exclude_types",
+            details.join("; ")
+        );
+    }
+
+    // Their structs would shadow the prelude types the generated code uses
+    let shadows = crate::validation::find_prelude_shadows(&schema);
+    if !shadows.is_empty() {
+        anyhow::bail!(
+            "Types named like Rust prelude types would shadow them: {}\nRename them or exclude them with exclude_types",
+            shadows.join(", ")
+        );
+    }
+
+    Ok(schema)
+}
+
+/// [`prepare_schema`] without the table name collision check, for `validate`
+/// to report collisions alongside its other diagnostics
+pub(crate) fn narrow_schema(
+    schema: &ParsedSchema,
+    config: &Config,
+) -> anyhow::Result<ParsedSchema> {
+    let mut exclude = config.exclude_types.clone();
+
+    for pattern in config.skip_patterns() {
+        let regex = regex::Regex::new(&format!("^(?:{})$", pattern))
+            .map_err(|e| anyhow::anyhow!("Invalid skip pattern '{}': {}", pattern, e))?;
+        exclude.extend(
+            schema
+                .types
+                .keys()
+                .filter(|name| regex.is_match(name))
+                .cloned(),
+        );
+    }
+
+    if !config.generate_root_types {
+        let roots = [
+            &schema.query_root,
+            &schema.mutation_root,
+            &schema.subscription_root,
+        ];
+        if roots.iter().all(|root| root.is_none()) {
+            exclude.extend(["Query", "Mutation", "Subscription"].map(String::from));
+        } else {
+            exclude.extend(roots.into_iter().flatten().cloned());
+        }
+    }
+
+    let full_schema = schema;
+    let mut schema = schema.filter_types(&config.include_types, &exclude);
+
+    if config.skip_deprecated {
+        for parsed_enum in schema.enums.values_mut() {
+            let deprecations = std::mem::take(&mut parsed_enum.deprecations);
+            if deprecations.is_empty() {
+                continue;
+            }
+            // Pin the positional discriminants first, so the remaining values
+            // keep the integers they're stored as
+            parsed_enum.discriminants = enum_discriminants(parsed_enum)?
+                .into_iter()
+                .map(|(value, discriminant)| (value.to_string(), discriminant))
+                .collect();
+            parsed_enum
+                .values
+                .retain(|value| !deprecations.contains_key(value));
+        }
+    }
+
+    if config.strict_scalars {
+        let scalars = fallback_scalars(&schema, config);
+        if !scalars.is_empty() {
+            anyhow::bail!(
+                "No Rust type for custom scalar(s): {}\nAdd them to scalar_mappings or turn off strict_scalars",
+                scalars.join(", ")
+            );
+        }
+    }
+
+    for path in &config.embed_as_json {
+        // Fields of filtered-out types have nothing left to embed into
+        let type_name = path.split_once('.').map_or(path.as_str(), |(t, _)| t);
+        if !schema.types.contains_key(type_name) && full_schema.types.contains_key(type_name) {
+            continue;
+        }
+        let field = path
+            .split_once('.')
+            .and_then(|(type_name, field_name)| {
+                schema
+                    .types
+                    .get_mut(type_name)?
+                    .fields
+                    .iter_mut()
+                    .find(|f| f.name == field_name)
+            })
+            .ok_or_else(|| anyhow::anyhow!("embed_as_json names unknown field '{}'", path))?;
+        // The whole value, lists included, is one JSON document
+        field.field_type = crate::parser::FieldType::Scalar("JSON".to_string());
+        field.graphql_type = if field.is_nullable { "JSON" } else { "JSON!" }.to_string();
+        field.is_list = false;
+        field.list_depth = 0;
+    }
+
+    for (type_name, parsed_type) in &schema.types {
+        for key in config
+            .composite_key(type_name, parsed_type)
+            .unwrap_or_default()
+        {
+            if !parsed_type.fields.iter().any(|f| f.name == key) {
+                anyhow::bail!(
+                    "Composite key of '{}' names unknown field '{}'",
+                    type_name,
+                    key
+                );
+            }
+        }
+    }
+
+    Ok(schema)
+}
+
+/// GraphQL names of a type's primary key fields: its composite key when it
+/// has one, otherwise the single detected key, or none for field-less types
+/// that get an implicit `id` column
+pub fn primary_key_names(
+    type_name: &str,
+    parsed_type: &ParsedType,
+    config: &Config,
+) -> Vec<String> {
+    config
+        .composite_key(type_name, parsed_type)
+        .unwrap_or_else(|| {
+            parsed_type
+                .primary_key_field()
+                .map(|f| vec![f.name.clone()])
+                .unwrap_or_default()
+        })
+}
+
+/// A type whose generation failed or panicked
+#[derive(Debug)]
+pub struct TypeFailure {
+    pub type_name: String,
+    pub error: anyhow::Error,
+}
+
+/// Generate every type on its own and collect the ones that fail.
+///
+/// Panics are caught and reported like errors, so one malformed type can't
+/// take down the whole run. This generates once per type, so it's only worth
+/// running once generating the whole schema has failed.
+pub fn find_failing_types(
+    schema: &ParsedSchema,
+    config: &Config,
+    generator: &dyn CodeGenerator,
+) -> Vec<TypeFailure> {
+    let mut type_names: Vec<&String> = schema.types.keys().collect();
+    type_names.sort();
+
+    let mut failures = Vec::new();
+    for type_name in type_names {
+        let mut single = schema.clone();
+        single.types.retain(|name, _| name == type_name);
+
+        let result = catch_panic(|| {
+            generator.generate_schema(&single, config)?;
+            generator.generate_entities(&single, config)?;
+            if config.generate_migrations {
+                generator.generate_migrations(&single, config)?;
+            }
+            anyhow::Ok(())
+        });
+        let error = match result {
+            Ok(Ok(())) => continue,
+            Ok(Err(error)) => error,
+            Err(message) => anyhow::anyhow!("panicked: {}", message),
+        };
+        failures.push(TypeFailure {
+            type_name: type_name.clone(),
+            error,
+        });
+    }
+    failures
+}
+
+/// Run `generate`, returning the message of a panic as `Err`.
+///
+/// The process-wide panic hook is left alone, so the panic is still printed
+/// by it and panics on other threads aren't affected.
+pub fn catch_panic<T>(generate: impl FnOnce() -> T) -> Result<T, String> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(generate)).map_err(|panic| {
+        panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+/// Migrations for the tables whose SQL changed since the snapshot in `previous`.
+///
+/// Records the current SQL of every table in `snapshot`. New tables get their
+/// usual create migration, changed tables one that adds and drops columns with
+/// `ALTER TABLE` (see [`alter_table_sql`]), and tables whose type is gone from
+/// `full_schema` one that drops them, reversed with the previous SQL. Unchanged
+/// tables get no migration, and tables filtered out of `schema` or skipped keep
+/// their previous snapshot.
+pub fn changed_table_migrations(
+    full_schema: &ParsedSchema,
+    schema: &ParsedSchema,
+    config: &Config,
+    generator: &dyn CodeGenerator,
+    previous: &Manifest,
+    snapshot: &mut Manifest,
+) -> anyhow::Result<Vec<MigrationFile>> {
+    if config.orm == OrmType::SeaOrm && config.sea_orm_migration_style == SeaOrmMigrationStyle::Rust
+    {
+        anyhow::bail!(
+            "only_changed_tables needs SQL migrations; set sea_orm_migration_style to Raw"
+        );
+    }
+
+    let mut tables: Vec<String> = schema
+        .types
+        .iter()
+        .filter(|(_, t)| matches!(t.kind, crate::parser::TypeKind::Object))
+        .map(|(name, _)| entity_table_name(name, config))
+        .collect();
+    tables.sort();
+
+    let generated = generator.generate_migrations(schema, config)?;
+    let stamp = chrono::Utc::now().timestamp();
+    let mut migrations = Vec::new();
+
+    for table in tables {
+        let suffix = format!("create_{}_table", table);
+        let Some(MigrationFile::Sql {
+            name,
+            up_sql,
+            down_sql,
+        }) = generated
+            .iter()
+            .find(|m| m.name() == suffix || m.name().ends_with(&format!("_{}", suffix)))
+        else {
+            continue;
+        };
+        let current = TableSnapshot {
+            up_sql: up_sql.clone(),
+            down_sql: down_sql.clone(),
+        };
+
+        match previous.tables.get(&table) {
+            None => migrations.push(MigrationFile::Sql {
+                name: name.clone(),
+                up_sql: up_sql.clone(),
+                down_sql: down_sql.clone(),
+            }),
+            Some(old) if *old != current => {
+                let (up_sql, down_sql) = alter_table_sql(&table, &old.up_sql, up_sql)?;
+                migrations.push(MigrationFile::Sql {
+                    name: format!("m{}_update_{}_table", stamp, table),
+                    up_sql,
+                    down_sql,
+                });
+            }
+            Some(_) => {}
+        }
+        snapshot.tables.insert(table, current);
+    }
+
+    let live_tables = schema_table_names(full_schema, config);
+    for (table, old) in &previous.tables {
+        if snapshot.tables.contains_key(table) {
+            continue;
+        }
+        if live_tables.contains(table) {
+            snapshot.tables.insert(table.clone(), old.clone());
+        } else {
+            migrations.push(MigrationFile::Sql {
+                name: format!("m{}_drop_{}_table", stamp, table),
+                up_sql: old.down_sql.clone(),
+                down_sql: old.up_sql.clone(),
+            });
+        }
+    }
+
+    Ok(migrations)
+}
+
+/// Table a generated migration named like `create_{table}_table`, optionally
+/// after a version such as `m0001_`, creates, updates or drops
+fn migration_table(name: &str) -> Option<&str> {
+    let name = match name.split_once('_') {
+        Some((version, rest))
+            if version
+                .trim_start_matches('m')
+                .chars()
+                .all(|c| c.is_ascii_digit()) =>
+        {
+            rest
+        }
+        _ => name,
+    };
+    ["create_", "update_", "drop_"]
+        .iter()
+        .find_map(|action| name.strip_prefix(action))?
+        .strip_suffix("_table")
+}
+
+/// Table names of every object type in `schema`.
+///
+/// Given the unfiltered schema, these are the tables that still exist even when
+/// a run filters their type out or skips it.
+pub fn schema_table_names(schema: &ParsedSchema, config: &Config) -> BTreeSet<String> {
+    schema
+        .types
+        .iter()
+        .filter(|(_, t)| matches!(t.kind, crate::parser::TypeKind::Object))
+        .map(|(name, _)| entity_table_name(name, config))
+        .collect()
+}
+
+/// `ALTER TABLE` statements taking a table from its `old` create SQL to `new`,
+/// with the statements reverting them.
+///
+/// Only added and dropped columns can be migrated this way. Anything else, such
+/// as a column whose type changed, a new key or index, or an added `NOT NULL`
+/// column without a default, fails rather than falling back to recreating the
+/// table, which would lose its rows.
+fn alter_table_sql(table: &str, old: &str, new: &str) -> anyhow::Result<(String, String)> {
+    let cannot_alter = |reason: String| {
+        anyhow::anyhow!(
+            "Table '{}' can't be migrated in place: {}\n\nonly_changed_tables only adds and drops columns; write this migration by hand",
+            table,
+            reason
+        )
+    };
+    let (Some(before), Some(after)) = (
+        TableDefinition::parse(old, table),
+        TableDefinition::parse(new, table),
+    ) else {
+        return Err(cannot_alter(
+            "its CREATE TABLE statement wasn't found".to_string(),
+        ));
+    };
+    if before.rest != after.rest {
+        return Err(cannot_alter(
+            "its keys, constraints, indexes or comments changed".to_string(),
+        ));
+    }
+
+    let mut up = Vec::new();
+    let mut down = Vec::new();
+    for (name, definition) in &before.columns {
+        match after.column(name) {
+            Some(changed) if changed != definition => {
+                return Err(cannot_alter(format!(
+                    "column '{}' changed from `{}` to `{}`",
+                    name, definition, changed
+                )));
+            }
+            Some(_) => {}
+            None => {
+                up.push(format!("ALTER TABLE {} DROP COLUMN {};", table, name));
+                down.push(format!("ALTER TABLE {} ADD COLUMN {};", table, definition));
+            }
+        }
+    }
+    for (name, definition) in &after.columns {
+        if before.column(name).is_some() {
+            continue;
+        }
+        if definition.contains(" PRIMARY KEY") || definition.contains(" UNIQUE") {
+            return Err(cannot_alter(format!(
+                "new column '{}' is a key (`{}`)",
+                name, definition
+            )));
+        }
+        if definition.contains(" NOT NULL") && !definition.contains(" DEFAULT ") {
+            return Err(cannot_alter(format!(
+                "new column '{}' is NOT NULL without a default, so existing rows have no value for it",
+                name
+            )));
+        }
+        up.push(format!("ALTER TABLE {} ADD COLUMN {};", table, definition));
+        down.push(format!("ALTER TABLE {} DROP COLUMN {};", table, name));
+    }
+    down.reverse();
+
+    Ok((up.join("\n"), down.join("\n")))
+}
+
+/// A generated `CREATE TABLE` split into its column definitions and the rest of
+/// the create SQL (table constraints, indexes, comments).
+struct TableDefinition {
+    columns: Vec<(String, String)>,
+    rest: String,
+}
+
+impl TableDefinition {
+    fn parse(sql: &str, table: &str) -> Option<Self> {
+        let open = format!("CREATE TABLE {} (\n", table);
+        let start = sql.find(&open)? + open.len();
+        let end = start + sql[start..].find("\n)")?;
+
+        let mut columns = Vec::new();
+        let mut constraints = Vec::new();
+        for line in sql[start..end].split(",\n").map(str::trim) {
+            let name = line.split_whitespace().next().unwrap_or_default();
+            if matches!(
+                name,
+                "PRIMARY" | "FOREIGN" | "CONSTRAINT" | "UNIQUE" | "CHECK"
+            ) {
+                constraints.push(line);
+            } else {
+                columns.push((name.to_string(), line.to_string()));
+            }
+        }
+        let rest = format!(
+            "{}{}{}",
+            &sql[..start],
+            constraints.join(",\n"),
+            &sql[end..]
+        );
+        Some(Self { columns, rest })
+    }
+
+    fn column(&self, name: &str) -> Option<&String> {
+        self.columns
+            .iter()
+            .find(|(column, _)| column == name)
+            .map(|(_, definition)| definition)
+    }
+}
+
+/// Create a scratch directory for a check run, seeded with the manifest of
+/// `output_dir` so incremental modes behave as they would there
+pub fn create_check_dir(output_dir: &Path) -> anyhow::Result<PathBuf> {
+    let check_dir = std::env::temp_dir().join(format!(
+        "graphql-codegen-rust-check-{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    fs_err::create_dir_all(&check_dir)?;
+
+    let manifest_path = Manifest::path(output_dir);
+    if manifest_path.exists() {
+        fs_err::copy(&manifest_path, Manifest::path(&check_dir))?;
+    }
+    Ok(check_dir)
+}
+
+/// Start of the banner line marking generated files, after the comment marker
+const BANNER_PREFIX: &str = "@generated by graphql-codegen-rust";
+
+/// Text of the banner placed at the top of every generated file.
+///
+/// Names the generator version and, unless `deterministic_output` is set, the
+/// time of generation. Compute it once per run so all files share a timestamp.
+pub fn banner_text(config: &Config) -> String {
+    let banner = format!(
+        "{} v{} \u{2014} do not edit",
+        BANNER_PREFIX,
+        env!("CARGO_PKG_VERSION")
+    );
+    if config.deterministic_output {
+        banner
+    } else {
+        format!(
+            "{} ({})",
+            banner,
+            chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        )
+    }
+}
+
+/// Prefix `content` with `banner` as a comment suited to the file at `path`.
+///
+/// SQL files get a `--` comment, TOML files `#` and everything else `//`.
+/// Content that already starts with a banner is returned unchanged.
+pub fn with_banner(path: &str, content: &str, banner: &str) -> String {
+    if is_generated(content) {
+        return content.to_string();
+    }
+    let marker = if path.ends_with(".sql") {
+        "--"
+    } else if path.ends_with(".toml") {
+        "#"
+    } else {
+        "//"
+    };
+    format!("{} {}\n{}", marker, banner, content)
+}
+
+/// Whether `content` starts with the banner of a generated file
+pub fn is_generated(content: &str) -> bool {
+    strip_banner(content).len() != content.len()
+}
+
+/// `content` without its leading banner line, if it has one
+pub fn strip_banner(content: &str) -> &str {
+    let is_banner = ["// ", "-- ", "# "].iter().any(|marker| {
+        content
+            .strip_prefix(marker)
+            .is_some_and(|rest| rest.starts_with(BANNER_PREFIX))
+    });
+    if !is_banner {
+        return content;
+    }
+    content.find('\n').map_or("", |end| &content[end + 1..])
+}
+
+/// Files generated into `generated_dir` that are missing from `output_dir` or
+/// differ from it, as sorted paths relative to both
+pub fn changed_files(generated_dir: &Path, output_dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    fn visit(
+        dir: &Path,
+        generated_dir: &Path,
+        output_dir: &Path,
+        changed: &mut Vec<PathBuf>,
+    ) -> anyhow::Result<()> {
+        for entry in fs_err::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                visit(&path, generated_dir, output_dir, changed)?;
+                continue;
+            }
+            let relative = path.strip_prefix(generated_dir)?.to_path_buf();
+            // The banner's timestamp alone doesn't make a file out of date
+            let existing = fs_err::read_to_string(output_dir.join(&relative)).ok();
+            let generated = fs_err::read_to_string(&path)?;
+            if existing.as_deref().map(strip_banner) != Some(strip_banner(&generated)) {
+                changed.push(relative);
+            }
+        }
+        Ok(())
+    }
+
+    let mut changed = Vec::new();
+    visit(generated_dir, generated_dir, output_dir, &mut changed)?;
+    changed.sort();
+    Ok(changed)
+}
+
+/// Rust and SQL files under `output_dir`, relative to it, each with whether
+/// it starts with the generated banner. Hidden entries, such as the manifest
+/// or `.git`, and `target` are left out.
+fn source_files(output_dir: &Path) -> anyhow::Result<Vec<(PathBuf, bool)>> {
+    fn visit(
+        dir: &Path,
+        output_dir: &Path,
+        files: &mut Vec<(PathBuf, bool)>,
+    ) -> anyhow::Result<()> {
+        for entry in fs_err::read_dir(dir)? {
+            let path = entry?.path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if name.starts_with('.') || name == "target" {
+                continue;
+            }
+            if path.is_dir() {
+                visit(&path, output_dir, files)?;
+                continue;
+            }
+            if !matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("rs" | "sql")
+            ) {
+                continue;
+            }
+            let content = fs_err::read(&path)?;
+            files.push((
+                path.strip_prefix(output_dir)?.to_path_buf(),
+                is_generated(&String::from_utf8_lossy(&content)),
+            ));
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    if output_dir.is_dir() {
+        visit(output_dir, output_dir, &mut files)?;
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Rust and SQL files in the output directory that this tool didn't generate,
+/// as sorted paths relative to it.
+///
+/// Generating could overwrite them, so this fails unless `force` is set, in
+/// which case the files are returned for the caller to warn about.
+pub fn check_foreign_files(config: &Config) -> anyhow::Result<Vec<PathBuf>> {
+    let foreign: Vec<PathBuf> = source_files(&config.output_dir)?
+        .into_iter()
+        .filter(|(_, generated)| !generated)
+        .map(|(path, _)| path)
+        .collect();
+    if !foreign.is_empty() && !config.force {
+        let list: Vec<String> = foreign
+            .iter()
+            .map(|path| format!("  - {}", path.display()))
+            .collect();
+        anyhow::bail!(
+            "Output directory {} contains files not generated by graphql-codegen-rust:\n{}\n\nGenerating could overwrite them. Move them out of the output directory, or pass --force to generate anyway.",
+            config.output_dir.display(),
+            list.join("\n")
+        );
+    }
+    Ok(foreign)
+}
+
+/// Remove the [`stale_files`] of the output directory. Directories emptied
+/// along the way go too. Returns the removed files, relative to the output
+/// directory.
+pub fn remove_stale_files(
+    schema: &ParsedSchema,
+    config: &Config,
+    written: &BTreeSet<PathBuf>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let stale = stale_files(schema, config, written)?;
+    for path in &stale {
+        let full_path = config.output_dir.join(path);
+        fs_err::remove_file(&full_path)?;
+        let mut dir = full_path.parent();
+        while let Some(parent) = dir.filter(|d| *d != config.output_dir) {
+            // Fails, and stops, at the first directory that still has entries
+            if std::fs::remove_dir(parent).is_err() {
+                break;
+            }
+            dir = parent.parent();
+        }
+    }
+    Ok(stale)
+}
+
+/// Generated files left in the output directory by earlier runs that the
+/// current run, which wrote `written` (paths relative to the output
+/// directory), no longer produces, relative to the output directory.
+///
+/// Only entity files of types deleted from the unfiltered `schema` are
+/// stale, unless `force` is set; types a run filters out or skips keep theirs.
+/// Migrations are applied history, so even with `force` those of tables still
+/// in `schema` stay. Files without the generated banner are never stale.
+/// Nothing is stale in `schema_only` mode, and migrations are kept with
+/// `only_changed_tables`, which only emits the migrations of changed tables.
+pub fn stale_files(
+    schema: &ParsedSchema,
+    config: &Config,
+    written: &BTreeSet<PathBuf>,
+) -> anyhow::Result<Vec<PathBuf>> {
+    if config.schema_only {
+        return Ok(Vec::new());
+    }
+    let entities_dir = PathBuf::from(config.entity_file(""));
+    let migrations_dir = PathBuf::from(config.migration_file(""));
+    let live_entities: BTreeSet<PathBuf> = schema
+        .types
+        .keys()
+        .chain(schema.enums.keys())
+        .map(|name| PathBuf::from(config.entity_file(&module_file_name(name))))
+        .collect();
+    let live_tables = schema_table_names(schema, config);
+    let is_live_migration = |path: &Path| {
+        path.components().any(|component| {
+            let name = Path::new(component.as_os_str())
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or_default();
+            (name.ends_with("create_enum_types") && !schema.enums.is_empty())
+                || migration_table(name).is_some_and(|table| live_tables.contains(table))
+        })
+    };
+    let mut stale = Vec::new();
+    for (path, generated) in source_files(&config.output_dir)? {
+        let is_migration = path.starts_with(&migrations_dir);
+        let is_entity = path.starts_with(&entities_dir) && !is_migration;
+        if !generated
+            || written.contains(&path)
+            || (is_entity && live_entities.contains(&path))
+            || (is_migration && is_live_migration(&path))
+            || !(config.force || is_entity)
+            || (config.only_changed_tables && is_migration)
+        {
+            continue;
+        }
+        stale.push(path);
+    }
+    Ok(stale)
 }
 
 pub fn create_generator(orm: &OrmType) -> Box<dyn CodeGenerator> {
-    match orm {
-        OrmType::Diesel => Box::new(diesel::DieselGenerator::new()),
-        OrmType::SeaOrm => Box::new(sea_orm::SeaOrmGenerator::new()),
+    GeneratorRegistry::default()
+        .create(orm.name())
+        .expect("built-in generators are always registered")
+}
+
+/// Builds a fresh generator for a registered name
+pub type GeneratorFactory = Box<dyn Fn() -> Box<dyn CodeGenerator> + Send + Sync>;
+
+/// What a generation run produced besides the files themselves
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenerationSummary {
+    /// External crates the generated types use, such as `uuid` or `chrono`,
+    /// which the crate holding the output has to depend on
+    pub required_crates: HashSet<String>,
+    /// Custom scalars that had no mapping and were generated as `String`
+    pub fallback_scalars: Vec<String>,
+    /// Types left out under `continue_on_error`, with the error each failed with
+    pub skipped_types: BTreeMap<String, String>,
+    /// Errors the server returned along with a usable introspected schema
+    pub warnings: Vec<String>,
+    /// Entity files, one per generated type and relative to the output
+    /// directory, whether written or left unchanged
+    pub entity_files: Vec<PathBuf>,
+    /// Files written, relative to the output directory
+    pub written_files: Vec<PathBuf>,
+    /// Entity files left untouched in incremental mode because they didn't change
+    pub unchanged_files: Vec<PathBuf>,
+    /// Stale generated files removed from the output directory
+    pub removed_files: Vec<PathBuf>,
+    /// Files in the output directory that weren't generated by this tool,
+    /// only generated over with `force`
+    pub foreign_files: Vec<PathBuf>,
+}
+
+/// External crates introduced by the Rust types of the generated keys and
+/// fields, resolved the same way as the entity and model code.
+///
+/// Each crate is the first segment of a path in a type, such as `rust_decimal`
+/// in `Vec<rust_decimal::Decimal>`; the standard library's `std`, `core` and
+/// `alloc` aren't counted.
+pub fn required_crates(schema: &ParsedSchema, config: &Config) -> HashSet<String> {
+    let mut crates = HashSet::new();
+    let mut track = |rust_type: &str| {
+        let paths = rust_type.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'));
+        for path in paths {
+            let Some((name, _)) = path.trim_start_matches("::").split_once("::") else {
+                continue;
+            };
+            if !matches!(name, "std" | "core" | "alloc" | "crate" | "self" | "super") {
+                crates.insert(name.to_string());
+            }
+        }
+    };
+
+    track(IdType::resolve(&config.db, &config.id_mapping).rust_type(&config.db));
+    for parsed_type in schema.types.values() {
+        if !matches!(parsed_type.kind, crate::parser::TypeKind::Object) {
+            continue;
+        }
+        for field in &parsed_type.fields {
+            track(&rust_type_for_field(field, config));
+        }
     }
+    crates
+}
+
+/// Code generators by name, so backends can be added without changing [`OrmType`].
+///
+/// The default registry holds the built-in generators under their `--orm` names
+/// (`diesel`, `sea-orm` and `plain`).
+pub struct GeneratorRegistry {
+    factories: BTreeMap<String, GeneratorFactory>,
+}
+
+impl Default for GeneratorRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(OrmType::Diesel.name(), || {
+            Box::new(diesel::DieselGenerator::new())
+        });
+        registry.register(OrmType::SeaOrm.name(), || {
+            Box::new(sea_orm::SeaOrmGenerator::new())
+        });
+        registry.register(OrmType::Plain.name(), || {
+            Box::new(plain::PlainGenerator::new())
+        });
+        registry
+    }
+}
+
+impl GeneratorRegistry {
+    /// Registry with no generators, not even the built-in ones
+    pub fn empty() -> Self {
+        Self {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    /// Register a generator under `name`, replacing any generator already there
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        factory: impl Fn() -> Box<dyn CodeGenerator> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.factories.insert(name.into(), Box::new(factory));
+        self
+    }
+
+    /// Create the generator registered under `name`
+    pub fn create(&self, name: &str) -> anyhow::Result<Box<dyn CodeGenerator>> {
+        let factory = self.factories.get(name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown generator '{}'\n\nAvailable generators: {}",
+                name,
+                self.names().collect::<Vec<_>>().join(", ")
+            )
+        })?;
+        Ok(factory())
+    }
+
+    /// Registered generator names, in sorted order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+}
+
+/// `pub mod` declaration for `src/lib.rs` of a module whose file is at
+/// `path`, relative to the output directory, adding a `#[path]` attribute
+/// when the file isn't where Rust would look for it
+pub fn lib_module_declaration(name: &str, path: &str) -> String {
+    if path == format!("src/{}.rs", name) || path == format!("src/{}/mod.rs", name) {
+        return format!("pub mod {};\n", name);
+    }
+    let relative = match path.strip_prefix("src/") {
+        Some(relative) => relative.to_string(),
+        None => format!("../{}", path),
+    };
+    format!("#[path = \"{}\"]\npub mod {};\n", relative, name)
+}
+
+/// `prelude` module for `src/lib.rs` re-exporting the given items, each an
+/// optional `#[cfg]` attribute and a path under the `entities` module, with
+/// everything also re-exported at the crate root
+pub fn prelude_module(items: &[(Option<String>, String)]) -> String {
+    let mut output = String::new();
+    output.push_str("/// Generated types, for `use <crate>::prelude::*;`\n");
+    output.push_str("pub mod prelude {\n");
+    for (cfg, path) in items {
+        if let Some(cfg) = cfg {
+            output.push_str(&format!("    {}\n", cfg));
+        }
+        output.push_str(&format!("    pub use super::entities::{};\n", path));
+    }
+    output.push_str("}\n\n");
+    output.push_str("// Re-exports for convenience\n");
+    output.push_str("pub use prelude::*;\n");
+    output
+}
+
+/// Get the `#[cfg(feature = ...)]` attribute gating an entity module, if any
+pub fn entity_cfg_attribute(type_name: &str, config: &Config) -> Option<String> {
+    let feature = config.feature_map.get(type_name).cloned().or_else(|| {
+        config
+            .feature_per_entity
+            .then(|| format!("entity_{}", to_snake_case(type_name)))
+    })?;
+    Some(format!("#[cfg(feature = \"{}\")]", feature))
+}
+
+/// Rust enum for a GraphQL union, with a variant wrapping each member's row
+/// type as named by `member_type`.
+///
+/// Members that aren't generated object types are left out, and `None` is
+/// returned when none remain. With `serde`, the enum derives `Deserialize` and
+/// `Serialize` untagged, so values read the same as the member they hold.
+pub fn union_enum(
+    schema: &ParsedSchema,
+    type_name: &str,
+    parsed_type: &ParsedType,
+    config: &Config,
+    derives: &str,
+    serde: bool,
+    member_type: impl Fn(&str) -> String,
+) -> Option<String> {
+    let members: Vec<&String> = parsed_type
+        .union_members
+        .iter()
+        .filter(|member| {
+            schema
+                .types
+                .get(*member)
+                .is_some_and(|t| matches!(t.kind, crate::parser::TypeKind::Object))
+        })
+        .collect();
+    if members.is_empty() {
+        return None;
+    }
+
+    let mut output = String::new();
+    match &parsed_type.description {
+        Some(description) => output.push_str(&doc_comment(description, "")),
+        None => output.push_str(&format!(
+            "/// A value of one of the types in the `{}` union\n",
+            type_name
+        )),
+    }
+    output.push_str(&format!("#[derive({})]\n", derives));
+    if serde {
+        output.push_str("#[serde(untagged)]\n");
+    }
+    output.push_str(&format!("pub enum {} {{\n", type_name));
+    for member in members {
+        if let Some(cfg) = entity_cfg_attribute(member, config) {
+            output.push_str(&format!("    {}\n", cfg));
+        }
+        output.push_str(&format!("    {}({}),\n", member, member_type(member)));
+    }
+    output.push_str("}\n");
+    Some(output)
+}
+
+/// Format generated Rust code with `rustfmt`.
+///
+/// Best-effort: if `rustfmt` is not installed or fails to parse the code,
+/// the input is returned unchanged.
+pub fn format_rust_code(code: &str) -> String {
+    let Ok(mut child) = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    else {
+        return code.to_string();
+    };
+
+    // Feed stdin from a separate thread so large files can't deadlock on a full pipe
+    let Some(mut stdin) = child.stdin.take() else {
+        return code.to_string();
+    };
+    let input = code.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+    let output = child.wait_with_output();
+    let written = writer.join().is_ok_and(|result| result.is_ok());
+
+    match output {
+        Ok(output) if written && output.status.success() => {
+            String::from_utf8(output.stdout).unwrap_or_else(|_| code.to_string())
+        }
+        _ => code.to_string(),
+    }
+}
+
+/// `snake_case` or `camelCase` to `PascalCase`: `user_profile` and `userProfile`
+/// both become `UserProfile`.
+pub fn to_pascal_case(s: &str) -> String {
+    let mut result = String::new();
+    let mut capitalize = true;
+    for ch in s.chars() {
+        if ch == '_' {
+            capitalize = true;
+        } else if capitalize {
+            result.extend(ch.to_uppercase());
+            capitalize = false;
+        } else {
+            result.push(ch);
+        }
+    }
+    result
 }
 
 pub fn to_snake_case(s: &str) -> String {
@@ -43,11 +1247,11 @@ pub fn to_snake_case(s: &str) -> String {
         if ch.is_uppercase() {
             // Add underscore if:
             // 1. Not the first character AND previous character exists AND either:
-            //    a. Previous was lowercase, OR
+            //    a. Previous was lowercase or a digit (`S3Bucket`), OR
             //    b. Previous was uppercase and next is lowercase (end of acronym)
             if i > 0 {
                 let prev = chars[i - 1];
-                let should_add_underscore = if prev.is_lowercase() {
+                let should_add_underscore = if prev.is_lowercase() || prev.is_ascii_digit() {
                     true
                 } else if prev.is_uppercase() {
                     // Check if next character exists and is lowercase
@@ -61,6 +1265,17 @@ pub fn to_snake_case(s: &str) -> String {
                 }
             }
             result.push(ch.to_lowercase().next().unwrap());
+        } else if ch.is_ascii_digit() && i > 0 && chars[i - 1].is_lowercase() {
+            // Digits stay with the word before them (`sha256Hash`) unless they
+            // start a lowercase word of their own (`user2fa`)
+            let starts_word = chars[i..]
+                .iter()
+                .find(|c| !c.is_ascii_digit())
+                .is_some_and(|c| c.is_lowercase());
+            if starts_word {
+                result.push('_');
+            }
+            result.push(ch);
         } else {
             result.push(ch);
         }
@@ -69,86 +1284,314 @@ pub fn to_snake_case(s: &str) -> String {
     result
 }
 
-pub fn rust_type_for_field(
-    field: &ParsedField,
+/// Rust keywords, strict and reserved, that can't be bare identifiers
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Keywords that can't be raw identifiers either
+const NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// A name as a Rust identifier: keywords are raw-escaped (`r#type`), and the
+/// few that can't be (`self`, `Self`, `super`, `crate`) get a trailing
+/// underscore instead
+pub fn rust_ident(name: &str) -> String {
+    if NON_RAW_KEYWORDS.contains(&name) {
+        format!("{}_", name)
+    } else if RUST_KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Whether `rust_ident` renamed `name` rather than escaping it, so the
+/// original has to be spelled out in a `#[serde(rename)]` or column attribute
+pub fn is_renamed_ident(name: &str) -> bool {
+    NON_RAW_KEYWORDS.contains(&name)
+}
+
+/// Rust name of the module holding a type's generated code
+pub fn module_name(type_name: &str) -> String {
+    rust_ident(&to_snake_case(type_name))
+}
+
+/// File of the module holding a type's generated code, where `mod` looks
+/// for it
+pub fn module_file_name(type_name: &str) -> String {
+    format!("{}.rs", module_name(type_name).trim_start_matches("r#"))
+}
+
+/// Database table of a type: its snake_case name, pluralized with
+/// `pluralize_tables`. Struct and module names never change with it.
+pub fn entity_table_name(type_name: &str, config: &Config) -> String {
+    let table_name = to_snake_case(type_name);
+    if config.pluralize_tables {
+        pluralize(&table_name)
+    } else {
+        table_name
+    }
+}
+
+/// Regular English plural of a snake_case name, changing only its last word
+fn pluralize(name: &str) -> String {
+    let is_consonant = |c: char| c.is_ascii_alphabetic() && !"aeiou".contains(c);
+    if name.ends_with('y') && name[..name.len() - 1].ends_with(is_consonant) {
+        format!("{}ies", &name[..name.len() - 1])
+    } else if ["s", "x", "z", "ch", "sh"]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+    {
+        format!("{}es", name)
+    } else {
+        format!("{}s", name)
+    }
+}
+
+/// Rust prelude types that a schema type or mapping could shadow, with their full paths
+pub const RUST_PRELUDE_TYPES: &[(&str, &str)] = &[
+    ("Box", "std::boxed::Box"),
+    ("Option", "std::option::Option"),
+    ("Result", "std::result::Result"),
+    ("String", "std::string::String"),
+    ("Vec", "std::vec::Vec"),
+];
+
+/// Standard library types outside the prelude that mappings may name unqualified
+pub const STD_TYPE_PATHS: &[(&str, &str)] = &[
+    ("BTreeMap", "std::collections::BTreeMap"),
+    ("BTreeSet", "std::collections::BTreeSet"),
+    ("HashMap", "std::collections::HashMap"),
+    ("HashSet", "std::collections::HashSet"),
+    ("VecDeque", "std::collections::VecDeque"),
+    ("IpAddr", "std::net::IpAddr"),
+    ("Ipv4Addr", "std::net::Ipv4Addr"),
+    ("Ipv6Addr", "std::net::Ipv6Addr"),
+    ("SocketAddr", "std::net::SocketAddr"),
+    ("PathBuf", "std::path::PathBuf"),
+    ("Duration", "std::time::Duration"),
+    ("SystemTime", "std::time::SystemTime"),
+    ("Cow", "std::borrow::Cow"),
+    ("Arc", "std::sync::Arc"),
+    ("Rc", "std::rc::Rc"),
+];
+
+/// Fully qualify the standard library types named in a mapped type.
+///
+/// Generated files carry no imports for mapped types, and modules may define a
+/// type with the same name as a prelude type (e.g. a GraphQL enum called
+/// `Option`), so every bare prelude or [`STD_TYPE_PATHS`] name in the mapping,
+/// including generic arguments, is written as a full path:
+/// `HashMap<String, IpAddr>` becomes
+/// `std::collections::HashMap<std::string::String, std::net::IpAddr>`.
+/// Names that are already part of a path and other types are left unchanged.
+pub fn qualify_mapped_type(rust_type: &str) -> String {
+    let mut qualified = String::with_capacity(rust_type.len());
+    let mut rest = rust_type;
+    while let Some(start) = rest.find(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        let (before, ident_start) = rest.split_at(start);
+        let end = ident_start
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(ident_start.len());
+        let (ident, after) = ident_start.split_at(end);
+        qualified.push_str(before);
+
+        let in_path = qualified.trim_end().ends_with("::") || after.trim_start().starts_with("::");
+        let path = RUST_PRELUDE_TYPES
+            .iter()
+            .chain(STD_TYPE_PATHS)
+            .find(|(name, _)| *name == ident)
+            .map(|(_, path)| *path)
+            .filter(|_| !in_path);
+        qualified.push_str(path.unwrap_or(ident));
+        rest = after;
+    }
+    qualified.push_str(rest);
+    qualified
+}
+
+/// `///` doc comment lines for a GraphQL description, one per description line
+pub fn doc_comment(description: &str, indent: &str) -> String {
+    description
+        .trim()
+        .lines()
+        .map(|line| {
+            let line = line.trim_end();
+            if line.is_empty() {
+                format!("{}///\n", indent)
+            } else {
+                format!("{}/// {}\n", indent, line)
+            }
+        })
+        .collect()
+}
+
+/// Custom scalars commonly used for 64-bit integers, mapped to `i64`/`BIGINT`
+/// unless the scalar has its own mapping
+pub fn is_int64_scalar(scalar: &str) -> bool {
+    matches!(scalar, "Long" | "BigInt")
+}
+
+/// Custom scalars holding arbitrary JSON, mapped to `serde_json::Value` and a
+/// JSON column unless the scalar has its own mapping
+pub fn is_json_scalar(scalar: &str) -> bool {
+    matches!(scalar, "JSON" | "JSONB")
+}
+
+/// Built-in Rust type for the `DateTime`, `Date` and `Time` scalars.
+///
+/// `DateTime` is zone-aware only on PostgreSQL, whose `TIMESTAMPTZ` column
+/// keeps the offset; SQLite and MySQL timestamps are naive.
+pub fn datetime_rust_type(
+    scalar: &str,
+    datetime_crate: &DateTimeCrate,
     db_type: &DatabaseType,
-    scalar_mappings: &HashMap<String, String>,
-) -> String {
+) -> Option<&'static str> {
+    let zoned = matches!(db_type, DatabaseType::Postgres);
+    match (scalar, datetime_crate) {
+        ("DateTime", DateTimeCrate::Chrono) if zoned => Some("chrono::DateTime<chrono::Utc>"),
+        ("DateTime", DateTimeCrate::Chrono) => Some("chrono::NaiveDateTime"),
+        ("Date", DateTimeCrate::Chrono) => Some("chrono::NaiveDate"),
+        ("Time", DateTimeCrate::Chrono) => Some("chrono::NaiveTime"),
+        ("DateTime", DateTimeCrate::Time) if zoned => Some("time::OffsetDateTime"),
+        ("DateTime", DateTimeCrate::Time) => Some("time::PrimitiveDateTime"),
+        ("Date", DateTimeCrate::Time) => Some("time::Date"),
+        ("Time", DateTimeCrate::Time) => Some("time::Time"),
+        _ => None,
+    }
+}
+
+/// Rust type configured for a GraphQL scalar, with `type_mappings` taking
+/// precedence over `scalar_mappings`
+pub fn mapped_scalar<'a>(
+    type_mappings: &'a HashMap<String, String>,
+    scalar_mappings: &'a HashMap<String, String>,
+    scalar: &str,
+) -> Option<&'a String> {
+    type_mappings
+        .get(scalar)
+        .or_else(|| scalar_mappings.get(scalar))
+}
+
+pub fn rust_type_for_field(field: &ParsedField, config: &Config) -> String {
+    let db_type = &config.db;
+    let id_type = IdType::resolve(db_type, &config.id_mapping);
     match &field.field_type {
         crate::parser::FieldType::Scalar(scalar_type) => match scalar_type.as_str() {
-            "ID" => match db_type {
-                DatabaseType::Sqlite => "i32".to_string(),
-                DatabaseType::Postgres => "uuid::Uuid".to_string(),
-                DatabaseType::Mysql => "u32".to_string(),
-            },
+            "ID" => id_type.rust_type(db_type).to_string(),
             "String" => "String".to_string(),
+            "Int" if config.int_as_i64 => "i64".to_string(),
             "Int" => "i32".to_string(),
             "Float" => "f64".to_string(),
             "Boolean" => "bool".to_string(),
-            custom => scalar_mappings
-                .get(custom)
-                .cloned()
+            custom => mapped_scalar(&config.type_mappings, &config.scalar_mappings, custom)
+                .map(|mapped| qualify_mapped_type(mapped))
+                .or_else(|| {
+                    datetime_rust_type(custom, &config.datetime_crate, db_type).map(str::to_string)
+                })
+                .or_else(|| is_int64_scalar(custom).then(|| "i64".to_string()))
+                .or_else(|| is_json_scalar(custom).then(|| "serde_json::Value".to_string()))
                 .unwrap_or_else(|| "String".to_string()),
         },
-        crate::parser::FieldType::Reference(_type_name) => {
-            // For references, we'll assume they're other entities
-            // In a real implementation, we'd need to handle foreign keys
-            match db_type {
-                DatabaseType::Sqlite => "i32".to_string(),
-                DatabaseType::Postgres => "uuid::Uuid".to_string(),
-                DatabaseType::Mysql => "u32".to_string(),
-            }
-        }
-        crate::parser::FieldType::Enum(enum_name) => enum_name.clone(),
+        // Object and enum type names are only looked up in `type_mappings`
+        crate::parser::FieldType::Reference(type_name) => match config.type_mappings.get(type_name)
+        {
+            Some(mapped) => qualify_mapped_type(mapped),
+            // Other entities are referred to by their key
+            None => id_type.rust_type(db_type).to_string(),
+        },
+        crate::parser::FieldType::Enum(enum_name) => config
+            .type_mappings
+            .get(enum_name)
+            .map(|mapped| qualify_mapped_type(mapped))
+            .unwrap_or_else(|| enum_name.clone()),
     }
 }
 
-pub fn diesel_column_type_for_field(
-    field: &ParsedField,
-    db_type: &DatabaseType,
-    scalar_mappings: &HashMap<String, String>,
-) -> String {
+pub fn diesel_column_type_for_field(field: &ParsedField, config: &Config) -> String {
+    let db_type = &config.db;
+    let is_mapped = |scalar: &str| {
+        mapped_scalar(&config.type_mappings, &config.scalar_mappings, scalar).is_some()
+    };
+    let id_type = IdType::resolve(db_type, &config.id_mapping);
     match &field.field_type {
         crate::parser::FieldType::Scalar(scalar_type) => match scalar_type.as_str() {
-            "ID" => match db_type {
-                DatabaseType::Sqlite => "Integer".to_string(),
-                DatabaseType::Postgres => "Uuid".to_string(),
-                DatabaseType::Mysql => "Unsigned<Integer>".to_string(),
-            },
+            "ID" => id_type.diesel_type(db_type).to_string(),
             "String" => "Text".to_string(),
+            "Int" if config.int_as_i64 => "BigInt".to_string(),
             "Int" => "Integer".to_string(),
             "Float" => "Double".to_string(),
             "Boolean" => "Bool".to_string(),
-            custom => scalar_mappings
-                .get(custom)
-                .cloned()
-                .unwrap_or_else(|| "Text".to_string()),
+            "DateTime" if !is_mapped("DateTime") => match db_type {
+                DatabaseType::Postgres => "Timestamptz".to_string(),
+                DatabaseType::Mysql => "Datetime".to_string(),
+                DatabaseType::Sqlite => "Timestamp".to_string(),
+            },
+            "Date" if !is_mapped("Date") => "Date".to_string(),
+            "Time" if !is_mapped("Time") => "Time".to_string(),
+            int64 if is_int64_scalar(int64) && !is_mapped(int64) => "BigInt".to_string(),
+            json if is_json_scalar(json) && !is_mapped(json) => match db_type {
+                DatabaseType::Postgres => "Jsonb".to_string(),
+                DatabaseType::Sqlite | DatabaseType::Mysql => "Json".to_string(),
+            },
+            // Mappings name Rust types, so other scalars are stored as text
+            _ => "Text".to_string(),
         },
         crate::parser::FieldType::Reference(_) => {
             // Foreign key
-            match db_type {
-                DatabaseType::Sqlite => "Integer".to_string(),
-                DatabaseType::Postgres => "Uuid".to_string(),
-                DatabaseType::Mysql => "Unsigned<Integer>".to_string(),
-            }
+            id_type.diesel_type(db_type).to_string()
         }
-        crate::parser::FieldType::Enum(_) => "Text".to_string(),
+        // The SqlType struct in schema.rs's sql_types module
+        crate::parser::FieldType::Enum(enum_name) if uses_native_enums(config) => enum_name.clone(),
+        crate::parser::FieldType::Enum(_) => match config.enum_storage {
+            EnumStorage::Text => "Text".to_string(),
+            EnumStorage::Int => "Integer".to_string(),
+        },
     }
 }
 
-pub fn sql_type_for_field(
-    field: &ParsedField,
-    db_type: &DatabaseType,
-    scalar_mappings: &HashMap<String, String>,
-) -> String {
-    match &field.field_type {
+/// Length of MySQL string key columns, the longest utf8mb4 `VARCHAR` that fits
+/// InnoDB's 767-byte index prefix limit
+pub const MYSQL_KEY_LENGTH: u32 = 191;
+
+/// The table column a field is generated into, for the per-field settings in
+/// [`Config`] such as string lengths, SQL type overrides and keys
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnContext<'a> {
+    /// GraphQL type owning the field
+    pub type_name: &'a str,
+    /// Whether the field is (part of) the table's primary key
+    pub is_primary_key: bool,
+}
+
+/// SQL column type for a field. A configured string length turns String
+/// columns into `VARCHAR(n)` on MySQL and PostgreSQL, and a `sql_types`
+/// override replaces the mapped type entirely. Columns that can't be MySQL
+/// `TEXT` (see [`needs_bounded_text`]) become `VARCHAR(191)` there.
+pub fn sql_type_for_field(field: &ParsedField, column: ColumnContext, config: &Config) -> String {
+    if let Some(sql_type) = config.sql_type_override(column.type_name, field) {
+        return sql_type.to_string();
+    }
+    let db_type = &config.db;
+    let max_length = config.string_length(column.type_name, field);
+    let is_mapped = |scalar: &str| {
+        mapped_scalar(&config.type_mappings, &config.scalar_mappings, scalar).is_some()
+    };
+    let id_type = IdType::resolve(db_type, &config.id_mapping);
+    let sql_type = match &field.field_type {
         crate::parser::FieldType::Scalar(scalar_type) => match scalar_type.as_str() {
-            "ID" => match db_type {
-                DatabaseType::Sqlite => "INTEGER".to_string(),
-                DatabaseType::Postgres => "UUID".to_string(),
-                DatabaseType::Mysql => "INT UNSIGNED".to_string(),
+            "ID" => id_type.sql_type(db_type).to_string(),
+            "String" => match (db_type, max_length) {
+                (DatabaseType::Mysql | DatabaseType::Postgres, Some(length)) => {
+                    format!("VARCHAR({})", length)
+                }
+                _ => "TEXT".to_string(),
             },
-            "String" => "TEXT".to_string(),
+            "Int" if config.int_as_i64 => "BIGINT".to_string(),
             "Int" => "INTEGER".to_string(),
             "Float" => "REAL".to_string(),
             "Boolean" => match db_type {
@@ -156,23 +1599,532 @@ pub fn sql_type_for_field(
                 DatabaseType::Postgres => "BOOLEAN".to_string(),
                 DatabaseType::Mysql => "TINYINT(1)".to_string(),
             },
-            custom => scalar_mappings
-                .get(custom)
-                .cloned()
-                .unwrap_or_else(|| "TEXT".to_string()),
+            "DateTime" if !is_mapped("DateTime") => match db_type {
+                DatabaseType::Postgres => "TIMESTAMPTZ".to_string(),
+                DatabaseType::Mysql => "DATETIME".to_string(),
+                DatabaseType::Sqlite => "TIMESTAMP".to_string(),
+            },
+            "Date" if !is_mapped("Date") => "DATE".to_string(),
+            "Time" if !is_mapped("Time") => "TIME".to_string(),
+            int64 if is_int64_scalar(int64) && !is_mapped(int64) => "BIGINT".to_string(),
+            json if is_json_scalar(json) && !is_mapped(json) => match db_type {
+                DatabaseType::Postgres => "JSONB".to_string(),
+                DatabaseType::Mysql => "JSON".to_string(),
+                DatabaseType::Sqlite => "TEXT".to_string(),
+            },
+            // Mappings name Rust types; sql_type_overrides sets other column types
+            _ => "TEXT".to_string(),
         },
         crate::parser::FieldType::Reference(_) => {
             // Foreign key
-            match db_type {
-                DatabaseType::Sqlite => "INTEGER".to_string(),
-                DatabaseType::Postgres => "UUID".to_string(),
-                DatabaseType::Mysql => "INT UNSIGNED".to_string(),
+            id_type.sql_type(db_type).to_string()
+        }
+        crate::parser::FieldType::Enum(enum_name) if uses_native_enums(config) => {
+            native_enum_name(enum_name)
+        }
+        crate::parser::FieldType::Enum(_) => match config.enum_storage {
+            EnumStorage::Text => "TEXT".to_string(),
+            EnumStorage::Int => "INTEGER".to_string(),
+        },
+    };
+    if matches!(db_type, DatabaseType::Mysql)
+        && sql_type == "TEXT"
+        && needs_bounded_text(column.type_name, field, column.is_primary_key, config)
+    {
+        format!("VARCHAR({})", MYSQL_KEY_LENGTH)
+    } else {
+        sql_type
+    }
+}
+
+/// Whether a column needs a bounded string type rather than MySQL `TEXT`:
+/// keys and indexed columns, since MySQL can't index `TEXT` without a prefix
+/// length, and columns with a default, since `TEXT` can't take a literal one
+pub fn needs_bounded_text(
+    type_name: &str,
+    field: &ParsedField,
+    is_primary_key: bool,
+    config: &Config,
+) -> bool {
+    is_primary_key
+        || config.is_unique(type_name, field)
+        || config.is_indexed(type_name, field)
+        || config.default_value(type_name, field).is_some()
+}
+
+/// Whether enum columns use `CREATE TYPE ... AS ENUM` types, which only
+/// PostgreSQL has and only text storage can use
+pub fn uses_native_enums(config: &Config) -> bool {
+    config.postgres_native_enums
+        && config.db == DatabaseType::Postgres
+        && config.enum_storage == EnumStorage::Text
+}
+
+/// Name of the PostgreSQL type backing an enum
+pub fn native_enum_name(enum_name: &str) -> String {
+    to_snake_case(enum_name)
+}
+
+/// `CREATE TYPE` statements for every enum and the `DROP TYPE` statements
+/// undoing them, or `None` when native enums are off
+pub fn native_enum_types_sql(schema: &ParsedSchema, config: &Config) -> Option<(String, String)> {
+    if !uses_native_enums(config) || schema.enums.is_empty() {
+        return None;
+    }
+    let mut enums: Vec<_> = schema.enums.iter().collect();
+    enums.sort_by(|a, b| a.0.cmp(b.0));
+
+    let up_sql: Vec<String> = enums
+        .iter()
+        .map(|(name, parsed_enum)| {
+            let values: Vec<String> = parsed_enum
+                .values
+                .iter()
+                .map(|value| sql_string_literal(value, &config.db))
+                .collect();
+            format!(
+                "CREATE TYPE {} AS ENUM ({});",
+                native_enum_name(name),
+                values.join(", ")
+            )
+        })
+        .collect();
+    let down_sql: Vec<String> = enums
+        .iter()
+        .rev()
+        .map(|(name, _)| format!("DROP TYPE {};", native_enum_name(name)))
+        .collect();
+    Some((up_sql.join("\n"), down_sql.join("\n")))
+}
+
+/// Integer discriminant for each enum value, in declaration order.
+///
+/// Values take their `@value(int: N)` discriminant when present, otherwise the
+/// previous discriminant plus one (starting at 0), as Rust assigns them.
+pub fn enum_discriminants(parsed_enum: &ParsedEnum) -> anyhow::Result<Vec<(&str, i32)>> {
+    let mut discriminants: Vec<(&str, i32)> = Vec::new();
+    let mut next = Some(0i32);
+    for value in &parsed_enum.values {
+        let discriminant = match parsed_enum.discriminants.get(value) {
+            Some(discriminant) => *discriminant,
+            None => next.ok_or_else(|| {
+                anyhow::anyhow!("Enum value '{}' overflows the i32 discriminants", value)
+            })?,
+        };
+        if let Some((other, _)) = discriminants.iter().find(|(_, d)| *d == discriminant) {
+            anyhow::bail!(
+                "Enum values '{}' and '{}' share the discriminant {}",
+                other,
+                value,
+                discriminant
+            );
+        }
+        discriminants.push((value, discriminant));
+        next = discriminant.checked_add(1);
+    }
+    Ok(discriminants)
+}
+
+/// Rust variant name for a GraphQL enum value.
+///
+/// Characters that can't appear in identifiers (as in `text/plain` from some
+/// introspected APIs) become underscores. Values starting with a digit, and a
+/// bare `_`, aren't valid identifiers and get `prefix`, or `V` when the prefix
+/// is empty, and keywords are escaped with `rust_ident`.
+pub fn enum_variant_name(value: &str, prefix: &str) -> String {
+    let sanitized: String = value
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if sanitized.is_empty() || sanitized == "_" || sanitized.starts_with(|c: char| c.is_numeric()) {
+        let prefix = if prefix.is_empty() { "V" } else { prefix };
+        format!("{}{}", prefix, sanitized)
+    } else {
+        rust_ident(&sanitized)
+    }
+}
+
+/// `#[deprecated]` attribute for a deprecated enum value, noting its reason
+pub fn deprecated_attribute(parsed_enum: &ParsedEnum, value: &str) -> Option<String> {
+    match parsed_enum.deprecations.get(value)? {
+        Some(reason) => Some(format!("#[deprecated(note = {:?})]", reason)),
+        None => Some("#[deprecated]".to_string()),
+    }
+}
+
+/// `TryFrom<i32>` impl mapping stored integers back to enum variants.
+///
+/// The impl allows `deprecated` so naming deprecated variants doesn't warn.
+pub fn enum_try_from_impl(
+    enum_name: &str,
+    discriminants: &[(&str, i32)],
+    variant_prefix: &str,
+    has_deprecations: bool,
+) -> String {
+    let mut output = String::from("\n");
+    if has_deprecations {
+        output.push_str("#[allow(deprecated)]\n");
+    }
+    output.push_str(&format!("impl TryFrom<i32> for {} {{\n", enum_name));
+    output.push_str("    type Error = i32;\n\n");
+    output.push_str("    fn try_from(value: i32) -> Result<Self, Self::Error> {\n");
+    output.push_str("        match value {\n");
+    for (value, discriminant) in discriminants {
+        output.push_str(&format!(
+            "            {} => Ok({}::{}),\n",
+            discriminant,
+            enum_name,
+            enum_variant_name(value, variant_prefix)
+        ));
+    }
+    output.push_str("            other => Err(other),\n");
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+    output
+}
+
+/// Whether a field is stored in its table.
+///
+/// To-many relations (lists of object types) and fields marked `@computed`
+/// are not columns.
+pub fn is_column_field(field: &ParsedField, schema: &ParsedSchema) -> bool {
+    if field.has_directive("computed") {
+        return false;
+    }
+    !(field.is_list
+        && matches!(&field.field_type, crate::parser::FieldType::Reference(name)
+            if schema.types.contains_key(name)))
+}
+
+/// A single-column secondary index
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnIndex {
+    pub name: String,
+    pub column: String,
+    pub unique: bool,
+}
+
+/// Secondary indexes for a type's unique and indexed non-key fields
+pub fn column_indexes(
+    type_name: &str,
+    parsed_type: &ParsedType,
+    config: &Config,
+) -> Vec<ColumnIndex> {
+    let table_name = entity_table_name(type_name, config);
+    // Composite key columns after the first can still use their own index
+    let pk_names = primary_key_names(type_name, parsed_type, config);
+    let mut indexes = Vec::new();
+    for field in &parsed_type.fields {
+        if pk_names.first() == Some(&field.name) {
+            continue;
+        }
+        let column = to_snake_case(&field.name);
+        let unique = config.is_unique(type_name, field);
+        if unique || config.is_indexed(type_name, field) {
+            let prefix = if unique { "uq" } else { "idx" };
+            indexes.push(ColumnIndex {
+                name: index_name(prefix, &table_name, &column, &config.db),
+                column,
+                unique,
+            });
+        }
+    }
+    indexes
+}
+
+/// Indexes for a type's foreign key columns, unless `auto_fk_index` is off.
+///
+/// Columns already indexed explicitly are left to [`column_indexes`].
+pub fn foreign_key_indexes(
+    type_name: &str,
+    parsed_type: &ParsedType,
+    config: &Config,
+) -> Vec<ColumnIndex> {
+    if !config.auto_fk_index {
+        return Vec::new();
+    }
+    let table_name = entity_table_name(type_name, config);
+    parsed_type
+        .fields
+        .iter()
+        .filter(|field| matches!(field.field_type, crate::parser::FieldType::Reference(_)))
+        .filter(|field| !config.is_indexed(type_name, field))
+        .map(|field| {
+            let column = to_snake_case(&field.name);
+            ColumnIndex {
+                name: index_name("idx", &table_name, &column, &config.db),
+                column,
+                unique: false,
+            }
+        })
+        .collect()
+}
+
+/// `CREATE INDEX` statements for a table's secondary indexes
+pub fn create_indexes_sql(table_name: &str, indexes: &[ColumnIndex]) -> String {
+    indexes
+        .iter()
+        .map(|index| {
+            format!(
+                "\n\nCREATE {}INDEX {} ON {} ({});",
+                if index.unique { "UNIQUE " } else { "" },
+                index.name,
+                table_name,
+                index.column
+            )
+        })
+        .collect()
+}
+
+/// A string as an SQL literal; MySQL also treats backslashes as escapes
+fn sql_string_literal(value: &str, db_type: &DatabaseType) -> String {
+    let escaped = match db_type {
+        DatabaseType::Mysql => value.replace('\\', "\\\\"),
+        DatabaseType::Sqlite | DatabaseType::Postgres => value.to_string(),
+    };
+    format!("'{}'", escaped.replace('\'', "''"))
+}
+
+/// A column default, read from a GraphQL value literal
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefaultValue {
+    Null,
+    Boolean(bool),
+    Number(String),
+    Text(String),
+}
+
+impl DefaultValue {
+    /// Parse a GraphQL literal; bare words, such as enum values, are strings
+    pub fn parse(value: &str) -> anyhow::Result<Self> {
+        let value = value.trim();
+        Ok(match value {
+            "null" => Self::Null,
+            "true" => Self::Boolean(true),
+            "false" => Self::Boolean(false),
+            _ if value.starts_with('[') || value.starts_with('{') => {
+                anyhow::bail!("lists and input objects can't be column defaults")
+            }
+            _ if value.starts_with("\"\"\"") && value.len() >= 6 && value.ends_with("\"\"\"") => {
+                Self::Text(value[3..value.len() - 3].to_string())
+            }
+            // GraphQL string escapes are the JSON ones
+            _ if value.starts_with('"') => Self::Text(
+                serde_json::from_str(value)
+                    .map_err(|e| anyhow::anyhow!("invalid string {}: {}", value, e))?,
+            ),
+            _ if value.parse::<f64>().is_ok() => Self::Number(value.to_string()),
+            _ => Self::Text(value.to_string()),
+        })
+    }
+
+    /// The value as an SQL literal; booleans are `0`/`1` outside PostgreSQL
+    pub fn sql(&self, db_type: &DatabaseType) -> String {
+        match (self, db_type) {
+            (Self::Null, _) => "NULL".to_string(),
+            (Self::Boolean(value), DatabaseType::Postgres) => value.to_string(),
+            (Self::Boolean(value), _) => u8::from(*value).to_string(),
+            (Self::Number(value), _) => value.clone(),
+            (Self::Text(value), _) => sql_string_literal(value, db_type),
+        }
+    }
+
+    /// The value as a sea-query `.default(...)` argument; `None` for `null`
+    pub fn rust(&self) -> Option<String> {
+        match self {
+            Self::Null => None,
+            Self::Boolean(value) => Some(value.to_string()),
+            // Integer literals default to i32, which larger values overflow
+            Self::Number(value)
+                if !value.contains(['.', 'e', 'E']) && value.parse::<i32>().is_err() =>
+            {
+                Some(format!("{}i64", value))
             }
+            Self::Number(value) => Some(value.clone()),
+            Self::Text(value) => Some(format!("{:?}", value)),
+        }
+    }
+}
+
+/// Column default of a field from [`Config::default_value`], with enum values
+/// stored as integers resolved to their discriminant
+pub fn column_default(
+    schema: &ParsedSchema,
+    type_name: &str,
+    field: &ParsedField,
+    config: &Config,
+) -> anyhow::Result<Option<DefaultValue>> {
+    let Some(raw) = config.default_value(type_name, field) else {
+        return Ok(None);
+    };
+    let value = DefaultValue::parse(raw).map_err(|e| {
+        anyhow::anyhow!(
+            "Invalid default value of '{}.{}': {}",
+            type_name,
+            field.name,
+            e
+        )
+    })?;
+    Ok(Some(match (&field.field_type, value) {
+        (crate::parser::FieldType::Enum(enum_name), DefaultValue::Text(variant))
+            if matches!(config.enum_storage, EnumStorage::Int) =>
+        {
+            let parsed_enum = schema
+                .enums
+                .get(enum_name)
+                .ok_or_else(|| anyhow::anyhow!("Unknown enum '{}'", enum_name))?;
+            let discriminant = enum_discriminants(parsed_enum)?
+                .into_iter()
+                .find(|(name, _)| *name == variant)
+                .map(|(_, discriminant)| discriminant)
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "Default value of '{}.{}' is not a {} value: {}",
+                        type_name,
+                        field.name,
+                        enum_name,
+                        variant
+                    )
+                })?;
+            DefaultValue::Number(discriminant.to_string())
+        }
+        // Numbers given for string columns are still strings
+        (crate::parser::FieldType::Scalar(scalar), DefaultValue::Number(number))
+            if scalar == "String" =>
+        {
+            DefaultValue::Text(number)
+        }
+        (crate::parser::FieldType::Scalar(scalar), value)
+            if config.db == DatabaseType::Mysql
+                && is_json_scalar(scalar)
+                && mapped_scalar(&config.type_mappings, &config.scalar_mappings, scalar)
+                    .is_none()
+                && value != DefaultValue::Null =>
+        {
+            anyhow::bail!(
+                "Default value of '{}.{}': MySQL JSON columns can't have a literal default",
+                type_name,
+                field.name
+            )
+        }
+        (_, value) => value,
+    }))
+}
+
+/// Inline ` DEFAULT ...` clause for a column; empty without a default
+pub fn column_default_sql(
+    schema: &ParsedSchema,
+    type_name: &str,
+    field: &ParsedField,
+    config: &Config,
+) -> anyhow::Result<String> {
+    Ok(column_default(schema, type_name, field, config)?
+        .map(|value| format!(" DEFAULT {}", value.sql(&config.db)))
+        .unwrap_or_default())
+}
+
+/// Inline ` COMMENT '...'` clause for a MySQL column description; empty elsewhere
+pub fn column_comment_sql(description: Option<&str>, db_type: &DatabaseType) -> String {
+    match (description, db_type) {
+        (Some(description), DatabaseType::Mysql) => {
+            format!(" COMMENT {}", sql_string_literal(description, db_type))
         }
-        crate::parser::FieldType::Enum(_) => "TEXT".to_string(),
+        _ => String::new(),
     }
 }
 
+/// Table options after `CREATE TABLE (...)`: ` COMMENT='...'` for a described
+/// MySQL table; empty elsewhere
+pub fn table_comment_sql(description: Option<&str>, db_type: &DatabaseType) -> String {
+    match (description, db_type) {
+        (Some(description), DatabaseType::Mysql) => {
+            format!(" COMMENT={}", sql_string_literal(description, db_type))
+        }
+        _ => String::new(),
+    }
+}
+
+/// PostgreSQL `COMMENT ON` statements for a table and its described columns,
+/// given as `(column, description)`, one per entry; empty for other databases,
+/// which either take inline comments or have none (SQLite)
+pub fn comment_statements_sql(
+    table_name: &str,
+    description: Option<&str>,
+    columns: &[(String, &str)],
+    db_type: &DatabaseType,
+) -> Vec<String> {
+    if !matches!(db_type, DatabaseType::Postgres) {
+        return Vec::new();
+    }
+    let mut statements = Vec::new();
+    if let Some(description) = description {
+        statements.push(format!(
+            "COMMENT ON TABLE {} IS {};",
+            table_name,
+            sql_string_literal(description, db_type)
+        ));
+    }
+    for (column, description) in columns {
+        statements.push(format!(
+            "COMMENT ON COLUMN {}.{} IS {};",
+            table_name,
+            column,
+            sql_string_literal(description, db_type)
+        ));
+    }
+    statements
+}
+
+/// `DROP INDEX` statements reverting [`create_indexes_sql`]
+pub fn drop_indexes_sql(
+    table_name: &str,
+    indexes: &[ColumnIndex],
+    db_type: &DatabaseType,
+) -> String {
+    indexes
+        .iter()
+        .map(|index| match db_type {
+            DatabaseType::Mysql => format!("DROP INDEX {} ON {};\n", index.name, table_name),
+            DatabaseType::Sqlite | DatabaseType::Postgres => {
+                format!("DROP INDEX {};\n", index.name)
+            }
+        })
+        .collect()
+}
+
+/// Build an index name, shortened to the database's identifier limit.
+///
+/// Names that are too long keep a hash suffix of the full name so two long
+/// names sharing a prefix stay distinct.
+pub fn index_name(prefix: &str, table_name: &str, column: &str, db_type: &DatabaseType) -> String {
+    let name = format!("{}_{}_{}", prefix, table_name, column);
+    let max_len = match db_type {
+        DatabaseType::Postgres => 63,
+        DatabaseType::Mysql => 64,
+        DatabaseType::Sqlite => return name,
+    };
+    if name.len() <= max_len {
+        return name;
+    }
+
+    // FNV-1a keeps the suffix stable across runs and toolchains
+    let hash = name.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    let mut end = max_len - 9;
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}_{:08x}", &name[..end], hash)
+}
+
 /// Detect if a field is likely a foreign key relationship
 #[allow(dead_code)]
 pub fn is_foreign_key_field(field: &ParsedField) -> Option<String> {
@@ -201,6 +2153,127 @@ pub fn is_foreign_key_field(field: &ParsedField) -> Option<String> {
     None
 }
 
+/// Type that a foreign key field such as `authorId` points at.
+///
+/// A sibling field referencing an object type takes precedence, so `parentId`
+/// next to `parent: Category` points at `Category`; otherwise `authorId`
+/// points at an `Author` type when the schema has one.
+pub fn foreign_key_target(
+    schema: &ParsedSchema,
+    parsed_type: &ParsedType,
+    field: &ParsedField,
+) -> Option<String> {
+    let related_type = is_foreign_key_field(field)?;
+    let base = &field.name[..field.name.len() - 2];
+    let sibling = parsed_type
+        .fields
+        .iter()
+        .find_map(|sibling| match &sibling.field_type {
+            crate::parser::FieldType::Reference(target) if sibling.name == base => Some(target),
+            _ => None,
+        })
+        .filter(|target| schema.types.contains_key(*target));
+    match sibling {
+        Some(target) => Some(target.clone()),
+        None => schema
+            .types
+            .contains_key(&related_type)
+            .then_some(related_type),
+    }
+}
+
+/// A foreign key column of an entity, resolved to the column it references
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForeignKey {
+    /// Relation name, the field name without its `Id` suffix in PascalCase
+    pub name: String,
+    pub column: String,
+    pub related_type: String,
+    pub related_column: String,
+    pub nullable: bool,
+}
+
+impl ForeignKey {
+    /// Whether the key points back at its own table, e.g. `Category.parentId`
+    pub fn is_self_reference(&self, type_name: &str) -> bool {
+        self.related_type == type_name
+    }
+
+    /// Optional keys are cleared when the referenced row goes, required ones
+    /// take the referencing row with them
+    pub fn on_delete_sql(&self) -> &'static str {
+        if self.nullable { "SET NULL" } else { "CASCADE" }
+    }
+
+    /// `on_delete` name used by Sea-ORM and sea-query
+    pub fn on_delete_action(&self) -> &'static str {
+        if self.nullable { "SetNull" } else { "Cascade" }
+    }
+}
+
+/// Foreign keys of a type, in field order.
+///
+/// Keys into types with a composite primary key are skipped, since a single
+/// column can't reference them.
+pub fn foreign_keys(
+    schema: &ParsedSchema,
+    parsed_type: &ParsedType,
+    config: &Config,
+) -> Vec<ForeignKey> {
+    parsed_type
+        .fields
+        .iter()
+        .filter_map(|field| {
+            let related_type = foreign_key_target(schema, parsed_type, field)?;
+            let related = schema.types.get(&related_type)?;
+            if !matches!(related.kind, crate::parser::TypeKind::Object) {
+                return None;
+            }
+            let related_column = match primary_key_names(&related_type, related, config).as_slice()
+            {
+                [] => "id".to_string(),
+                [key] => to_snake_case(key),
+                _ => return None,
+            };
+            Some(ForeignKey {
+                name: to_pascal_case(&field.name[..field.name.len() - 2]),
+                column: to_snake_case(&field.name),
+                related_type,
+                related_column,
+                nullable: field.is_nullable,
+            })
+        })
+        .collect()
+}
+
+/// `FOREIGN KEY` constraints for a type's self-referencing keys.
+///
+/// Only keys back into the same table are declared: they are valid within a
+/// single `CREATE TABLE`, whereas keys into other tables would depend on the
+/// order the migrations run in.
+pub fn self_reference_constraints_sql(
+    schema: &ParsedSchema,
+    type_name: &str,
+    parsed_type: &ParsedType,
+    config: &Config,
+) -> Vec<String> {
+    let table_name = entity_table_name(type_name, config);
+    foreign_keys(schema, parsed_type, config)
+        .into_iter()
+        .filter(|key| key.is_self_reference(type_name))
+        .map(|key| {
+            format!(
+                "    CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({}) ON UPDATE CASCADE ON DELETE {}",
+                index_name("fk", &table_name, &key.column, &config.db),
+                key.column,
+                table_name,
+                key.related_column,
+                key.on_delete_sql()
+            )
+        })
+        .collect()
+}
+
 /// Detect relationships between types in the schema
 #[allow(dead_code)]
 pub fn detect_relationships(
@@ -216,17 +2289,13 @@ pub fn detect_relationships(
         let mut type_relationships = Vec::new();
 
         for field in &parsed_type.fields {
-            if let Some(related_type) = is_foreign_key_field(field) {
-                // Check if the related type exists in the schema
-                if schema.types.contains_key(&related_type) {
-                    let relationship = Relationship {
-                        field_name: field.name.clone(),
-                        related_type: related_type.clone(),
-                        relationship_type: RelationshipType::BelongsTo,
-                        foreign_key: true,
-                    };
-                    type_relationships.push(relationship);
-                }
+            if let Some(related_type) = foreign_key_target(schema, parsed_type, field) {
+                type_relationships.push(Relationship {
+                    field_name: field.name.clone(),
+                    related_type,
+                    relationship_type: RelationshipType::BelongsTo,
+                    foreign_key: true,
+                });
             }
         }
 
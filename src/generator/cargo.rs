@@ -0,0 +1,159 @@
+use crate::cli::{DatabaseType, OrmType};
+use crate::config::Config;
+use crate::generator::required_crates;
+use crate::parser::ParsedSchema;
+
+/// Generate a `Cargo.toml` for the output directory, so the generated code
+/// builds as a crate of its own.
+///
+/// The ORM dependency gets the feature for the configured database, and
+/// `chrono`, `time`, `uuid` and `serde_json` are added (along with the matching
+/// ORM features) only when a generated field uses them. Other crates named by
+/// type mappings are added without a version constraint.
+pub fn generate_cargo_toml(schema: &ParsedSchema, config: &Config) -> anyhow::Result<String> {
+    let crates = required_crates(schema, config);
+    let uses = |name: &str| crates.contains(name);
+
+    let mut output = String::new();
+    output.push_str("[package]\n");
+    output.push_str(&format!("name = \"{}\"\n", package_name(config)));
+    output.push_str("version = \"0.1.0\"\n");
+    output.push_str("edition = \"2021\"\n\n");
+    output.push_str("[dependencies]\n");
+
+    match config.orm {
+        OrmType::Diesel => {
+            let mut features = vec![
+                match config.db {
+                    DatabaseType::Sqlite => "sqlite",
+                    DatabaseType::Postgres => "postgres",
+                    DatabaseType::Mysql => "mysql",
+                }
+                .to_string(),
+            ];
+            for (name, feature) in [
+                ("chrono", "chrono"),
+                ("time", "time"),
+                ("uuid", "uuid"),
+                ("serde_json", "serde_json"),
+            ] {
+                if uses(name) {
+                    features.push(feature.to_string());
+                }
+            }
+            output.push_str(&dependency("diesel", "2.2", &features));
+        }
+        OrmType::SeaOrm => {
+            let mut features = vec![
+                match config.db {
+                    DatabaseType::Sqlite => "sqlx-sqlite",
+                    DatabaseType::Postgres => "sqlx-postgres",
+                    DatabaseType::Mysql => "sqlx-mysql",
+                }
+                .to_string(),
+                "runtime-tokio-rustls".to_string(),
+                "macros".to_string(),
+            ];
+            // Round-trip tests run against an in-memory SQLite database
+            if config.generate_tests && config.db != DatabaseType::Sqlite {
+                features.push("sqlx-sqlite".to_string());
+            }
+            for (name, feature) in [
+                ("chrono", "with-chrono"),
+                ("time", "with-time"),
+                ("uuid", "with-uuid"),
+                ("serde_json", "with-json"),
+            ] {
+                if uses(name) {
+                    features.push(feature.to_string());
+                }
+            }
+            output.push_str(&dependency("sea-orm", "1.1", &features));
+        }
+        OrmType::Plain => {}
+    }
+
+    output.push_str(&dependency("serde", "1.0", &["derive".to_string()]));
+    if uses("chrono") {
+        output.push_str(&dependency("chrono", "0.4", &["serde".to_string()]));
+    }
+    if uses("time") {
+        output.push_str(&dependency("time", "0.3", &["serde".to_string()]));
+    }
+    if uses("uuid") {
+        output.push_str(&dependency(
+            "uuid",
+            "1",
+            &["serde".to_string(), "v4".to_string()],
+        ));
+    }
+    if uses("serde_json") || config.generate_client {
+        output.push_str(&dependency("serde_json", "1.0", &[]));
+    }
+    if config.generate_client {
+        output.push_str(&dependency("anyhow", "1.0", &[]));
+        output.push_str(&dependency("reqwest", "0.12", &["json".to_string()]));
+    }
+
+    let mut mapped: Vec<&String> = crates
+        .iter()
+        .filter(|name| {
+            let name = name.as_str();
+            !(KNOWN_CRATES.contains(&name)
+                || (config.generate_client && ["anyhow", "reqwest"].contains(&name)))
+        })
+        .collect();
+    if !mapped.is_empty() {
+        mapped.sort();
+        output.push_str("# Used by type mappings; pin the versions and features you need\n");
+        for name in mapped {
+            output.push_str(&dependency(name, "*", &[]));
+        }
+    }
+
+    if config.orm == OrmType::SeaOrm && config.generate_tests {
+        output.push_str("\n[dev-dependencies]\n");
+        output.push_str(&dependency(
+            "tokio",
+            "1",
+            &["macros".to_string(), "rt-multi-thread".to_string()],
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Crates that always get their own dependency line when used
+const KNOWN_CRATES: &[&str] = &["serde", "chrono", "time", "uuid", "serde_json"];
+
+/// Package name from the last component of the output directory
+fn package_name(config: &Config) -> String {
+    let name: String = config
+        .output_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let name = name.trim_matches('-');
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        "generated".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// One `[dependencies]` line
+fn dependency(name: &str, version: &str, features: &[String]) -> String {
+    if features.is_empty() {
+        return format!("{} = \"{}\"\n", name, version);
+    }
+    let features: Vec<String> = features.iter().map(|f| format!("\"{}\"", f)).collect();
+    format!(
+        "{} = {{ version = \"{}\", features = [{}] }}\n",
+        name,
+        version,
+        features.join(", ")
+    )
+}
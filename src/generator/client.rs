@@ -0,0 +1,333 @@
+use std::collections::BTreeSet;
+
+use crate::cli::DatabaseType;
+use crate::config::Config;
+use crate::generator::{
+    datetime_rust_type, doc_comment, is_int64_scalar, mapped_scalar, qualify_mapped_type,
+    rust_ident, to_pascal_case, to_snake_case,
+};
+use crate::parser::{FieldType, ParsedArgument, ParsedField, ParsedSchema, ParsedType, TypeKind};
+
+/// Generate a typed client with one `async fn` per root query and mutation field.
+///
+/// Each function sends its operation to the configured `url` and deserializes the
+/// field's value from the response. Only the names of the configured `headers`
+/// are written out: their values often hold credentials, so the client reads
+/// them from environment variables at runtime (see [`header_env_var`]). Only leaf
+/// fields of returned objects are selected, and fields taking non-scalar
+/// arguments are skipped for now.
+pub fn generate_client(schema: &ParsedSchema, config: &Config) -> anyhow::Result<String> {
+    let mut operations = Vec::new();
+    let mut response_types = BTreeSet::new();
+
+    let roots = [
+        ("query", schema.query_root.as_deref().unwrap_or("Query")),
+        (
+            "mutation",
+            schema.mutation_root.as_deref().unwrap_or("Mutation"),
+        ),
+    ];
+    for (operation, root_name) in roots {
+        let Some(root) = schema.types.get(root_name) else {
+            continue;
+        };
+        for field in &root.fields {
+            operations.push(generate_operation(
+                schema,
+                config,
+                operation,
+                field,
+                &mut response_types,
+            ));
+        }
+    }
+
+    let mut code = String::new();
+    code.push_str("// Generated GraphQL client\n");
+    code.push_str("// Do not edit this file manually\n\n");
+    code.push_str("use serde::Deserialize;\n\n");
+
+    code.push_str(&format!(
+        "pub const GRAPHQL_URL: &str = {:?};\n\n",
+        config.url
+    ));
+
+    let mut headers: Vec<_> = config.headers.iter().collect();
+    headers.sort();
+    code.push_str("/// Headers sent with each request, as (header, environment variable) pairs.\n");
+    code.push_str("/// A header is left out when its variable is not set.\n");
+    code.push_str("const HEADERS: &[(&str, &str)] = &[\n");
+    for (name, _) in headers {
+        code.push_str(&format!("    ({:?}, {:?}),\n", name, header_env_var(name)));
+    }
+    code.push_str("];\n\n");
+
+    code.push_str(
+        "async fn execute<T: serde::de::DeserializeOwned>(\n    query: &str,\n    variables: serde_json::Value,\n    field: &str,\n) -> anyhow::Result<T> {\n",
+    );
+    code.push_str("    let mut request = reqwest::Client::new()\n");
+    code.push_str("        .post(GRAPHQL_URL)\n");
+    code.push_str(
+        "        .json(&serde_json::json!({ \"query\": query, \"variables\": variables }));\n",
+    );
+    code.push_str("    for (name, var) in HEADERS {\n");
+    code.push_str("        if let Ok(value) = std::env::var(var) {\n");
+    code.push_str("            request = request.header(*name, value);\n");
+    code.push_str("        }\n");
+    code.push_str("    }\n\n");
+    code.push_str("    let response: serde_json::Value =\n");
+    code.push_str("        request.send().await?.error_for_status()?.json().await?;\n");
+    code.push_str("    if let Some(errors) = response.get(\"errors\") {\n");
+    code.push_str("        anyhow::bail!(\"GraphQL request failed: {}\", errors);\n");
+    code.push_str("    }\n\n");
+    code.push_str("    let value = response\n");
+    code.push_str("        .get(\"data\")\n");
+    code.push_str("        .and_then(|data| data.get(field))\n");
+    code.push_str("        .cloned()\n");
+    code.push_str("        .unwrap_or(serde_json::Value::Null);\n");
+    code.push_str("    Ok(serde_json::from_value(value)?)\n");
+    code.push_str("}\n\n");
+
+    for type_name in &response_types {
+        if let Some(parsed_type) = schema.types.get(type_name) {
+            code.push_str(&generate_response_struct(schema, config, parsed_type));
+        }
+    }
+
+    for operation in operations {
+        code.push_str(&operation);
+    }
+
+    Ok(code)
+}
+
+fn generate_operation(
+    schema: &ParsedSchema,
+    config: &Config,
+    operation: &str,
+    field: &ParsedField,
+    response_types: &mut BTreeSet<String>,
+) -> String {
+    if let Some(arg) = field
+        .arguments
+        .iter()
+        .find(|arg| !is_leaf_type(schema, &arg.field_type))
+    {
+        return format!(
+            "// Skipped {} '{}': argument '{}' is not a scalar\n\n",
+            operation, field.name, arg.name
+        );
+    }
+
+    let selection = match &field.field_type {
+        FieldType::Reference(name) if !is_leaf_type(schema, &field.field_type) => {
+            let Some(parsed_type) = schema
+                .types
+                .get(name)
+                .filter(|t| !matches!(t.kind, TypeKind::Union))
+            else {
+                return format!(
+                    "// Skipped {} '{}': return type '{}' is not an object\n\n",
+                    operation, field.name, name
+                );
+            };
+            let leaf_fields: Vec<&str> = selectable_fields(schema, parsed_type)
+                .map(|f| f.name.as_str())
+                .collect();
+            if leaf_fields.is_empty() {
+                return format!(
+                    "// Skipped {} '{}': '{}' has no scalar fields to select\n\n",
+                    operation, field.name, name
+                );
+            }
+            response_types.insert(name.clone());
+            format!(" {{ {} }}", leaf_fields.join(" "))
+        }
+        _ => String::new(),
+    };
+
+    let variables = if field.arguments.is_empty() {
+        String::new()
+    } else {
+        let definitions: Vec<String> = field
+            .arguments
+            .iter()
+            .map(|arg| format!("${}: {}", arg.name, arg.graphql_type))
+            .collect();
+        format!("({})", definitions.join(", "))
+    };
+    let arguments = if field.arguments.is_empty() {
+        String::new()
+    } else {
+        let uses: Vec<String> = field
+            .arguments
+            .iter()
+            .map(|arg| format!("{}: ${}", arg.name, arg.name))
+            .collect();
+        format!("({})", uses.join(", "))
+    };
+    let document = format!(
+        "{} {}{} {{ {}{}{} }}",
+        operation,
+        to_pascal_case(&field.name),
+        variables,
+        field.name,
+        arguments,
+        selection
+    );
+
+    let params: Vec<String> = field
+        .arguments
+        .iter()
+        .map(|arg| {
+            format!(
+                "{}: {}",
+                rust_ident(&to_snake_case(&arg.name)),
+                argument_rust_type(schema, config, arg)
+            )
+        })
+        .collect();
+    let variable_values: Vec<String> = field
+        .arguments
+        .iter()
+        .map(|arg| format!("{:?}: {}", arg.name, rust_ident(&to_snake_case(&arg.name))))
+        .collect();
+
+    let mut code = String::new();
+    if let Some(description) = &field.description {
+        code.push_str(&doc_comment(description, ""));
+    }
+    code.push_str(&format!(
+        "pub async fn {}({}) -> anyhow::Result<{}> {{\n",
+        rust_ident(&to_snake_case(&field.name)),
+        params.join(", "),
+        field_rust_type(schema, config, field)
+    ));
+    code.push_str(&format!("    let query = {:?};\n", document));
+    code.push_str(&format!(
+        "    let variables = serde_json::json!({{ {} }});\n",
+        variable_values.join(", ")
+    ));
+    code.push_str(&format!(
+        "    execute(query, variables, {:?}).await\n",
+        field.name
+    ));
+    code.push_str("}\n\n");
+    code
+}
+
+fn generate_response_struct(
+    schema: &ParsedSchema,
+    config: &Config,
+    parsed_type: &ParsedType,
+) -> String {
+    let mut code = String::new();
+    if let Some(description) = &parsed_type.description {
+        code.push_str(&doc_comment(description, ""));
+    }
+    code.push_str("#[derive(Debug, Clone, Deserialize)]\n");
+    code.push_str(&format!("pub struct {} {{\n", parsed_type.name));
+    for field in selectable_fields(schema, parsed_type) {
+        let rust_name = rust_ident(&to_snake_case(&field.name));
+        if rust_name.trim_start_matches("r#") != field.name {
+            code.push_str(&format!("    #[serde(rename = \"{}\")]\n", field.name));
+        }
+        code.push_str(&format!(
+            "    pub {}: {},\n",
+            rust_name,
+            field_rust_type(schema, config, field)
+        ));
+    }
+    code.push_str("}\n\n");
+    code
+}
+
+/// Fields of a returned object that can be selected without nested selections
+fn selectable_fields<'a>(
+    schema: &'a ParsedSchema,
+    parsed_type: &'a ParsedType,
+) -> impl Iterator<Item = &'a ParsedField> {
+    parsed_type.fields.iter().filter(|f| {
+        is_leaf_type(schema, &f.field_type) && f.arguments.iter().all(|arg| arg.is_nullable)
+    })
+}
+
+/// Whether a type is a scalar, custom scalar or enum
+fn is_leaf_type(schema: &ParsedSchema, field_type: &FieldType) -> bool {
+    match field_type {
+        FieldType::Scalar(_) | FieldType::Enum(_) => true,
+        FieldType::Reference(name) => {
+            schema.enums.contains_key(name) || schema.scalars.contains(name)
+        }
+    }
+}
+
+fn leaf_rust_type(schema: &ParsedSchema, config: &Config, field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::Scalar(name) => match name.as_str() {
+            "Int" if config.int_as_i64 => "i64".to_string(),
+            "Int" => "i32".to_string(),
+            "Float" => "f64".to_string(),
+            "Boolean" => "bool".to_string(),
+            "String" | "ID" => "String".to_string(),
+            custom => custom_scalar_rust_type(config, custom),
+        },
+        FieldType::Enum(_) => "String".to_string(),
+        FieldType::Reference(name) if schema.enums.contains_key(name) => "String".to_string(),
+        FieldType::Reference(name) => custom_scalar_rust_type(config, name),
+    }
+}
+
+fn custom_scalar_rust_type(config: &Config, name: &str) -> String {
+    mapped_scalar(&config.type_mappings, &config.scalar_mappings, name)
+        .map(|mapped| qualify_mapped_type(mapped))
+        .or_else(|| {
+            // The client decodes the API's wire format, not a database column:
+            // GraphQL timestamps carry an offset whichever database is configured
+            datetime_rust_type(name, &config.datetime_crate, &DatabaseType::Postgres)
+                .map(str::to_string)
+        })
+        .or_else(|| is_int64_scalar(name).then(|| "i64".to_string()))
+        .unwrap_or_else(|| "serde_json::Value".to_string())
+}
+
+fn field_rust_type(schema: &ParsedSchema, config: &Config, field: &ParsedField) -> String {
+    let mut rust_type = match &field.field_type {
+        FieldType::Reference(name) if !is_leaf_type(schema, &field.field_type) => name.clone(),
+        field_type => leaf_rust_type(schema, config, field_type),
+    };
+    for _ in 0..field.list_depth {
+        rust_type = format!("Vec<{}>", rust_type);
+    }
+    if field.is_nullable {
+        rust_type = format!("Option<{}>", rust_type);
+    }
+    rust_type
+}
+
+fn argument_rust_type(schema: &ParsedSchema, config: &Config, arg: &ParsedArgument) -> String {
+    let mut rust_type = leaf_rust_type(schema, config, &arg.field_type);
+    if arg.is_list {
+        rust_type = format!("Vec<{}>", rust_type);
+    }
+    if arg.is_nullable {
+        rust_type = format!("Option<{}>", rust_type);
+    }
+    rust_type
+}
+
+/// Environment variable the generated client reads a header's value from, e.g.
+/// `GRAPHQL_AUTHORIZATION` for `Authorization` or `GRAPHQL_X_API_KEY` for `X-API-Key`.
+pub fn header_env_var(header: &str) -> String {
+    let name: String = header
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("GRAPHQL_{}", name)
+}
@@ -1,12 +1,18 @@
 use std::collections::HashMap;
 
-use crate::cli::DatabaseType;
-use crate::config::Config;
+use crate::config::{Config, EnumStorage};
 use crate::generator::{
-    CodeGenerator, MigrationFile, diesel_column_type_for_field, rust_type_for_field,
-    sql_type_for_field, to_snake_case,
+    CodeGenerator, ColumnContext, ForeignKey, IdType, MigrationFile, column_comment_sql,
+    column_default_sql, column_indexes, comment_statements_sql, create_indexes_sql,
+    deprecated_attribute, diesel_column_type_for_field, doc_comment, drop_indexes_sql,
+    entity_cfg_attribute, entity_table_name, enum_discriminants, enum_try_from_impl,
+    enum_variant_name, foreign_key_indexes, foreign_keys, is_column_field, is_renamed_ident,
+    lib_module_declaration, module_file_name, module_name, native_enum_name, native_enum_types_sql,
+    prelude_module, primary_key_names, rust_ident, rust_type_for_field,
+    self_reference_constraints_sql, sql_type_for_field, table_comment_sql, to_snake_case,
+    union_enum, uses_native_enums,
 };
-use crate::parser::{ParsedEnum, ParsedSchema, ParsedType};
+use crate::parser::{ParsedEnum, ParsedField, ParsedSchema, ParsedType};
 
 pub struct DieselGenerator;
 
@@ -34,8 +40,31 @@ impl CodeGenerator for DieselGenerator {
         // Add imports
         output.push_str("use diesel::prelude::*;\n\n");
 
-        // Generate table! macros for each type
-        for (type_name, parsed_type) in &schema.types {
+        // SQL types for the native enum columns, as diesel print-schema names them
+        if uses_native_enums(config) && !schema.enums.is_empty() {
+            let mut enum_names: Vec<_> = schema.enums.keys().collect();
+            enum_names.sort();
+            output.push_str("pub mod sql_types {\n");
+            for (i, enum_name) in enum_names.into_iter().enumerate() {
+                if i > 0 {
+                    output.push('\n');
+                }
+                output.push_str(
+                    "    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]\n",
+                );
+                output.push_str(&format!(
+                    "    #[diesel(postgres_type(name = \"{}\"))]\n",
+                    native_enum_name(enum_name)
+                ));
+                output.push_str(&format!("    pub struct {};\n", enum_name));
+            }
+            output.push_str("}\n\n");
+        }
+
+        // Generate table! macros for each type, sorted so the output is stable
+        let mut types: Vec<_> = schema.types.iter().collect();
+        types.sort_by(|a, b| a.0.cmp(b.0));
+        for (type_name, parsed_type) in types {
             if !matches!(parsed_type.kind, crate::parser::TypeKind::Object) {
                 continue; // Skip interfaces and unions for Diesel schema
             }
@@ -54,10 +83,12 @@ impl CodeGenerator for DieselGenerator {
         }
 
         // Generate enum types if needed
-        for (enum_name, parsed_enum) in &schema.enums {
+        let mut enums: Vec<_> = schema.enums.iter().collect();
+        enums.sort_by(|a, b| a.0.cmp(b.0));
+        for (enum_name, parsed_enum) in enums {
             output.push_str(
                 &self
-                    .generate_enum_type(enum_name, parsed_enum)
+                    .generate_enum_type(enum_name, parsed_enum, config, false)
                     .map_err(|e| {
                         anyhow::anyhow!("Failed to generate enum type '{}': {}", enum_name, e)
                     })?,
@@ -84,7 +115,7 @@ impl CodeGenerator for DieselGenerator {
         for (type_name, parsed_type) in &schema.types {
             if matches!(parsed_type.kind, crate::parser::TypeKind::Object) {
                 let entity_code = self
-                    .generate_entity_struct(type_name, parsed_type, config)
+                    .generate_entity_struct(schema, type_name, parsed_type, config)
                     .map_err(|e| {
                         anyhow::anyhow!(
                             "Failed to generate entity struct for type '{}': {}",
@@ -92,18 +123,27 @@ impl CodeGenerator for DieselGenerator {
                             e
                         )
                     })?;
-                entities.insert(format!("{}.rs", to_snake_case(type_name)), entity_code);
+                entities.insert(module_file_name(type_name), entity_code);
+            }
+        }
+
+        // Unions become enums over their members' structs
+        for (type_name, parsed_type) in &schema.types {
+            if let Some(code) =
+                self.generate_union_enum(schema, type_name, parsed_type, config, true)
+            {
+                entities.insert(module_file_name(type_name), code);
             }
         }
 
         // Generate enums
         for (enum_name, parsed_enum) in &schema.enums {
             let enum_code = self
-                .generate_enum_type(enum_name, parsed_enum)
+                .generate_enum_type(enum_name, parsed_enum, config, true)
                 .map_err(|e| {
                     anyhow::anyhow!("Failed to generate enum type '{}': {}", enum_name, e)
                 })?;
-            entities.insert(format!("{}.rs", to_snake_case(enum_name)), enum_code);
+            entities.insert(module_file_name(enum_name), enum_code);
         }
 
         Ok(entities)
@@ -121,11 +161,21 @@ impl CodeGenerator for DieselGenerator {
             return Ok(migrations);
         }
 
+        // Enum types come first, since the tables' columns use them; the
+        // zero version sorts the directory ahead of every table migration
+        if let Some((up_sql, down_sql)) = native_enum_types_sql(schema, config) {
+            migrations.push(MigrationFile::Sql {
+                name: "00000000000000_create_enum_types".to_string(),
+                up_sql,
+                down_sql,
+            });
+        }
+
         // Generate migrations for Object types (not interfaces or unions)
         for (type_name, parsed_type) in &schema.types {
             if matches!(parsed_type.kind, crate::parser::TypeKind::Object) {
                 let migration = self
-                    .generate_table_migration(type_name, parsed_type, config)
+                    .generate_table_migration(schema, type_name, parsed_type, config)
                     .map_err(|e| {
                         anyhow::anyhow!(
                             "Failed to generate migration for type '{}': {}",
@@ -139,41 +189,216 @@ impl CodeGenerator for DieselGenerator {
 
         Ok(migrations)
     }
+
+    fn generate_module_files(
+        &self,
+        schema: &ParsedSchema,
+        config: &Config,
+    ) -> anyhow::Result<Vec<(String, String)>> {
+        // Entity modules in a stable order, gated like their files
+        let mut modules: Vec<(&String, bool)> = schema
+            .types
+            .iter()
+            .filter(|(_, t)| matches!(t.kind, crate::parser::TypeKind::Object))
+            .map(|(name, _)| (name, true))
+            .chain(schema.enums.keys().map(|name| (name, false)))
+            .chain(
+                schema
+                    .types
+                    .iter()
+                    .filter(|(name, t)| {
+                        self.generate_union_enum(schema, name, t, config, true)
+                            .is_some()
+                    })
+                    .map(|(name, _)| (name, false)),
+            )
+            .collect();
+        modules.sort();
+
+        let mut entities_mod = String::new();
+        entities_mod.push_str("//! Diesel entities generated from GraphQL schema\n\n");
+
+        let mut lib = String::new();
+        lib.push_str("//! Diesel models generated from GraphQL schema\n\n");
+        lib.push_str(&lib_module_declaration(
+            "entities",
+            &config.entity_file("mod.rs"),
+        ));
+        let schema_file = config.schema_file().unwrap_or_default();
+        lib.push_str(&lib_module_declaration("schema", &schema_file));
+        lib.push('\n');
+
+        let mut prelude = Vec::new();
+        for (name, is_table) in modules {
+            let module_name = module_name(name);
+            let cfg = entity_cfg_attribute(name, config);
+            let cfg_line = cfg
+                .as_ref()
+                .map(|cfg| format!("{}\n", cfg))
+                .unwrap_or_default();
+            entities_mod.push_str(&format!("{}pub mod {};\n", cfg_line, module_name));
+            if is_table {
+                let has_changeset =
+                    !changeset_fields(schema, name, &schema.types[name], config).is_empty();
+                let update = if has_changeset {
+                    format!(", Update{}", name)
+                } else {
+                    String::new()
+                };
+                prelude.push((
+                    cfg,
+                    format!("{}::{{{}, New{}{}}}", module_name, name, name, update),
+                ));
+            } else {
+                prelude.push((cfg, format!("{}::{}", module_name, name)));
+            }
+        }
+        lib.push_str(&prelude_module(&prelude));
+
+        let mut files = Vec::new();
+        if config.generate_lib_rs {
+            files.push(("src/lib.rs".to_string(), lib));
+        }
+        files.push(("src/entities/mod.rs".to_string(), entities_mod));
+        Ok(files)
+    }
+
+    fn generate_single_file(
+        &self,
+        schema: &ParsedSchema,
+        config: &Config,
+    ) -> anyhow::Result<String> {
+        // The schema already carries the imports, table! macros and enums, and
+        // the models sit beside the tables they refer to
+        let mut output = self.generate_schema(schema, config)?;
+
+        let mut types: Vec<_> = schema
+            .types
+            .iter()
+            .filter(|(_, t)| matches!(t.kind, crate::parser::TypeKind::Object))
+            .collect();
+        types.sort_by(|a, b| a.0.cmp(b.0));
+        for (type_name, parsed_type) in types {
+            output.push('\n');
+            output.push_str(&self.generate_entity_models(
+                schema,
+                type_name,
+                parsed_type,
+                config,
+                false,
+            ));
+        }
+
+        let mut unions: Vec<_> = schema.types.iter().collect();
+        unions.sort_by(|a, b| a.0.cmp(b.0));
+        for (type_name, parsed_type) in unions {
+            if let Some(code) =
+                self.generate_union_enum(schema, type_name, parsed_type, config, false)
+            {
+                output.push('\n');
+                output.push_str(&code);
+            }
+        }
+
+        Ok(output)
+    }
 }
 
 impl DieselGenerator {
+    /// Enum over the structs of a union's members; `in_module` names them
+    /// through their entity modules rather than as siblings
+    fn generate_union_enum(
+        &self,
+        schema: &ParsedSchema,
+        type_name: &str,
+        parsed_type: &ParsedType,
+        config: &Config,
+        in_module: bool,
+    ) -> Option<String> {
+        if !matches!(parsed_type.kind, crate::parser::TypeKind::Union) {
+            return None;
+        }
+        // The structs don't derive serde, so neither can the enum
+        union_enum(
+            schema,
+            type_name,
+            parsed_type,
+            config,
+            "Debug",
+            false,
+            |member| {
+                if in_module {
+                    format!("super::{}::{}", module_name(member), member)
+                } else {
+                    member.to_string()
+                }
+            },
+        )
+    }
+
     fn generate_table_macro(
         &self,
         type_name: &str,
         parsed_type: &ParsedType,
         config: &Config,
     ) -> anyhow::Result<String> {
-        let table_name = to_snake_case(type_name);
-        let mut output = format!("table! {{\n    {} (", table_name);
+        let table_name = entity_table_name(type_name, config);
+        let mut output = String::from("table! {\n");
 
-        // Primary key - assume first field named 'id' or add one
-        let id_field = parsed_type
+        // Custom column types replace the default sql_types import
+        let mut native_enums: Vec<&str> = parsed_type
             .fields
             .iter()
-            .find(|f| f.name == "id")
-            .or_else(|| parsed_type.fields.first());
+            .filter_map(|field| match &field.field_type {
+                crate::parser::FieldType::Enum(enum_name) if uses_native_enums(config) => {
+                    Some(enum_name.as_str())
+                }
+                _ => None,
+            })
+            .collect();
+        native_enums.sort();
+        native_enums.dedup();
+        if !native_enums.is_empty() {
+            output.push_str("    use diesel::sql_types::*;\n");
+            let imports = if native_enums.len() == 1 {
+                native_enums[0].to_string()
+            } else {
+                format!("{{{}}}", native_enums.join(", "))
+            };
+            output.push_str(&format!("    use super::sql_types::{};\n\n", imports));
+        }
+        if is_renamed_ident(&table_name) {
+            output.push_str(&format!("    #[sql_name = \"{}\"]\n", table_name));
+        }
+        output.push_str(&format!("    {} (", rust_ident(&table_name)));
 
-        if let Some(id_field) = id_field {
-            output.push_str(&format!("{}\n    ) {{\n", id_field.name));
-        } else {
+        // Primary key - use the key fields or fall back to 'id'
+        let pk_names = primary_key_names(type_name, parsed_type, config);
+        if pk_names.is_empty() {
             output.push_str("id\n    ) {\n");
+        } else {
+            let key_columns: Vec<String> = pk_names
+                .iter()
+                .map(|name| rust_ident(&to_snake_case(name)))
+                .collect();
+            output.push_str(&format!("{}\n    ) {{\n", key_columns.join(", ")));
         }
 
         // Generate columns
         for field in &parsed_type.fields {
             let column_name = to_snake_case(&field.name);
-            let column_type =
-                diesel_column_type_for_field(field, &config.db, &config.type_mappings);
+            let column_type = diesel_column_type_for_field(field, config);
 
             let nullable = if field.is_nullable { "" } else { ".not_null()" };
+            // Keywords that can't be raw identifiers keep their name in SQL
+            if is_renamed_ident(&column_name) {
+                output.push_str(&format!("        #[sql_name = \"{}\"]\n", column_name));
+            }
             output.push_str(&format!(
                 "        {} -> {}{},\n",
-                column_name, column_type, nullable
+                rust_ident(&column_name),
+                column_type,
+                nullable
             ));
         }
 
@@ -183,27 +408,173 @@ impl DieselGenerator {
 
     fn generate_entity_struct(
         &self,
+        schema: &ParsedSchema,
         type_name: &str,
         parsed_type: &ParsedType,
         config: &Config,
     ) -> anyhow::Result<String> {
-        let struct_name = type_name.to_string();
-        let table_name = to_snake_case(type_name);
+        let table_name = entity_table_name(type_name, config);
 
         let mut output = String::new();
 
         // Add imports
         output.push_str("#[macro_use]\nextern crate diesel;\n\n");
         output.push_str("use diesel::prelude::*;\n");
-        output.push_str(&format!("use super::{}::*;\n\n", table_name));
+        output.push_str(&format!(
+            "use crate::schema::{}::*;\n\n",
+            rust_ident(&table_name)
+        ));
+
+        output.push_str(&self.generate_entity_models(schema, type_name, parsed_type, config, true));
+
+        if config.generate_tests {
+            output.push_str(&self.generate_test_module(type_name, config));
+        }
+
+        Ok(output)
+    }
+
+    /// Ignored round-trip test against an in-memory SQLite database
+    fn generate_test_module(&self, type_name: &str, config: &Config) -> String {
+        let table_name = entity_table_name(type_name, config);
+        let table_ident = rust_ident(&table_name);
+
+        let mut output = String::new();
+        output.push_str("\n#[cfg(test)]\nmod tests {\n");
+        output.push_str("    use super::*;\n");
+        output.push_str(&format!("    use crate::schema::{};\n", table_ident));
+        output.push_str("    use diesel::sqlite::SqliteConnection;\n\n");
+        output.push_str("    /// The row inserted by the round-trip test\n");
+        output.push_str(&format!("    fn new_row() -> New{} {{\n", type_name));
+        output.push_str(&format!(
+            "        todo!(\"fill in the fields of a New{}\")\n",
+            type_name
+        ));
+        output.push_str("    }\n\n");
+        output.push_str("    #[test]\n");
+        output.push_str("    #[ignore = \"fill in the new row before enabling\"]\n");
+        output.push_str(&format!("    fn {}_round_trip() {{\n", table_name));
+        output.push_str("        let mut conn = SqliteConnection::establish(\":memory:\")\n");
+        output.push_str("            .expect(\"Failed to open in-memory database\");\n");
+        output.push_str(&format!(
+            "        // Create the `{}` table here, e.g. by running the generated migrations\n\n",
+            table_name
+        ));
+        output.push_str(&format!(
+            "        diesel::insert_into({}::table)\n",
+            table_ident
+        ));
+        output.push_str("            .values(&new_row())\n");
+        output.push_str("            .execute(&mut conn)\n");
+        output.push_str("            .expect(\"Failed to insert\");\n\n");
+        output.push_str(&format!(
+            "        let rows: Vec<{}> = {}::table\n",
+            type_name, table_ident
+        ));
+        output.push_str("            .load(&mut conn)\n");
+        output.push_str("            .expect(\"Failed to select\");\n");
+        output.push_str("        assert_eq!(rows.len(), 1);\n");
+        output.push_str("    }\n");
+        output.push_str("}\n");
+        output
+    }
+
+    /// Queryable, Insertable and AsChangeset structs for a type, without imports;
+    /// `in_module` names tables and related structs through their modules
+    /// rather than as siblings
+    fn generate_entity_models(
+        &self,
+        schema: &ParsedSchema,
+        type_name: &str,
+        parsed_type: &ParsedType,
+        config: &Config,
+        in_module: bool,
+    ) -> String {
+        let struct_name = type_name.to_string();
+        let table_name = entity_table_name(type_name, config);
+
+        let mut output = String::new();
 
         // Generate the struct
-        output.push_str("#[derive(Queryable, Debug)]\n");
+        if let Some(description) = &parsed_type.description {
+            output.push_str(&doc_comment(description, ""));
+        }
+        // Diesel has no counterpart to interfaces, so they're only noted
+        if !parsed_type.interfaces.is_empty() {
+            if parsed_type.description.is_some() {
+                output.push_str("///\n");
+            }
+            let interfaces: Vec<String> = parsed_type
+                .interfaces
+                .iter()
+                .map(|name| format!("`{}`", name))
+                .collect();
+            output.push_str(&format!(
+                "/// Implements the GraphQL interface{} {}\n",
+                if interfaces.len() == 1 { "" } else { "s" },
+                interfaces.join(", ")
+            ));
+        }
+        // Identifiable needs the key among the struct's fields
+        let pk_names = primary_key_names(type_name, parsed_type, config);
+        let key_columns = key_columns(type_name, parsed_type, config);
+        // Diesel allows one belongs_to per parent type
+        let mut parents: Vec<ForeignKey> = Vec::new();
+        for key in foreign_keys(schema, parsed_type, config) {
+            if !parents.iter().any(|p| p.related_type == key.related_type) {
+                parents.push(key);
+            }
+        }
+
+        let mut derives = vec!["Queryable"];
+        if !key_columns.is_empty() {
+            derives.push("Identifiable");
+        }
+        if !parents.is_empty() {
+            derives.push("Associations");
+        }
+        derives.push("Debug");
+        output.push_str(&format!("#[derive({})]\n", derives.join(", ")));
+        let table_path = if in_module {
+            format!("crate::schema::{}", rust_ident(&table_name))
+        } else {
+            rust_ident(&table_name)
+        };
+        // Identifiable and Associations look the table up
+        if !key_columns.is_empty() || !parents.is_empty() {
+            output.push_str(&format!("#[diesel(table_name = {})]\n", table_path));
+        }
+        if !key_columns.is_empty() && key_columns != ["id"] {
+            let key_idents: Vec<String> = key_columns.iter().map(|c| rust_ident(c)).collect();
+            output.push_str(&format!(
+                "#[diesel(primary_key({}))]\n",
+                key_idents.join(", ")
+            ));
+        }
+        for parent in &parents {
+            let parent_path = if in_module && !parent.is_self_reference(type_name) {
+                format!(
+                    "super::{}::{}",
+                    module_name(&parent.related_type),
+                    parent.related_type
+                )
+            } else {
+                parent.related_type.clone()
+            };
+            output.push_str(&format!(
+                "#[diesel(belongs_to({}, foreign_key = {}))]\n",
+                parent_path,
+                rust_ident(&parent.column)
+            ));
+        }
         output.push_str(&format!("pub struct {} {{\n", struct_name));
 
         for field in &parsed_type.fields {
-            let field_name = to_snake_case(&field.name);
-            let field_type = rust_type_for_field(field, &config.db, &config.type_mappings);
+            let field_name = rust_ident(&to_snake_case(&field.name));
+            let field_type = rust_type_for_field(field, config);
+            if let Some(description) = &field.description {
+                output.push_str(&doc_comment(description, "    "));
+            }
             output.push_str(&format!("    pub {}: {},\n", field_name, field_type));
         }
 
@@ -211,114 +582,476 @@ impl DieselGenerator {
 
         // Generate Insertable struct
         output.push_str("#[derive(Insertable)]\n");
-        output.push_str(&format!("#[table_name = \"{}\"]\n", table_name));
+        output.push_str(&format!("#[diesel(table_name = {})]\n", table_path));
         output.push_str(&format!("pub struct New{} {{\n", struct_name));
 
+        // Composite key columns are always supplied by the caller
         for field in &parsed_type.fields {
-            if field.name != "id" {
-                // Skip id for inserts
-                let field_name = to_snake_case(&field.name);
-                let field_type = rust_type_for_field(field, &config.db, &config.type_mappings);
+            let is_generated_key = pk_names == [field.name.as_str()]
+                && matches!(&field.field_type, crate::parser::FieldType::Scalar(s) if s == "ID");
+            if !is_generated_key {
+                // Skip database-assigned ID keys for inserts
+                let field_name = rust_ident(&to_snake_case(&field.name));
+                let field_type = rust_type_for_field(field, config);
+                if let Some(description) = &field.description {
+                    output.push_str(&doc_comment(description, "    "));
+                }
                 output.push_str(&format!("    pub {}: {},\n", field_name, field_type));
             }
         }
 
         output.push_str("}\n\n");
 
+        // Generate AsChangeset struct, where None leaves a column as it is
+        let updatable = changeset_fields(schema, type_name, parsed_type, config);
+        if !updatable.is_empty() {
+            output.push_str("#[derive(AsChangeset, Default)]\n");
+            output.push_str(&format!("#[diesel(table_name = {})]\n", table_path));
+            output.push_str(&format!("pub struct Update{} {{\n", struct_name));
+            for field in updatable {
+                let field_type = rust_type_for_field(field, config);
+                if let Some(description) = &field.description {
+                    output.push_str(&doc_comment(description, "    "));
+                }
+                output.push_str(&format!(
+                    "    pub {}: Option<{}>,\n",
+                    rust_ident(&to_snake_case(&field.name)),
+                    field_type
+                ));
+            }
+            output.push_str("}\n\n");
+        }
+
         // Generate relationships based on detected foreign keys
         // For now, we'll add a comment about potential relationships
         // Full relationship generation would require schema-wide analysis
         output.push_str("// TODO: Generate joinable! macros for relationships\n");
 
-        Ok(output)
+        output
     }
 
+    /// Enum for an enum type; `in_module` names a native enum's SQL type
+    /// through the schema module rather than as a sibling
     fn generate_enum_type(
         &self,
         enum_name: &str,
         parsed_enum: &ParsedEnum,
+        config: &Config,
+        in_module: bool,
     ) -> anyhow::Result<String> {
         let mut output = String::new();
 
         if let Some(description) = &parsed_enum.description {
-            output.push_str(&format!("/// {}\n", description));
+            output.push_str(&doc_comment(description, ""));
         }
 
-        output.push_str("#[derive(Debug, Clone, PartialEq, Eq, Hash)]\n");
-        output.push_str("#[derive(diesel::deserialize::FromSqlRow, diesel::serialize::ToSql)]\n");
-        output.push_str("#[sql_type = \"diesel::sql_types::Text\"]\n");
-        output.push_str(&format!("pub enum {} {{\n", enum_name));
+        let allow_deprecated = if parsed_enum.deprecations.is_empty() {
+            ""
+        } else {
+            "#[allow(deprecated)]\n"
+        };
 
-        for value in &parsed_enum.values {
-            output.push_str(&format!("    {},\n", value));
-        }
+        match config.enum_storage {
+            EnumStorage::Text => {
+                let sql_type = match (uses_native_enums(config), in_module) {
+                    (true, true) => format!("crate::schema::sql_types::{}", enum_name),
+                    (true, false) => format!("sql_types::{}", enum_name),
+                    (false, _) => "diesel::sql_types::Text".to_string(),
+                };
+                output.push_str("#[derive(Debug, Clone, PartialEq, Eq, Hash)]\n");
+                output.push_str(
+                    "#[derive(diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)]\n",
+                );
+                output.push_str(&format!("#[diesel(sql_type = {})]\n", sql_type));
+                output.push_str(&format!("pub enum {} {{\n", enum_name));
 
-        output.push_str("}\n");
+                let mut arms = Vec::new();
+                for value in &parsed_enum.values {
+                    let variant = enum_variant_name(value, &config.prefix_enum_values);
+                    if variant.trim_start_matches("r#") != value {
+                        output.push_str(&format!("    /// GraphQL value `{}`\n", value));
+                    }
+                    if let Some(attribute) = deprecated_attribute(parsed_enum, value) {
+                        output.push_str(&format!("    {}\n", attribute));
+                    }
+                    output.push_str(&format!("    {},\n", variant));
+                    arms.push((format!("{}::{}", enum_name, variant), value.as_str()));
+                }
+
+                output.push_str("}\n");
+                if uses_native_enums(config) {
+                    output.push_str(&native_enum_sql_impls(
+                        enum_name,
+                        &sql_type,
+                        &arms,
+                        allow_deprecated,
+                    ));
+                } else {
+                    output.push_str(&text_enum_sql_impls(enum_name, &arms, allow_deprecated));
+                }
+            }
+            EnumStorage::Int => {
+                let discriminants = enum_discriminants(parsed_enum)?;
+
+                output.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]\n");
+                output.push_str(
+                    "#[derive(diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)]\n",
+                );
+                output.push_str("#[diesel(sql_type = diesel::sql_types::Integer)]\n");
+                output.push_str("#[repr(i32)]\n");
+                output.push_str(&format!("pub enum {} {{\n", enum_name));
+
+                let mut arms = Vec::new();
+                for (value, discriminant) in &discriminants {
+                    let variant = enum_variant_name(value, &config.prefix_enum_values);
+                    if variant.trim_start_matches("r#") != *value {
+                        output.push_str(&format!("    /// GraphQL value `{}`\n", value));
+                    }
+                    if let Some(attribute) = deprecated_attribute(parsed_enum, value) {
+                        output.push_str(&format!("    {}\n", attribute));
+                    }
+                    output.push_str(&format!("    {} = {},\n", variant, discriminant));
+                    arms.push((format!("{}::{}", enum_name, variant), *discriminant));
+                }
+
+                output.push_str("}\n");
+                output.push_str(&enum_try_from_impl(
+                    enum_name,
+                    &discriminants,
+                    &config.prefix_enum_values,
+                    !parsed_enum.deprecations.is_empty(),
+                ));
+                output.push_str(&int_enum_sql_impls(enum_name, &arms, allow_deprecated));
+            }
+        }
 
         Ok(output)
     }
 
     fn generate_table_migration(
         &self,
+        schema: &ParsedSchema,
         type_name: &str,
         parsed_type: &ParsedType,
         config: &Config,
     ) -> anyhow::Result<MigrationFile> {
-        let table_name = to_snake_case(type_name);
+        let table_name = entity_table_name(type_name, config);
         let migration_name = format!("create_{}_table", table_name);
 
-        let mut up_sql = format!("CREATE TABLE {} (\n", table_name);
+        let mut up_sql = String::new();
 
         let mut columns = Vec::new();
+        let mut comments = Vec::new();
 
-        // Add id column if not present
-        let has_id = parsed_type.fields.iter().any(|f| f.name == "id");
-        if !has_id {
-            let id_type = match config.db {
-                DatabaseType::Sqlite => "INTEGER PRIMARY KEY AUTOINCREMENT",
-                DatabaseType::Postgres => "UUID PRIMARY KEY DEFAULT gen_random_uuid()",
-                DatabaseType::Mysql => "INT UNSIGNED PRIMARY KEY AUTO_INCREMENT",
-            };
-            columns.push(format!("    id {}", id_type));
+        // Add id column if no primary key field was detected
+        let id_type = IdType::resolve(&config.db, &config.id_mapping);
+        if let Some(extension) = id_type.extension_sql(&config.db, &config.pk_default) {
+            up_sql.push_str(&format!("{}\n\n", extension));
+        }
+        up_sql.push_str(&format!("CREATE TABLE {} (\n", table_name));
+        let pk_names = primary_key_names(type_name, parsed_type, config);
+        let composite = pk_names.len() > 1;
+        if pk_names.is_empty() {
+            columns.push(format!(
+                "    id {}",
+                id_type.primary_key_sql(
+                    &config.db,
+                    &config.pk_default,
+                    config.sqlite_autoincrement,
+                )
+            ));
         }
 
         for field in &parsed_type.fields {
             let column_name = to_snake_case(&field.name);
-            let sql_type = sql_type_for_field(field, &config.db, &config.type_mappings);
+            let is_primary_key = pk_names.contains(&field.name);
+            // Composite keys are declared once after the columns
+            let is_single_key = is_primary_key && !composite;
+            let comment = column_comment_sql(field.description.as_deref(), &config.db);
+            if let Some(description) = &field.description {
+                comments.push((column_name.clone(), description.as_str()));
+            }
+
+            // ID primary keys share the implicit id column's definition so the
+            // migration agrees with the entity's auto-increment behaviour
+            if is_single_key
+                && matches!(&field.field_type, crate::parser::FieldType::Scalar(s) if s == "ID")
+            {
+                columns.push(format!(
+                    "    {} {}{}",
+                    column_name,
+                    id_type.primary_key_sql(
+                        &config.db,
+                        &config.pk_default,
+                        config.sqlite_autoincrement,
+                    ),
+                    comment
+                ));
+                continue;
+            }
+
+            let sql_type = sql_type_for_field(
+                field,
+                ColumnContext {
+                    type_name,
+                    is_primary_key,
+                },
+                config,
+            );
 
             let nullable = if field.is_nullable { "" } else { " NOT NULL" };
-            let primary_key = if field.name == "id" {
-                " PRIMARY KEY"
+            let default = column_default_sql(schema, type_name, field, config)?;
+            let primary_key = if is_single_key { " PRIMARY KEY" } else { "" };
+            let unique = if !is_single_key && config.is_unique(type_name, field) {
+                " UNIQUE"
             } else {
                 ""
             };
 
             columns.push(format!(
-                "    {} {}{}{}",
-                column_name, sql_type, nullable, primary_key
+                "    {} {}{}{}{}{}{}",
+                column_name, sql_type, nullable, default, primary_key, unique, comment
             ));
         }
 
-        up_sql.push_str(&columns.join(",\n"));
-        up_sql.push_str("\n);");
+        if composite {
+            let key_columns: Vec<String> =
+                pk_names.iter().map(|name| to_snake_case(name)).collect();
+            columns.push(format!("    PRIMARY KEY ({})", key_columns.join(", ")));
+        }
+        columns.extend(self_reference_constraints_sql(
+            schema,
+            type_name,
+            parsed_type,
+            config,
+        ));
 
-        // Add indexes for foreign keys (simplified)
-        for field in &parsed_type.fields {
-            if let crate::parser::FieldType::Reference(_) = &field.field_type {
-                let column_name = to_snake_case(&field.name);
-                up_sql.push_str(&format!(
-                    "\n\nCREATE INDEX idx_{}_{} ON {} ({});",
-                    table_name, column_name, table_name, column_name
-                ));
-            }
+        up_sql.push_str(&columns.join(",\n"));
+        up_sql.push_str(&format!(
+            "\n){};",
+            table_comment_sql(parsed_type.description.as_deref(), &config.db)
+        ));
+        let mut indexes = column_indexes(type_name, parsed_type, config);
+        indexes.extend(foreign_key_indexes(type_name, parsed_type, config));
+        up_sql.push_str(&create_indexes_sql(&table_name, &indexes));
+        for statement in comment_statements_sql(
+            &table_name,
+            parsed_type.description.as_deref(),
+            &comments,
+            &config.db,
+        ) {
+            up_sql.push_str(&format!("\n\n{}", statement));
         }
 
-        let down_sql = format!("DROP TABLE {};", table_name);
+        let down_sql = format!(
+            "{}DROP TABLE {};",
+            drop_indexes_sql(&table_name, &indexes, &config.db),
+            table_name
+        );
 
-        Ok(MigrationFile {
+        Ok(MigrationFile::Sql {
             name: migration_name,
             up_sql,
             down_sql,
         })
     }
 }
+
+/// Snake-case key columns of a table, or none when it has no key field
+fn key_columns(type_name: &str, parsed_type: &ParsedType, config: &Config) -> Vec<String> {
+    let pk_names = primary_key_names(type_name, parsed_type, config);
+    if pk_names.is_empty() {
+        parsed_type
+            .fields
+            .iter()
+            .filter(|field| field.name == "id")
+            .map(|_| "id".to_string())
+            .collect()
+    } else {
+        pk_names.iter().map(|name| to_snake_case(name)).collect()
+    }
+}
+
+/// Fields of the `Update*` changeset: every non-key column, leaving out
+/// to-many relations and `@computed` fields, or none when changesets are
+/// turned off
+fn changeset_fields<'a>(
+    schema: &ParsedSchema,
+    type_name: &str,
+    parsed_type: &'a ParsedType,
+    config: &Config,
+) -> Vec<&'a ParsedField> {
+    if !config.generate_changesets {
+        return Vec::new();
+    }
+    let key_columns = key_columns(type_name, parsed_type, config);
+    parsed_type
+        .fields
+        .iter()
+        .filter(|field| {
+            is_column_field(field, schema) && !key_columns.contains(&to_snake_case(&field.name))
+        })
+        .collect()
+}
+
+/// `ToSql`/`FromSql` impls storing an enum as its GraphQL value in a `Text`
+/// column, for any backend with string support.
+///
+/// The values are `'static`, since `to_sql` borrows them for the lifetime of
+/// the output.
+fn text_enum_sql_impls(enum_name: &str, arms: &[(String, &str)], allow_deprecated: &str) -> String {
+    let mut output = String::from("\n");
+    output.push_str(allow_deprecated);
+    output.push_str(&format!(
+        "impl<DB> diesel::serialize::ToSql<diesel::sql_types::Text, DB> for {}\n",
+        enum_name
+    ));
+    output.push_str("where\n");
+    output.push_str("    DB: diesel::backend::Backend,\n");
+    output.push_str("    str: diesel::serialize::ToSql<diesel::sql_types::Text, DB>,\n");
+    output.push_str("{\n");
+    output.push_str("    fn to_sql<'b>(\n");
+    output.push_str("        &'b self,\n");
+    output.push_str("        out: &mut diesel::serialize::Output<'b, '_, DB>,\n");
+    output.push_str("    ) -> diesel::serialize::Result {\n");
+    output.push_str("        let value: &'static str = match self {\n");
+    for (variant, value) in arms {
+        output.push_str(&format!("            {} => {:?},\n", variant, value));
+    }
+    output.push_str("        };\n");
+    output.push_str(
+        "        <str as diesel::serialize::ToSql<diesel::sql_types::Text, DB>>::to_sql(value, out)\n",
+    );
+    output.push_str("    }\n");
+    output.push_str("}\n\n");
+
+    output.push_str(allow_deprecated);
+    output.push_str(&format!(
+        "impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Text, DB> for {}\n",
+        enum_name
+    ));
+    output.push_str("where\n");
+    output.push_str("    DB: diesel::backend::Backend,\n");
+    output.push_str("    String: diesel::deserialize::FromSql<diesel::sql_types::Text, DB>,\n");
+    output.push_str("{\n");
+    output.push_str(
+        "    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {\n",
+    );
+    output.push_str(
+        "        let value = <String as diesel::deserialize::FromSql<diesel::sql_types::Text, DB>>::from_sql(bytes)?;\n",
+    );
+    output.push_str("        match value.as_str() {\n");
+    for (variant, value) in arms {
+        output.push_str(&format!("            {:?} => Ok({}),\n", value, variant));
+    }
+    output.push_str(&format!(
+        "            other => Err(format!(\"Unrecognized {} value: {{}}\", other).into()),\n",
+        enum_name
+    ));
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+    output
+}
+
+/// `ToSql`/`FromSql` impls for a PostgreSQL enum type, which travels as the
+/// value's bytes
+fn native_enum_sql_impls(
+    enum_name: &str,
+    sql_type: &str,
+    arms: &[(String, &str)],
+    allow_deprecated: &str,
+) -> String {
+    let mut output = String::from("\n");
+    output.push_str(allow_deprecated);
+    output.push_str(&format!(
+        "impl diesel::serialize::ToSql<{}, diesel::pg::Pg> for {} {{\n",
+        sql_type, enum_name
+    ));
+    output.push_str("    fn to_sql<'b>(\n");
+    output.push_str("        &'b self,\n");
+    output.push_str("        out: &mut diesel::serialize::Output<'b, '_, diesel::pg::Pg>,\n");
+    output.push_str("    ) -> diesel::serialize::Result {\n");
+    output.push_str("        use std::io::Write;\n\n");
+    output.push_str("        let value = match self {\n");
+    for (variant, value) in arms {
+        output.push_str(&format!("            {} => {:?},\n", variant, value));
+    }
+    output.push_str("        };\n");
+    output.push_str("        out.write_all(value.as_bytes())?;\n");
+    output.push_str("        Ok(diesel::serialize::IsNull::No)\n");
+    output.push_str("    }\n");
+    output.push_str("}\n\n");
+
+    output.push_str(allow_deprecated);
+    output.push_str(&format!(
+        "impl diesel::deserialize::FromSql<{}, diesel::pg::Pg> for {} {{\n",
+        sql_type, enum_name
+    ));
+    output.push_str(
+        "    fn from_sql(bytes: diesel::pg::PgValue<'_>) -> diesel::deserialize::Result<Self> {\n",
+    );
+    output.push_str("        match bytes.as_bytes() {\n");
+    for (variant, value) in arms {
+        output.push_str(&format!("            b{:?} => Ok({}),\n", value, variant));
+    }
+    output.push_str(&format!(
+        "            other => Err(format!(\"Unrecognized {} value: {{}}\", String::from_utf8_lossy(other)).into()),\n",
+        enum_name
+    ));
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+    output
+}
+
+/// `ToSql`/`FromSql` impls storing an enum as its discriminant in an
+/// `Integer` column, reading it back through the `TryFrom<i32>` impl
+fn int_enum_sql_impls(enum_name: &str, arms: &[(String, i32)], allow_deprecated: &str) -> String {
+    let mut output = String::from("\n");
+    output.push_str(allow_deprecated);
+    output.push_str(&format!(
+        "impl<DB> diesel::serialize::ToSql<diesel::sql_types::Integer, DB> for {}\n",
+        enum_name
+    ));
+    output.push_str("where\n");
+    output.push_str("    DB: diesel::backend::Backend,\n");
+    output.push_str("    i32: diesel::serialize::ToSql<diesel::sql_types::Integer, DB>,\n");
+    output.push_str("{\n");
+    output.push_str("    fn to_sql<'b>(\n");
+    output.push_str("        &'b self,\n");
+    output.push_str("        out: &mut diesel::serialize::Output<'b, '_, DB>,\n");
+    output.push_str("    ) -> diesel::serialize::Result {\n");
+    output.push_str("        let value: &'static i32 = match self {\n");
+    for (variant, discriminant) in arms {
+        output.push_str(&format!("            {} => &{},\n", variant, discriminant));
+    }
+    output.push_str("        };\n");
+    output.push_str(
+        "        <i32 as diesel::serialize::ToSql<diesel::sql_types::Integer, DB>>::to_sql(value, out)\n",
+    );
+    output.push_str("    }\n");
+    output.push_str("}\n\n");
+
+    output.push_str(&format!(
+        "impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Integer, DB> for {}\n",
+        enum_name
+    ));
+    output.push_str("where\n");
+    output.push_str("    DB: diesel::backend::Backend,\n");
+    output.push_str("    i32: diesel::deserialize::FromSql<diesel::sql_types::Integer, DB>,\n");
+    output.push_str("{\n");
+    output.push_str(
+        "    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {\n",
+    );
+    output.push_str(
+        "        let value = <i32 as diesel::deserialize::FromSql<diesel::sql_types::Integer, DB>>::from_sql(bytes)?;\n",
+    );
+    output.push_str(&format!(
+        "        Self::try_from(value).map_err(|value| format!(\"Unrecognized {} value: {{}}\", value).into())\n",
+        enum_name
+    ));
+    output.push_str("    }\n");
+    output.push_str("}\n");
+    output
+}
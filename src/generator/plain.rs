@@ -0,0 +1,321 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::config::{Config, EnumStorage};
+use crate::generator::{
+    CodeGenerator, MigrationFile, deprecated_attribute, diesel::DieselGenerator, doc_comment,
+    entity_cfg_attribute, enum_discriminants, enum_try_from_impl, enum_variant_name,
+    is_column_field, is_renamed_ident, lib_module_declaration, module_file_name, module_name,
+    prelude_module, rust_ident, rust_type_for_field, to_snake_case, union_enum,
+};
+use crate::parser::{FieldType, ParsedEnum, ParsedSchema, ParsedType, TypeKind};
+
+/// Generates ORM-free serde structs and enums plus raw SQL migrations
+pub struct PlainGenerator;
+
+impl PlainGenerator {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for PlainGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CodeGenerator for PlainGenerator {
+    fn generate_schema(&self, _schema: &ParsedSchema, _config: &Config) -> anyhow::Result<String> {
+        // There is no ORM schema to describe; callers don't write this file
+        Ok(String::new())
+    }
+
+    fn generate_entities(
+        &self,
+        schema: &ParsedSchema,
+        config: &Config,
+    ) -> anyhow::Result<HashMap<String, String>> {
+        let mut entities = HashMap::new();
+
+        for (type_name, parsed_type) in &schema.types {
+            if matches!(parsed_type.kind, TypeKind::Object) {
+                let mut code = String::from("use serde::{Deserialize, Serialize};\n");
+                for enum_name in referenced_enums(parsed_type) {
+                    code.push_str(&format!(
+                        "use super::{}::{};\n",
+                        module_name(enum_name),
+                        enum_name
+                    ));
+                }
+                code.push('\n');
+                code.push_str(&self.generate_struct(schema, type_name, parsed_type, config));
+                entities.insert(module_file_name(type_name), code);
+            } else if let Some(code) =
+                self.generate_union_enum(schema, type_name, parsed_type, config, true)
+            {
+                entities.insert(
+                    module_file_name(type_name),
+                    format!("use serde::{{Deserialize, Serialize}};\n\n{}", code),
+                );
+            }
+        }
+
+        for (enum_name, parsed_enum) in &schema.enums {
+            let mut code = String::from("use serde::{Deserialize, Serialize};\n\n");
+            code.push_str(
+                &self
+                    .generate_enum(enum_name, parsed_enum, config)
+                    .map_err(|e| {
+                        anyhow::anyhow!("Failed to generate enum type '{}': {}", enum_name, e)
+                    })?,
+            );
+            entities.insert(module_file_name(enum_name), code);
+        }
+
+        Ok(entities)
+    }
+
+    fn generate_migrations(
+        &self,
+        schema: &ParsedSchema,
+        config: &Config,
+    ) -> anyhow::Result<Vec<MigrationFile>> {
+        // Diesel's migrations are plain SQL with no Diesel-specific parts
+        DieselGenerator::new().generate_migrations(schema, config)
+    }
+
+    fn generate_module_files(
+        &self,
+        schema: &ParsedSchema,
+        config: &Config,
+    ) -> anyhow::Result<Vec<(String, String)>> {
+        let mut modules: Vec<&String> = schema
+            .types
+            .iter()
+            .filter(|(name, t)| {
+                matches!(t.kind, TypeKind::Object)
+                    || self
+                        .generate_union_enum(schema, name, t, config, true)
+                        .is_some()
+            })
+            .map(|(name, _)| name)
+            .chain(schema.enums.keys())
+            .collect();
+        modules.sort();
+
+        let mut entities_mod = String::new();
+        entities_mod.push_str("//! Plain structs generated from GraphQL schema\n\n");
+
+        let mut lib = String::new();
+        lib.push_str("//! Plain models generated from GraphQL schema\n\n");
+        lib.push_str(&lib_module_declaration(
+            "entities",
+            &config.entity_file("mod.rs"),
+        ));
+        lib.push('\n');
+
+        let mut prelude = Vec::new();
+        for name in modules {
+            let module_name = module_name(name);
+            let cfg = entity_cfg_attribute(name, config);
+            let cfg_line = cfg
+                .as_ref()
+                .map(|cfg| format!("{}\n", cfg))
+                .unwrap_or_default();
+            entities_mod.push_str(&format!("{}pub mod {};\n", cfg_line, module_name));
+            prelude.push((cfg, format!("{}::{}", module_name, name)));
+        }
+        lib.push_str(&prelude_module(&prelude));
+
+        let mut files = Vec::new();
+        if config.generate_lib_rs {
+            files.push(("src/lib.rs".to_string(), lib));
+        }
+        files.push(("src/entities/mod.rs".to_string(), entities_mod));
+        Ok(files)
+    }
+
+    fn generate_single_file(
+        &self,
+        schema: &ParsedSchema,
+        config: &Config,
+    ) -> anyhow::Result<String> {
+        let mut output = String::from("use serde::{Deserialize, Serialize};\n");
+
+        let mut enums: Vec<_> = schema.enums.iter().collect();
+        enums.sort_by(|a, b| a.0.cmp(b.0));
+        for (enum_name, parsed_enum) in enums {
+            output.push('\n');
+            output.push_str(&self.generate_enum(enum_name, parsed_enum, config)?);
+        }
+
+        let mut types: Vec<_> = schema
+            .types
+            .iter()
+            .filter(|(_, t)| matches!(t.kind, TypeKind::Object))
+            .collect();
+        types.sort_by(|a, b| a.0.cmp(b.0));
+        for (type_name, parsed_type) in types {
+            output.push('\n');
+            output.push_str(&self.generate_struct(schema, type_name, parsed_type, config));
+        }
+
+        let mut unions: Vec<_> = schema.types.iter().collect();
+        unions.sort_by(|a, b| a.0.cmp(b.0));
+        for (type_name, parsed_type) in unions {
+            if let Some(code) =
+                self.generate_union_enum(schema, type_name, parsed_type, config, false)
+            {
+                output.push('\n');
+                output.push_str(&code);
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+impl PlainGenerator {
+    /// Untagged enum over the structs of a union's members; `in_module` names
+    /// them through their entity modules rather than as siblings
+    fn generate_union_enum(
+        &self,
+        schema: &ParsedSchema,
+        type_name: &str,
+        parsed_type: &ParsedType,
+        config: &Config,
+        in_module: bool,
+    ) -> Option<String> {
+        if !matches!(parsed_type.kind, TypeKind::Union) {
+            return None;
+        }
+        union_enum(
+            schema,
+            type_name,
+            parsed_type,
+            config,
+            "Debug, Clone, PartialEq, Serialize, Deserialize",
+            true,
+            |member| {
+                if in_module {
+                    format!("super::{}::{}", module_name(member), member)
+                } else {
+                    member.to_string()
+                }
+            },
+        )
+    }
+
+    /// Row struct for a type's columns, with nullable columns as `Option` and
+    /// lists as `Vec`. Relations and `@computed` fields are left out.
+    fn generate_struct(
+        &self,
+        schema: &ParsedSchema,
+        type_name: &str,
+        parsed_type: &ParsedType,
+        config: &Config,
+    ) -> String {
+        let mut output = String::new();
+        if let Some(description) = &parsed_type.description {
+            output.push_str(&doc_comment(description, ""));
+        }
+        output.push_str("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\n");
+        output.push_str(&format!("pub struct {} {{\n", type_name));
+
+        for field in parsed_type
+            .fields
+            .iter()
+            .filter(|f| is_column_field(f, schema))
+        {
+            let column_name = to_snake_case(&field.name);
+            let field_name = rust_ident(&column_name);
+            let mut field_type = rust_type_for_field(field, config);
+            for _ in 0..field.list_depth {
+                field_type = format!("Vec<{}>", field_type);
+            }
+            let field_type = if field.is_nullable {
+                format!("Option<{}>", field_type)
+            } else {
+                field_type
+            };
+
+            if let Some(description) = &field.description {
+                output.push_str(&doc_comment(description, "    "));
+            }
+            if config.preserve_graphql_names && column_name != field.name {
+                output.push_str(&format!("    #[serde(rename = \"{}\")]\n", field.name));
+            } else if is_renamed_ident(&column_name) {
+                output.push_str(&format!("    #[serde(rename = \"{}\")]\n", column_name));
+            }
+            output.push_str(&format!("    pub {}: {},\n", field_name, field_type));
+        }
+
+        output.push_str("}\n");
+        output
+    }
+
+    fn generate_enum(
+        &self,
+        enum_name: &str,
+        parsed_enum: &ParsedEnum,
+        config: &Config,
+    ) -> anyhow::Result<String> {
+        let mut output = String::new();
+        if let Some(description) = &parsed_enum.description {
+            output.push_str(&doc_comment(description, ""));
+        }
+        output.push_str(
+            "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]\n",
+        );
+
+        let discriminants = match config.enum_storage {
+            EnumStorage::Text => None,
+            EnumStorage::Int => {
+                output.push_str("#[repr(i32)]\n");
+                Some(enum_discriminants(parsed_enum)?)
+            }
+        };
+        output.push_str(&format!("pub enum {} {{\n", enum_name));
+
+        for (index, value) in parsed_enum.values.iter().enumerate() {
+            let variant = enum_variant_name(value, &config.prefix_enum_values);
+            // Serialized values stay the GraphQL ones
+            if variant.trim_start_matches("r#") != value {
+                output.push_str(&format!("    #[serde(rename = \"{}\")]\n", value));
+            }
+            if let Some(attribute) = deprecated_attribute(parsed_enum, value) {
+                output.push_str(&format!("    {}\n", attribute));
+            }
+            match &discriminants {
+                Some(discriminants) => {
+                    output.push_str(&format!("    {} = {},\n", variant, discriminants[index].1))
+                }
+                None => output.push_str(&format!("    {},\n", variant)),
+            }
+        }
+
+        output.push_str("}\n");
+        if let Some(discriminants) = &discriminants {
+            output.push_str(&enum_try_from_impl(
+                enum_name,
+                discriminants,
+                &config.prefix_enum_values,
+                !parsed_enum.deprecations.is_empty(),
+            ));
+        }
+
+        Ok(output)
+    }
+}
+
+/// Enums used by a type's fields, in name order
+fn referenced_enums(parsed_type: &ParsedType) -> BTreeSet<&str> {
+    parsed_type
+        .fields
+        .iter()
+        .filter_map(|field| match &field.field_type {
+            FieldType::Enum(name) => Some(name.as_str()),
+            _ => None,
+        })
+        .collect()
+}
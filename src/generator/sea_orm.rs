@@ -1,11 +1,19 @@
 use std::collections::HashMap;
 
 use crate::cli::DatabaseType;
-use crate::config::Config;
+use crate::config::{Config, EnumStorage, SeaOrmMigrationStyle};
 use crate::generator::{
-    CodeGenerator, MigrationFile, rust_type_for_field, sql_type_for_field, to_snake_case,
+    CodeGenerator, ColumnContext, DefaultValue, IdType, MYSQL_KEY_LENGTH, MigrationFile,
+    column_comment_sql, column_default, column_default_sql, column_indexes, comment_statements_sql,
+    create_indexes_sql, deprecated_attribute, doc_comment, drop_indexes_sql, entity_cfg_attribute,
+    entity_table_name, enum_discriminants, enum_try_from_impl, enum_variant_name, foreign_keys,
+    index_name, is_column_field, is_int64_scalar, is_json_scalar, is_renamed_ident,
+    lib_module_declaration, mapped_scalar, module_file_name, module_name, native_enum_name,
+    native_enum_types_sql, needs_bounded_text, prelude_module, primary_key_names, rust_ident,
+    rust_type_for_field, self_reference_constraints_sql, sql_type_for_field, table_comment_sql,
+    to_pascal_case, to_snake_case, union_enum, uses_native_enums,
 };
-use crate::parser::{ParsedEnum, ParsedSchema, ParsedType};
+use crate::parser::{FieldType, ParsedEnum, ParsedField, ParsedSchema, ParsedType, TypeKind};
 
 pub struct SeaOrmGenerator;
 
@@ -22,7 +30,7 @@ impl Default for SeaOrmGenerator {
 }
 
 impl CodeGenerator for SeaOrmGenerator {
-    fn generate_schema(&self, schema: &ParsedSchema, _config: &Config) -> anyhow::Result<String> {
+    fn generate_schema(&self, schema: &ParsedSchema, config: &Config) -> anyhow::Result<String> {
         // Handle empty schemas gracefully
         if schema.types.is_empty() && schema.enums.is_empty() {
             return Ok("// No GraphQL types or enums found in schema\n".to_string());
@@ -33,35 +41,39 @@ impl CodeGenerator for SeaOrmGenerator {
         // Add header comment
         output.push_str("//! Sea-ORM entities generated from GraphQL schema\n\n");
 
-        // Generate module declarations for all entities
-        for type_name in schema.types.keys() {
-            let module_name = to_snake_case(type_name);
+        // Generate module declarations for entities, interface traits and union
+        // enums, sorted so the output is stable between runs
+        let mut type_names: Vec<&String> = schema
+            .types
+            .iter()
+            .filter(|(name, t)| match t.kind {
+                TypeKind::Object | TypeKind::Interface => true,
+                TypeKind::Union => self.generate_union_enum(schema, name, t, config).is_some(),
+            })
+            .map(|(name, _)| name)
+            .collect();
+        type_names.sort();
+        for type_name in type_names {
+            let module_name = module_name(type_name);
+            if let Some(cfg) = entity_cfg_attribute(type_name, config) {
+                output.push_str(&format!("{}\n", cfg));
+            }
             output.push_str(&format!("pub mod {};\n", module_name));
         }
 
         // Generate module declarations for enums
-        for enum_name in schema.enums.keys() {
-            let module_name = to_snake_case(enum_name);
+        let mut enum_names: Vec<&String> = schema.enums.keys().collect();
+        enum_names.sort();
+        for enum_name in enum_names {
+            let module_name = module_name(enum_name);
+            if let Some(cfg) = entity_cfg_attribute(enum_name, config) {
+                output.push_str(&format!("{}\n", cfg));
+            }
             output.push_str(&format!("pub mod {};\n", module_name));
         }
 
         output.push('\n');
-
-        // Generate re-exports for convenience
-        output.push_str("// Re-exports for convenience\n");
-        for type_name in schema.types.keys() {
-            let module_name = to_snake_case(type_name);
-            output.push_str(&format!("pub use {}::Entity;\n", module_name));
-            output.push_str(&format!("pub use {}::Model;\n", module_name));
-            output.push_str(&format!("pub use {}::ActiveModel;\n", module_name));
-            output.push_str(&format!("pub use {}::Column;\n", module_name));
-        }
-
-        // Re-export enums
-        for enum_name in schema.enums.keys() {
-            let module_name = to_snake_case(enum_name);
-            output.push_str(&format!("pub use {}::{};\n", module_name, enum_name));
-        }
+        output.push_str(&self.generate_reexports(schema, config));
 
         Ok(output)
     }
@@ -82,7 +94,7 @@ impl CodeGenerator for SeaOrmGenerator {
         for (type_name, parsed_type) in &schema.types {
             if matches!(parsed_type.kind, crate::parser::TypeKind::Object) {
                 let entity_code = self
-                    .generate_entity_struct(type_name, parsed_type, config)
+                    .generate_entity_struct(schema, type_name, parsed_type, config)
                     .map_err(|e| {
                         anyhow::anyhow!(
                             "Failed to generate Sea-ORM entity for type '{}': {}",
@@ -90,18 +102,33 @@ impl CodeGenerator for SeaOrmGenerator {
                             e
                         )
                     })?;
-                entities.insert(format!("{}.rs", to_snake_case(type_name)), entity_code);
+                entities.insert(module_file_name(type_name), entity_code);
+            }
+        }
+
+        // Interfaces become traits over the models implementing them, and
+        // unions enums over their members' models
+        for (type_name, parsed_type) in &schema.types {
+            let code = match parsed_type.kind {
+                TypeKind::Object => continue,
+                TypeKind::Interface => {
+                    Some(self.generate_interface_trait(schema, type_name, parsed_type, config))
+                }
+                TypeKind::Union => self.generate_union_enum(schema, type_name, parsed_type, config),
+            };
+            if let Some(code) = code {
+                entities.insert(module_file_name(type_name), code);
             }
         }
 
         // Generate enums
         for (enum_name, parsed_enum) in &schema.enums {
             let enum_code = self
-                .generate_enum_type(enum_name, parsed_enum)
+                .generate_enum_type(enum_name, parsed_enum, config)
                 .map_err(|e| {
                     anyhow::anyhow!("Failed to generate Sea-ORM enum '{}': {}", enum_name, e)
                 })?;
-            entities.insert(format!("{}.rs", to_snake_case(enum_name)), enum_code);
+            entities.insert(module_file_name(enum_name), enum_code);
         }
 
         // Handle empty schemas gracefully - no error for empty schemas
@@ -116,27 +143,247 @@ impl CodeGenerator for SeaOrmGenerator {
     ) -> anyhow::Result<Vec<MigrationFile>> {
         let mut migrations = Vec::new();
 
+        // Enum types come first, since the tables' columns use them
+        if let Some((up_sql, down_sql)) = native_enum_types_sql(schema, config) {
+            migrations.push(self.generate_enum_types_migration(up_sql, down_sql, config));
+        }
+
         // Only generate migrations for Object types (not interfaces or unions)
         for (type_name, parsed_type) in &schema.types {
             if matches!(parsed_type.kind, crate::parser::TypeKind::Object) {
-                let migration = self.generate_table_migration(type_name, parsed_type, config)?;
+                let migration = match config.sea_orm_migration_style {
+                    SeaOrmMigrationStyle::Raw => {
+                        self.generate_table_migration(schema, type_name, parsed_type, config)?
+                    }
+                    SeaOrmMigrationStyle::Rust => {
+                        self.generate_rust_table_migration(schema, type_name, parsed_type, config)?
+                    }
+                };
                 migrations.push(migration);
             }
         }
 
         Ok(migrations)
     }
+
+    fn generate_migrator(
+        &self,
+        migrations: &[MigrationFile],
+        _config: &Config,
+    ) -> anyhow::Result<Option<String>> {
+        // Rust migrations are collected by a Migrator in the crate root
+        let mut names: Vec<&str> = migrations
+            .iter()
+            .filter(|m| matches!(m, MigrationFile::Rust { .. }))
+            .map(|m| m.name())
+            .collect();
+        if names.is_empty() {
+            return Ok(None);
+        }
+        // Migration names sort into application order
+        names.sort();
+        Ok(Some(self.migrator_code(&names)))
+    }
+
+    fn generate_module_files(
+        &self,
+        schema: &ParsedSchema,
+        config: &Config,
+    ) -> anyhow::Result<Vec<(String, String)>> {
+        if !config.generate_lib_rs {
+            return Ok(Vec::new());
+        }
+
+        // Entity files sit in their own directory, which needs a copy of the
+        // root mod.rs declaring them unless the schema file is already there
+        let entities_mod_path = config.entity_file("mod.rs");
+        let mut files = Vec::new();
+        if config.schema_file().as_deref() != Some(entities_mod_path.as_str()) {
+            files.push((
+                "src/entities/mod.rs".to_string(),
+                self.generate_schema(schema, config)?,
+            ));
+        }
+
+        let mut lib = String::new();
+        lib.push_str("//! Sea-ORM entities generated from GraphQL schema\n\n");
+        lib.push_str(&lib_module_declaration("entities", &entities_mod_path));
+        lib.push('\n');
+
+        // Entities are named after their type, like sea-orm-cli's prelude
+        let mut prelude = Vec::new();
+        let mut types: Vec<_> = schema.types.iter().collect();
+        types.sort_by(|a, b| a.0.cmp(b.0));
+        for (type_name, parsed_type) in types {
+            let module_name = module_name(type_name);
+            let cfg = entity_cfg_attribute(type_name, config);
+            match parsed_type.kind {
+                TypeKind::Object => {
+                    prelude.push((cfg, format!("{}::Entity as {}", module_name, type_name)));
+                }
+                TypeKind::Interface => {
+                    prelude.push((cfg, format!("{}::{}", module_name, type_name)));
+                }
+                TypeKind::Union => {
+                    if self
+                        .generate_union_enum(schema, type_name, parsed_type, config)
+                        .is_some()
+                    {
+                        prelude.push((cfg, format!("{}::{}", module_name, type_name)));
+                    }
+                }
+            }
+        }
+        let mut enum_names: Vec<&String> = schema.enums.keys().collect();
+        enum_names.sort();
+        for enum_name in enum_names {
+            let module_name = module_name(enum_name);
+            let cfg = entity_cfg_attribute(enum_name, config);
+            prelude.push((cfg, format!("{}::{}", module_name, enum_name)));
+        }
+        lib.push_str(&prelude_module(&prelude));
+
+        files.push(("src/lib.rs".to_string(), lib));
+        Ok(files)
+    }
+
+    fn generate_single_file(
+        &self,
+        schema: &ParsedSchema,
+        config: &Config,
+    ) -> anyhow::Result<String> {
+        if schema.types.is_empty() && schema.enums.is_empty() {
+            return Ok("// No GraphQL types or enums found in schema\n".to_string());
+        }
+
+        // Each entity defines its own Entity/Model/Column, so entities become
+        // inline modules in place of the per-entity files
+        let mut modules = Vec::new();
+        for (type_name, parsed_type) in &schema.types {
+            if matches!(parsed_type.kind, crate::parser::TypeKind::Object) {
+                let code = self
+                    .generate_entity_struct(schema, type_name, parsed_type, config)
+                    .map_err(|e| {
+                        anyhow::anyhow!(
+                            "Failed to generate Sea-ORM entity for type '{}': {}",
+                            type_name,
+                            e
+                        )
+                    })?;
+                modules.push((type_name, code));
+            } else if matches!(parsed_type.kind, TypeKind::Interface) {
+                let code = self.generate_interface_trait(schema, type_name, parsed_type, config);
+                modules.push((type_name, code));
+            } else if let Some(code) =
+                self.generate_union_enum(schema, type_name, parsed_type, config)
+            {
+                modules.push((type_name, code));
+            }
+        }
+        for (enum_name, parsed_enum) in &schema.enums {
+            let code = self
+                .generate_enum_type(enum_name, parsed_enum, config)
+                .map_err(|e| {
+                    anyhow::anyhow!("Failed to generate Sea-ORM enum '{}': {}", enum_name, e)
+                })?;
+            modules.push((
+                enum_name,
+                format!("use sea_orm::entity::prelude::*;\n\n{}", code),
+            ));
+        }
+        modules.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut output = String::new();
+        output.push_str("// Sea-ORM entities generated from GraphQL schema\n\n");
+        for (name, code) in modules {
+            if let Some(cfg) = entity_cfg_attribute(name, config) {
+                output.push_str(&format!("{}\n", cfg));
+            }
+            output.push_str(&format!("pub mod {} {{\n", module_name(name)));
+            output.push_str(&indent(&code));
+            output.push_str("}\n\n");
+        }
+        output.push_str(&self.generate_reexports(schema, config));
+
+        Ok(output)
+    }
 }
 
 impl SeaOrmGenerator {
+    /// Convenience re-exports of entity and enum items, plus shared helpers
+    fn generate_reexports(&self, schema: &ParsedSchema, config: &Config) -> String {
+        let mut output = String::new();
+
+        // Generate re-exports for convenience, gated like their modules
+        output.push_str("// Re-exports for convenience\n");
+        let mut types: Vec<_> = schema.types.iter().collect();
+        types.sort_by(|a, b| a.0.cmp(b.0));
+        for (type_name, parsed_type) in types {
+            let module_name = module_name(type_name);
+            let cfg = entity_cfg_attribute(type_name, config)
+                .map(|cfg| format!("{}\n", cfg))
+                .unwrap_or_default();
+            match parsed_type.kind {
+                TypeKind::Object => {}
+                TypeKind::Interface => {
+                    output.push_str(&format!("{}pub use {}::{};\n", cfg, module_name, type_name));
+                    continue;
+                }
+                TypeKind::Union => {
+                    if self
+                        .generate_union_enum(schema, type_name, parsed_type, config)
+                        .is_some()
+                    {
+                        output
+                            .push_str(&format!("{}pub use {}::{};\n", cfg, module_name, type_name));
+                    }
+                    continue;
+                }
+            }
+            output.push_str(&format!("{}pub use {}::Entity;\n", cfg, module_name));
+            output.push_str(&format!("{}pub use {}::Model;\n", cfg, module_name));
+            output.push_str(&format!("{}pub use {}::ActiveModel;\n", cfg, module_name));
+            output.push_str(&format!("{}pub use {}::Column;\n", cfg, module_name));
+        }
+
+        // Re-export enums
+        let mut enum_names: Vec<&String> = schema.enums.keys().collect();
+        enum_names.sort();
+        for enum_name in enum_names {
+            let module_name = module_name(enum_name);
+            let cfg = entity_cfg_attribute(enum_name, config)
+                .map(|cfg| format!("{}\n", cfg))
+                .unwrap_or_default();
+            output.push_str(&format!("{}pub use {}::{};\n", cfg, module_name, enum_name));
+        }
+
+        if config.generate_pagination {
+            output.push('\n');
+            output.push_str("/// A page of entity models returned by `find_paginated`\n");
+            output.push_str(
+                "#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]\n",
+            );
+            output.push_str("pub struct Paginated<T> {\n");
+            output.push_str("    pub items: Vec<T>,\n");
+            output.push_str("    pub page: u64,\n");
+            output.push_str("    pub per_page: u64,\n");
+            output.push_str("    pub total_items: u64,\n");
+            output.push_str("    pub total_pages: u64,\n");
+            output.push_str("}\n");
+        }
+
+        output
+    }
+
     fn generate_entity_struct(
         &self,
+        schema: &ParsedSchema,
         type_name: &str,
         parsed_type: &ParsedType,
         config: &Config,
     ) -> anyhow::Result<String> {
         let _struct_name = type_name.to_string();
-        let table_name = to_snake_case(type_name);
+        let table_name = entity_table_name(type_name, config);
 
         let mut output = String::new();
 
@@ -145,64 +392,92 @@ impl SeaOrmGenerator {
         output.push_str("use serde::{Deserialize, Serialize};\n\n");
 
         // Generate the entity struct
+        if let Some(description) = &parsed_type.description {
+            output.push_str(&doc_comment(description, ""));
+        }
         output.push_str(
             "#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Deserialize, Serialize)]\n",
         );
         output.push_str(&format!("#[sea_orm(table_name = \"{}\")]\n", table_name));
         output.push_str("pub struct Model {\n");
 
+        // Only integer ID keys auto-increment, matching the migration's primary key clause
+        let id_type = IdType::resolve(&config.db, &config.id_mapping);
+        let pk_names = primary_key_names(type_name, parsed_type, config);
         for field in &parsed_type.fields {
-            let field_name = to_snake_case(&field.name);
-            let field_type = rust_type_for_field(field, &config.db, &config.type_mappings);
-            let column_attr = format!("#[sea_orm(column_name = \"{}\")]", field_name);
+            let column_name = to_snake_case(&field.name);
+            let field_name = rust_ident(&column_name);
+            let field_type = model_field_type(schema, field, config);
+
+            if let Some(description) = &field.description {
+                output.push_str(&doc_comment(description, "    "));
+            }
+
+            // JSON from the API keeps the GraphQL field names, and renamed
+            // keywords keep their column names
+            let serde_rename = if config.preserve_graphql_names && column_name != field.name {
+                format!("    #[serde(rename = \"{}\")]\n", field.name)
+            } else if is_renamed_ident(&column_name) {
+                format!("    #[serde(rename = \"{}\")]\n", column_name)
+            } else {
+                String::new()
+            };
+
+            // Relations and computed values live on the model but not in the table
+            if !is_column_field(field, schema) {
+                output.push_str("    #[sea_orm(ignore)]\n");
+                output.push_str(&serde_rename);
+                output.push_str(&format!("    pub {}: {},\n", field_name, field_type));
+                continue;
+            }
 
+            let column_attr = format!("#[sea_orm(column_name = \"{}\")]", column_name);
+
+            if pk_names.contains(&field.name) {
+                let is_id = matches!(&field.field_type, FieldType::Scalar(s) if s == "ID");
+                if is_id && id_type.is_auto_increment() && pk_names.len() == 1 {
+                    output.push_str("    #[sea_orm(primary_key)]\n");
+                } else {
+                    output.push_str("    #[sea_orm(primary_key, auto_increment = false)]\n");
+                }
+            }
             output.push_str(&format!("    {}\n", column_attr));
+            output.push_str(&serde_rename);
             output.push_str(&format!("    pub {}: {},\n", field_name, field_type));
         }
 
         output.push_str("}\n\n");
 
-        // Generate relation enum (empty for now)
-        output.push_str("#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]\n");
-        output.push_str("pub enum Relation {}\n\n");
+        output.push_str(&self.generate_relations(schema, type_name, parsed_type, config));
 
         // Generate ActiveModel
         output.push_str("#[derive(Copy, Clone, Debug, EnumIter, DeriveCustomColumn)]\n");
         output.push_str("pub enum Column {\n");
-        for field in &parsed_type.fields {
-            let field_name = to_snake_case(&field.name);
+        for field in parsed_type
+            .fields
+            .iter()
+            .filter(|f| is_column_field(f, schema))
+        {
+            let field_name = rust_ident(&to_snake_case(&field.name));
             output.push_str(&format!("    {},\n", field_name));
         }
         output.push_str("}\n\n");
 
-        // Generate PrimaryKey
-        output.push_str("#[derive(Copy, Clone, Debug, EnumIter)]\n");
-        output.push_str("pub enum PrimaryKey {\n");
-        // Assume id is primary key
-        output.push_str("    Id,\n");
-        output.push_str("}\n\n");
-
-        // Determine the ID type based on database
-        let id_type = match config.db {
-            DatabaseType::Sqlite => "i32",
-            DatabaseType::Postgres => "uuid::Uuid",
-            DatabaseType::Mysql => "u32",
-        };
+        output.push_str("impl ActiveModelBehavior for ActiveModel {}\n\n");
+        output.push_str(&self.generate_interface_impls(schema, parsed_type, config));
+        output.push_str(&self.generate_constructor(schema, type_name, parsed_type, config));
 
-        let auto_increment = match config.db {
-            DatabaseType::Sqlite => "true",
-            DatabaseType::Postgres => "false", // UUIDs don't auto-increment
-            DatabaseType::Mysql => "true",
-        };
+        if config.generate_pagination {
+            output.push_str(&self.generate_pagination_helper());
+        }
 
-        output.push_str("impl PrimaryKeyTrait for PrimaryKey {\n");
-        output.push_str(&format!("    type ValueType = {};\n", id_type));
-        output.push_str("    fn auto_increment() -> bool {\n");
-        output.push_str(&format!("        {}\n", auto_increment));
-        output.push_str("    }\n");
-        output.push_str("}\n\n");
+        if config.generate_crud {
+            output.push_str(&self.generate_crud_helpers(type_name, parsed_type, config));
+        }
 
-        output.push_str("impl ActiveModelBehavior for ActiveModel {}\n\n");
+        if config.generate_iden {
+            output.push_str(&self.generate_iden_enum(schema, type_name, parsed_type, config));
+        }
 
         // Generate Entity constant (Sea-ORM convention)
         output.push_str("pub struct Entity;\n\n");
@@ -212,127 +487,1020 @@ impl SeaOrmGenerator {
         output.push_str("    }\n");
         output.push_str("}\n\n");
 
-        // Generate relationships based on detected foreign keys
-        // For Sea-ORM, we can use derive macros and relationship definitions
-        let mut has_relationships = false;
+        if config.generate_tests {
+            output.push_str(&self.generate_test_module(type_name, parsed_type, config));
+        }
 
+        Ok(output)
+    }
+
+    /// `Relation` enum with a `belongs_to` variant per foreign key; keys back
+    /// into the same table point at this module's own `Entity`
+    fn generate_relations(
+        &self,
+        schema: &ParsedSchema,
+        type_name: &str,
+        parsed_type: &ParsedType,
+        config: &Config,
+    ) -> String {
+        let foreign_keys = foreign_keys(schema, parsed_type, config);
+        let mut output = String::from("#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]\n");
+        if foreign_keys.is_empty() {
+            output.push_str("pub enum Relation {}\n\n");
+            return output;
+        }
+
+        output.push_str("pub enum Relation {\n");
+        for key in foreign_keys {
+            let related = if key.is_self_reference(type_name) {
+                String::new()
+            } else {
+                format!("super::{}::", module_name(&key.related_type))
+            };
+            output.push_str("    #[sea_orm(\n");
+            output.push_str(&format!("        belongs_to = \"{}Entity\",\n", related));
+            output.push_str(&format!("        from = \"Column::{}\",\n", key.column));
+            output.push_str(&format!(
+                "        to = \"{}Column::{}\",\n",
+                related, key.related_column
+            ));
+            output.push_str("        on_update = \"Cascade\",\n");
+            output.push_str(&format!(
+                "        on_delete = \"{}\"\n",
+                key.on_delete_action()
+            ));
+            output.push_str("    )]\n");
+            output.push_str(&format!("    {},\n", key.name));
+        }
+        output.push_str("}\n\n");
+        output
+    }
+
+    /// Trait with a getter per field of a GraphQL interface, implemented by
+    /// the models of its object types
+    fn generate_interface_trait(
+        &self,
+        schema: &ParsedSchema,
+        type_name: &str,
+        parsed_type: &ParsedType,
+        config: &Config,
+    ) -> String {
+        let mut output = String::new();
+        match &parsed_type.description {
+            Some(description) => output.push_str(&doc_comment(description, "")),
+            None => output.push_str(&format!(
+                "/// Fields shared by the types implementing `{}`\n",
+                type_name
+            )),
+        }
+        output.push_str(&format!("pub trait {} {{\n", type_name));
         for field in &parsed_type.fields {
-            if field.name.ends_with("Id") && field.name.len() > 2 {
-                let related_type = &field.name[..field.name.len() - 2];
-                if related_type
-                    .chars()
-                    .next()
-                    .is_some_and(|c| c.is_uppercase())
-                {
-                    if !has_relationships {
-                        output.push_str("// Relationships\n");
-                        has_relationships = true;
-                    }
-                    let _relation_name = to_snake_case(&field.name[..field.name.len() - 2]);
-                    output.push_str("#[derive(Clone, Debug, PartialEq, DeriveRelation)]\n");
-                    output.push_str(&format!("#[sea_orm(table_name = \"{}\")]\n", table_name));
-                    output.push_str("pub enum Relation {\n");
-                    output.push_str("    #[sea_orm(\n");
-                    output.push_str(&format!(
-                        "        belongs_to = \"super::{}::Entity\",\n",
-                        related_type
-                    ));
-                    output.push_str(&format!("        from = \"Column::{}\",\n", field.name));
-                    output.push_str(&format!(
-                        "        to = \"super::{}::Column::Id\",\n",
-                        related_type
-                    ));
-                    output.push_str("        on_update = \"Cascade\",\n");
-                    output.push_str("        on_delete = \"Cascade\"\n");
-                    output.push_str("    )]\n");
-                    output.push_str(&format!("    {},\n", related_type));
-                    output.push_str("}\n\n");
+            if let Some(description) = &field.description {
+                output.push_str(&doc_comment(description, "    "));
+            }
+            output.push_str(&format!(
+                "    fn {}(&self) -> &{};\n",
+                rust_ident(&to_snake_case(&field.name)),
+                model_field_type(schema, field, config)
+            ));
+        }
+        output.push_str("}\n");
+        output
+    }
+
+    /// Untagged enum over the models of a union's members, as an entity file
+    fn generate_union_enum(
+        &self,
+        schema: &ParsedSchema,
+        type_name: &str,
+        parsed_type: &ParsedType,
+        config: &Config,
+    ) -> Option<String> {
+        let code = union_enum(
+            schema,
+            type_name,
+            parsed_type,
+            config,
+            "Clone, Debug, PartialEq, Deserialize, Serialize",
+            true,
+            |member| format!("super::{}::Model", module_name(member)),
+        )?;
+        Some(format!(
+            "use serde::{{Deserialize, Serialize}};\n\n{}",
+            code
+        ))
+    }
+
+    /// Impls of the interface traits for a model, for the interfaces that
+    /// are generated
+    fn generate_interface_impls(
+        &self,
+        schema: &ParsedSchema,
+        parsed_type: &ParsedType,
+        config: &Config,
+    ) -> String {
+        let mut output = String::new();
+        for interface_name in &parsed_type.interfaces {
+            let Some(interface) = schema
+                .types
+                .get(interface_name)
+                .filter(|t| matches!(t.kind, TypeKind::Interface))
+            else {
+                continue;
+            };
+
+            output.push_str(&format!(
+                "impl super::{}::{} for Model {{\n",
+                module_name(interface_name),
+                interface_name
+            ));
+            for (index, field) in interface.fields.iter().enumerate() {
+                if index > 0 {
+                    output.push('\n');
                 }
+                let field_name = rust_ident(&to_snake_case(&field.name));
+                output.push_str(&format!(
+                    "    fn {}(&self) -> &{} {{\n",
+                    field_name,
+                    model_field_type(schema, field, config)
+                ));
+                output.push_str(&format!("        &self.{}\n", field_name));
+                output.push_str("    }\n");
             }
+            output.push_str("}\n\n");
         }
+        output
+    }
 
-        Ok(output)
+    /// `ActiveModel` constructor taking the required columns, like Diesel's
+    /// `New*` struct; generated ID keys and nullable columns stay `NotSet`
+    fn generate_constructor(
+        &self,
+        schema: &ParsedSchema,
+        type_name: &str,
+        parsed_type: &ParsedType,
+        config: &Config,
+    ) -> String {
+        // Only a single ID key can come from the database, through
+        // auto-increment or a column default; every other key is the caller's
+        let id_type = IdType::resolve(&config.db, &config.id_mapping);
+        let key_is_generated = id_type.is_auto_increment()
+            || id_type
+                .default_sql(&config.db, &config.pk_default)
+                .is_some();
+        let pk_names = primary_key_names(type_name, parsed_type, config);
+        let required: Vec<(String, String)> = parsed_type
+            .fields
+            .iter()
+            .filter(|f| {
+                is_column_field(f, schema) && (!f.is_nullable || pk_names.contains(&f.name))
+            })
+            .filter(|f| {
+                !key_is_generated
+                    || pk_names != [f.name.as_str()]
+                    || !matches!(&f.field_type, FieldType::Scalar(s) if s == "ID")
+            })
+            .map(|f| {
+                let field_type = rust_type_for_field(f, config);
+                (rust_ident(&to_snake_case(&f.name)), field_type)
+            })
+            .collect();
+
+        let mut output = String::new();
+        output.push_str("impl ActiveModel {\n");
+        output.push_str(
+            "    /// New row with the required columns and keys set; a generated key and optional\n",
+        );
+        output.push_str("    /// columns are left `NotSet`\n");
+        if required.len() > 7 {
+            output.push_str("    #[allow(clippy::too_many_arguments)]\n");
+        }
+        let params: Vec<String> = required
+            .iter()
+            .map(|(name, field_type)| format!("{}: {}", name, field_type))
+            .collect();
+        output.push_str(&format!(
+            "    pub fn new_{}({}) -> Self {{\n",
+            to_snake_case(type_name),
+            params.join(", ")
+        ));
+        output.push_str("        Self {\n");
+        for (name, _) in &required {
+            output.push_str(&format!(
+                "            {}: ActiveValue::Set({}),\n",
+                name, name
+            ));
+        }
+        output.push_str("            ..Default::default()\n");
+        output.push_str("        }\n");
+        output.push_str("    }\n");
+        output.push_str("}\n\n");
+        output
+    }
+
+    /// Ignored round-trip test against an in-memory SQLite database
+    fn generate_test_module(
+        &self,
+        type_name: &str,
+        parsed_type: &ParsedType,
+        config: &Config,
+    ) -> String {
+        // Composite keys are looked up by a tuple of their columns
+        let mut pk_values: Vec<String> = primary_key_names(type_name, parsed_type, config)
+            .iter()
+            .map(|name| format!("inserted.{}.clone()", rust_ident(&to_snake_case(name))))
+            .collect();
+        if pk_values.is_empty() {
+            pk_values.push("inserted.id.clone()".to_string());
+        }
+        let pk_value = if pk_values.len() == 1 {
+            pk_values.remove(0)
+        } else {
+            format!("({})", pk_values.join(", "))
+        };
+
+        let mut output = String::new();
+        output.push_str("#[cfg(test)]\nmod tests {\n");
+        output.push_str("    use super::*;\n");
+        output.push_str("    use sea_orm::{ConnectionTrait, Database, Schema};\n\n");
+        output.push_str("    #[tokio::test]\n");
+        output.push_str("    #[ignore = \"fill in the new row before enabling\"]\n");
+        output.push_str(&format!(
+            "    async fn {}_round_trip() {{\n",
+            to_snake_case(type_name)
+        ));
+        output.push_str("        let db = Database::connect(\"sqlite::memory:\")\n");
+        output.push_str("            .await\n");
+        output.push_str("            .expect(\"Failed to open in-memory database\");\n");
+        output.push_str("        let backend = db.get_database_backend();\n");
+        output.push_str(
+            "        let create = Schema::new(backend).create_table_from_entity(Entity);\n",
+        );
+        output.push_str("        db.execute(backend.build(&create))\n");
+        output.push_str("            .await\n");
+        output.push_str("            .expect(\"Failed to create table\");\n\n");
+        output.push_str("        // Set the new row's fields\n");
+        output.push_str("        let new_row = ActiveModel {\n");
+        output.push_str("            ..Default::default()\n");
+        output.push_str("        };\n");
+        output.push_str(
+            "        let inserted = new_row.insert(&db).await.expect(\"Failed to insert\");\n\n",
+        );
+        output.push_str(&format!(
+            "        let found = Entity::find_by_id({})\n",
+            pk_value
+        ));
+        output.push_str("            .one(&db)\n");
+        output.push_str("            .await\n");
+        output.push_str("            .expect(\"Failed to select\");\n");
+        output.push_str("        assert_eq!(found, Some(inserted));\n");
+        output.push_str("    }\n");
+        output.push_str("}\n\n");
+        output
+    }
+
+    fn generate_iden_enum(
+        &self,
+        schema: &ParsedSchema,
+        type_name: &str,
+        parsed_type: &ParsedType,
+        config: &Config,
+    ) -> String {
+        let enum_name = format!("{}Iden", type_name);
+        let mut idens = vec![("Table".to_string(), entity_table_name(type_name, config))];
+        for field in parsed_type
+            .fields
+            .iter()
+            .filter(|f| is_column_field(f, schema))
+        {
+            let column_name = to_snake_case(&field.name);
+            let mut variant = to_pascal_case(&column_name);
+            // Keep `Table` for the table name
+            if variant == "Table" {
+                variant.push_str("Column");
+            }
+            idens.push((rust_ident(&variant), column_name));
+        }
+
+        let mut output = String::new();
+        output.push_str("/// Table and column identifiers for building queries with sea-query\n");
+        output.push_str("#[derive(Copy, Clone, Debug)]\n");
+        output.push_str(&format!("pub enum {} {{\n", enum_name));
+        for (variant, _) in &idens {
+            output.push_str(&format!("    {},\n", variant));
+        }
+        output.push_str("}\n\n");
+
+        output.push_str(&format!(
+            "impl sea_orm::sea_query::Iden for {} {{\n",
+            enum_name
+        ));
+        output.push_str("    fn unquoted(&self, s: &mut dyn std::fmt::Write) {\n");
+        output.push_str("        let name = match self {\n");
+        for (variant, name) in &idens {
+            output.push_str(&format!("            Self::{} => \"{}\",\n", variant, name));
+        }
+        output.push_str("        };\n");
+        output.push_str("        write!(s, \"{}\", name).unwrap();\n");
+        output.push_str("    }\n");
+        output.push_str("}\n\n");
+        output
+    }
+
+    fn generate_pagination_helper(&self) -> String {
+        let mut output = String::new();
+        output.push_str("impl Entity {\n");
+        output.push_str("    /// Fetch one page of models, with `page` starting at 0\n");
+        output.push_str("    pub async fn find_paginated(\n");
+        output.push_str("        db: &DatabaseConnection,\n");
+        output.push_str("        page: u64,\n");
+        output.push_str("        per_page: u64,\n");
+        output.push_str("    ) -> Result<super::Paginated<Model>, DbErr> {\n");
+        output.push_str("        let paginator = Entity::find().paginate(db, per_page);\n");
+        output.push_str("        let totals = paginator.num_items_and_pages().await?;\n");
+        output.push_str("        let items = paginator.fetch_page(page).await?;\n");
+        output.push_str("        Ok(super::Paginated {\n");
+        output.push_str("            items,\n");
+        output.push_str("            page,\n");
+        output.push_str("            per_page,\n");
+        output.push_str("            total_items: totals.number_of_items,\n");
+        output.push_str("            total_pages: totals.number_of_pages,\n");
+        output.push_str("        })\n");
+        output.push_str("    }\n");
+        output.push_str("}\n\n");
+        output
+    }
+
+    /// `create`/`find_by_id`/`update`/`delete` functions, taking the key as its Rust type.
+    ///
+    /// They're free functions so `find_by_id` doesn't shadow `EntityTrait::find_by_id`.
+    fn generate_crud_helpers(
+        &self,
+        type_name: &str,
+        parsed_type: &ParsedType,
+        config: &Config,
+    ) -> String {
+        // Composite keys take a tuple, as `Entity::find_by_id` expects
+        let key_types: Vec<String> = primary_key_names(type_name, parsed_type, config)
+            .iter()
+            .filter_map(|name| parsed_type.fields.iter().find(|f| &f.name == name))
+            .map(|field| rust_type_for_field(field, config))
+            .collect();
+        let id_type = match key_types.as_slice() {
+            [] => IdType::resolve(&config.db, &config.id_mapping)
+                .rust_type(&config.db)
+                .to_string(),
+            [key_type] => key_type.clone(),
+            key_types => format!("({})", key_types.join(", ")),
+        };
+
+        let mut output = String::new();
+        output.push_str("/// Insert a new row and return the stored model\n");
+        output.push_str(
+            "pub async fn create(db: &DatabaseConnection, model: ActiveModel) -> Result<Model, DbErr> {\n",
+        );
+        output.push_str("    model.insert(db).await\n");
+        output.push_str("}\n\n");
+        output.push_str("/// Find a row by its primary key\n");
+        output.push_str(&format!(
+            "pub async fn find_by_id(db: &DatabaseConnection, id: {}) -> Result<Option<Model>, DbErr> {{\n",
+            id_type
+        ));
+        output.push_str("    Entity::find_by_id(id).one(db).await\n");
+        output.push_str("}\n\n");
+        output.push_str("/// Save the changed fields of an existing row\n");
+        output.push_str(
+            "pub async fn update(db: &DatabaseConnection, model: ActiveModel) -> Result<Model, DbErr> {\n",
+        );
+        output.push_str("    model.update(db).await\n");
+        output.push_str("}\n\n");
+        output.push_str(
+            "/// Delete a row by its primary key, returning the number of rows removed\n",
+        );
+        output.push_str(&format!(
+            "pub async fn delete(db: &DatabaseConnection, id: {}) -> Result<u64, DbErr> {{\n",
+            id_type
+        ));
+        output.push_str("    let result = Entity::delete_by_id(id).exec(db).await?;\n");
+        output.push_str("    Ok(result.rows_affected)\n");
+        output.push_str("}\n\n");
+        output
     }
 
     fn generate_enum_type(
         &self,
         enum_name: &str,
         parsed_enum: &ParsedEnum,
+        config: &Config,
     ) -> anyhow::Result<String> {
         let mut output = String::new();
 
         if let Some(description) = &parsed_enum.description {
-            output.push_str(&format!("/// {}\n", description));
+            output.push_str(&doc_comment(description, ""));
         }
 
         output.push_str("#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum)]\n");
-        output.push_str("#[sea_orm(rs_type = \"String\", db_type = \"String(Some(1))\")]\n");
-        output.push_str(&format!("pub enum {} {{\n", enum_name));
+        match config.enum_storage {
+            EnumStorage::Text if uses_native_enums(config) => {
+                output.push_str(&format!(
+                    "#[sea_orm(rs_type = \"String\", db_type = \"Enum\", enum_name = \"{}\")]\n",
+                    native_enum_name(enum_name)
+                ));
+                output.push_str(&format!("pub enum {} {{\n", enum_name));
 
-        for value in &parsed_enum.values {
-            output.push_str(&format!("    #[sea_orm(string_value = \"{}\")]\n", value));
-            output.push_str(&format!("    {},\n", value));
-        }
+                for value in &parsed_enum.values {
+                    output.push_str(&format!("    #[sea_orm(string_value = \"{}\")]\n", value));
+                    if let Some(attribute) = deprecated_attribute(parsed_enum, value) {
+                        output.push_str(&format!("    {}\n", attribute));
+                    }
+                    output.push_str(&format!(
+                        "    {},\n",
+                        enum_variant_name(value, &config.prefix_enum_values)
+                    ));
+                }
 
-        output.push_str("}\n");
+                output.push_str("}\n");
+            }
+            EnumStorage::Text => {
+                output
+                    .push_str("#[sea_orm(rs_type = \"String\", db_type = \"String(Some(1))\")]\n");
+                output.push_str(&format!("pub enum {} {{\n", enum_name));
+
+                for value in &parsed_enum.values {
+                    output.push_str(&format!("    #[sea_orm(string_value = \"{}\")]\n", value));
+                    if let Some(attribute) = deprecated_attribute(parsed_enum, value) {
+                        output.push_str(&format!("    {}\n", attribute));
+                    }
+                    output.push_str(&format!(
+                        "    {},\n",
+                        enum_variant_name(value, &config.prefix_enum_values)
+                    ));
+                }
+
+                output.push_str("}\n");
+            }
+            EnumStorage::Int => {
+                let discriminants = enum_discriminants(parsed_enum)?;
+
+                output.push_str("#[sea_orm(rs_type = \"i32\", db_type = \"Integer\")]\n");
+                output.push_str(&format!("pub enum {} {{\n", enum_name));
+
+                for (value, discriminant) in &discriminants {
+                    output.push_str(&format!("    #[sea_orm(num_value = {})]\n", discriminant));
+                    if let Some(attribute) = deprecated_attribute(parsed_enum, value) {
+                        output.push_str(&format!("    {}\n", attribute));
+                    }
+                    output.push_str(&format!(
+                        "    {},\n",
+                        enum_variant_name(value, &config.prefix_enum_values)
+                    ));
+                }
+
+                output.push_str("}\n");
+                output.push_str(&enum_try_from_impl(
+                    enum_name,
+                    &discriminants,
+                    &config.prefix_enum_values,
+                    !parsed_enum.deprecations.is_empty(),
+                ));
+            }
+        }
 
         Ok(output)
     }
 
     fn generate_table_migration(
         &self,
+        schema: &ParsedSchema,
         type_name: &str,
         parsed_type: &ParsedType,
         config: &Config,
     ) -> anyhow::Result<MigrationFile> {
-        let table_name = to_snake_case(type_name);
-        let migration_name = format!(
-            "m{}_create_{}_table",
-            chrono::Utc::now().timestamp(),
-            table_name
-        );
+        let table_name = entity_table_name(type_name, config);
+        let migration_name = table_migration_name(schema, type_name, config);
 
-        let mut up_sql = format!("CREATE TABLE {} (\n", table_name);
+        let mut up_sql = String::new();
 
         let mut columns = Vec::new();
+        let mut comments = Vec::new();
 
-        // Add id column if not present
-        let has_id = parsed_type.fields.iter().any(|f| f.name == "id");
-        if !has_id {
-            let id_type = match config.db {
-                DatabaseType::Sqlite => "INTEGER PRIMARY KEY AUTOINCREMENT",
-                DatabaseType::Postgres => "UUID PRIMARY KEY DEFAULT gen_random_uuid()",
-                DatabaseType::Mysql => "INT UNSIGNED PRIMARY KEY AUTO_INCREMENT",
-            };
-            columns.push(format!("    id {}", id_type));
+        // Add id column if no primary key field was detected
+        let id_type = IdType::resolve(&config.db, &config.id_mapping);
+        if let Some(extension) = id_type.extension_sql(&config.db, &config.pk_default) {
+            up_sql.push_str(&format!("{}\n\n", extension));
+        }
+        up_sql.push_str(&format!("CREATE TABLE {} (\n", table_name));
+        let pk_names = primary_key_names(type_name, parsed_type, config);
+        let composite = pk_names.len() > 1;
+        if pk_names.is_empty() {
+            columns.push(format!(
+                "    id {}",
+                id_type.primary_key_sql(
+                    &config.db,
+                    &config.pk_default,
+                    config.sqlite_autoincrement,
+                )
+            ));
         }
 
-        for field in &parsed_type.fields {
+        for field in parsed_type
+            .fields
+            .iter()
+            .filter(|f| is_column_field(f, schema))
+        {
             let column_name = to_snake_case(&field.name);
-            let sql_type = sql_type_for_field(field, &config.db, &config.type_mappings);
+            let is_primary_key = pk_names.contains(&field.name);
+            // Composite keys are declared once after the columns
+            let is_single_key = is_primary_key && !composite;
+            let comment = column_comment_sql(field.description.as_deref(), &config.db);
+            if let Some(description) = &field.description {
+                comments.push((column_name.clone(), description.as_str()));
+            }
+
+            // ID primary keys share the implicit id column's definition so the
+            // migration agrees with the entity's auto-increment behaviour
+            if is_single_key
+                && matches!(&field.field_type, crate::parser::FieldType::Scalar(s) if s == "ID")
+            {
+                columns.push(format!(
+                    "    {} {}{}",
+                    column_name,
+                    id_type.primary_key_sql(
+                        &config.db,
+                        &config.pk_default,
+                        config.sqlite_autoincrement,
+                    ),
+                    comment
+                ));
+                continue;
+            }
+
+            let sql_type = sql_type_for_field(
+                field,
+                ColumnContext {
+                    type_name,
+                    is_primary_key,
+                },
+                config,
+            );
 
             let nullable = if field.is_nullable { "" } else { " NOT NULL" };
-            let primary_key = if field.name == "id" {
-                " PRIMARY KEY"
+            let default = column_default_sql(schema, type_name, field, config)?;
+            let primary_key = if is_single_key { " PRIMARY KEY" } else { "" };
+            let unique = if !is_single_key && config.is_unique(type_name, field) {
+                " UNIQUE"
             } else {
                 ""
             };
 
             columns.push(format!(
-                "    {} {}{}{}",
-                column_name, sql_type, nullable, primary_key
+                "    {} {}{}{}{}{}{}",
+                column_name, sql_type, nullable, default, primary_key, unique, comment
             ));
         }
 
+        if composite {
+            let key_columns: Vec<String> =
+                pk_names.iter().map(|name| to_snake_case(name)).collect();
+            columns.push(format!("    PRIMARY KEY ({})", key_columns.join(", ")));
+        }
+        columns.extend(self_reference_constraints_sql(
+            schema,
+            type_name,
+            parsed_type,
+            config,
+        ));
+
         up_sql.push_str(&columns.join(",\n"));
-        up_sql.push_str("\n);");
+        up_sql.push_str(&format!(
+            "\n){};",
+            table_comment_sql(parsed_type.description.as_deref(), &config.db)
+        ));
+        let indexes = column_indexes(type_name, parsed_type, config);
+        up_sql.push_str(&create_indexes_sql(&table_name, &indexes));
+        for statement in comment_statements_sql(
+            &table_name,
+            parsed_type.description.as_deref(),
+            &comments,
+            &config.db,
+        ) {
+            up_sql.push_str(&format!("\n\n{}", statement));
+        }
 
-        let down_sql = format!("DROP TABLE {};", table_name);
+        let down_sql = format!(
+            "{}DROP TABLE {};",
+            drop_indexes_sql(&table_name, &indexes, &config.db),
+            table_name
+        );
 
-        Ok(MigrationFile {
+        Ok(MigrationFile::Sql {
             name: migration_name,
             up_sql,
             down_sql,
         })
     }
+
+    fn generate_rust_table_migration(
+        &self,
+        schema: &ParsedSchema,
+        type_name: &str,
+        parsed_type: &ParsedType,
+        config: &Config,
+    ) -> anyhow::Result<MigrationFile> {
+        let table_name = entity_table_name(type_name, config);
+        let migration_name = table_migration_name(schema, type_name, config);
+
+        let id_type = IdType::resolve(&config.db, &config.id_mapping);
+        let pk_names = primary_key_names(type_name, parsed_type, config);
+        let composite = pk_names.len() > 1;
+
+        let mut columns = Vec::new();
+        let mut comments = Vec::new();
+        // sea-query only renders `.comment(..)` for MySQL; PostgreSQL gets
+        // `COMMENT ON` statements after the table is created
+        let inline_comments = matches!(config.db, DatabaseType::Mysql);
+
+        // Database-side key generation shared by the implicit and ID primary keys
+        // SQLite's rowid already numbers plain integer keys; sea-query's
+        // `auto_increment` would add the stricter `AUTOINCREMENT` keyword
+        let sqlite_rowid =
+            matches!(config.db, DatabaseType::Sqlite) && !config.sqlite_autoincrement;
+        let mut id_key = String::from(".not_null()");
+        if id_type.is_auto_increment() && !sqlite_rowid {
+            id_key.push_str(".auto_increment()");
+        }
+        if let Some(default) = id_type.default_sql(&config.db, &config.pk_default) {
+            id_key.push_str(&format!(".default(Expr::cust({:?}))", default));
+        }
+        id_key.push_str(".primary_key()");
+
+        // Add id column if no primary key field was detected
+        if pk_names.is_empty() {
+            columns.push(format!(
+                "ColumnDef::new(Alias::new(\"id\")){}{}",
+                sea_query_id_method(id_type),
+                id_key
+            ));
+        }
+
+        let is_mapped = |scalar: &str| {
+            mapped_scalar(&config.type_mappings, &config.scalar_mappings, scalar).is_some()
+        };
+        for field in parsed_type
+            .fields
+            .iter()
+            .filter(|f| is_column_field(f, schema))
+        {
+            let column_name = to_snake_case(&field.name);
+            let is_primary_key = pk_names.contains(&field.name);
+            // Composite keys are declared on the table instead of a column
+            let is_single_key = is_primary_key && !composite;
+            let is_id = matches!(&field.field_type, FieldType::Scalar(s) if s == "ID");
+
+            let max_length = config
+                .string_length(type_name, field)
+                .filter(|_| matches!(config.db, DatabaseType::Mysql | DatabaseType::Postgres));
+            let type_method = if let Some(sql_type) = config.sql_type_override(type_name, field) {
+                format!(".custom(Alias::new({:?}))", sql_type)
+            } else {
+                match &field.field_type {
+                    FieldType::Scalar(scalar) => match (scalar.as_str(), max_length) {
+                        ("ID", _) => sea_query_id_method(id_type).to_string(),
+                        ("Int", _) if config.int_as_i64 => ".big_integer()".to_string(),
+                        ("Int", _) => ".integer()".to_string(),
+                        ("Float", _) => ".double()".to_string(),
+                        ("Boolean", _) => ".boolean()".to_string(),
+                        ("DateTime", _) => match config.db {
+                            DatabaseType::Postgres => ".timestamp_with_time_zone()".to_string(),
+                            DatabaseType::Mysql => ".date_time()".to_string(),
+                            DatabaseType::Sqlite => ".timestamp()".to_string(),
+                        },
+                        ("Date", _) => ".date()".to_string(),
+                        ("Time", _) => ".time()".to_string(),
+                        ("String", Some(length)) => format!(".string_len({})", length),
+                        (int64, _) if is_int64_scalar(int64) && !is_mapped(int64) => {
+                            ".big_integer()".to_string()
+                        }
+                        (json, _) if is_json_scalar(json) && !is_mapped(json) => match config.db {
+                            DatabaseType::Postgres => ".json_binary()".to_string(),
+                            DatabaseType::Sqlite | DatabaseType::Mysql => ".json()".to_string(),
+                        },
+                        _ => ".text()".to_string(),
+                    },
+                    FieldType::Reference(_) => sea_query_id_method(id_type).to_string(),
+                    FieldType::Enum(enum_name) if uses_native_enums(config) => {
+                        format!(".custom(Alias::new({:?}))", native_enum_name(enum_name))
+                    }
+                    FieldType::Enum(_) => match config.enum_storage {
+                        EnumStorage::Text => ".text()".to_string(),
+                        EnumStorage::Int => ".integer()".to_string(),
+                    },
+                }
+            };
+            let type_method = if needs_bounded_text(type_name, field, is_primary_key, config)
+                && matches!(config.db, DatabaseType::Mysql)
+                && type_method == ".text()"
+            {
+                format!(".string_len({})", MYSQL_KEY_LENGTH)
+            } else {
+                type_method
+            };
+
+            let mut column = format!(
+                "ColumnDef::new(Alias::new(\"{}\")){}",
+                column_name, type_method
+            );
+            if is_single_key && is_id {
+                column.push_str(&id_key);
+            } else if is_single_key {
+                column.push_str(".not_null().primary_key()");
+            } else if field.is_nullable {
+                column.push_str(".null()");
+            } else {
+                column.push_str(".not_null()");
+            }
+            if let Some(default) = column_default(schema, type_name, field, config)?
+                .as_ref()
+                .and_then(DefaultValue::rust)
+            {
+                column.push_str(&format!(".default({})", default));
+            }
+            if !is_single_key && config.is_unique(type_name, field) {
+                column.push_str(".unique_key()");
+            }
+            if let Some(description) = &field.description {
+                if inline_comments {
+                    column.push_str(&format!(".comment({:?})", description));
+                }
+                comments.push((column_name, description.as_str()));
+            }
+            columns.push(column);
+        }
+
+        let mut code = String::new();
+        code.push_str("use sea_orm_migration::prelude::*;\n\n");
+        code.push_str("#[derive(DeriveMigrationName)]\n");
+        code.push_str("pub struct Migration;\n\n");
+        code.push_str("#[async_trait::async_trait]\n");
+        code.push_str("impl MigrationTrait for Migration {\n");
+        code.push_str("    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {\n");
+        if let Some(extension) = id_type.extension_sql(&config.db, &config.pk_default) {
+            code.push_str("        manager\n");
+            code.push_str("            .get_connection()\n");
+            code.push_str(&format!(
+                "            .execute_unprepared({:?})\n",
+                extension
+            ));
+            code.push_str("            .await?;\n\n");
+        }
+        code.push_str("        manager\n");
+        code.push_str("            .create_table(\n");
+        code.push_str("                Table::create()\n");
+        code.push_str(&format!(
+            "                    .table(Alias::new(\"{}\"))\n",
+            table_name
+        ));
+        code.push_str("                    .if_not_exists()\n");
+        for column in &columns {
+            code.push_str(&format!("                    .col({})\n", column));
+        }
+        if composite {
+            let key_columns: String = pk_names
+                .iter()
+                .map(|name| format!(".col(Alias::new(\"{}\"))", to_snake_case(name)))
+                .collect();
+            code.push_str(&format!(
+                "                    .primary_key(Index::create(){})\n",
+                key_columns
+            ));
+        }
+        // Only keys back into this table; see self_reference_constraints_sql
+        for key in foreign_keys(schema, parsed_type, config)
+            .into_iter()
+            .filter(|key| key.is_self_reference(type_name))
+        {
+            code.push_str(&format!(
+                "                    .foreign_key(ForeignKey::create().name({:?}).from(Alias::new({:?}), Alias::new({:?})).to(Alias::new({:?}), Alias::new({:?})).on_update(ForeignKeyAction::Cascade).on_delete(ForeignKeyAction::{}))\n",
+                index_name("fk", &table_name, &key.column, &config.db),
+                table_name,
+                key.column,
+                table_name,
+                key.related_column,
+                key.on_delete_action()
+            ));
+        }
+        if let Some(description) = parsed_type.description.as_ref().filter(|_| inline_comments) {
+            code.push_str(&format!(
+                "                    .comment({:?})\n",
+                description
+            ));
+        }
+        code.push_str("                    .to_owned(),\n");
+        code.push_str("            )\n");
+        let indexes = column_indexes(type_name, parsed_type, config);
+        let comment_statements = comment_statements_sql(
+            &table_name,
+            parsed_type.description.as_deref(),
+            &comments,
+            &config.db,
+        );
+        if indexes.is_empty() && comment_statements.is_empty() {
+            code.push_str("            .await\n");
+        } else {
+            code.push_str("            .await?;\n");
+            for index in &indexes {
+                code.push_str("\n        manager\n");
+                code.push_str("            .create_index(\n");
+                code.push_str("                Index::create()\n");
+                code.push_str(&format!("                    .name(\"{}\")\n", index.name));
+                code.push_str(&format!(
+                    "                    .table(Alias::new(\"{}\"))\n",
+                    table_name
+                ));
+                code.push_str(&format!(
+                    "                    .col(Alias::new(\"{}\"))\n",
+                    index.column
+                ));
+                if index.unique {
+                    code.push_str("                    .unique()\n");
+                }
+                code.push_str("                    .if_not_exists()\n");
+                code.push_str("                    .to_owned(),\n");
+                code.push_str("            )\n");
+                code.push_str("            .await?;\n");
+            }
+            for statement in &comment_statements {
+                code.push_str("\n        manager\n");
+                code.push_str("            .get_connection()\n");
+                code.push_str(&format!(
+                    "            .execute_unprepared({:?})\n",
+                    statement
+                ));
+                code.push_str("            .await?;\n");
+            }
+            code.push_str("        Ok(())\n");
+        }
+        code.push_str("    }\n\n");
+        code.push_str("    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {\n");
+        for index in &indexes {
+            code.push_str("        manager\n");
+            code.push_str(&format!(
+                "            .drop_index(Index::drop().name(\"{}\").table(Alias::new(\"{}\")).to_owned())\n",
+                index.name, table_name
+            ));
+            code.push_str("            .await?;\n");
+        }
+        code.push_str("        manager\n");
+        code.push_str(&format!(
+            "            .drop_table(Table::drop().table(Alias::new(\"{}\")).to_owned())\n",
+            table_name
+        ));
+        code.push_str("            .await\n");
+        code.push_str("    }\n");
+        code.push_str("}\n");
+
+        Ok(MigrationFile::Rust {
+            name: migration_name,
+            code,
+        })
+    }
+
+    /// Migration creating the native enum types, named to sort ahead of the
+    /// table migrations that use them
+    fn generate_enum_types_migration(
+        &self,
+        up_sql: String,
+        down_sql: String,
+        config: &Config,
+    ) -> MigrationFile {
+        let name = "m0000_create_enum_types".to_string();
+        if config.sea_orm_migration_style == SeaOrmMigrationStyle::Raw {
+            return MigrationFile::Sql {
+                name,
+                up_sql,
+                down_sql,
+            };
+        }
+
+        let mut code = String::new();
+        code.push_str("use sea_orm_migration::prelude::*;\n\n");
+        code.push_str("#[derive(DeriveMigrationName)]\n");
+        code.push_str("pub struct Migration;\n\n");
+        code.push_str("#[async_trait::async_trait]\n");
+        code.push_str("impl MigrationTrait for Migration {\n");
+        for (method, sql) in [("up", &up_sql), ("down", &down_sql)] {
+            if method == "down" {
+                code.push('\n');
+            }
+            code.push_str(&format!(
+                "    async fn {}(&self, manager: &SchemaManager) -> Result<(), DbErr> {{\n",
+                method
+            ));
+            code.push_str("        manager\n");
+            code.push_str("            .get_connection()\n");
+            code.push_str(&format!("            .execute_unprepared({:?})\n", sql));
+            code.push_str("            .await?;\n");
+            code.push_str("        Ok(())\n");
+            code.push_str("    }\n");
+        }
+        code.push_str("}\n");
+
+        MigrationFile::Rust { name, code }
+    }
+
+    /// `Migrator` running the named migration modules in the given order
+    fn migrator_code(&self, names: &[&str]) -> String {
+        let mut code = String::new();
+        code.push_str("pub use sea_orm_migration::prelude::*;\n\n");
+        for name in names {
+            code.push_str(&format!("mod {};\n", name));
+        }
+        code.push_str("\npub struct Migrator;\n\n");
+        code.push_str("#[async_trait::async_trait]\n");
+        code.push_str("impl MigratorTrait for Migrator {\n");
+        code.push_str("    fn migrations() -> Vec<Box<dyn MigrationTrait>> {\n");
+        code.push_str("        vec![\n");
+        for name in names {
+            code.push_str(&format!("            Box::new({}::Migration),\n", name));
+        }
+        code.push_str("        ]\n");
+        code.push_str("    }\n");
+        code.push_str("}\n");
+        code
+    }
+}
+
+/// Rust type of a field on the `Model`; relations that aren't columns hold
+/// their values in a `Vec`
+fn model_field_type(schema: &ParsedSchema, field: &ParsedField, config: &Config) -> String {
+    let field_type = rust_type_for_field(field, config);
+    if !is_column_field(field, schema) && field.is_list {
+        format!("Vec<{}>", field_type)
+    } else {
+        field_type
+    }
+}
+
+/// Name of a type's create table migration, numbered by [`foreign_key_depth`]
+/// so every table sorts after `m0000_create_enum_types` and after the tables
+/// its foreign keys reference. The table name keeps it unique, and the number
+/// only changes when the type's own references do, so the name stays the same
+/// from one run to the next and as unrelated tables are added.
+fn table_migration_name(schema: &ParsedSchema, type_name: &str, config: &Config) -> String {
+    let depth = foreign_key_depth(schema, type_name, config, &mut Vec::new());
+    format!(
+        "m{:04}_create_{}_table",
+        depth,
+        entity_table_name(type_name, config)
+    )
+}
+
+/// 1 for a type without foreign keys, otherwise one more than the deepest
+/// type it references. Self references and the reference closing a cycle,
+/// found through the types in `visiting`, are left out.
+fn foreign_key_depth<'a>(
+    schema: &'a ParsedSchema,
+    type_name: &'a str,
+    config: &Config,
+    visiting: &mut Vec<&'a str>,
+) -> usize {
+    let Some(parsed_type) = schema.types.get(type_name) else {
+        return 0;
+    };
+    visiting.push(type_name);
+    let mut depth = 1;
+    for key in foreign_keys(schema, parsed_type, config) {
+        let Some((related, _)) = schema.types.get_key_value(&key.related_type) else {
+            continue;
+        };
+        if !visiting.contains(&related.as_str()) {
+            depth = depth.max(1 + foreign_key_depth(schema, related, config, visiting));
+        }
+    }
+    visiting.pop();
+    depth
+}
+
+/// `sea_query` column type method for ID columns
+fn sea_query_id_method(id_type: IdType) -> &'static str {
+    match id_type {
+        IdType::Integer => ".integer()",
+        IdType::String => ".string()",
+        IdType::Uuid => ".uuid()",
+    }
+}
+
+/// Indent every non-empty line of `code` one level, for inline modules
+fn indent(code: &str) -> String {
+    code.lines()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("    {}", line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
 }
@@ -1,10 +1,22 @@
 use reqwest::header::{HeaderName, HeaderValue};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::path::Path;
 
-#[derive(Debug, Serialize)]
+use crate::config::{Config, IntrospectionMethod, IntrospectionRequest};
+
+#[derive(Debug, Clone, Serialize)]
 struct IntrospectionQuery {
-    query: String,
+    /// Left out when a persisted query is sent by its hash alone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    query: Option<String>,
+    #[serde(rename = "operationName", skip_serializing_if = "Option::is_none")]
+    operation_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    variables: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extensions: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -20,12 +32,14 @@ struct GraphQLError {
 
 #[derive(Debug, Deserialize)]
 struct IntrospectionData {
-    #[serde(rename = "__schema")]
-    schema: Schema,
+    /// Missing or `null` when the server couldn't resolve the schema
+    #[serde(rename = "__schema", default)]
+    schema: Option<Schema>,
 }
 
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
+#[serde(rename_all = "camelCase")]
 pub struct Schema {
     pub query_type: Option<TypeRef>,
     pub mutation_type: Option<TypeRef>,
@@ -36,6 +50,7 @@ pub struct Schema {
 
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
+#[serde(rename_all = "camelCase")]
 pub struct Type {
     pub name: Option<String>,
     pub kind: TypeKind,
@@ -62,6 +77,7 @@ pub enum TypeKind {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TypeRef {
     pub name: Option<String>,
     pub kind: Option<TypeKind>,
@@ -70,37 +86,41 @@ pub struct TypeRef {
 
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
+#[serde(rename_all = "camelCase")]
 pub struct Field {
     pub name: String,
     pub description: Option<String>,
     pub args: Vec<InputValue>,
+    #[serde(rename = "type")]
     pub type_: TypeRef,
-    #[serde(rename = "isDeprecated")]
     pub is_deprecated: bool,
     pub deprecation_reason: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
+#[serde(rename_all = "camelCase")]
 pub struct InputValue {
     pub name: String,
     pub description: Option<String>,
+    #[serde(rename = "type")]
     pub type_: TypeRef,
     pub default_value: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
+#[serde(rename_all = "camelCase")]
 pub struct EnumValue {
     pub name: String,
     pub description: Option<String>,
-    #[serde(rename = "isDeprecated")]
     pub is_deprecated: bool,
     pub deprecation_reason: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
+#[serde(rename_all = "camelCase")]
 pub struct Directive {
     pub name: String,
     pub description: Option<String>,
@@ -134,6 +154,7 @@ pub enum DirectiveLocation {
 
 pub struct Introspector {
     client: reqwest::Client,
+    request: IntrospectionRequest,
 }
 
 #[allow(dead_code)]
@@ -145,17 +166,81 @@ impl Default for Introspector {
 
 #[allow(dead_code)]
 impl Introspector {
+    /// Introspector with a default HTTP client.
+    ///
+    /// Requests go through the proxies named by the `HTTP_PROXY`, `HTTPS_PROXY`
+    /// and `NO_PROXY` environment variables (and their lowercase forms).
     pub fn new() -> Self {
         Self {
             client: reqwest::Client::new(),
+            request: IntrospectionRequest::default(),
         }
     }
 
+    /// Introspector using the config's request envelope and extra CA certificate
+    pub fn for_config(config: &Config) -> anyhow::Result<Self> {
+        let introspector = Self::new().with_request(config.introspection.clone());
+        match &config.ca_cert {
+            Some(path) => introspector.with_ca_cert(path),
+            None => Ok(introspector),
+        }
+    }
+
+    /// Trust the PEM root certificate at `path` in addition to the system roots
+    pub fn with_ca_cert(mut self, path: &Path) -> anyhow::Result<Self> {
+        let pem = fs_err::read(path).map_err(|e| {
+            anyhow::anyhow!("Failed to read CA certificate '{}': {}", path.display(), e)
+        })?;
+        let certificate = reqwest::Certificate::from_pem(&pem).map_err(|e| {
+            anyhow::anyhow!(
+                "Invalid CA certificate '{}': {}\n\nThe file must contain a PEM encoded certificate.",
+                path.display(),
+                e
+            )
+        })?;
+        self.client = reqwest::Client::builder()
+            .add_root_certificate(certificate)
+            .build()?;
+        Ok(self)
+    }
+
+    /// Use the given envelope and HTTP method for the introspection query
+    pub fn with_request(mut self, request: IntrospectionRequest) -> Self {
+        self.request = request;
+        self
+    }
+
+    /// Fetch and extract the schema, along with the errors the server returned
+    /// next to it (see [`parse_introspection_json`](Self::parse_introspection_json))
     pub async fn introspect_schema(
         &self,
         url: &str,
         headers: &HashMap<String, String>,
-    ) -> anyhow::Result<Schema> {
+    ) -> anyhow::Result<(Schema, Vec<String>)> {
+        let json = self.fetch_introspection_json(url, headers).await?;
+        self.parse_introspection_json(&json)
+    }
+
+    /// Send the introspection query and return the raw response body
+    pub async fn fetch_introspection_json(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+    ) -> anyhow::Result<String> {
+        // The document names the same operation as the envelope, so gateways
+        // that only run registered operations can match it
+        let operation_name = &self.request.operation_name;
+        let is_name = |name: &str| {
+            name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        };
+        if !operation_name.is_empty() && !is_name(operation_name) {
+            anyhow::bail!(
+                "Invalid introspection operation name '{}': expected a GraphQL name such as IntrospectionQuery",
+                operation_name
+            );
+        }
+
         let introspection_query = r#"
             query IntrospectionQuery {
                 __schema {
@@ -218,45 +303,84 @@ impl Introspector {
                 defaultValue
             }
 
-            fragment TypeRef on __Type {
-                kind
-                name
-                ofType {
-                    kind
-                    name
-                    ofType {
-                        kind
-                        name
-                        ofType {
-                            kind
-                            name
-                            ofType {
-                                kind
-                                name
-                                ofType {
-                                    kind
-                                    name
-                                    ofType {
-                                        kind
-                                        name
-                                        ofType {
-                                            kind
-                                            name
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
         "#;
 
+        let document = introspection_query.replacen(
+            "query IntrospectionQuery",
+            &format!("query {}", operation_name),
+            1,
+        ) + &type_ref_fragment(self.request.depth);
         let query = IntrospectionQuery {
-            query: introspection_query.to_string(),
+            extensions: self
+                .request
+                .use_apq
+                .then(|| persisted_query_extension(&document)),
+            query: Some(document),
+            operation_name: Some(self.request.operation_name.clone())
+                .filter(|name| !name.is_empty()),
+            variables: self.request.send_variables.then(|| serde_json::json!({})),
         };
 
-        let mut request = self.client.post(url).json(&query);
+        if self.request.use_apq {
+            // Servers that already know the hash answer without the document
+            let hashed = IntrospectionQuery {
+                query: None,
+                ..query.clone()
+            };
+            let (status, body) = self.send_query(url, headers, &hashed).await?;
+            if has_persisted_query_error(
+                &body,
+                "PersistedQueryNotSupported",
+                "PERSISTED_QUERY_NOT_SUPPORTED",
+            ) {
+                // Servers without APQ only take the plain document
+                let plain = IntrospectionQuery {
+                    extensions: None,
+                    ..query
+                };
+                let (status, body) = self.send_query(url, headers, &plain).await?;
+                return introspection_body(status, body, url);
+            }
+            if !has_persisted_query_error(
+                &body,
+                "PersistedQueryNotFound",
+                "PERSISTED_QUERY_NOT_FOUND",
+            ) {
+                return introspection_body(status, body, url);
+            }
+        }
+
+        // With APQ, sending the document along with its hash registers it
+        let (status, body) = self.send_query(url, headers, &query).await?;
+        introspection_body(status, body, url)
+    }
+
+    /// Send one introspection request and return its status and body
+    async fn send_query(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+        query: &IntrospectionQuery,
+    ) -> anyhow::Result<(reqwest::StatusCode, String)> {
+        let mut request = match self.request.method {
+            IntrospectionMethod::Post => self.client.post(url).json(query),
+            IntrospectionMethod::Get => {
+                let mut params = Vec::new();
+                if let Some(document) = &query.query {
+                    params.push(("query", document.clone()));
+                }
+                if let Some(operation_name) = &query.operation_name {
+                    params.push(("operationName", operation_name.clone()));
+                }
+                if let Some(variables) = &query.variables {
+                    params.push(("variables", variables.to_string()));
+                }
+                if let Some(extensions) = &query.extensions {
+                    params.push(("extensions", extensions.to_string()));
+                }
+                self.client.get(url).query(&params)
+            }
+        };
 
         // Add custom headers
         for (key, value) in headers {
@@ -267,29 +391,27 @@ impl Introspector {
 
         let response = request.send().await?;
         let status = response.status();
+        Ok((status, response.text().await?))
+    }
 
-        if !status.is_success() {
-            let status_code = status.as_u16();
-            let error_msg = match status_code {
-                400 => "Bad Request - The GraphQL query may be malformed",
-                401 => "Unauthorized - Authentication required. Check your headers",
-                403 => "Forbidden - Access denied. Verify your credentials and permissions",
-                404 => "Not Found - GraphQL endpoint not found at the specified URL",
-                500 => "Internal Server Error - The GraphQL server encountered an error",
-                _ => "HTTP request failed",
-            };
-
-            return Err(anyhow::anyhow!(
-                "GraphQL introspection failed with HTTP {}: {}\nURL: {}\n\nTroubleshooting:\n- Verify the URL is correct and accessible\n- Check authentication headers if required\n- Ensure the server supports GraphQL introspection",
-                status_code,
-                error_msg,
-                url
-            ));
-        }
-
-        let introspection_response: IntrospectionResponse = response.json().await?;
-
-        if let Some(errors) = introspection_response.errors {
+    /// Extract the schema from a raw introspection response body.
+    ///
+    /// Errors only fail the introspection when they come without a `__schema`;
+    /// next to one they are returned as warnings, for the caller to report.
+    pub fn parse_introspection_json(&self, json: &str) -> anyhow::Result<(Schema, Vec<String>)> {
+        let introspection_response: IntrospectionResponse = serde_json::from_str(json)
+            .map_err(|e| anyhow::anyhow!("Failed to parse introspection response: {}", e))?;
+
+        let schema = introspection_response.data.and_then(|data| data.schema);
+        let mut warnings = Vec::new();
+        if let (Some(_), Some(errors)) = (&schema, &introspection_response.errors) {
+            warnings.extend(errors.iter().map(|e| {
+                format!(
+                    "Introspection returned an error along with the schema: {}",
+                    e.message
+                )
+            }));
+        } else if let Some(errors) = introspection_response.errors {
             let error_messages: Vec<String> = errors.into_iter().map(|e| e.message).collect();
             let error_count = error_messages.len();
 
@@ -312,16 +434,14 @@ impl Introspector {
             return Err(anyhow::anyhow!(error_text));
         }
 
-        let schema = introspection_response
-            .data
-            .ok_or_else(|| {
-                anyhow::anyhow!(
-                    "No data returned from GraphQL introspection\n\nThis typically indicates:\n- The GraphQL endpoint returned an empty response\n- The server may not support the introspection query\n- Network issues prevented a complete response\n\nTry:\n- Checking if the endpoint supports GraphQL introspection\n- Verifying network connectivity\n- Testing with a simple GraphQL query first"
-                )
-            })?
-            .schema;
+        let schema = schema.ok_or_else(|| {
+            anyhow::anyhow!(
+                "No data returned from GraphQL introspection\n\nThis typically indicates:\n- The GraphQL endpoint returned an empty response\n- The server may not support the introspection query\n- Network issues prevented a complete response\n\nTry:\n- Checking if the endpoint supports GraphQL introspection\n- Verifying network connectivity\n- Testing with a simple GraphQL query first"
+            )
+        })?;
 
-        Ok(schema)
+        check_type_depth(&schema, self.request.depth)?;
+        Ok((schema, warnings))
     }
 
     fn object_type_to_sdl(&self, type_def: &Type) -> String {
@@ -565,3 +685,115 @@ impl Introspector {
         sdl
     }
 }
+
+/// The body of a successful introspection response, or an error explaining
+/// the HTTP status
+fn introspection_body(
+    status: reqwest::StatusCode,
+    body: String,
+    url: &str,
+) -> anyhow::Result<String> {
+    if !status.is_success() {
+        let status_code = status.as_u16();
+        let error_msg = match status_code {
+            400 => "Bad Request - The GraphQL query may be malformed",
+            401 => "Unauthorized - Authentication required. Check your headers",
+            403 => "Forbidden - Access denied. Verify your credentials and permissions",
+            404 => "Not Found - GraphQL endpoint not found at the specified URL",
+            500 => "Internal Server Error - The GraphQL server encountered an error",
+            _ => "HTTP request failed",
+        };
+
+        return Err(anyhow::anyhow!(
+            "GraphQL introspection failed with HTTP {}: {}\nURL: {}\n\nTroubleshooting:\n- Verify the URL is correct and accessible\n- Check authentication headers if required\n- Ensure the server supports GraphQL introspection",
+            status_code,
+            error_msg,
+            url
+        ));
+    }
+
+    Ok(body)
+}
+
+/// `extensions` entry naming a persisted query by the SHA-256 of its document
+fn persisted_query_extension(document: &str) -> serde_json::Value {
+    let hash: String = Sha256::digest(document.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    serde_json::json!({ "persistedQuery": { "version": 1, "sha256Hash": hash } })
+}
+
+/// Whether a response to a hashed query carries the given APQ error, either as
+/// its message or its `extensions.code`: `PersistedQueryNotFound` when the
+/// server doesn't know the hash yet, `PersistedQueryNotSupported` when it
+/// doesn't do APQ at all
+fn has_persisted_query_error(body: &str, message: &str, code: &str) -> bool {
+    let Ok(response) = serde_json::from_str::<serde_json::Value>(body) else {
+        return false;
+    };
+    response["errors"].as_array().is_some_and(|errors| {
+        errors
+            .iter()
+            .any(|error| error["message"] == message || error["extensions"]["code"] == code)
+    })
+}
+
+/// `TypeRef` fragment selecting `depth` levels of `ofType` below the outer type
+fn type_ref_fragment(depth: usize) -> String {
+    let mut selection = String::new();
+    for level in 0..=depth {
+        let indent = "    ".repeat(level + 4);
+        selection.push_str(&format!("{}kind\n{}name\n", indent, indent));
+        if level < depth {
+            selection.push_str(&format!("{}ofType {{\n", indent));
+        }
+    }
+    for level in (0..depth).rev() {
+        selection.push_str(&format!("{}}}\n", "    ".repeat(level + 4)));
+    }
+    format!(
+        "\n            fragment TypeRef on __Type {{\n{}            }}\n",
+        selection
+    )
+}
+
+/// Fail when a list or non-null wrapper lost its inner type because the
+/// query's `ofType` unrolling stopped short of it
+fn check_type_depth(schema: &Schema, depth: usize) -> anyhow::Result<()> {
+    fn is_truncated(type_ref: &TypeRef) -> bool {
+        match (&type_ref.kind, &type_ref.of_type) {
+            (_, Some(of_type)) => is_truncated(of_type),
+            (Some(TypeKind::List | TypeKind::NonNull), None) => true,
+            _ => false,
+        }
+    }
+
+    for type_def in &schema.types {
+        let type_name = type_def.name.as_deref().unwrap_or_default();
+        let fields = type_def.fields.iter().flatten().map(|field| {
+            let args = field.args.iter().map(move |arg| {
+                (
+                    format!("{}.{}({})", type_name, field.name, arg.name),
+                    &arg.type_,
+                )
+            });
+            std::iter::once((format!("{}.{}", type_name, field.name), &field.type_)).chain(args)
+        });
+        let input_fields = type_def
+            .input_fields
+            .iter()
+            .flatten()
+            .map(|field| (format!("{}.{}", type_name, field.name), &field.type_));
+        for (location, type_ref) in fields.flatten().chain(input_fields) {
+            if is_truncated(type_ref) {
+                anyhow::bail!(
+                    "Type nesting exceeds introspection depth {} at '{}', increase --introspection-depth",
+                    depth,
+                    location
+                );
+            }
+        }
+    }
+    Ok(())
+}
@@ -43,18 +43,12 @@
 //!
 //! # async fn example() -> anyhow::Result<()> {
 //! // Create configuration programmatically
-//! let config = Config {
-//!     url: "https://api.example.com/graphql".to_string(),
-//!     orm: OrmType::Diesel,
-//!     db: DatabaseType::Postgres,
-//!     output_dir: "./generated".into(),
-//!     headers: std::collections::HashMap::new(),
-//!     type_mappings: std::collections::HashMap::new(),
-//!     scalar_mappings: std::collections::HashMap::new(),
-//!     table_naming: Default::default(),
-//!     generate_migrations: true,
-//!     generate_entities: true,
-//! };
+//! let config = Config::builder()
+//!     .url("https://api.example.com/graphql")
+//!     .orm(OrmType::Diesel)
+//!     .db(DatabaseType::Postgres)
+//!     .output_dir("./generated")
+//!     .build()?;
 //!
 //! // Generate code
 //! let generator = CodeGenerator::new(&config.orm);
@@ -99,10 +93,13 @@
 //! ```text
 //! generated/
 //! ├── src/
+//! │   ├── lib.rs             # Diesel hub: `schema`/`entities` modules and re-exports
 //! │   ├── schema.rs          # Diesel schema definitions
 //! │   ├── entities/
+//! │   │   ├── mod.rs        # Diesel entity module declarations
 //! │   │   ├── user.rs       # Entity structs and implementations
 //! │   │   └── post.rs
+//! │   ├── client.rs          # Typed GraphQL client (generate_client)
 //! │   └── mod.rs            # Sea-ORM module definitions
 //! └── migrations/
 //!     └── 0001_create_users_table/
@@ -112,13 +109,16 @@
 //!
 //! ## Error Handling
 //!
-//! The library uses [`anyhow`](https://docs.rs/anyhow) for error handling, providing
-//! detailed error messages with context. Common error scenarios:
+//! The generation entry points return [`CodegenError`], whose variants tell
+//! failure kinds apart while carrying [`anyhow`](https://docs.rs/anyhow) errors
+//! with detailed context. Lower-level APIs return `anyhow::Result` directly.
 //!
-//! - **Network errors**: GraphQL endpoint unreachable or authentication failures
-//! - **Schema errors**: Invalid GraphQL schema or introspection disabled
-//! - **Configuration errors**: Missing or invalid configuration files
-//! - **Generation errors**: Unsupported GraphQL types or ORM constraints
+//! - **Network errors** (`Network`): GraphQL endpoint unreachable
+//! - **Schema errors** (`Introspection`, `Parse`): authentication failures, introspection
+//!   disabled or an invalid GraphQL schema
+//! - **Configuration errors** (`Config`): Missing or invalid configuration files
+//! - **Generation errors** (`Generation`, `Io`): Unsupported GraphQL types, ORM
+//!   constraints or file system failures
 //!
 //! ## Feature Flags
 //!
@@ -131,16 +131,25 @@
 //! - A GraphQL API that supports introspection
 //! - Appropriate database dependencies based on your ORM choice
 
+pub mod app;
 pub mod cli;
 pub mod config;
+pub mod error;
 pub mod generator;
 pub mod introspection;
+pub mod logger;
+pub mod manifest;
 pub mod parser;
+pub mod secrets;
+pub mod validation;
+pub mod watch;
 
 pub use config::Config;
-pub use generator::create_generator;
+pub use error::CodegenError;
+pub use generator::{GenerationSummary, GeneratorRegistry, create_generator};
 
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use fs_err as fs;
 
@@ -177,6 +186,7 @@ use fs_err as fs;
 /// ```
 pub struct CodeGenerator {
     inner: Box<dyn generator::CodeGenerator>,
+    secret_resolver: Box<dyn secrets::SecretResolver>,
 }
 
 impl CodeGenerator {
@@ -197,9 +207,58 @@ impl CodeGenerator {
     pub fn new(orm: &cli::OrmType) -> Self {
         Self {
             inner: generator::create_generator(orm),
+            secret_resolver: Box::new(secrets::NoopSecretResolver),
         }
     }
 
+    /// Creates a code generator from a name in `registry`, such as a custom
+    /// backend registered with [`GeneratorRegistry::register`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use graphql_codegen_rust::{CodeGenerator, GeneratorRegistry};
+    ///
+    /// # fn example() -> anyhow::Result<()> {
+    /// let registry = GeneratorRegistry::default();
+    /// let generator = CodeGenerator::from_registry(&registry, "sea-orm")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_registry(registry: &GeneratorRegistry, name: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            inner: registry.create(name)?,
+            secret_resolver: Box::new(secrets::NoopSecretResolver),
+        })
+    }
+
+    /// Sets the resolver used for secret references in the url and headers.
+    ///
+    /// References are only resolved when their scheme is listed in
+    /// `Config::secret_schemes`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use graphql_codegen_rust::{CodeGenerator, cli::OrmType, secrets::SecretResolver};
+    ///
+    /// struct EnvResolver;
+    ///
+    /// impl SecretResolver for EnvResolver {
+    ///     fn resolve(&self, reference: &str) -> anyhow::Result<String> {
+    ///         let key = reference.trim_start_matches("env://");
+    ///         Ok(std::env::var(key)?)
+    ///     }
+    /// }
+    ///
+    /// let generator = CodeGenerator::new(&OrmType::Diesel).with_secret_resolver(EnvResolver);
+    /// ```
+    pub fn with_secret_resolver(
+        mut self,
+        resolver: impl secrets::SecretResolver + 'static,
+    ) -> Self {
+        self.secret_resolver = Box::new(resolver);
+        self
+    }
+
     /// Generates complete ORM code from a GraphQL configuration.
     ///
     /// This method orchestrates the full code generation pipeline:
@@ -213,16 +272,18 @@ impl CodeGenerator {
     ///   database settings, and output preferences
     ///
     /// # Returns
-    /// - `Ok(())` on successful code generation
-    /// - `Err(anyhow::Error)` with detailed context on failure
+    /// - `Ok(GenerationSummary)` on success, listing the crates the output needs
+    ///   and any errors the server returned along with the schema
+    /// - `Err(CodegenError)` with detailed context on failure
     ///
     /// # Errors
-    /// This method can fail due to:
-    /// - Network issues when accessing the GraphQL endpoint
-    /// - Authentication failures (invalid headers)
-    /// - Schema parsing errors (invalid GraphQL schema)
-    /// - File system errors (permission issues, disk space)
-    /// - Code generation constraints (unsupported GraphQL types)
+    /// The [`CodegenError`] variant names the stage that failed:
+    /// - `Config`: secret references in the url or headers couldn't be resolved
+    /// - `Network`: the GraphQL endpoint couldn't be reached
+    /// - `Introspection`: HTTP or GraphQL errors, or a malformed response
+    /// - `Parse`: the introspected schema couldn't be converted
+    /// - `Io`: file system errors (permission issues, disk space)
+    /// - `Generation`: code generation constraints (unsupported GraphQL types)
     ///
     /// # Example
     /// ```rust,no_run
@@ -243,15 +304,124 @@ impl CodeGenerator {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn generate_from_config(&self, config: &Config) -> anyhow::Result<()> {
-        // Fetch and parse schema
-        let parser = parser::GraphQLParser::new();
-        let schema = parser
-            .parse_from_introspection(&config.url, &config.headers)
-            .await?;
+    pub async fn generate_from_config(
+        &self,
+        config: &Config,
+    ) -> Result<GenerationSummary, CodegenError> {
+        let (schema, warnings) = self.introspect(config).await?;
 
         // Generate all code
-        generate_all_code(&schema, config, &*self.inner).await
+        let summary = generate_all_code(&schema, config, &*self.inner)
+            .await
+            .map_err(CodegenError::generation)?;
+        Ok(GenerationSummary {
+            warnings,
+            ..summary
+        })
+    }
+
+    /// Runs the full pipeline from a configuration but returns the generated
+    /// files instead of writing them.
+    ///
+    /// Paths in the map are relative to `config.output_dir`, matching the layout
+    /// [`generate_from_config`](Self::generate_from_config) writes, and the
+    /// summary is that of [`generate_to_map`](crate::generate_to_map) plus the
+    /// errors the server returned along with the schema. Useful for build
+    /// scripts and services that handle the output themselves.
+    pub async fn generate_to_map(
+        &self,
+        config: &Config,
+    ) -> anyhow::Result<(BTreeMap<PathBuf, String>, GenerationSummary)> {
+        let (schema, warnings) = self.introspect(config).await?;
+        let (files, summary) = generate_to_map(&schema, config, &*self.inner)?;
+        Ok((
+            files,
+            GenerationSummary {
+                warnings,
+                ..summary
+            },
+        ))
+    }
+
+    /// Fetch and parse the schema from the configured endpoint, or read it
+    /// from `introspection_file` when set, along with the errors the server
+    /// returned next to it
+    async fn introspect(
+        &self,
+        config: &Config,
+    ) -> Result<(parser::ParsedSchema, Vec<String>), CodegenError> {
+        if let Some(path) = config.introspection_file.as_deref() {
+            let json = fs::read_to_string(path)?;
+            let (introspected, warnings) = introspection::Introspector::new()
+                .parse_introspection_json(&json)
+                .map_err(CodegenError::Introspection)?;
+            let schema = parser::GraphQLParser::new()
+                .parse_schema(introspected)
+                .map_err(CodegenError::Parse)?;
+            return Ok((schema, warnings));
+        }
+
+        // Resolve secret references before they reach the endpoint
+        let resolver = &*self.secret_resolver;
+        let url =
+            secrets::resolve_value(&config.url, &config.secret_schemes, resolver).map_err(|e| {
+                CodegenError::Config(anyhow::anyhow!("Failed to resolve secret for url: {}", e))
+            })?;
+        let headers = secrets::resolve_headers(&config.headers, &config.secret_schemes, resolver)
+            .map_err(CodegenError::Config)?;
+        config::validate_endpoint_url(&url).map_err(CodegenError::Config)?;
+
+        // Keep transport, response and conversion failures apart
+        let introspector =
+            introspection::Introspector::for_config(config).map_err(CodegenError::Config)?;
+        let json = introspector
+            .fetch_introspection_json(&url, &headers)
+            .await
+            .map_err(CodegenError::introspection)?;
+        if let Some(path) = config.dump_introspection.as_deref() {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, &json)?;
+        }
+        let (introspected, warnings) = introspector
+            .parse_introspection_json(&json)
+            .map_err(CodegenError::Introspection)?;
+        let schema = parser::GraphQLParser::new()
+            .parse_schema(introspected)
+            .map_err(CodegenError::Parse)?;
+        Ok((schema, warnings))
+    }
+
+    /// Generates code into a caller-provided sink instead of the file system.
+    ///
+    /// The sink is invoked once per generated file with its path relative to the
+    /// output directory and its content, so streaming consumers (zip or tar
+    /// writers, network uploads) don't need to buffer the whole output.
+    ///
+    /// # Example
+    /// ```rust
+    /// use graphql_codegen_rust::{CodeGenerator, Config, parser::GraphQLParser};
+    ///
+    /// # fn example() -> anyhow::Result<()> {
+    /// let schema = GraphQLParser::new().parse_from_sdl("type User { id: ID! name: String! }")?;
+    /// let config = Config::default();
+    ///
+    /// let mut paths = Vec::new();
+    /// CodeGenerator::new(&config.orm).generate_into(&schema, &config, &mut |path, _content| {
+    ///     paths.push(path.to_string());
+    /// })?;
+    /// assert!(paths.contains(&"src/entities/user.rs".to_string()));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn generate_into(
+        &self,
+        schema: &parser::ParsedSchema,
+        config: &Config,
+        sink: &mut dyn FnMut(&str, &str),
+    ) -> anyhow::Result<()> {
+        generate_into(schema, config, &*self.inner, sink)
     }
 }
 
@@ -270,15 +440,13 @@ impl CodeGenerator {
 /// - `config_path`: Path to the configuration file. Can be any type that converts to `Path`.
 ///
 /// # Returns
-/// - `Ok(())` on successful code generation
-/// - `Err(anyhow::Error)` with context about what failed
+/// - `Ok(GenerationSummary)` on success, listing the crates the output needs
+/// - `Err(CodegenError)` with context about what failed
 ///
 /// # Errors
 /// This function can fail due to:
-/// - Configuration file not found or unreadable
-/// - Invalid configuration format or content
-/// - Network issues during GraphQL introspection
-/// - Code generation failures
+/// - Configuration file not found, unreadable or invalid ([`CodegenError::Config`])
+/// - Any failure of [`CodeGenerator::generate_from_config`]
 ///
 /// # Example
 /// ```rust,no_run
@@ -293,59 +461,403 @@ impl CodeGenerator {
 /// # Ok(())
 /// # }
 /// ```
-pub async fn generate_from_config_file<P: AsRef<Path>>(config_path: P) -> anyhow::Result<()> {
+pub async fn generate_from_config_file<P: AsRef<Path>>(
+    config_path: P,
+) -> Result<GenerationSummary, CodegenError> {
     let path_buf = config_path.as_ref().to_path_buf();
-    let config = Config::from_file(&path_buf)?;
+    let config = Config::from_file(&path_buf).map_err(CodegenError::Config)?;
     let generator = CodeGenerator::new(&config.orm);
     generator.generate_from_config(&config).await
 }
 
+/// Generates code into a scratch directory and compares it with `config.output_dir`.
+///
+/// Nothing in the output directory is modified. Returns the sorted paths, relative
+/// to the output directory, of generated files that are missing there or would
+/// change, and of stale files a regeneration would remove; an empty list means the
+/// committed code is up to date.
+pub async fn check_all_code(
+    schema: &parser::ParsedSchema,
+    config: &Config,
+    generator: &dyn generator::CodeGenerator,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let check_dir = generator::create_check_dir(&config.output_dir)?;
+    let check_config = Config {
+        output_dir: check_dir.clone(),
+        ..config.clone()
+    };
+
+    let result = match generate_all_code(schema, &check_config, generator).await {
+        Ok(summary) => check_drift(schema, config, &check_dir, summary),
+        Err(e) => Err(e),
+    };
+    let _ = fs::remove_dir_all(&check_dir);
+    result
+}
+
+/// Files of `config.output_dir` that differ from a run into `check_dir` or that
+/// the run would remove
+fn check_drift(
+    schema: &parser::ParsedSchema,
+    config: &Config,
+    check_dir: &Path,
+    summary: GenerationSummary,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let mut changed = generator::changed_files(check_dir, &config.output_dir)?;
+    if config.output_dir.exists() {
+        let generated = summary
+            .written_files
+            .into_iter()
+            .chain(summary.unchanged_files)
+            .collect();
+        changed.extend(generator::stale_files(schema, config, &generated)?);
+        changed.sort();
+    }
+    Ok(changed)
+}
+
 pub async fn generate_all_code(
     schema: &parser::ParsedSchema,
     config: &Config,
     generator: &dyn generator::CodeGenerator,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<GenerationSummary> {
+    let foreign_files = generator::check_foreign_files(config)?;
+    let previous_manifest = manifest::Manifest::load(&config.output_dir);
+    let GeneratedFiles {
+        files,
+        manifest,
+        skipped_types,
+    } = generate_files(schema, config, generator, &previous_manifest)?;
+    let entity_files = manifest
+        .entities
+        .keys()
+        .map(|filename| PathBuf::from(config.entity_file(filename)))
+        .collect();
+
     // Create output directory structure
-    fs::create_dir_all(&config.output_dir)?;
-    let src_dir = config.output_dir.join("src");
-    fs::create_dir_all(&src_dir)?;
-
-    // Generate schema file
-    let schema_code = generator.generate_schema(schema, config)?;
-    if config.orm == cli::OrmType::Diesel {
-        let schema_path = src_dir.join("schema.rs");
-        fs::write(schema_path, schema_code)?;
-    } else if config.orm == cli::OrmType::SeaOrm {
-        // Sea-ORM generates a mod.rs file at the root
-        let mod_path = config.output_dir.join("mod.rs");
-        fs::write(mod_path, schema_code)?;
+    fs::create_dir_all(config.output_dir.join("src"))?;
+
+    let mut written_files = Vec::new();
+    let mut unchanged_files = Vec::new();
+    for (path, content) in &files {
+        let full_path = config.output_dir.join(path);
+        // In incremental mode, leave unchanged entity files untouched
+        if config.incremental && path.starts_with(config.entity_file("")) {
+            if let Some(filename) = path.file_name().and_then(|name| name.to_str()) {
+                if previous_manifest.is_unchanged(filename, content, &full_path) {
+                    unchanged_files.push(path.clone());
+                    continue;
+                }
+            }
+        }
+        if let Some(parent) = full_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(full_path, content)?;
+        written_files.push(path.clone());
     }
 
-    // Generate entity files
-    let entities = generator.generate_entities(schema, config)?;
-    let entities_dir = src_dir.join("entities");
-    fs::create_dir_all(&entities_dir)?;
+    // Entity files of types gone from the schema, and more with `force`
+    let generated = files.keys().cloned().collect();
+    let removed_files = generator::remove_stale_files(schema, config, &generated)?;
 
-    for (filename, code) in entities {
-        let entity_path = entities_dir.join(filename);
-        fs::write(entity_path, code)?;
+    if config.incremental || config.only_changed_tables {
+        manifest.save(&config.output_dir)?;
+    }
+
+    let schema = generator::prepare_schema(schema, config)?;
+    Ok(GenerationSummary {
+        required_crates: generator::required_crates(&schema, config),
+        fallback_scalars: generator::fallback_scalars(&schema, config),
+        skipped_types,
+        entity_files,
+        written_files,
+        unchanged_files,
+        removed_files,
+        foreign_files,
+        ..Default::default()
+    })
+}
+
+/// Generates all code in memory, without touching the output directory.
+///
+/// Returns the content of every file [`generate_all_code`] would write, keyed
+/// by path relative to the output directory, and a summary with the required
+/// crates, fallback scalars and the types skipped under `continue_on_error`.
+/// The previous run's manifest is still read so `only_changed_tables` can diff
+/// against it.
+pub fn generate_to_map(
+    schema: &parser::ParsedSchema,
+    config: &Config,
+    generator: &dyn generator::CodeGenerator,
+) -> anyhow::Result<(BTreeMap<PathBuf, String>, GenerationSummary)> {
+    let previous_manifest = manifest::Manifest::load(&config.output_dir);
+    let GeneratedFiles {
+        files,
+        skipped_types,
+        ..
+    } = generate_files(schema, config, generator, &previous_manifest)?;
+
+    let schema = generator::prepare_schema(schema, config)?;
+    Ok((
+        files,
+        GenerationSummary {
+            required_crates: generator::required_crates(&schema, config),
+            fallback_scalars: generator::fallback_scalars(&schema, config),
+            skipped_types,
+            ..Default::default()
+        },
+    ))
+}
+
+/// Output of [`generate_files`]
+struct GeneratedFiles {
+    /// Generated files keyed by relative path
+    files: BTreeMap<PathBuf, String>,
+    /// Manifest describing the files
+    manifest: manifest::Manifest,
+    /// Types left out under `continue_on_error`, with their errors
+    skipped_types: BTreeMap<String, String>,
+}
+
+/// Generate every file in memory.
+///
+/// With `continue_on_error`, a failed run is followed by generating each type
+/// on its own to find the failing ones, which are then left out.
+fn generate_files(
+    schema: &parser::ParsedSchema,
+    config: &Config,
+    generator: &dyn generator::CodeGenerator,
+    previous_manifest: &manifest::Manifest,
+) -> anyhow::Result<GeneratedFiles> {
+    // Restrict generation to the types that become entities and tables; the
+    // client is still generated from the full schema so it can see the roots
+    let prepared = generator::prepare_schema(schema, config)?;
+    let generate = |types: &parser::ParsedSchema| {
+        generator::catch_panic(|| {
+            let mut files = BTreeMap::new();
+            let manifest = generate_prepared_files(
+                schema,
+                types,
+                config,
+                generator,
+                previous_manifest,
+                &mut |path, content| {
+                    files.insert(PathBuf::from(path), content);
+                },
+            )?;
+            Ok((files, manifest))
+        })
+    };
+
+    let error = match generate(&prepared) {
+        Ok(Ok((files, manifest))) => {
+            return Ok(GeneratedFiles {
+                files,
+                manifest,
+                skipped_types: BTreeMap::new(),
+            });
+        }
+        Ok(Err(error)) => error,
+        Err(message) if config.continue_on_error => {
+            anyhow::anyhow!("Code generation panicked: {}", message)
+        }
+        Err(message) => anyhow::bail!(
+            "Code generation panicked: {}\nUse --continue-on-error to skip the types that fail",
+            message
+        ),
+    };
+    if !config.continue_on_error {
+        return Err(error);
+    }
+
+    let failures = generator::find_failing_types(&prepared, config, generator);
+    if failures.is_empty() {
+        return Err(error);
+    }
+    let failed: Vec<String> = failures.iter().map(|f| f.type_name.clone()).collect();
+    let (files, manifest) = generate(&prepared.filter_types(&[], &failed))
+        .unwrap_or_else(|message| Err(anyhow::anyhow!("Code generation panicked: {}", message)))?;
+    Ok(GeneratedFiles {
+        files,
+        manifest,
+        skipped_types: failures
+            .into_iter()
+            .map(|failure| (failure.type_name, failure.error.to_string()))
+            .collect(),
+    })
+}
+
+/// Pass every generated file for the types of an already prepared schema to
+/// `emit` as `(relative path, content)`, returning the manifest describing them
+fn generate_prepared_files(
+    full_schema: &parser::ParsedSchema,
+    schema: &parser::ParsedSchema,
+    config: &Config,
+    generator: &dyn generator::CodeGenerator,
+    previous_manifest: &manifest::Manifest,
+    emit: &mut dyn FnMut(&str, String),
+) -> anyhow::Result<manifest::Manifest> {
+    // Generated Rust files are optionally passed through rustfmt
+    let format = |code: String| {
+        if config.format_output {
+            generator::format_rust_code(&code)
+        } else {
+            code
+        }
+    };
+
+    // Generated files start with a banner and use the configured line endings
+    let banner = generator::banner_text(config);
+    let mut add = |path: &str, content: String| {
+        let content = generator::with_banner(path, &content, &banner);
+        emit(path, config.line_endings.apply(&content));
+    };
+
+    let mut manifest = manifest::Manifest::default();
+
+    if config.single_file {
+        // One self-contained file replaces the schema, entity and module files
+        add(
+            "src/generated.rs",
+            format(generator.generate_single_file(schema, config)?),
+        );
+        if config.schema_only {
+            return Ok(manifest);
+        }
+    } else {
+        // Generate schema file
+        let schema_code = format(generator.generate_schema(schema, config)?);
+        // Plain structs need no schema file
+        if let Some(path) = config.schema_file() {
+            add(&path, schema_code);
+        }
+
+        if config.schema_only {
+            return Ok(manifest);
+        }
+
+        // Generate entity files, in file name order
+        let entities: BTreeMap<_, _> = generator
+            .generate_entities(schema, config)?
+            .into_iter()
+            .collect();
+        for (filename, code) in entities {
+            let code = config.line_endings.apply(&format(code));
+            manifest.record(&filename, &code);
+            add(&config.entity_file(&filename), code);
+        }
+
+        // Generate module wiring files
+        for (path, code) in generator.generate_module_files(schema, config)? {
+            add(&config.module_file(&path), format(code));
+        }
+    }
+
+    // Generate typed client
+    if config.generate_client {
+        let client_code = format(generator::client::generate_client(full_schema, config)?);
+        add("src/client.rs", client_code);
+    }
+
+    // Generate crate manifest
+    if config.generate_cargo_toml {
+        add(
+            "Cargo.toml",
+            generator::cargo::generate_cargo_toml(schema, config)?,
+        );
     }
 
     // Generate migrations
-    let migrations = generator.generate_migrations(schema, config)?;
-    let migrations_dir = config.output_dir.join("migrations");
-    fs::create_dir_all(&migrations_dir)?;
+    let migrations = if config.only_changed_tables {
+        generator::changed_table_migrations(
+            full_schema,
+            schema,
+            config,
+            generator,
+            previous_manifest,
+            &mut manifest,
+        )?
+    } else {
+        // Keep the table snapshot for a later only_changed_tables run
+        manifest.tables = previous_manifest.tables.clone();
+        generator.generate_migrations(schema, config)?
+    };
+    if let Some(code) = generator.generate_migrator(&migrations, config)? {
+        add(&config.migration_file("src/lib.rs"), format(code));
+    }
 
     for migration in migrations {
-        let migration_dir = migrations_dir.join(&migration.name);
-        fs::create_dir_all(&migration_dir)?;
+        match migration {
+            generator::MigrationFile::Sql {
+                name,
+                up_sql,
+                down_sql,
+            } => {
+                add(&config.migration_file(&format!("{}/up.sql", name)), up_sql);
+                add(
+                    &config.migration_file(&format!("{}/down.sql", name)),
+                    down_sql,
+                );
+            }
+            generator::MigrationFile::Rust { name, code } => {
+                // Rust migrations live in a migration crate's src directory
+                add(
+                    &config.migration_file(&format!("src/{}.rs", name)),
+                    format(code),
+                );
+            }
+        }
+    }
 
-        let up_path = migration_dir.join("up.sql");
-        let down_path = migration_dir.join("down.sql");
+    Ok(manifest)
+}
 
-        fs::write(up_path, migration.up_sql)?;
-        fs::write(down_path, migration.down_sql)?;
+/// Generates all code and passes each file to `sink` as `(relative path, content)`.
+///
+/// The files are those [`generate_to_map`] returns, each passed on as soon as
+/// it is generated: the schema file, the entity files in file name order, the
+/// module wiring, then the client, Cargo.toml and migrations. Paths use `/`
+/// separators on every platform and match the layout written by
+/// [`generate_all_code`].
+///
+/// With `continue_on_error` the failing types are only known once a whole run
+/// failed, so the files are buffered and passed on in path order instead.
+pub fn generate_into(
+    schema: &parser::ParsedSchema,
+    config: &Config,
+    generator: &dyn generator::CodeGenerator,
+    sink: &mut dyn FnMut(&str, &str),
+) -> anyhow::Result<()> {
+    if config.continue_on_error {
+        let (files, _) = generate_to_map(schema, config, generator)?;
+        for (path, content) in files {
+            let components: Vec<_> = path
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect();
+            sink(&components.join("/"), &content);
+        }
+        return Ok(());
     }
 
+    let prepared = generator::prepare_schema(schema, config)?;
+    let previous_manifest = manifest::Manifest::load(&config.output_dir);
+    generator::catch_panic(|| {
+        generate_prepared_files(
+            schema,
+            &prepared,
+            config,
+            generator,
+            &previous_manifest,
+            &mut |path, content| sink(path, &content),
+        )
+    })
+    .unwrap_or_else(|message| {
+        Err(anyhow::anyhow!(
+            "Code generation panicked: {}\nUse --continue-on-error to skip the types that fail",
+            message
+        ))
+    })?;
     Ok(())
 }
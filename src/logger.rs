@@ -0,0 +1,132 @@
+//! Progress and diagnostic output for the command-line tool.
+
+use std::cell::RefCell;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// How log messages are written
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines with status markers
+    #[default]
+    Text,
+    /// One JSON object per line, as `{"level":"info","msg":"..."}`
+    Json,
+}
+
+/// Logger that respects verbosity levels, `--quiet` and the log format.
+///
+/// Info and success messages go to stdout, everything else to stderr.
+/// Messages can also be copied to a log file with [`Logger::with_file`].
+pub struct Logger {
+    verbosity: u8,
+    quiet: bool,
+    format: LogFormat,
+    stdout: RefCell<Box<dyn Write>>,
+    stderr: RefCell<Box<dyn Write>>,
+    file: Option<RefCell<BufWriter<fs_err::File>>>,
+}
+
+impl Logger {
+    /// Logger writing to the process's stdout and stderr
+    pub fn new(verbosity: u8, quiet: bool, format: LogFormat) -> Self {
+        Self::with_writers(
+            verbosity,
+            quiet,
+            format,
+            Box::new(std::io::stdout()),
+            Box::new(std::io::stderr()),
+        )
+    }
+
+    /// Logger writing to the given streams in place of stdout and stderr
+    pub fn with_writers(
+        verbosity: u8,
+        quiet: bool,
+        format: LogFormat,
+        stdout: Box<dyn Write>,
+        stderr: Box<dyn Write>,
+    ) -> Self {
+        Self {
+            verbosity,
+            quiet,
+            format,
+            stdout: RefCell::new(stdout),
+            stderr: RefCell::new(stderr),
+            file: None,
+        }
+    }
+
+    /// Also write every emitted message to the file at `path`, replacing it.
+    ///
+    /// Writes are buffered and flushed when the logger is dropped.
+    pub fn with_file(mut self, path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = fs_err::File::create(path.as_ref())?;
+        self.file = Some(RefCell::new(BufWriter::new(file)));
+        Ok(self)
+    }
+
+    pub fn info(&self, message: &str) {
+        if self.verbosity >= 1 && !self.quiet {
+            self.emit(&self.stdout, "info", "", message);
+        }
+    }
+
+    pub fn debug(&self, message: &str) {
+        if self.verbosity >= 2 && !self.quiet {
+            self.emit(&self.stderr, "debug", "DEBUG: ", message);
+        }
+    }
+
+    pub fn trace(&self, message: &str) {
+        if self.verbosity >= 3 && !self.quiet {
+            self.emit(&self.stderr, "trace", "TRACE: ", message);
+        }
+    }
+
+    /// Informational note shown at every verbosity, such as a validation hint
+    pub fn note(&self, message: &str) {
+        if !self.quiet {
+            self.emit(&self.stdout, "info", "ℹ️  ", message);
+        }
+    }
+
+    pub fn success(&self, message: &str) {
+        if !self.quiet {
+            self.emit(&self.stdout, "success", "✅ ", message);
+        }
+    }
+
+    pub fn warning(&self, message: &str) {
+        if !self.quiet {
+            self.emit(&self.stderr, "warning", "⚠️  ", message);
+        }
+    }
+
+    /// Errors are written even with `--quiet`
+    pub fn error(&self, message: &str) {
+        self.emit(&self.stderr, "error", "❌ ", message);
+    }
+
+    fn emit(&self, stream: &RefCell<Box<dyn Write>>, level: &str, marker: &str, message: &str) {
+        let line = match self.format {
+            LogFormat::Text => format!("{}{}", marker, message),
+            LogFormat::Json => serde_json::json!({ "level": level, "msg": message }).to_string(),
+        };
+        // Like println!, but a closed pipe isn't worth a panic
+        let _ = writeln!(stream.borrow_mut(), "{}", line);
+        if let Some(file) = &self.file {
+            let _ = writeln!(file.borrow_mut(), "{}", line);
+        }
+    }
+}
+
+impl Drop for Logger {
+    fn drop(&mut self) {
+        if let Some(file) = &self.file {
+            let _ = file.borrow_mut().flush();
+        }
+        let _ = self.stdout.get_mut().flush();
+        let _ = self.stderr.get_mut().flush();
+    }
+}
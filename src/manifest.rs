@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use fs_err as fs;
+
+/// Record of the entity files written by the previous generation run.
+///
+/// Used by incremental mode to skip rewriting entity files whose generated
+/// content has not changed since the last run, and by `only_changed_tables`
+/// to diff table definitions against the previous run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Content hash of each generated entity file, keyed by file name
+    #[serde(default)]
+    pub entities: BTreeMap<String, String>,
+    /// Migration SQL of each table at the last run, keyed by table name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tables: BTreeMap<String, TableSnapshot>,
+}
+
+/// A table's create and drop SQL as of a generation run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableSnapshot {
+    pub up_sql: String,
+    pub down_sql: String,
+}
+
+impl Manifest {
+    /// Get the manifest path for a given output directory
+    pub fn path(output_dir: &Path) -> PathBuf {
+        output_dir.join(".graphql-codegen-rust.manifest.json")
+    }
+
+    /// Load the manifest from the output directory, or an empty one if absent or unreadable
+    pub fn load(output_dir: &Path) -> Self {
+        fs::read_to_string(Self::path(output_dir))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the manifest to the output directory
+    pub fn save(&self, output_dir: &Path) -> anyhow::Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(Self::path(output_dir), json)?;
+        Ok(())
+    }
+
+    /// Check whether an entity file is unchanged since the last run
+    pub fn is_unchanged(&self, filename: &str, code: &str, entity_path: &Path) -> bool {
+        entity_path.exists()
+            && self
+                .entities
+                .get(filename)
+                .is_some_and(|hash| *hash == content_hash(crate::generator::strip_banner(code)))
+    }
+
+    /// Record the hash of a generated entity file.
+    ///
+    /// The banner line is left out so its timestamp doesn't count as a change.
+    pub fn record(&mut self, filename: &str, code: &str) {
+        self.entities.insert(
+            filename.to_string(),
+            content_hash(crate::generator::strip_banner(code)),
+        );
+    }
+}
+
+/// Stable FNV-1a hash of generated content, hex encoded
+pub fn content_hash(content: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in content.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
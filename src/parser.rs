@@ -1,23 +1,81 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
+use crate::config::IntrospectionRequest;
 use crate::introspection::{Introspector, Schema as IntrospectionSchema};
 
-#[derive(Debug, Clone)]
+pub mod diff;
+
+#[derive(Debug, Clone, PartialEq)]
 #[allow(dead_code)]
 pub struct ParsedSchema {
     pub types: HashMap<String, ParsedType>,
     pub enums: HashMap<String, ParsedEnum>,
     pub scalars: Vec<String>,
+    pub query_root: Option<String>, // Name of the query root type, if known
+    pub mutation_root: Option<String>, // Name of the mutation root type, if known
+    pub subscription_root: Option<String>, // Name of the subscription root type, if known
+    pub input_types: HashMap<String, Vec<ParsedArgument>>, // Input objects and their fields
+}
+
+impl ParsedSchema {
+    /// Keep only the types and enums selected by `include`/`exclude`.
+    ///
+    /// An empty `include` keeps everything; `exclude` is applied afterwards.
+    /// Enums used by a kept type are kept with it unless excluded themselves.
+    /// Fields referencing a removed type or enum are dropped so no relation,
+    /// migration column or entity field points at it.
+    pub fn filter_types(&self, include: &[String], exclude: &[String]) -> ParsedSchema {
+        let keep = |name: &String| {
+            (include.is_empty() || include.contains(name)) && !exclude.contains(name)
+        };
+
+        let mut filtered = self.clone();
+        filtered.types.retain(|name, _| keep(name));
+        let used_enums: HashSet<String> = filtered
+            .types
+            .values()
+            .flat_map(|parsed_type| &parsed_type.fields)
+            .filter_map(|field| match &field.field_type {
+                FieldType::Enum(name) => Some(name.clone()),
+                _ => None,
+            })
+            .collect();
+        filtered
+            .enums
+            .retain(|name, _| keep(name) || (used_enums.contains(name) && !exclude.contains(name)));
+
+        let removed: Vec<&String> = self
+            .types
+            .keys()
+            .filter(|name| !filtered.types.contains_key(*name))
+            .chain(
+                self.enums
+                    .keys()
+                    .filter(|name| !filtered.enums.contains_key(*name)),
+            )
+            .collect();
+        for parsed_type in filtered.types.values_mut() {
+            parsed_type.fields.retain(|field| match &field.field_type {
+                FieldType::Reference(target) | FieldType::Enum(target) => {
+                    !removed.contains(&target)
+                }
+                FieldType::Scalar(_) => true,
+            });
+        }
+
+        filtered
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum TypeKind {
     Object,
     Interface,
     Union,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ParsedType {
     #[allow(dead_code)]
     pub name: String,
@@ -29,9 +87,40 @@ pub struct ParsedType {
     pub interfaces: Vec<String>, // For objects and interfaces: implemented interfaces
     #[allow(dead_code)]
     pub union_members: Vec<String>, // For unions: member types
+    pub primary_key: Option<String>, // Detected primary key field name
+}
+
+impl ParsedType {
+    /// Get the primary key field, detecting it when `primary_key` is unset
+    pub fn primary_key_field(&self) -> Option<&ParsedField> {
+        let key = self
+            .primary_key
+            .clone()
+            .or_else(|| detect_primary_key(&self.fields))?;
+        self.fields.iter().find(|f| f.name == key)
+    }
 }
 
-#[derive(Debug, Clone)]
+/// Detect the primary key among a type's fields.
+///
+/// Prefers a field literally named `id`, then the first non-null `ID` scalar
+/// field, then the first field.
+pub fn detect_primary_key(fields: &[ParsedField]) -> Option<String> {
+    fields
+        .iter()
+        .find(|f| f.name == "id")
+        .or_else(|| {
+            fields.iter().find(|f| {
+                !f.is_nullable
+                    && !f.is_list
+                    && matches!(&f.field_type, FieldType::Scalar(s) if s == "ID")
+            })
+        })
+        .or_else(|| fields.first())
+        .map(|f| f.name.clone())
+}
+
+#[derive(Debug, Clone, PartialEq)]
 #[allow(dead_code)]
 pub struct ParsedField {
     pub name: String,
@@ -39,22 +128,62 @@ pub struct ParsedField {
     pub description: Option<String>,
     pub is_nullable: bool,
     pub is_list: bool,
+    pub list_depth: usize, // Number of list wrappers; above 1 for nested lists
+    pub arguments: Vec<ParsedArgument>,
+    pub max_length: Option<u32>, // From an SDL `@maxLength(n)` directive
+    pub directives: Vec<ParsedDirective>,
+    pub default_value: Option<String>, // GraphQL literal, e.g. `false` or `"draft"`
+    pub deprecation: Option<Option<String>>, // Set when deprecated, with the reason if given
+    pub graphql_type: String,          // Original type notation, e.g. `[User!]!`
 }
 
-#[derive(Debug, Clone)]
+impl ParsedField {
+    /// Whether the field carries a directive such as `@unique`
+    pub fn has_directive(&self, name: &str) -> bool {
+        self.directives.iter().any(|d| d.name == name)
+    }
+}
+
+/// A directive applied to a field, e.g. `@maxLength(value: 255)`
+#[derive(Debug, Clone, PartialEq)]
+#[allow(dead_code)]
+pub struct ParsedDirective {
+    pub name: String,
+    pub arguments: Vec<(String, String)>, // Argument values in GraphQL notation
+}
+
+/// An argument accepted by a field, e.g. `country(code: ID!)`, or a field of
+/// an input object
+#[derive(Debug, Clone, PartialEq)]
+#[allow(dead_code)]
+pub struct ParsedArgument {
+    pub name: String,
+    pub field_type: FieldType,
+    pub is_nullable: bool,
+    pub is_list: bool,
+    pub graphql_type: String, // Original type notation, e.g. `[ID!]!`
+    pub default_value: Option<String>, // GraphQL literal, e.g. `10`
+}
+
+#[derive(Debug, Clone, PartialEq)]
 #[allow(dead_code)]
 pub enum FieldType {
+    /// A built-in or declared custom scalar
     Scalar(String),
+    /// An object, interface or union type
     Reference(String),
+    /// A declared enum; the parser resolves enum names given as references
     Enum(String),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[allow(dead_code)]
 pub struct ParsedEnum {
     pub name: String,
     pub values: Vec<String>,
     pub description: Option<String>,
+    pub discriminants: HashMap<String, i32>, // Explicit values from `@value(int: N)`
+    pub deprecations: HashMap<String, Option<String>>, // Deprecated values and their reasons
 }
 
 pub struct GraphQLParser {
@@ -76,14 +205,87 @@ impl GraphQLParser {
         }
     }
 
-    /// Parse schema from introspection
+    /// Use the given envelope and HTTP method for introspection queries
+    pub fn with_introspection(mut self, request: IntrospectionRequest) -> Self {
+        self.introspector = self.introspector.with_request(request);
+        self
+    }
+
+    /// Parser whose introspection requests follow the config's envelope and TLS settings
+    pub fn for_config(config: &crate::config::Config) -> anyhow::Result<Self> {
+        Ok(Self {
+            introspector: Introspector::for_config(config)?,
+        })
+    }
+
+    /// Parse schema from introspection, along with the errors the server
+    /// returned next to it
     pub async fn parse_from_introspection(
         &self,
         url: &str,
         headers: &HashMap<String, String>,
-    ) -> anyhow::Result<ParsedSchema> {
-        let schema = self.introspector.introspect_schema(url, headers).await?;
-        self.parse_schema(schema)
+    ) -> anyhow::Result<(ParsedSchema, Vec<String>)> {
+        self.parse_from_introspection_with_dump(url, headers, None)
+            .await
+    }
+
+    /// Parse schema from introspection, first writing the raw response JSON to
+    /// `dump_path` when given
+    pub async fn parse_from_introspection_with_dump(
+        &self,
+        url: &str,
+        headers: &HashMap<String, String>,
+        dump_path: Option<&Path>,
+    ) -> anyhow::Result<(ParsedSchema, Vec<String>)> {
+        crate::config::validate_endpoint_url(url)?;
+        let json = self
+            .introspector
+            .fetch_introspection_json(url, headers)
+            .await?;
+        if let Some(path) = dump_path {
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs_err::create_dir_all(parent)?;
+            }
+            fs_err::write(path, &json)?;
+        }
+        self.parse_from_introspection_json(&json)
+    }
+
+    /// Parse schema from an introspection response JSON string, such as one
+    /// saved with `dump_introspection`, along with the errors the response
+    /// carried next to a usable schema
+    pub fn parse_from_introspection_json(
+        &self,
+        json: &str,
+    ) -> anyhow::Result<(ParsedSchema, Vec<String>)> {
+        let (schema, warnings) = self.introspector.parse_introspection_json(json)?;
+        Ok((self.parse_schema(schema)?, warnings))
+    }
+
+    /// Parse schema from an introspection response JSON file, along with the
+    /// errors the response carried next to a usable schema
+    pub fn parse_from_introspection_file(
+        &self,
+        path: &Path,
+    ) -> anyhow::Result<(ParsedSchema, Vec<String>)> {
+        let json = fs_err::read_to_string(path)?;
+        self.parse_from_introspection_json(&json)
+    }
+
+    /// Parse schema from a file holding either an introspection response, when
+    /// it has a `.json` extension, or SDL, along with the errors an
+    /// introspection response carried next to a usable schema
+    pub fn parse_from_schema_file(
+        &self,
+        path: &Path,
+    ) -> anyhow::Result<(ParsedSchema, Vec<String>)> {
+        if path.extension().is_some_and(|ext| ext == "json") {
+            return self.parse_from_introspection_file(path);
+        }
+        Ok((
+            self.parse_from_sdl(&fs_err::read_to_string(path)?)?,
+            Vec::new(),
+        ))
     }
 
     /// Parse schema from SDL string
@@ -109,60 +311,101 @@ impl GraphQLParser {
         let mut types = HashMap::new();
         let mut enums = HashMap::new();
         let mut scalars = Vec::new();
+        let mut input_types = HashMap::new();
+        let mut schema_definition = None;
+        let mut extensions = Vec::new();
 
         for definition in document.definitions {
             match definition {
-                graphql_parser::schema::Definition::TypeDefinition(type_def) => {
-                    match type_def {
-                        graphql_parser::schema::TypeDefinition::Object(obj) => {
-                            if let Some(parsed_type) = self.parse_sdl_object_type(&obj) {
-                                types.insert(obj.name.to_string(), parsed_type);
-                            }
-                        }
-                        graphql_parser::schema::TypeDefinition::Enum(enum_def) => {
-                            if let Some(parsed_enum) = self.parse_sdl_enum_type(&enum_def) {
-                                enums.insert(enum_def.name.to_string(), parsed_enum);
-                            }
-                        }
-                        graphql_parser::schema::TypeDefinition::Scalar(scalar) => {
-                            scalars.push(scalar.name.to_string());
+                graphql_parser::schema::Definition::TypeDefinition(type_def) => match type_def {
+                    graphql_parser::schema::TypeDefinition::Object(obj) => {
+                        if let Some(parsed_type) = self.parse_sdl_object_type(&obj) {
+                            types.insert(obj.name.to_string(), parsed_type);
                         }
-                        graphql_parser::schema::TypeDefinition::Interface(interface) => {
-                            if let Some(parsed_type) = self.parse_sdl_interface_type(&interface) {
-                                types.insert(interface.name.to_string(), parsed_type);
-                            }
+                    }
+                    graphql_parser::schema::TypeDefinition::Enum(enum_def) => {
+                        if let Some(parsed_enum) = self.parse_sdl_enum_type(&enum_def) {
+                            enums.insert(enum_def.name.to_string(), parsed_enum);
                         }
-                        graphql_parser::schema::TypeDefinition::Union(union_def) => {
-                            if let Some(parsed_type) = self.parse_sdl_union_type(&union_def) {
-                                types.insert(union_def.name.to_string(), parsed_type);
-                            }
+                    }
+                    graphql_parser::schema::TypeDefinition::Scalar(scalar) => {
+                        scalars.push(scalar.name.to_string());
+                    }
+                    graphql_parser::schema::TypeDefinition::Interface(interface) => {
+                        if let Some(parsed_type) = self.parse_sdl_interface_type(&interface) {
+                            types.insert(interface.name.to_string(), parsed_type);
                         }
-                        graphql_parser::schema::TypeDefinition::InputObject(_) => {
-                            // Skip input objects for now - they don't affect ORM generation
+                    }
+                    graphql_parser::schema::TypeDefinition::Union(union_def) => {
+                        if let Some(parsed_type) = self.parse_sdl_union_type(&union_def) {
+                            types.insert(union_def.name.to_string(), parsed_type);
                         }
                     }
+                    graphql_parser::schema::TypeDefinition::InputObject(input) => {
+                        let fields = input
+                            .fields
+                            .iter()
+                            .filter_map(|field| self.parse_sdl_input_value(field))
+                            .collect();
+                        input_types.insert(input.name.to_string(), fields);
+                    }
+                },
+                graphql_parser::schema::Definition::SchemaDefinition(schema_def) => {
+                    schema_definition = Some(schema_def);
                 }
-                graphql_parser::schema::Definition::SchemaDefinition(_)
-                | graphql_parser::schema::Definition::DirectiveDefinition(_) => {
-                    // Skip schema and directive definitions for ORM generation
+                graphql_parser::schema::Definition::DirectiveDefinition(_) => {
+                    // Skip directive definitions for ORM generation
                 }
-                graphql_parser::schema::Definition::TypeExtension(_) => {
-                    // Skip type extensions for now
+                graphql_parser::schema::Definition::TypeExtension(extension) => {
+                    extensions.push(extension);
                 }
             }
         }
 
+        // An extension may come before the type it extends, so extensions are
+        // merged once every definition has been read
+        for extension in extensions {
+            self.merge_sdl_extension(extension, &mut types, &mut enums, &mut input_types)?;
+        }
+
+        // Without a schema definition, root types use their conventional names
+        let root = |declared: Option<&str>, conventional: &str| match &schema_definition {
+            Some(_) => declared.map(str::to_string),
+            None => types
+                .contains_key(conventional)
+                .then(|| conventional.to_string()),
+        };
+        let query_root = root(schema_definition.as_ref().and_then(|s| s.query), "Query");
+        let mutation_root = root(
+            schema_definition.as_ref().and_then(|s| s.mutation),
+            "Mutation",
+        );
+        let subscription_root = root(
+            schema_definition.as_ref().and_then(|s| s.subscription),
+            "Subscription",
+        );
+
+        resolve_scalar_references(&mut types, &scalars);
+        resolve_enum_references(&mut types, &enums);
+        apply_input_defaults(&mut types, &input_types);
+
         Ok(ParsedSchema {
             types,
             enums,
             scalars,
+            query_root,
+            mutation_root,
+            subscription_root,
+            input_types,
         })
     }
 
-    fn parse_schema(&self, schema: IntrospectionSchema) -> anyhow::Result<ParsedSchema> {
+    /// Parse an already-fetched introspection schema
+    pub fn parse_schema(&self, schema: IntrospectionSchema) -> anyhow::Result<ParsedSchema> {
         let mut types = HashMap::new();
         let mut enums = HashMap::new();
         let mut scalars = Vec::new();
+        let mut input_types = HashMap::new();
 
         for type_def in schema.types {
             if let Some(name) = &type_def.name {
@@ -206,17 +449,34 @@ impl GraphQLParser {
                     crate::introspection::TypeKind::Scalar => {
                         scalars.push(name.clone());
                     }
+                    crate::introspection::TypeKind::InputObject => {
+                        let fields = type_def
+                            .input_fields
+                            .iter()
+                            .flatten()
+                            .filter_map(|field| self.parse_input_value(field))
+                            .collect();
+                        input_types.insert(name.clone(), fields);
+                    }
                     _ => {
-                        // Skip input objects and other types for ORM generation
+                        // Skip other types for ORM generation
                     }
                 }
             }
         }
 
+        resolve_scalar_references(&mut types, &scalars);
+        resolve_enum_references(&mut types, &enums);
+        apply_input_defaults(&mut types, &input_types);
+
         Ok(ParsedSchema {
             types,
             enums,
             scalars,
+            query_root: schema.query_type.and_then(|t| t.name),
+            mutation_root: schema.mutation_type.and_then(|t| t.name),
+            subscription_root: schema.subscription_type.and_then(|t| t.name),
+            input_types,
         })
     }
 
@@ -241,6 +501,8 @@ impl GraphQLParser {
             .map(|interfaces| interfaces.iter().filter_map(|i| i.name.clone()).collect())
             .unwrap_or_default();
 
+        let primary_key = detect_primary_key(&fields);
+
         Some(ParsedType {
             name: name.clone(),
             kind: TypeKind::Object,
@@ -248,6 +510,7 @@ impl GraphQLParser {
             description: type_def.description.clone(),
             interfaces,
             union_members: vec![],
+            primary_key,
         })
     }
 
@@ -269,6 +532,8 @@ impl GraphQLParser {
             .map(|interfaces| interfaces.iter().filter_map(|i| i.name.clone()).collect())
             .unwrap_or_default();
 
+        let primary_key = detect_primary_key(&fields);
+
         Some(ParsedType {
             name: name.clone(),
             kind: TypeKind::Interface,
@@ -276,6 +541,7 @@ impl GraphQLParser {
             description: type_def.description.clone(),
             interfaces,
             union_members: vec![],
+            primary_key,
         })
     }
 
@@ -296,18 +562,51 @@ impl GraphQLParser {
             description: type_def.description.clone(),
             interfaces: vec![],
             union_members,
+            primary_key: None,
         })
     }
 
     fn parse_field(&self, field: &crate::introspection::Field) -> Option<ParsedField> {
-        let (field_type, is_nullable, is_list) = self.parse_type_ref(&field.type_)?;
+        let (field_type, is_nullable, list_depth) = self.parse_type_ref(&field.type_)?;
+        let arguments = field
+            .args
+            .iter()
+            .filter_map(|arg| self.parse_input_value(arg))
+            .collect();
 
         Some(ParsedField {
             name: field.name.clone(),
             field_type,
             description: field.description.clone(),
             is_nullable,
-            is_list,
+            is_list: list_depth > 0,
+            list_depth,
+            arguments,
+            max_length: None,
+            // Introspection doesn't expose directives applied to fields
+            directives: Vec::new(),
+            // Filled in from a matching input type's defaults
+            default_value: None,
+            deprecation: field
+                .is_deprecated
+                .then(|| field.deprecation_reason.clone()),
+            graphql_type: type_ref_notation(&field.type_)?,
+        })
+    }
+
+    /// An introspected argument or input object field
+    fn parse_input_value(
+        &self,
+        value: &crate::introspection::InputValue,
+    ) -> Option<ParsedArgument> {
+        let (field_type, is_nullable, list_depth) = self.parse_type_ref(&value.type_)?;
+        Some(ParsedArgument {
+            name: value.name.clone(),
+            field_type,
+            is_nullable,
+            is_list: list_depth > 0,
+            graphql_type: type_ref_notation(&value.type_)?,
+            default_value: value.default_value.clone(),
         })
     }
 
@@ -315,20 +614,20 @@ impl GraphQLParser {
     fn parse_type_ref(
         &self,
         type_ref: &crate::introspection::TypeRef,
-    ) -> Option<(FieldType, bool, bool)> {
+    ) -> Option<(FieldType, bool, usize)> {
         match type_ref.kind {
             Some(crate::introspection::TypeKind::NonNull) => {
                 if let Some(of_type) = &type_ref.of_type {
-                    let (field_type, _, is_list) = self.parse_type_ref(of_type)?;
-                    Some((field_type, false, is_list))
+                    let (field_type, _, list_depth) = self.parse_type_ref(of_type)?;
+                    Some((field_type, false, list_depth))
                 } else {
                     None
                 }
             }
             Some(crate::introspection::TypeKind::List) => {
                 if let Some(of_type) = &type_ref.of_type {
-                    let (field_type, is_nullable, _) = self.parse_type_ref(of_type)?;
-                    Some((field_type, is_nullable, true))
+                    let (field_type, is_nullable, list_depth) = self.parse_type_ref(of_type)?;
+                    Some((field_type, is_nullable, list_depth + 1))
                 } else {
                     None
                 }
@@ -344,7 +643,7 @@ impl GraphQLParser {
                             FieldType::Reference(name.clone())
                         }
                     };
-                    Some((field_type, true, false))
+                    Some((field_type, true, 0))
                 } else {
                     None
                 }
@@ -355,10 +654,14 @@ impl GraphQLParser {
     fn parse_enum_type(&self, type_def: &crate::introspection::Type) -> Option<ParsedEnum> {
         let name = type_def.name.as_ref()?;
         let mut values = Vec::new();
+        let mut deprecations = HashMap::new();
 
         if let Some(enum_values) = &type_def.enum_values {
             for value in enum_values {
                 values.push(value.name.clone());
+                if value.is_deprecated {
+                    deprecations.insert(value.name.clone(), value.deprecation_reason.clone());
+                }
             }
         }
 
@@ -366,6 +669,8 @@ impl GraphQLParser {
             name: name.clone(),
             values,
             description: type_def.description.clone(),
+            discriminants: HashMap::new(), // Introspection doesn't expose applied directives
+            deprecations,
         })
     }
 
@@ -388,6 +693,8 @@ impl GraphQLParser {
             .map(|name| name.to_string())
             .collect();
 
+        let primary_key = detect_primary_key(&fields);
+
         Some(ParsedType {
             name: obj.name.to_string(),
             kind: TypeKind::Object,
@@ -395,6 +702,7 @@ impl GraphQLParser {
             description: obj.description.as_ref().map(|s| s.to_string()),
             interfaces,
             union_members: vec![],
+            primary_key,
         })
     }
 
@@ -416,6 +724,8 @@ impl GraphQLParser {
             .map(|name| name.to_string())
             .collect();
 
+        let primary_key = detect_primary_key(&fields);
+
         Some(ParsedType {
             name: interface.name.to_string(),
             kind: TypeKind::Interface,
@@ -423,9 +733,138 @@ impl GraphQLParser {
             description: interface.description.as_ref().map(|s| s.to_string()),
             interfaces,
             union_members: vec![],
+            primary_key,
         })
     }
 
+    /// Merge an `extend` definition into the type it extends, or add it as
+    /// the type itself when the schema never defines the base
+    fn merge_sdl_extension<'a>(
+        &self,
+        extension: graphql_parser::schema::TypeExtension<'a, &'a str>,
+        types: &mut HashMap<String, ParsedType>,
+        enums: &mut HashMap<String, ParsedEnum>,
+        input_types: &mut HashMap<String, Vec<ParsedArgument>>,
+    ) -> anyhow::Result<()> {
+        use graphql_parser::schema::{
+            EnumType, InterfaceType, ObjectType, TypeExtension, UnionType,
+        };
+
+        let parsed_type = match extension {
+            TypeExtension::Object(ext) => self.parse_sdl_object_type(&ObjectType {
+                position: ext.position,
+                description: None,
+                name: ext.name,
+                implements_interfaces: ext.implements_interfaces,
+                directives: ext.directives,
+                fields: ext.fields,
+            }),
+            TypeExtension::Interface(ext) => self.parse_sdl_interface_type(&InterfaceType {
+                position: ext.position,
+                description: None,
+                name: ext.name,
+                implements_interfaces: ext.implements_interfaces,
+                directives: ext.directives,
+                fields: ext.fields,
+            }),
+            TypeExtension::Union(ext) => self.parse_sdl_union_type(&UnionType {
+                position: ext.position,
+                description: None,
+                name: ext.name,
+                directives: ext.directives,
+                types: ext.types,
+            }),
+            TypeExtension::Enum(ext) => {
+                let Some(extension) = self.parse_sdl_enum_type(&EnumType {
+                    position: ext.position,
+                    description: None,
+                    name: ext.name,
+                    directives: ext.directives,
+                    values: ext.values,
+                }) else {
+                    return Ok(());
+                };
+                match enums.get_mut(&extension.name) {
+                    Some(base) => {
+                        for value in extension.values {
+                            if base.values.contains(&value) {
+                                anyhow::bail!(
+                                    "Enum value '{}.{}' is defined more than once",
+                                    base.name,
+                                    value
+                                );
+                            }
+                            base.values.push(value);
+                        }
+                        base.discriminants.extend(extension.discriminants);
+                        base.deprecations.extend(extension.deprecations);
+                    }
+                    None => {
+                        enums.insert(extension.name.clone(), extension);
+                    }
+                }
+                return Ok(());
+            }
+            TypeExtension::InputObject(ext) => {
+                let fields = ext
+                    .fields
+                    .iter()
+                    .filter_map(|field| self.parse_sdl_input_value(field));
+                input_types
+                    .entry(ext.name.to_string())
+                    .or_default()
+                    .extend(fields);
+                return Ok(());
+            }
+            // Scalar extensions only add directives
+            TypeExtension::Scalar(_) => return Ok(()),
+        };
+        let Some(extension) = parsed_type else {
+            return Ok(());
+        };
+
+        let Some(base) = types.get_mut(&extension.name) else {
+            types.insert(extension.name.clone(), extension);
+            return Ok(());
+        };
+        if base.kind != extension.kind {
+            anyhow::bail!(
+                "'extend' of type '{}' doesn't match its {:?} definition",
+                base.name,
+                base.kind
+            );
+        }
+        for field in extension.fields {
+            if base
+                .fields
+                .iter()
+                .any(|existing| existing.name == field.name)
+            {
+                anyhow::bail!(
+                    "Field '{}.{}' is defined more than once",
+                    base.name,
+                    field.name
+                );
+            }
+            base.fields.push(field);
+        }
+        for name in extension.interfaces {
+            if !base.interfaces.contains(&name) {
+                base.interfaces.push(name);
+            }
+        }
+        for name in extension.union_members {
+            if !base.union_members.contains(&name) {
+                base.union_members.push(name);
+            }
+        }
+        // A key field may only arrive with the extension
+        if base.primary_key.is_none() {
+            base.primary_key = detect_primary_key(&base.fields);
+        }
+        Ok(())
+    }
+
     fn parse_sdl_union_type<'a>(
         &self,
         union_def: &graphql_parser::schema::UnionType<'a, &'a str>,
@@ -444,6 +883,7 @@ impl GraphQLParser {
             description: union_def.description.as_ref().map(|s| s.to_string()),
             interfaces: vec![],
             union_members,
+            primary_key: None,
         })
     }
 
@@ -456,11 +896,23 @@ impl GraphQLParser {
             .iter()
             .map(|value| value.name.to_string())
             .collect();
+        let discriminants = enum_def
+            .values
+            .iter()
+            .filter_map(|value| Some((value.name.to_string(), sdl_int_value(&value.directives)?)))
+            .collect();
+        let deprecations = enum_def
+            .values
+            .iter()
+            .filter_map(|value| Some((value.name.to_string(), sdl_deprecation(&value.directives)?)))
+            .collect();
 
         Some(ParsedEnum {
             name: enum_def.name.to_string(),
             values,
             description: enum_def.description.as_ref().map(|s| s.to_string()),
+            discriminants,
+            deprecations,
         })
     }
 
@@ -468,14 +920,53 @@ impl GraphQLParser {
         &self,
         field: &graphql_parser::schema::Field<'a, &'a str>,
     ) -> Option<ParsedField> {
-        let (field_type, is_nullable, is_list) = self.parse_sdl_type(&field.field_type)?;
+        let (field_type, is_nullable, list_depth) = self.parse_sdl_type(&field.field_type)?;
+        let arguments = field
+            .arguments
+            .iter()
+            .filter_map(|arg| self.parse_sdl_input_value(arg))
+            .collect();
 
         Some(ParsedField {
             name: field.name.to_string(),
             field_type,
             description: field.description.as_ref().map(|s| s.to_string()),
             is_nullable,
-            is_list,
+            is_list: list_depth > 0,
+            list_depth,
+            arguments,
+            max_length: sdl_max_length(&field.directives),
+            default_value: sdl_default_value(&field.directives),
+            deprecation: sdl_deprecation(&field.directives),
+            graphql_type: field.field_type.to_string(),
+            directives: field
+                .directives
+                .iter()
+                .map(|directive| ParsedDirective {
+                    name: directive.name.to_string(),
+                    arguments: directive
+                        .arguments
+                        .iter()
+                        .map(|(name, value)| (name.to_string(), value.to_string()))
+                        .collect(),
+                })
+                .collect(),
+        })
+    }
+
+    /// An SDL argument or input object field
+    fn parse_sdl_input_value<'a>(
+        &self,
+        value: &graphql_parser::schema::InputValue<'a, &'a str>,
+    ) -> Option<ParsedArgument> {
+        let (field_type, is_nullable, list_depth) = self.parse_sdl_type(&value.value_type)?;
+        Some(ParsedArgument {
+            name: value.name.to_string(),
+            field_type,
+            is_nullable,
+            is_list: list_depth > 0,
+            graphql_type: value.value_type.to_string(),
+            default_value: value.default_value.as_ref().map(|v| v.to_string()),
         })
     }
 
@@ -483,7 +974,7 @@ impl GraphQLParser {
     fn parse_sdl_type<'a>(
         &self,
         field_type: &graphql_parser::schema::Type<'a, &'a str>,
-    ) -> Option<(FieldType, bool, bool)> {
+    ) -> Option<(FieldType, bool, usize)> {
         match field_type {
             graphql_parser::schema::Type::NamedType(name) => {
                 let field_type = match *name {
@@ -492,18 +983,18 @@ impl GraphQLParser {
                     }
                     _ => FieldType::Reference(name.to_string()),
                 };
-                Some((field_type, true, false)) // Named types are nullable by default
+                Some((field_type, true, 0)) // Named types are nullable by default
             }
             graphql_parser::schema::Type::ListType(inner_type) => {
-                if let Some((inner_field_type, _, _)) = self.parse_sdl_type(inner_type) {
-                    Some((inner_field_type, true, true))
+                if let Some((inner_field_type, _, list_depth)) = self.parse_sdl_type(inner_type) {
+                    Some((inner_field_type, true, list_depth + 1))
                 } else {
                     None
                 }
             }
             graphql_parser::schema::Type::NonNullType(inner_type) => {
-                if let Some((inner_field_type, _, is_list)) = self.parse_sdl_type(inner_type) {
-                    Some((inner_field_type, false, is_list))
+                if let Some((inner_field_type, _, list_depth)) = self.parse_sdl_type(inner_type) {
+                    Some((inner_field_type, false, list_depth))
                 } else {
                     None
                 }
@@ -511,3 +1002,135 @@ impl GraphQLParser {
         }
     }
 }
+
+/// Render an introspection type reference in GraphQL notation, e.g. `[ID!]!`
+fn type_ref_notation(type_ref: &crate::introspection::TypeRef) -> Option<String> {
+    match type_ref.kind {
+        Some(crate::introspection::TypeKind::NonNull) => Some(format!(
+            "{}!",
+            type_ref_notation(type_ref.of_type.as_ref()?)?
+        )),
+        Some(crate::introspection::TypeKind::List) => Some(format!(
+            "[{}]",
+            type_ref_notation(type_ref.of_type.as_ref()?)?
+        )),
+        _ => type_ref.name.clone(),
+    }
+}
+
+/// Read the length from a `@maxLength(n)` directive, if present
+fn sdl_max_length<'a>(
+    directives: &[graphql_parser::schema::Directive<'a, &'a str>],
+) -> Option<u32> {
+    directives
+        .iter()
+        .find(|directive| directive.name == "maxLength")?
+        .arguments
+        .iter()
+        .find_map(|(_, value)| match value {
+            graphql_parser::schema::Value::Int(n) => n.as_i64().and_then(|n| u32::try_from(n).ok()),
+            _ => None,
+        })
+}
+
+/// Value from a `@default(value: ...)` directive, in GraphQL notation
+fn sdl_default_value<'a>(
+    directives: &[graphql_parser::schema::Directive<'a, &'a str>],
+) -> Option<String> {
+    directives
+        .iter()
+        .find(|directive| directive.name == "default")?
+        .arguments
+        .iter()
+        .find_map(|(name, value)| (*name == "value").then(|| value.to_string()))
+}
+
+/// Integer from an `@value(int: N)` directive on an enum value
+fn sdl_int_value<'a>(directives: &[graphql_parser::schema::Directive<'a, &'a str>]) -> Option<i32> {
+    directives
+        .iter()
+        .find(|directive| directive.name == "value")?
+        .arguments
+        .iter()
+        .find_map(|(name, value)| match (*name, value) {
+            ("int", graphql_parser::schema::Value::Int(n)) => {
+                n.as_i64().and_then(|n| i32::try_from(n).ok())
+            }
+            _ => None,
+        })
+}
+
+/// Reason from a `@deprecated` directive, `Some(None)` when it gives none
+fn sdl_deprecation<'a>(
+    directives: &[graphql_parser::schema::Directive<'a, &'a str>],
+) -> Option<Option<String>> {
+    let directive = directives
+        .iter()
+        .find(|directive| directive.name == "deprecated")?;
+    Some(
+        directive
+            .arguments
+            .iter()
+            .find_map(|(name, value)| match (*name, value) {
+                ("reason", graphql_parser::schema::Value::String(reason)) => Some(reason.clone()),
+                _ => None,
+            }),
+    )
+}
+
+/// Mark fields pointing at declared custom scalars as scalar fields
+fn resolve_scalar_references(types: &mut HashMap<String, ParsedType>, scalars: &[String]) {
+    for parsed_type in types.values_mut() {
+        for field in &mut parsed_type.fields {
+            if let FieldType::Reference(name) = &field.field_type {
+                if scalars.contains(name) {
+                    field.field_type = FieldType::Scalar(name.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Mark fields pointing at declared enums as enum fields
+fn resolve_enum_references(
+    types: &mut HashMap<String, ParsedType>,
+    enums: &HashMap<String, ParsedEnum>,
+) {
+    for parsed_type in types.values_mut() {
+        for field in &mut parsed_type.fields {
+            if let FieldType::Reference(name) = &field.field_type {
+                if enums.contains_key(name) {
+                    field.field_type = FieldType::Enum(name.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Give fields the default values of the matching field of the type's
+/// `Create<Type>Input` or `<Type>Input`, unless the field has its own
+fn apply_input_defaults(
+    types: &mut HashMap<String, ParsedType>,
+    input_types: &HashMap<String, Vec<ParsedArgument>>,
+) {
+    for (type_name, parsed_type) in types.iter_mut() {
+        let inputs: Vec<&Vec<ParsedArgument>> = [
+            format!("Create{}Input", type_name),
+            format!("{}Input", type_name),
+        ]
+        .iter()
+        .filter_map(|input_name| input_types.get(input_name))
+        .collect();
+        for field in &mut parsed_type.fields {
+            if field.default_value.is_none() {
+                field.default_value = inputs.iter().find_map(|fields| {
+                    fields
+                        .iter()
+                        .find(|input| input.name == field.name)?
+                        .default_value
+                        .clone()
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//! Differences between two parsed schemas, such as before and after an API
+//! upgrade.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use super::{ParsedArgument, ParsedField, ParsedSchema, TypeKind};
+
+/// Whether something was added, removed or changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A single difference between two schemas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaChange {
+    pub kind: ChangeKind,
+    /// What changed, e.g. `type User`, `field User.email` or `enum value Role.ADMIN`
+    pub subject: String,
+    /// The old and new definitions for changes, or the definition for
+    /// additions and removals
+    pub detail: Option<String>,
+    /// Whether code or queries written against the old schema can break
+    pub breaking: bool,
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = match self.kind {
+            ChangeKind::Added => "+",
+            ChangeKind::Removed => "-",
+            ChangeKind::Changed => "~",
+        };
+        write!(f, "{} {}", marker, self.subject)?;
+        if let Some(detail) = &self.detail {
+            write!(f, ": {}", detail)?;
+        }
+        if self.breaking {
+            write!(f, " (breaking)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Compare two schemas by type, field, argument, union member, input type,
+/// enum and enum value.
+///
+/// Removals and changed type kinds are breaking. Additions aren't, except for
+/// required arguments and input fields. A changed type is breaking unless it
+/// only changes nullability the safe way: output fields may become non-null,
+/// and arguments and input fields nullable. Changes are sorted by subject so
+/// the report is stable.
+pub fn diff_schemas(old: &ParsedSchema, new: &ParsedSchema) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+
+    let type_names: BTreeSet<&String> = old.types.keys().chain(new.types.keys()).collect();
+    for name in type_names {
+        match (old.types.get(name), new.types.get(name)) {
+            (Some(_), None) => changes.push(SchemaChange {
+                kind: ChangeKind::Removed,
+                subject: format!("type {}", name),
+                detail: None,
+                breaking: true,
+            }),
+            (None, Some(_)) => changes.push(SchemaChange {
+                kind: ChangeKind::Added,
+                subject: format!("type {}", name),
+                detail: None,
+                breaking: false,
+            }),
+            (Some(old_type), Some(new_type)) => {
+                if old_type.kind != new_type.kind {
+                    changes.push(SchemaChange {
+                        kind: ChangeKind::Changed,
+                        subject: format!("type {}", name),
+                        detail: Some(format!(
+                            "{} \u{2192} {}",
+                            kind_name(&old_type.kind),
+                            kind_name(&new_type.kind)
+                        )),
+                        breaking: true,
+                    });
+                }
+                diff_fields(name, &old_type.fields, &new_type.fields, &mut changes);
+                diff_union_members(
+                    name,
+                    &old_type.union_members,
+                    &new_type.union_members,
+                    &mut changes,
+                );
+            }
+            (None, None) => {}
+        }
+    }
+
+    let input_names: BTreeSet<&String> = old
+        .input_types
+        .keys()
+        .chain(new.input_types.keys())
+        .collect();
+    for name in input_names {
+        match (old.input_types.get(name), new.input_types.get(name)) {
+            (Some(_), None) => changes.push(SchemaChange {
+                kind: ChangeKind::Removed,
+                subject: format!("input {}", name),
+                detail: None,
+                breaking: true,
+            }),
+            (None, Some(_)) => changes.push(SchemaChange {
+                kind: ChangeKind::Added,
+                subject: format!("input {}", name),
+                detail: None,
+                breaking: false,
+            }),
+            (Some(old_fields), Some(new_fields)) => diff_input_values(
+                old_fields,
+                new_fields,
+                |field| format!("input field {}.{}", name, field),
+                &mut changes,
+            ),
+            (None, None) => {}
+        }
+    }
+
+    let enum_names: BTreeSet<&String> = old.enums.keys().chain(new.enums.keys()).collect();
+    for name in enum_names {
+        match (old.enums.get(name), new.enums.get(name)) {
+            (Some(_), None) => changes.push(SchemaChange {
+                kind: ChangeKind::Removed,
+                subject: format!("enum {}", name),
+                detail: None,
+                breaking: true,
+            }),
+            (None, Some(_)) => changes.push(SchemaChange {
+                kind: ChangeKind::Added,
+                subject: format!("enum {}", name),
+                detail: None,
+                breaking: false,
+            }),
+            (Some(old_enum), Some(new_enum)) => {
+                for value in &old_enum.values {
+                    if !new_enum.values.contains(value) {
+                        changes.push(SchemaChange {
+                            kind: ChangeKind::Removed,
+                            subject: format!("enum value {}.{}", name, value),
+                            detail: None,
+                            breaking: true,
+                        });
+                    }
+                }
+                for value in &new_enum.values {
+                    if !old_enum.values.contains(value) {
+                        changes.push(SchemaChange {
+                            kind: ChangeKind::Added,
+                            subject: format!("enum value {}.{}", name, value),
+                            detail: None,
+                            breaking: false,
+                        });
+                    }
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    changes.sort_by(|a, b| a.subject.cmp(&b.subject).then(a.kind.cmp(&b.kind)));
+    changes
+}
+
+/// Field additions, removals and type changes of a type present in both
+/// schemas, along with the changes to each remaining field's arguments
+fn diff_fields(
+    type_name: &str,
+    old_fields: &[ParsedField],
+    new_fields: &[ParsedField],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_field in old_fields {
+        let subject = format!("field {}.{}", type_name, old_field.name);
+        match new_fields.iter().find(|f| f.name == old_field.name) {
+            None => changes.push(SchemaChange {
+                kind: ChangeKind::Removed,
+                subject,
+                detail: Some(old_field.graphql_type.clone()),
+                breaking: true,
+            }),
+            Some(new_field) => {
+                let (old_type, new_type) = (&old_field.graphql_type, &new_field.graphql_type);
+                if old_type != new_type {
+                    changes.push(SchemaChange {
+                        kind: ChangeKind::Changed,
+                        subject,
+                        detail: Some(format!("{} \u{2192} {}", old_type, new_type)),
+                        // Clients already handle every value of the old type
+                        breaking: !only_drops_non_null(new_type, old_type),
+                    });
+                }
+                diff_input_values(
+                    &old_field.arguments,
+                    &new_field.arguments,
+                    |argument| format!("argument {}.{}({})", type_name, old_field.name, argument),
+                    changes,
+                );
+            }
+        }
+    }
+    for new_field in new_fields {
+        if !old_fields.iter().any(|f| f.name == new_field.name) {
+            changes.push(SchemaChange {
+                kind: ChangeKind::Added,
+                subject: format!("field {}.{}", type_name, new_field.name),
+                detail: Some(new_field.graphql_type.clone()),
+                breaking: false,
+            });
+        }
+    }
+}
+
+/// Additions, removals and type changes of a field's arguments or an input
+/// object's fields, named in subjects by `subject`.
+///
+/// Requests sent against the old schema break when a value they pass goes
+/// away or narrows its type, or when a new one is required.
+fn diff_input_values(
+    old_values: &[ParsedArgument],
+    new_values: &[ParsedArgument],
+    subject: impl Fn(&str) -> String,
+    changes: &mut Vec<SchemaChange>,
+) {
+    for old_value in old_values {
+        match new_values.iter().find(|v| v.name == old_value.name) {
+            None => changes.push(SchemaChange {
+                kind: ChangeKind::Removed,
+                subject: subject(&old_value.name),
+                detail: Some(old_value.graphql_type.clone()),
+                breaking: true,
+            }),
+            Some(new_value) if new_value.graphql_type != old_value.graphql_type => {
+                changes.push(SchemaChange {
+                    kind: ChangeKind::Changed,
+                    subject: subject(&old_value.name),
+                    detail: Some(format!(
+                        "{} \u{2192} {}",
+                        old_value.graphql_type, new_value.graphql_type
+                    )),
+                    breaking: !only_drops_non_null(
+                        &old_value.graphql_type,
+                        &new_value.graphql_type,
+                    ),
+                });
+            }
+            Some(_) => {}
+        }
+    }
+    for new_value in new_values {
+        if !old_values.iter().any(|v| v.name == new_value.name) {
+            changes.push(SchemaChange {
+                kind: ChangeKind::Added,
+                subject: subject(&new_value.name),
+                detail: Some(new_value.graphql_type.clone()),
+                breaking: !new_value.is_nullable && new_value.default_value.is_none(),
+            });
+        }
+    }
+}
+
+/// Members added to or removed from a union present in both schemas
+fn diff_union_members(
+    union_name: &str,
+    old_members: &[String],
+    new_members: &[String],
+    changes: &mut Vec<SchemaChange>,
+) {
+    for member in old_members.iter().filter(|m| !new_members.contains(m)) {
+        changes.push(SchemaChange {
+            kind: ChangeKind::Removed,
+            subject: format!("union member {}.{}", union_name, member),
+            detail: None,
+            breaking: true,
+        });
+    }
+    for member in new_members.iter().filter(|m| !old_members.contains(m)) {
+        changes.push(SchemaChange {
+            kind: ChangeKind::Added,
+            subject: format!("union member {}.{}", union_name, member),
+            detail: None,
+            breaking: false,
+        });
+    }
+}
+
+/// Whether type notation `to` is `from` with only some of its `!` markers
+/// dropped, such as `[String!]!` to `[String]`, so it accepts every value
+/// `from` does
+fn only_drops_non_null(from: &str, to: &str) -> bool {
+    let mut to = to.chars().peekable();
+    for c in from.chars() {
+        if to.peek() == Some(&c) {
+            to.next();
+        } else if c != '!' {
+            return false;
+        }
+    }
+    to.next().is_none()
+}
+
+fn kind_name(kind: &TypeKind) -> &'static str {
+    match kind {
+        TypeKind::Object => "object",
+        TypeKind::Interface => "interface",
+        TypeKind::Union => "union",
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+/// Resolves secret references in the endpoint URL and header values.
+///
+/// Implement this to fetch tokens from Vault, AWS Secrets Manager or a similar
+/// store. Only references whose scheme is listed in `Config::secret_schemes`
+/// (e.g. `vault` for `vault://secret/api#token`) are passed to the resolver.
+pub trait SecretResolver: Send + Sync {
+    /// Resolve a reference such as `vault://secret/api#token` to the secret value
+    fn resolve(&self, reference: &str) -> anyhow::Result<String>;
+}
+
+/// Resolver that leaves references unchanged
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopSecretResolver;
+
+impl SecretResolver for NoopSecretResolver {
+    fn resolve(&self, reference: &str) -> anyhow::Result<String> {
+        Ok(reference.to_string())
+    }
+}
+
+/// Check whether a value is a reference using one of the given schemes
+pub fn is_secret_reference(value: &str, schemes: &[String]) -> bool {
+    value
+        .split_once("://")
+        .is_some_and(|(scheme, _)| schemes.iter().any(|s| s == scheme))
+}
+
+/// Resolve every whitespace-separated reference in a value.
+///
+/// This keeps prefixes intact, so `Bearer vault://api#token` becomes
+/// `Bearer <token>`.
+pub fn resolve_value(
+    value: &str,
+    schemes: &[String],
+    resolver: &dyn SecretResolver,
+) -> anyhow::Result<String> {
+    if !value
+        .split_whitespace()
+        .any(|word| is_secret_reference(word, schemes))
+    {
+        return Ok(value.to_string());
+    }
+
+    let words = value
+        .split_whitespace()
+        .map(|word| {
+            if is_secret_reference(word, schemes) {
+                resolver.resolve(word)
+            } else {
+                Ok(word.to_string())
+            }
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(words.join(" "))
+}
+
+/// Resolve secret references in header values
+pub fn resolve_headers(
+    headers: &HashMap<String, String>,
+    schemes: &[String],
+    resolver: &dyn SecretResolver,
+) -> anyhow::Result<HashMap<String, String>> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = resolve_value(value, schemes, resolver).map_err(|e| {
+                anyhow::anyhow!("Failed to resolve secret for header '{}': {}", name, e)
+            })?;
+            Ok((name.clone(), value))
+        })
+        .collect()
+}
@@ -0,0 +1,369 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use crate::config::Config;
+use crate::generator::{
+    RUST_PRELUDE_TYPES, datetime_rust_type, entity_table_name, enum_variant_name, is_int64_scalar,
+    is_json_scalar, native_enum_name, to_snake_case,
+};
+use crate::parser::{FieldType, ParsedSchema, ParsedType, TypeKind};
+
+/// How badly a schema construct is affected by code generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Generated code is faithful but may not be what the user expects
+    Info,
+    /// Generated code is lossy or relies on a fallback
+    Warning,
+    /// The construct cannot be generated at all
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single finding from schema validation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub type_name: String,
+    pub field_name: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.field_name {
+            Some(field_name) => write!(
+                f,
+                "{}: {}.{}: {}",
+                self.severity, self.type_name, field_name, self.message
+            ),
+            None => write!(f, "{}: {}: {}", self.severity, self.type_name, self.message),
+        }
+    }
+}
+
+/// Scan a parsed schema for constructs the generators can't faithfully represent.
+///
+/// Diagnostics are sorted by type and field name so the report is stable.
+pub fn validate_schema(schema: &ParsedSchema, config: &Config) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for (type_name, parsed_type) in &schema.types {
+        if matches!(parsed_type.kind, TypeKind::Object) {
+            validate_object_type(schema, type_name, parsed_type, &mut diagnostics);
+        }
+    }
+
+    // Generated types named like prelude types shadow them in their module
+    for name in find_prelude_shadows(schema) {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            message: format!("name shadows the Rust prelude type '{}'", name),
+            type_name: name,
+            field_name: None,
+        });
+    }
+    for name in schema.scalars.iter().filter(|name| is_prelude_type(name)) {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            type_name: name.clone(),
+            field_name: None,
+            message: format!(
+                "custom scalar shares its name with the Rust prelude type '{}'",
+                name
+            ),
+        });
+    }
+
+    // Both names would be written to the same table and entity module
+    for (table_name, type_names) in find_table_collisions(schema, config) {
+        for type_name in &type_names {
+            let others: Vec<&str> = type_names
+                .iter()
+                .filter(|other| *other != type_name)
+                .map(String::as_str)
+                .collect();
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                type_name: type_name.clone(),
+                field_name: None,
+                message: format!(
+                    "maps to table '{}' together with {}",
+                    table_name,
+                    others.join(", ")
+                ),
+            });
+        }
+    }
+
+    // Both values would become the same enum variant
+    for (enum_name, variant, values) in find_variant_collisions(schema, config) {
+        diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            type_name: enum_name,
+            field_name: None,
+            message: format!(
+                "values {} all become the variant '{}'",
+                values.join(", "),
+                variant
+            ),
+        });
+    }
+
+    diagnostics.sort_by(|a, b| {
+        (&a.type_name, &a.field_name, &a.message).cmp(&(&b.type_name, &b.field_name, &b.message))
+    });
+    diagnostics
+}
+
+/// Enum values whose Rust variant names collide, as `(enum, variant, values)`
+/// sorted by enum and variant name.
+///
+/// Sanitizing makes `text/plain` and `text_plain` both `text_plain`, and the
+/// `prefix_enum_values` prefix can turn `1` into an existing `V1`.
+pub fn find_variant_collisions(
+    schema: &ParsedSchema,
+    config: &Config,
+) -> Vec<(String, String, Vec<String>)> {
+    let mut collisions = Vec::new();
+    for (enum_name, parsed_enum) in &schema.enums {
+        let mut variants: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for value in &parsed_enum.values {
+            variants
+                .entry(enum_variant_name(value, &config.prefix_enum_values))
+                .or_default()
+                .push(format!("'{}'", value));
+        }
+        for (variant, values) in variants {
+            if values.len() > 1 {
+                collisions.push((enum_name.clone(), variant, values));
+            }
+        }
+    }
+    collisions.sort();
+    collisions
+}
+
+/// Types and enums named like a Rust prelude type, such as `String` or
+/// `Option`, in sorted order.
+///
+/// Their generated structs and enums would shadow the prelude type in the
+/// modules that use it.
+pub fn find_prelude_shadows(schema: &ParsedSchema) -> Vec<String> {
+    let mut names: Vec<String> = schema
+        .types
+        .keys()
+        .chain(schema.enums.keys())
+        .filter(|name| is_prelude_type(name))
+        .cloned()
+        .collect();
+    names.sort();
+    names
+}
+
+/// Object types and enums whose table, enum type or module names collide,
+/// keyed by that name.
+///
+/// Table names follow `pluralize_tables`, so a `User` type and a `Users` enum
+/// collide when it's on. Each entry lists the colliding GraphQL names in sorted
+/// order, and types colliding on several names are reported once.
+pub fn find_table_collisions(schema: &ParsedSchema, config: &Config) -> Vec<(String, Vec<String>)> {
+    let mut tables: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let object_types = schema
+        .types
+        .iter()
+        .filter(|(_, t)| matches!(t.kind, TypeKind::Object))
+        .map(|(name, _)| name);
+    for name in object_types {
+        for table_name in [to_snake_case(name), entity_table_name(name, config)] {
+            tables.entry(table_name).or_default().insert(name.clone());
+        }
+    }
+    for name in schema.enums.keys() {
+        tables
+            .entry(native_enum_name(name))
+            .or_default()
+            .insert(name.clone());
+    }
+
+    let mut reported = BTreeSet::new();
+    tables
+        .into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .map(|(table_name, names)| (table_name, names.into_iter().collect::<Vec<_>>()))
+        .filter(|(_, names)| reported.insert(names.clone()))
+        .collect()
+}
+
+/// Custom scalars without a configured or built-in Rust type, sorted by name
+pub fn unmapped_scalars(schema: &ParsedSchema, config: &Config) -> Vec<String> {
+    let mut scalars: Vec<String> = schema
+        .scalars
+        .iter()
+        .filter(|name| !matches!(name.as_str(), "ID" | "String" | "Int" | "Float" | "Boolean"))
+        .filter(|name| {
+            !config.type_mappings.contains_key(*name)
+                && !config.scalar_mappings.contains_key(*name)
+                && datetime_rust_type(name, &config.datetime_crate, &config.db).is_none()
+                && !is_int64_scalar(name)
+                && !is_json_scalar(name)
+        })
+        .cloned()
+        .collect();
+    scalars.sort();
+    scalars.dedup();
+    scalars
+}
+
+/// Summary of deprecated fields, grouped by type and sorted by name.
+///
+/// Each type gets a heading line followed by one indented line per field,
+/// giving its deprecation reason when the schema states one.
+pub fn deprecation_report(schema: &ParsedSchema) -> Vec<String> {
+    let mut types: Vec<_> = schema.types.iter().collect();
+    types.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut lines = Vec::new();
+    for (type_name, parsed_type) in types {
+        let mut deprecated: Vec<_> = parsed_type
+            .fields
+            .iter()
+            .filter_map(|field| Some((&field.name, field.deprecation.as_ref()?)))
+            .collect();
+        if deprecated.is_empty() {
+            continue;
+        }
+        deprecated.sort_by(|a, b| a.0.cmp(b.0));
+
+        lines.push(format!(
+            "Type '{}' has {} deprecated field{}:",
+            type_name,
+            deprecated.len(),
+            if deprecated.len() == 1 { "" } else { "s" }
+        ));
+        for (field_name, reason) in deprecated {
+            match reason {
+                Some(reason) => lines.push(format!("  {}: {}", field_name, reason)),
+                None => lines.push(format!("  {} (no reason given)", field_name)),
+            }
+        }
+    }
+    lines
+}
+
+fn validate_object_type(
+    schema: &ParsedSchema,
+    type_name: &str,
+    parsed_type: &ParsedType,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    let mut push = |severity, field_name: Option<&str>, message: String| {
+        diagnostics.push(Diagnostic {
+            severity,
+            type_name: type_name.to_string(),
+            field_name: field_name.map(str::to_string),
+            message,
+        });
+    };
+
+    if parsed_type.fields.is_empty() {
+        push(
+            Severity::Error,
+            None,
+            "type has no fields; no table can be generated".to_string(),
+        );
+        return;
+    }
+
+    if !parsed_type
+        .fields
+        .iter()
+        .any(|f| !matches!(f.field_type, FieldType::Reference(_)))
+    {
+        push(
+            Severity::Warning,
+            None,
+            "type has no scalar fields; its table will only contain foreign keys".to_string(),
+        );
+    }
+
+    let id_candidates: Vec<&str> = parsed_type
+        .fields
+        .iter()
+        .filter(|f| {
+            !f.is_nullable
+                && !f.is_list
+                && matches!(&f.field_type, FieldType::Scalar(s) if s == "ID")
+        })
+        .map(|f| f.name.as_str())
+        .collect();
+    // Two or more `@id` fields form a composite key rather than competing
+    let composite = parsed_type
+        .fields
+        .iter()
+        .filter(|f| f.has_directive("id"))
+        .count()
+        > 1;
+    if id_candidates.len() > 1 && !composite {
+        let chosen = parsed_type
+            .primary_key_field()
+            .map(|f| f.name.as_str())
+            .unwrap_or("id");
+        push(
+            Severity::Warning,
+            None,
+            format!(
+                "multiple primary key candidates ({}); using '{}'",
+                id_candidates.join(", "),
+                chosen
+            ),
+        );
+    }
+
+    for field in &parsed_type.fields {
+        if field.list_depth > 1 {
+            push(
+                Severity::Warning,
+                Some(&field.name),
+                format!(
+                    "nested list ({} levels) is flattened to a single column",
+                    field.list_depth
+                ),
+            );
+        }
+
+        if let FieldType::Reference(target) = &field.field_type {
+            match schema.types.get(target).map(|t| &t.kind) {
+                Some(TypeKind::Union) => push(
+                    Severity::Warning,
+                    Some(&field.name),
+                    format!(
+                        "union '{}' cannot be stored as a column; generated as a plain foreign key",
+                        target
+                    ),
+                ),
+                Some(TypeKind::Interface) => push(
+                    Severity::Info,
+                    Some(&field.name),
+                    format!("interface '{}' is generated as a plain foreign key", target),
+                ),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn is_prelude_type(name: &str) -> bool {
+    RUST_PRELUDE_TYPES
+        .iter()
+        .any(|(prelude, _)| *prelude == name)
+}
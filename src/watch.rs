@@ -0,0 +1,48 @@
+//! Schema polling for `watch` mode.
+//!
+//! The schema is loaded on an interval and compared with the previous one,
+//! so code is only regenerated when the schema actually changed.
+
+use crate::parser::ParsedSchema;
+
+/// Outcome of one poll of the schema source
+#[derive(Debug)]
+pub enum WatchEvent {
+    /// The first schema loaded since watching started
+    Initial(ParsedSchema),
+    /// A schema that differs from the previous one
+    Changed(ParsedSchema),
+    /// The same schema as last time
+    Unchanged,
+    /// Loading the schema failed; the next poll tries again
+    Failed(anyhow::Error),
+}
+
+/// Remembers the last schema seen
+#[derive(Debug, Default)]
+pub struct SchemaWatcher {
+    last: Option<ParsedSchema>,
+}
+
+impl SchemaWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Classify the result of loading the schema.
+    ///
+    /// Failures leave the remembered schema alone, so a schema that comes back
+    /// unchanged after an outage doesn't trigger a regeneration.
+    pub fn observe(&mut self, loaded: anyhow::Result<ParsedSchema>) -> WatchEvent {
+        let schema = match loaded {
+            Ok(schema) => schema,
+            Err(e) => return WatchEvent::Failed(e),
+        };
+
+        match self.last.replace(schema.clone()) {
+            None => WatchEvent::Initial(schema),
+            Some(previous) if previous != schema => WatchEvent::Changed(schema),
+            Some(_) => WatchEvent::Unchanged,
+        }
+    }
+}
@@ -1,5 +1,11 @@
 use clap::Parser;
-use graphql_codegen_rust::cli::{Cli, Commands, DatabaseType, OrmType};
+use graphql_codegen_rust::Config;
+use graphql_codegen_rust::cli::{AuthArgs, Cli, Commands, DatabaseType, OrmType};
+use graphql_codegen_rust::logger::{LogFormat, Logger};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io::Write;
+use std::rc::Rc;
 
 #[test]
 fn test_cli_no_args() {
@@ -30,12 +36,16 @@ fn test_cli_init_command() {
             db,
             output,
             headers,
+            auth,
+            ca_cert,
         }) => {
             assert_eq!(url, "https://api.example.com/graphql");
             assert_eq!(orm, OrmType::Diesel);
             assert_eq!(db, DatabaseType::Sqlite);
             assert_eq!(output, std::path::PathBuf::from("./generated"));
             assert!(headers.is_empty());
+            assert_eq!(auth, AuthArgs::default());
+            assert_eq!(ca_cert, None);
         }
         _ => panic!("Expected Init command"),
     }
@@ -68,6 +78,7 @@ fn test_cli_init_with_headers() {
             db,
             output,
             headers,
+            ..
         }) => {
             assert_eq!(url, "https://api.example.com/graphql");
             assert_eq!(orm, OrmType::SeaOrm);
@@ -95,7 +106,7 @@ fn test_cli_generate_command() {
     .unwrap();
 
     match cli.command {
-        Some(Commands::Generate { config, output }) => {
+        Some(Commands::Generate { config, output, .. }) => {
             assert_eq!(config, Some(std::path::PathBuf::from("codegen.yml")));
             assert!(output.is_none());
         }
@@ -114,7 +125,7 @@ fn test_cli_generate_with_output() {
     .unwrap();
 
     match cli.command {
-        Some(Commands::Generate { config, output }) => {
+        Some(Commands::Generate { config, output, .. }) => {
             assert!(config.is_none());
             assert_eq!(output, Some(std::path::PathBuf::from("./custom_output")));
         }
@@ -127,3 +138,505 @@ fn test_cli_invalid_command() {
     let result = Cli::try_parse_from(["graphql-codegen-rust", "invalid"]);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_cli_validate_schema_command() {
+    let cli = Cli::try_parse_from([
+        "graphql-codegen-rust",
+        "validate-schema",
+        "--config",
+        "codegen.yml",
+    ])
+    .unwrap();
+
+    match cli.command {
+        Some(Commands::ValidateSchema { config }) => {
+            assert_eq!(config, Some(std::path::PathBuf::from("codegen.yml")));
+        }
+        _ => panic!("Expected ValidateSchema command"),
+    }
+}
+
+#[test]
+fn test_cli_validate_command() {
+    let cli =
+        Cli::try_parse_from(["graphql-codegen-rust", "validate", "-c", "codegen.yml"]).unwrap();
+
+    match cli.command {
+        Some(Commands::Validate { config }) => {
+            assert_eq!(config, Some(std::path::PathBuf::from("codegen.yml")));
+        }
+        _ => panic!("Expected Validate command"),
+    }
+
+    let cli = Cli::try_parse_from(["graphql-codegen-rust", "validate"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Commands::Validate { config: None })
+    ));
+}
+
+#[test]
+fn test_cli_watch_command() {
+    let cli = Cli::try_parse_from(["graphql-codegen-rust", "watch", "--interval", "5"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Commands::Watch {
+            config: None,
+            interval: 5
+        })
+    ));
+
+    let cli = Cli::try_parse_from(["graphql-codegen-rust", "watch"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Commands::Watch { interval: 2, .. })
+    ));
+}
+
+#[test]
+fn test_cli_generate_type_filters() {
+    let cli = Cli::try_parse_from([
+        "graphql-codegen-rust",
+        "generate",
+        "--types",
+        "User",
+        "--types",
+        "Post,Comment",
+        "--exclude-types",
+        "Comment",
+    ])
+    .unwrap();
+
+    match cli.command {
+        Some(Commands::Generate {
+            types,
+            exclude_types,
+            ..
+        }) => {
+            assert_eq!(types, vec!["User", "Post", "Comment"]);
+            assert_eq!(exclude_types, vec!["Comment"]);
+        }
+        _ => panic!("Expected Generate command"),
+    }
+}
+
+#[test]
+fn test_cli_generate_check_flag() {
+    let cli = Cli::try_parse_from(["graphql-codegen-rust", "generate", "--check"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Commands::Generate { check: true, .. })
+    ));
+
+    let cli = Cli::try_parse_from(["graphql-codegen-rust", "generate"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Commands::Generate {
+            check: false,
+            force: false,
+            ..
+        })
+    ));
+
+    let cli = Cli::try_parse_from(["graphql-codegen-rust", "generate", "--force"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Some(Commands::Generate { force: true, .. })
+    ));
+}
+
+#[test]
+fn test_cli_bearer_and_basic_auth_headers() {
+    let cli = Cli::try_parse_from([
+        "graphql-codegen-rust",
+        "init",
+        "--url",
+        "https://api.example.com/graphql",
+        "--bearer",
+        "token123",
+    ])
+    .unwrap();
+    // The saved config refers to an environment variable, never the token
+    let config = Config::from(cli.command.as_ref().unwrap());
+    assert_eq!(
+        config.headers,
+        HashMap::from([(
+            "Authorization".to_string(),
+            "Bearer ${API_TOKEN}".to_string()
+        )])
+    );
+    let Some(Commands::Init { auth, .. }) = &cli.command else {
+        panic!("Expected Init command");
+    };
+    assert_eq!(auth.config_env_var(), Some("API_TOKEN"));
+    // The init request itself still sends the token
+    let mut headers = config.headers.clone();
+    auth.apply(&mut headers).unwrap();
+    assert_eq!(headers["Authorization"], "Bearer token123");
+
+    let cli = Cli::try_parse_from([
+        "graphql-codegen-rust",
+        "init",
+        "--url",
+        "https://api.example.com/graphql",
+        "-H",
+        "X-API-Key:key456",
+        "--basic",
+        "alice:s3cret",
+    ])
+    .unwrap();
+    let config = Config::from(cli.command.as_ref().unwrap());
+    assert_eq!(
+        config.headers,
+        HashMap::from([
+            ("X-API-Key".to_string(), "key456".to_string()),
+            (
+                "Authorization".to_string(),
+                "Basic ${API_BASIC_AUTH}".to_string()
+            ),
+        ])
+    );
+    let Some(Commands::Init { auth, .. }) = &cli.command else {
+        panic!("Expected Init command");
+    };
+    assert_eq!(
+        auth.credentials_header().as_deref(),
+        Some("Basic YWxpY2U6czNjcmV0")
+    );
+
+    // Only one auth shortcut may be given
+    assert!(
+        Cli::try_parse_from([
+            "graphql-codegen-rust",
+            "generate",
+            "--bearer",
+            "token123",
+            "--basic",
+            "alice:s3cret",
+        ])
+        .is_err()
+    );
+}
+
+#[test]
+fn test_cli_env_token_header() {
+    let cli = Cli::try_parse_from([
+        "graphql-codegen-rust",
+        "generate",
+        "--env-token",
+        "CODEGEN_TEST_TOKEN",
+    ])
+    .unwrap();
+    let Some(Commands::Generate { auth, .. }) = cli.command else {
+        panic!("Expected Generate command");
+    };
+
+    // SAFETY: no other test reads or writes this variable
+    unsafe { std::env::set_var("CODEGEN_TEST_TOKEN", "from-env") };
+    let mut headers = HashMap::from([("authorization".to_string(), "Bearer stale".to_string())]);
+    auth.apply(&mut headers).unwrap();
+    assert_eq!(
+        headers,
+        HashMap::from([("Authorization".to_string(), "Bearer from-env".to_string())])
+    );
+
+    unsafe { std::env::remove_var("CODEGEN_TEST_TOKEN") };
+    let error = auth.apply(&mut HashMap::new()).unwrap_err();
+    assert!(error.to_string().contains("CODEGEN_TEST_TOKEN"));
+}
+
+#[test]
+fn test_cli_quiet_and_log_format_flags() {
+    let cli =
+        Cli::try_parse_from(["graphql-codegen-rust", "--quiet", "--log-format", "json"]).unwrap();
+    assert!(cli.quiet);
+    assert_eq!(cli.log_format, LogFormat::Json);
+
+    let cli = Cli::try_parse_from(["graphql-codegen-rust"]).unwrap();
+    assert!(!cli.quiet);
+    assert_eq!(cli.log_format, LogFormat::Text);
+
+    assert!(Cli::try_parse_from(["graphql-codegen-rust", "-q", "-v"]).is_err());
+}
+
+/// Shared buffer standing in for stdout or stderr
+#[derive(Clone, Default)]
+struct Captured(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Captured {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Captured {
+    fn text(&self) -> String {
+        String::from_utf8(self.0.borrow().clone()).unwrap()
+    }
+}
+
+fn log_everything(quiet: bool, format: LogFormat) -> (String, String) {
+    let (stdout, stderr) = (Captured::default(), Captured::default());
+    let logger = Logger::with_writers(
+        1,
+        quiet,
+        format,
+        Box::new(stdout.clone()),
+        Box::new(stderr.clone()),
+    );
+    logger.info("Generating \"code\"");
+    logger.debug("hidden at -v");
+    logger.success("Done");
+    logger.warning("Careful");
+    logger.error("Broken");
+    (stdout.text(), stderr.text())
+}
+
+#[test]
+fn test_logger_text_and_json_output() {
+    let (stdout, stderr) = log_everything(false, LogFormat::Text);
+    assert_eq!(stdout, "Generating \"code\"\n✅ Done\n");
+    assert_eq!(stderr, "⚠️  Careful\n❌ Broken\n");
+
+    let (stdout, stderr) = log_everything(false, LogFormat::Json);
+    assert_eq!(
+        stdout,
+        "{\"level\":\"info\",\"msg\":\"Generating \\\"code\\\"\"}\n\
+         {\"level\":\"success\",\"msg\":\"Done\"}\n"
+    );
+    assert_eq!(
+        stderr,
+        "{\"level\":\"warning\",\"msg\":\"Careful\"}\n{\"level\":\"error\",\"msg\":\"Broken\"}\n"
+    );
+
+    // Quiet keeps only errors
+    let (stdout, stderr) = log_everything(true, LogFormat::Json);
+    assert_eq!(stdout, "");
+    assert_eq!(stderr, "{\"level\":\"error\",\"msg\":\"Broken\"}\n");
+}
+
+#[test]
+fn test_logger_tees_to_log_file() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("codegen.log");
+
+    let stdout = Captured::default();
+    let logger = Logger::with_writers(
+        2,
+        false,
+        LogFormat::Json,
+        Box::new(stdout.clone()),
+        Box::new(std::io::sink()),
+    )
+    .with_file(&log_path)
+    .unwrap();
+    logger.info("Fetching schema");
+    logger.debug("URL: http://localhost/graphql");
+    logger.trace("hidden at -vv");
+    logger.warning("Careful");
+    logger.error("Broken");
+    drop(logger);
+
+    let levels: Vec<String> = std::fs::read_to_string(&log_path)
+        .unwrap()
+        .lines()
+        .map(|line| {
+            let value: serde_json::Value = serde_json::from_str(line).unwrap();
+            value["level"].as_str().unwrap().to_string()
+        })
+        .collect();
+    assert_eq!(levels, ["info", "debug", "warning", "error"]);
+
+    // Console output is kept
+    assert_eq!(
+        stdout.text(),
+        "{\"level\":\"info\",\"msg\":\"Fetching schema\"}\n"
+    );
+
+    let cli = Cli::try_parse_from(["graphql-codegen-rust", "--log-file", "codegen.log"]).unwrap();
+    assert_eq!(cli.log_file, Some(std::path::PathBuf::from("codegen.log")));
+}
+
+#[test]
+fn test_fatal_errors_go_through_the_logger() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let log_path = temp_dir.path().join("codegen.log");
+
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_graphql-codegen-rust"))
+        .arg("--log-format")
+        .arg("json")
+        .arg("--log-file")
+        .arg(&log_path)
+        .arg("generate")
+        .arg("--config")
+        .arg(temp_dir.path().join("missing.toml"))
+        .output()
+        .unwrap();
+    assert!(!output.status.success(), "{:?}", output);
+
+    // The error is a JSON line on stderr and in the log file
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let last: serde_json::Value = serde_json::from_str(stderr.lines().last().unwrap()).unwrap();
+    assert_eq!(last["level"], "error", "{}", stderr);
+    assert!(
+        last["msg"].as_str().unwrap().contains("missing.toml"),
+        "{}",
+        stderr
+    );
+    assert!(!stderr.contains("Error: "), "{}", stderr);
+
+    let log = std::fs::read_to_string(&log_path).unwrap();
+    let last: serde_json::Value = serde_json::from_str(log.lines().last().unwrap()).unwrap();
+    assert_eq!(last["level"], "error", "{}", log);
+}
+
+#[test]
+fn test_generate_logs_entity_progress() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("graphql-codegen-rust.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "url = \"http://localhost/graphql\"\norm = \"Diesel\"\ndb = \"Sqlite\"\noutput_dir = {:?}\n",
+            temp_dir.path().join("generated")
+        ),
+    )
+    .unwrap();
+
+    let run = |verbosity: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_graphql-codegen-rust"))
+            .args(verbosity)
+            .arg("generate")
+            .arg("--config")
+            .arg(&config_path)
+            .arg("--introspection-file")
+            .arg(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/fixtures/introspection.json"
+            ))
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{:?}", output);
+        String::from_utf8(output.stderr).unwrap()
+    };
+
+    let stderr = run(&["-vv"]);
+    let progress: Vec<&str> = stderr
+        .lines()
+        .filter(|line| line.starts_with("DEBUG: Generating entity "))
+        .collect();
+    assert_eq!(
+        progress,
+        [
+            "DEBUG: Generating entity 1/3: continent.rs",
+            "DEBUG: Generating entity 2/3: country.rs",
+            "DEBUG: Generating entity 3/3: country_status.rs",
+        ],
+        "{}",
+        stderr
+    );
+
+    // Silent without -v
+    assert!(!run(&[]).contains("Generating entity"));
+}
+
+#[test]
+fn test_generate_warns_on_errors_with_partial_data() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("graphql-codegen-rust.toml");
+    std::fs::write(
+        &config_path,
+        format!(
+            "url = \"http://localhost/graphql\"\norm = \"Diesel\"\ndb = \"Sqlite\"\noutput_dir = {:?}\n",
+            temp_dir.path().join("generated")
+        ),
+    )
+    .unwrap();
+
+    // A schema along with a non-fatal error
+    let mut response: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/introspection.json")).unwrap();
+    response["errors"] = serde_json::json!([{ "message": "Field 'legacy' is deprecated" }]);
+    let response_path = temp_dir.path().join("partial.json");
+    std::fs::write(&response_path, response.to_string()).unwrap();
+
+    let run = |response_path: &std::path::Path| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_graphql-codegen-rust"))
+            .arg("generate")
+            .arg("--config")
+            .arg(&config_path)
+            .arg("--introspection-file")
+            .arg(response_path)
+            .output()
+            .unwrap()
+    };
+
+    let output = run(&response_path);
+    assert!(output.status.success(), "{:?}", output);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains(
+            "Introspection returned an error along with the schema: Field 'legacy' is deprecated"
+        ),
+        "{}",
+        stderr
+    );
+    assert!(
+        temp_dir
+            .path()
+            .join("generated/src/entities/country.rs")
+            .exists()
+    );
+
+    // Without data the errors are still fatal
+    response["data"] = serde_json::Value::Null;
+    std::fs::write(&response_path, response.to_string()).unwrap();
+    let output = run(&response_path);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("GraphQL introspection failed with 1 error")
+    );
+}
+
+#[test]
+fn test_diff_exits_non_zero_on_breaking_changes() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let old_path = temp_dir.path().join("old.graphql");
+    let added_path = temp_dir.path().join("added.graphql");
+    let removed_path = temp_dir.path().join("removed.graphql");
+    std::fs::write(&old_path, "type User { id: ID!\n name: String }").unwrap();
+    std::fs::write(
+        &added_path,
+        "type User { id: ID!\n name: String\n age: Int }",
+    )
+    .unwrap();
+    std::fs::write(&removed_path, "type User { id: ID! }").unwrap();
+
+    let diff = |old: &std::path::Path, new: &std::path::Path| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_graphql-codegen-rust"))
+            .arg("diff")
+            .arg(old)
+            .arg(new)
+            .output()
+            .unwrap()
+    };
+
+    let output = diff(&old_path, &added_path);
+    assert!(output.status.success(), "{:?}", output);
+
+    let output = diff(&old_path, &removed_path);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Found 1 breaking change out of 1"),
+        "{}",
+        stderr
+    );
+}
@@ -202,3 +202,198 @@ schema: https://api.example.com/graphql
     // Restore original directory
     std::env::set_current_dir(original_dir).unwrap();
 }
+
+#[test]
+fn test_config_interpolates_env_vars() {
+    // SAFETY: the variable name is unique to this test
+    unsafe {
+        std::env::set_var("CODEGEN_TEST_API_HOST", "api.example.com");
+        std::env::set_var("CODEGEN_TEST_TOKEN", "token123");
+    }
+    let toml_content = r#"
+url = "https://${CODEGEN_TEST_API_HOST}/graphql"
+orm = "Diesel"
+db = "Sqlite"
+output_dir = "./generated"
+
+[headers]
+Authorization = "Bearer $CODEGEN_TEST_TOKEN"
+X-Price = "$$5"
+"#;
+
+    let config = Config::from_toml_str(toml_content).unwrap();
+
+    assert_eq!(config.url, "https://api.example.com/graphql");
+    assert_eq!(
+        config.headers.get("Authorization"),
+        Some(&"Bearer token123".to_string())
+    );
+    assert_eq!(config.headers.get("X-Price"), Some(&"$5".to_string()));
+}
+
+#[test]
+fn test_config_missing_env_var_errors() {
+    let toml_content = r#"
+url = "https://api.example.com/graphql"
+orm = "Diesel"
+db = "Sqlite"
+output_dir = "./generated"
+
+[headers]
+Authorization = "Bearer ${CODEGEN_TEST_UNSET_TOKEN}"
+"#;
+
+    let err = Config::from_toml_str(toml_content).unwrap_err().to_string();
+
+    assert!(err.contains("Authorization"), "{}", err);
+    assert!(err.contains("CODEGEN_TEST_UNSET_TOKEN"), "{}", err);
+}
+
+#[test]
+fn test_config_builder_minimal() {
+    let config = Config::builder()
+        .url("https://api.example.com/graphql")
+        .build()
+        .unwrap();
+
+    assert_eq!(config.url, "https://api.example.com/graphql");
+    assert_eq!(config.orm, graphql_codegen_rust::cli::OrmType::Diesel);
+    assert_eq!(config.db, graphql_codegen_rust::cli::DatabaseType::Sqlite);
+    assert_eq!(config.output_dir, PathBuf::from("./generated"));
+    assert!(config.headers.is_empty());
+    assert!(config.generate_migrations);
+    assert!(config.generate_entities);
+}
+
+#[test]
+fn test_config_default_matches_omitted_settings() {
+    let config = Config::default();
+    assert!(config.auto_fk_index);
+    assert!(config.format_output);
+    assert_eq!(config.prefix_enum_values, "V");
+
+    // The same settings as a config file that leaves everything optional out
+    let parsed: Config = toml::from_str(
+        r#"
+url = ""
+orm = "Diesel"
+db = "Sqlite"
+output_dir = "./generated"
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        serde_json::to_value(&config).unwrap(),
+        serde_json::to_value(&parsed).unwrap()
+    );
+}
+
+#[test]
+fn test_config_builder_setters() {
+    let config = Config::builder()
+        .url("https://api.example.com/graphql")
+        .orm(graphql_codegen_rust::cli::OrmType::SeaOrm)
+        .db(graphql_codegen_rust::cli::DatabaseType::Postgres)
+        .output_dir("./out")
+        .header("Authorization", "Bearer token123")
+        .type_mapping("DateTime", "chrono::DateTime<chrono::Utc>")
+        .scalar_mapping("Date", "chrono::NaiveDate")
+        .table_naming(graphql_codegen_rust::config::TableNamingConvention::PascalCase)
+        .build()
+        .unwrap();
+
+    assert_eq!(config.orm, graphql_codegen_rust::cli::OrmType::SeaOrm);
+    assert_eq!(config.db, graphql_codegen_rust::cli::DatabaseType::Postgres);
+    assert_eq!(config.output_dir, PathBuf::from("./out"));
+    assert_eq!(
+        config.headers.get("Authorization"),
+        Some(&"Bearer token123".to_string())
+    );
+    assert_eq!(
+        config.type_mappings.get("DateTime"),
+        Some(&"chrono::DateTime<chrono::Utc>".to_string())
+    );
+    assert_eq!(
+        config.scalar_mappings.get("Date"),
+        Some(&"chrono::NaiveDate".to_string())
+    );
+    assert!(matches!(
+        config.table_naming,
+        graphql_codegen_rust::config::TableNamingConvention::PascalCase
+    ));
+}
+
+#[test]
+fn test_config_builder_requires_url() {
+    let err = Config::builder().build().unwrap_err().to_string();
+    assert!(err.contains("url is required"), "{}", err);
+}
+
+#[test]
+fn test_config_id_type_string() {
+    let toml_content = r#"
+url = "https://api.example.com/graphql"
+orm = "Diesel"
+db = "Postgres"
+output_dir = "./generated"
+id_type = "string"
+"#;
+
+    let config = Config::from_toml_str(toml_content).unwrap();
+    assert_eq!(
+        config.id_mapping,
+        graphql_codegen_rust::config::IdMapping::String
+    );
+
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl("type Node { id: ID! name: String! }")
+        .unwrap();
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    assert!(entities["node.rs"].contains("pub id: String,"));
+
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    match &migrations[0] {
+        graphql_codegen_rust::generator::MigrationFile::Sql { up_sql, .. } => {
+            assert!(up_sql.contains("id TEXT PRIMARY KEY"), "{}", up_sql);
+        }
+        other => panic!("Expected SQL migration, got {}", other.name()),
+    }
+}
+
+#[test]
+fn test_config_rejects_url_without_scheme() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("graphql-codegen-rust.toml");
+    let rest = "orm = \"Diesel\"\ndb = \"Sqlite\"\noutput_dir = \"./generated\"\n";
+
+    fs::write(
+        &config_path,
+        format!("{}{}", "url = \"api.example.com/graphql\"\n", rest),
+    )
+    .unwrap();
+    let err = Config::from_file(&config_path).unwrap_err().to_string();
+    assert!(err.contains("missing http:// or https://"), "{}", err);
+    assert!(err.contains("https://api.example.com/graphql"), "{}", err);
+
+    let err = Config::builder()
+        .url("ftp://api.example.com/graphql")
+        .build()
+        .unwrap_err();
+    assert!(err.to_string().contains("unsupported scheme 'ftp'"));
+
+    // Valid urls load, and a saved schema file needs no url at all
+    fs::write(
+        &config_path,
+        format!("{}{}", "url = \"http://localhost:4000/graphql\"\n", rest),
+    )
+    .unwrap();
+    assert!(Config::from_file(&config_path).is_ok());
+    fs::write(
+        &config_path,
+        format!("{}{}", "introspection_file = \"schema.json\"\n", rest),
+    )
+    .unwrap();
+    assert!(Config::from_file(&config_path).is_ok());
+}
@@ -19,6 +19,7 @@ fn test_codegen_creation() {
         table_naming: graphql_codegen_rust::config::TableNamingConvention::SnakeCase,
         generate_migrations: true,
         generate_entities: true,
+        ..Default::default()
     };
 
     let _generator = CodeGenerator::new(&config.orm);
@@ -40,6 +41,7 @@ fn test_config_defaults() {
         table_naming: graphql_codegen_rust::config::TableNamingConvention::SnakeCase,
         generate_migrations: true,
         generate_entities: true,
+        ..Default::default()
     };
 
     assert_eq!(config.url, "https://api.example.com/graphql");
@@ -60,6 +62,14 @@ fn test_table_naming_conventions() {
     assert_eq!(to_snake_case("APIKey"), "api_key");
     assert_eq!(to_snake_case("userName"), "user_name");
     assert_eq!(to_snake_case("XMLHttpRequest"), "xml_http_request");
+    assert_eq!(to_snake_case("parseJSON"), "parse_json");
+    assert_eq!(to_snake_case("HTTP2"), "http2");
+    assert_eq!(to_snake_case("S3Bucket"), "s3_bucket");
+    assert_eq!(to_snake_case("OAuth2Token"), "o_auth2_token");
+    assert_eq!(to_snake_case("sha256Hash"), "sha256_hash");
+    assert_eq!(to_snake_case("address2"), "address2");
+    assert_eq!(to_snake_case("user2fa"), "user_2fa");
+    assert_eq!(to_snake_case("snake_case"), "snake_case");
 }
 
 /// Test that generated Diesel code compiles successfully
@@ -75,25 +85,46 @@ async fn test_diesel_code_generation_compiles() {
     // Add User type
     let user_fields = vec![
         ParsedField {
+            graphql_type: String::new(),
             name: "id".to_string(),
             field_type: FieldType::Scalar("ID".to_string()),
             description: None,
             is_nullable: false,
             is_list: false,
+            list_depth: 0,
+            arguments: vec![],
+            max_length: None,
+            directives: vec![],
+            default_value: None,
+            deprecation: None,
         },
         ParsedField {
+            graphql_type: String::new(),
             name: "name".to_string(),
             field_type: FieldType::Scalar("String".to_string()),
             description: None,
             is_nullable: false,
             is_list: false,
+            list_depth: 0,
+            arguments: vec![],
+            max_length: None,
+            directives: vec![],
+            default_value: None,
+            deprecation: None,
         },
         ParsedField {
+            graphql_type: String::new(),
             name: "email".to_string(),
             field_type: FieldType::Scalar("String".to_string()),
             description: None,
             is_nullable: true,
             is_list: false,
+            list_depth: 0,
+            arguments: vec![],
+            max_length: None,
+            directives: vec![],
+            default_value: None,
+            deprecation: None,
         },
     ];
 
@@ -106,6 +137,7 @@ async fn test_diesel_code_generation_compiles() {
             fields: user_fields,
             description: Some("A user in the system".to_string()),
             interfaces: vec![],
+            primary_key: None,
         },
     );
 
@@ -116,6 +148,8 @@ async fn test_diesel_code_generation_compiles() {
             name: "Role".to_string(),
             values: vec!["ADMIN".to_string(), "USER".to_string()],
             description: Some("User roles".to_string()),
+            discriminants: HashMap::new(),
+            deprecations: HashMap::new(),
         },
     );
 
@@ -123,6 +157,10 @@ async fn test_diesel_code_generation_compiles() {
         types,
         enums,
         scalars: vec![],
+        query_root: None,
+        mutation_root: None,
+        subscription_root: None,
+        input_types: HashMap::new(),
     };
 
     // Create config for Diesel + SQLite
@@ -137,6 +175,7 @@ async fn test_diesel_code_generation_compiles() {
         table_naming: graphql_codegen_rust::config::TableNamingConvention::SnakeCase,
         generate_migrations: true,
         generate_entities: true,
+        ..Default::default()
     };
 
     // Generate code using the internal function with pre-parsed schema
@@ -184,25 +223,46 @@ async fn test_sea_orm_code_generation_compiles() {
     // Add Product type
     let product_fields = vec![
         ParsedField {
+            graphql_type: String::new(),
             name: "id".to_string(),
             field_type: FieldType::Scalar("ID".to_string()),
             description: None,
             is_nullable: false,
             is_list: false,
+            list_depth: 0,
+            arguments: vec![],
+            max_length: None,
+            directives: vec![],
+            default_value: None,
+            deprecation: None,
         },
         ParsedField {
+            graphql_type: String::new(),
             name: "title".to_string(),
             field_type: FieldType::Scalar("String".to_string()),
             description: None,
             is_nullable: false,
             is_list: false,
+            list_depth: 0,
+            arguments: vec![],
+            max_length: None,
+            directives: vec![],
+            default_value: None,
+            deprecation: None,
         },
         ParsedField {
+            graphql_type: String::new(),
             name: "price".to_string(),
             field_type: FieldType::Scalar("Float".to_string()),
             description: None,
             is_nullable: false,
             is_list: false,
+            list_depth: 0,
+            arguments: vec![],
+            max_length: None,
+            directives: vec![],
+            default_value: None,
+            deprecation: None,
         },
     ];
 
@@ -215,6 +275,7 @@ async fn test_sea_orm_code_generation_compiles() {
             fields: product_fields,
             description: Some("A product in the catalog".to_string()),
             interfaces: vec![],
+            primary_key: None,
         },
     );
 
@@ -225,6 +286,8 @@ async fn test_sea_orm_code_generation_compiles() {
             name: "Status".to_string(),
             values: vec!["ACTIVE".to_string(), "INACTIVE".to_string()],
             description: Some("Product status".to_string()),
+            discriminants: HashMap::new(),
+            deprecations: HashMap::new(),
         },
     );
 
@@ -232,6 +295,10 @@ async fn test_sea_orm_code_generation_compiles() {
         types,
         enums,
         scalars: vec![],
+        query_root: None,
+        mutation_root: None,
+        subscription_root: None,
+        input_types: HashMap::new(),
     };
 
     // Create config for Sea-ORM + PostgreSQL
@@ -246,6 +313,7 @@ async fn test_sea_orm_code_generation_compiles() {
         table_naming: graphql_codegen_rust::config::TableNamingConvention::SnakeCase,
         generate_migrations: true,
         generate_entities: true,
+        ..Default::default()
     };
 
     // Generate code using the internal function with pre-parsed schema
@@ -461,7 +529,8 @@ async fn test_real_graphql_apis() {
             graphql_codegen_rust::cli::OrmType::SeaOrm,
         ] {
             let db_type = match orm_type {
-                graphql_codegen_rust::cli::OrmType::Diesel => {
+                graphql_codegen_rust::cli::OrmType::Diesel
+                | graphql_codegen_rust::cli::OrmType::Plain => {
                     graphql_codegen_rust::cli::DatabaseType::Sqlite
                 }
                 graphql_codegen_rust::cli::OrmType::SeaOrm => {
@@ -480,6 +549,7 @@ async fn test_real_graphql_apis() {
                 table_naming: graphql_codegen_rust::config::TableNamingConvention::SnakeCase,
                 generate_migrations: true,
                 generate_entities: true,
+                ..Default::default()
             };
 
             // This should succeed for public APIs
@@ -525,7 +595,8 @@ async fn test_edge_cases() {
             graphql_codegen_rust::cli::OrmType::SeaOrm,
         ] {
             let db_type = match orm_type {
-                graphql_codegen_rust::cli::OrmType::Diesel => {
+                graphql_codegen_rust::cli::OrmType::Diesel
+                | graphql_codegen_rust::cli::OrmType::Plain => {
                     graphql_codegen_rust::cli::DatabaseType::Sqlite
                 }
                 graphql_codegen_rust::cli::OrmType::SeaOrm => {
@@ -544,6 +615,7 @@ async fn test_edge_cases() {
                 table_naming: graphql_codegen_rust::config::TableNamingConvention::SnakeCase,
                 generate_migrations: true,
                 generate_entities: true,
+                ..Default::default()
             };
 
             // Generate code using the internal function
@@ -570,21 +642,35 @@ async fn test_codegen_performance() {
     for i in 0..10 {
         let type_name = format!("Type{}", i);
         let mut fields = vec![ParsedField {
+            graphql_type: String::new(),
             name: "id".to_string(),
             field_type: FieldType::Scalar("ID".to_string()),
             description: None,
             is_nullable: false,
             is_list: false,
+            list_depth: 0,
+            arguments: vec![],
+            max_length: None,
+            directives: vec![],
+            default_value: None,
+            deprecation: None,
         }];
 
         // Add 5 additional fields
         for j in 0..5 {
             fields.push(ParsedField {
+                graphql_type: String::new(),
                 name: format!("field{}", j),
                 field_type: FieldType::Scalar("String".to_string()),
                 description: None,
                 is_nullable: true,
                 is_list: false,
+                list_depth: 0,
+                arguments: vec![],
+                max_length: None,
+                directives: vec![],
+                default_value: None,
+                deprecation: None,
             });
         }
 
@@ -597,6 +683,7 @@ async fn test_codegen_performance() {
                 fields,
                 description: Some(format!("Type {} description", i)),
                 interfaces: vec![],
+                primary_key: None,
             },
         );
     }
@@ -613,6 +700,8 @@ async fn test_codegen_performance() {
                     "VALUE3".to_string(),
                 ],
                 description: Some(format!("Enum {} description", i)),
+                discriminants: HashMap::new(),
+                deprecations: HashMap::new(),
             },
         );
     }
@@ -621,6 +710,10 @@ async fn test_codegen_performance() {
         types,
         enums,
         scalars: vec![],
+        query_root: None,
+        mutation_root: None,
+        subscription_root: None,
+        input_types: HashMap::new(),
     };
 
     let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -633,7 +726,8 @@ async fn test_codegen_performance() {
         graphql_codegen_rust::cli::OrmType::SeaOrm,
     ] {
         let db_type = match orm_type {
-            graphql_codegen_rust::cli::OrmType::Diesel => {
+            graphql_codegen_rust::cli::OrmType::Diesel
+            | graphql_codegen_rust::cli::OrmType::Plain => {
                 graphql_codegen_rust::cli::DatabaseType::Sqlite
             }
             graphql_codegen_rust::cli::OrmType::SeaOrm => {
@@ -652,6 +746,9 @@ async fn test_codegen_performance() {
             table_naming: graphql_codegen_rust::config::TableNamingConvention::SnakeCase,
             generate_migrations: true,
             generate_entities: true,
+            // Time the generator, not rustfmt
+            format_output: false,
+            ..Default::default()
         };
 
         let start = Instant::now();
@@ -693,11 +790,18 @@ async fn test_fuzz_schema_generation() {
         for i in 0..num_types {
             let type_name = format!("Type{}", i);
             let mut fields = vec![ParsedField {
+                graphql_type: String::new(),
                 name: "id".to_string(),
                 field_type: FieldType::Scalar("ID".to_string()),
                 description: None,
                 is_nullable: false,
                 is_list: false,
+                list_depth: 0,
+                arguments: vec![],
+                max_length: None,
+                directives: vec![],
+                default_value: None,
+                deprecation: None,
             }];
 
             // Random number of fields (1-3)
@@ -707,11 +811,18 @@ async fn test_fuzz_schema_generation() {
                 let random_type = field_types[rng.random_range(0..field_types.len())];
 
                 fields.push(ParsedField {
+                    graphql_type: String::new(),
                     name: format!("field{}", j),
                     field_type: FieldType::Scalar(random_type.to_string()),
                     description: None,
                     is_nullable: rng.random_bool(0.5), // 50% chance of being nullable
                     is_list: false,
+                    list_depth: 0,
+                    arguments: vec![],
+                    max_length: None,
+                    directives: vec![],
+                    default_value: None,
+                    deprecation: None,
                 });
             }
 
@@ -724,6 +835,7 @@ async fn test_fuzz_schema_generation() {
                     fields,
                     description: Some(format!("Random type {}", i)),
                     interfaces: vec![],
+                    primary_key: None,
                 },
             );
         }
@@ -741,6 +853,8 @@ async fn test_fuzz_schema_generation() {
                     name: format!("Enum{}", i),
                     values,
                     description: Some(format!("Random enum {}", i)),
+                    discriminants: HashMap::new(),
+                    deprecations: HashMap::new(),
                 },
             );
         }
@@ -749,6 +863,10 @@ async fn test_fuzz_schema_generation() {
             types,
             enums,
             scalars: vec![],
+            query_root: None,
+            mutation_root: None,
+            subscription_root: None,
+            input_types: HashMap::new(),
         };
 
         let temp_dir = TempDir::new().expect("Failed to create temp dir");
@@ -759,7 +877,8 @@ async fn test_fuzz_schema_generation() {
             graphql_codegen_rust::cli::OrmType::SeaOrm,
         ] {
             let db_type = match orm_type {
-                graphql_codegen_rust::cli::OrmType::Diesel => {
+                graphql_codegen_rust::cli::OrmType::Diesel
+                | graphql_codegen_rust::cli::OrmType::Plain => {
                     graphql_codegen_rust::cli::DatabaseType::Sqlite
                 }
                 graphql_codegen_rust::cli::OrmType::SeaOrm => {
@@ -778,6 +897,7 @@ async fn test_fuzz_schema_generation() {
                 table_naming: graphql_codegen_rust::config::TableNamingConvention::SnakeCase,
                 generate_migrations: true,
                 generate_entities: true,
+                ..Default::default()
             };
 
             // This should not panic even with random schemas
@@ -818,14 +938,22 @@ async fn test_multi_database_support() {
                 union_members: vec![],
                 name: "Test".to_string(),
                 fields: vec![ParsedField {
+                    graphql_type: String::new(),
                     name: "id".to_string(),
                     field_type: FieldType::Scalar("ID".to_string()),
                     description: None,
                     is_nullable: false,
                     is_list: false,
+                    list_depth: 0,
+                    arguments: vec![],
+                    max_length: None,
+                    directives: vec![],
+                    default_value: None,
+                    deprecation: None,
                 }],
                 description: None,
                 interfaces: vec![],
+                primary_key: None,
             },
         );
 
@@ -833,6 +961,10 @@ async fn test_multi_database_support() {
             types,
             enums: HashMap::new(),
             scalars: vec![],
+            query_root: None,
+            mutation_root: None,
+            subscription_root: None,
+            input_types: HashMap::new(),
         };
 
         // Test both ORMs
@@ -851,6 +983,7 @@ async fn test_multi_database_support() {
                 table_naming: graphql_codegen_rust::config::TableNamingConvention::SnakeCase,
                 generate_migrations: true,
                 generate_entities: true,
+                ..Default::default()
             };
 
             // Generate code using the internal function with pre-parsed schema
@@ -875,206 +1008,6586 @@ async fn test_multi_database_support() {
     }
 }
 
-// Helper functions for creating test schemas
+/// Test that incremental mode only rewrites entity files for changed types
+#[tokio::test]
+async fn test_incremental_generation_skips_unchanged_entities() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output_dir = temp_dir.path().to_path_buf();
 
-fn create_empty_schema() -> ParsedSchema {
-    ParsedSchema {
-        types: HashMap::new(),
-        enums: HashMap::new(),
-        scalars: vec![],
-    }
-}
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        output_dir: output_dir.clone(),
+        generate_migrations: true,
+        generate_entities: true,
+        incremental: true,
+        ..Default::default()
+    };
 
-fn create_single_field_schema() -> ParsedSchema {
-    let mut types = HashMap::new();
+    let generator_inner = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let mut schema = create_complex_relationships_schema();
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .expect("Code generation should succeed");
 
-    types.insert(
-        "Minimal".to_string(),
-        ParsedType {
-            kind: graphql_codegen_rust::parser::TypeKind::Object,
-            union_members: vec![],
-            name: "Minimal".to_string(),
-            fields: vec![ParsedField {
-                name: "id".to_string(),
-                field_type: FieldType::Scalar("ID".to_string()),
-                description: None,
-                is_nullable: false,
-                is_list: false,
-            }],
-            description: None,
-            interfaces: vec![],
-        },
+    assert!(
+        output_dir
+            .join(".graphql-codegen-rust.manifest.json")
+            .exists()
     );
 
-    ParsedSchema {
-        types,
-        enums: HashMap::new(),
-        scalars: vec![],
-    }
+    // Mark both entity files so we can tell which ones get rewritten
+    let author_path = output_dir.join("src/entities/author.rs");
+    let blog_post_path = output_dir.join("src/entities/blog_post.rs");
+    let untouched = "// @generated by graphql-codegen-rust (untouched)";
+    std::fs::write(&author_path, untouched).unwrap();
+    std::fs::write(&blog_post_path, untouched).unwrap();
+
+    // Change only the Author type
+    schema
+        .types
+        .get_mut("Author")
+        .unwrap()
+        .fields
+        .push(ParsedField {
+            graphql_type: String::new(),
+            name: "bio".to_string(),
+            field_type: FieldType::Scalar("String".to_string()),
+            description: None,
+            is_nullable: true,
+            is_list: false,
+            list_depth: 0,
+            arguments: vec![],
+            max_length: None,
+            directives: vec![],
+            default_value: None,
+            deprecation: None,
+        });
+
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .expect("Code generation should succeed");
+
+    let author_content = std::fs::read_to_string(&author_path).unwrap();
+    assert!(author_content.contains("pub bio: String"));
+
+    let blog_post_content = std::fs::read_to_string(&blog_post_path).unwrap();
+    assert_eq!(blog_post_content, untouched);
 }
 
-fn create_enum_only_schema() -> ParsedSchema {
-    let mut enums = HashMap::new();
+/// Test that Sea-ORM auto_increment and the migration primary key agree for SQLite string ids
+#[test]
+fn test_sqlite_string_ids_are_not_auto_increment() {
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        id_mapping: graphql_codegen_rust::config::IdMapping::String,
+        sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Raw,
+        ..Default::default()
+    };
 
-    enums.insert(
-        "Status".to_string(),
-        ParsedEnum {
-            name: "Status".to_string(),
-            values: vec![
-                "ACTIVE".to_string(),
-                "INACTIVE".to_string(),
-                "PENDING".to_string(),
-            ],
-            description: Some("Entity status".to_string()),
-        },
-    );
+    let schema = create_single_field_schema();
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
 
-    ParsedSchema {
-        types: HashMap::new(),
-        enums,
-        scalars: vec![],
-    }
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let entity = &entities["minimal.rs"];
+    assert!(entity.contains("#[sea_orm(primary_key, auto_increment = false)]"));
+    assert!(entity.contains("pub id: String,"));
+
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    assert_eq!(migrations.len(), 1);
+    assert!(up_sql(&migrations[0]).contains("id TEXT PRIMARY KEY"));
+    assert!(!up_sql(&migrations[0]).contains("AUTOINCREMENT"));
+
+    // The default SQLite mapping keeps database-assigned integer keys on both sides
+    let config = Config {
+        id_mapping: graphql_codegen_rust::config::IdMapping::Auto,
+        ..config
+    };
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    assert!(entities["minimal.rs"].contains("#[sea_orm(primary_key)]"));
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    assert!(up_sql(&migrations[0]).contains("id INTEGER PRIMARY KEY"));
 }
 
-fn create_complex_relationships_schema() -> ParsedSchema {
-    let mut types = HashMap::new();
-    let mut enums = HashMap::new();
+/// Test that formatted output is stable under a second rustfmt pass
+#[tokio::test]
+async fn test_format_output_is_idempotent() {
+    use graphql_codegen_rust::generator::format_rust_code;
 
-    // Author type
-    types.insert(
-        "Author".to_string(),
-        ParsedType {
-            kind: graphql_codegen_rust::parser::TypeKind::Object,
-            union_members: vec![],
-            name: "Author".to_string(),
-            fields: vec![
-                ParsedField {
-                    name: "id".to_string(),
-                    field_type: FieldType::Scalar("ID".to_string()),
-                    description: None,
-                    is_nullable: false,
-                    is_list: false,
-                },
-                ParsedField {
-                    name: "name".to_string(),
-                    field_type: FieldType::Scalar("String".to_string()),
-                    description: None,
-                    is_nullable: false,
-                    is_list: false,
-                },
-            ],
-            description: Some("Blog author".to_string()),
-            interfaces: vec![],
-        },
-    );
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output_dir = temp_dir.path().to_path_buf();
 
-    // Blog post type with relationships
-    types.insert(
-        "BlogPost".to_string(),
-        ParsedType {
-            kind: graphql_codegen_rust::parser::TypeKind::Object,
-            union_members: vec![],
-            name: "BlogPost".to_string(),
-            fields: vec![
-                ParsedField {
-                    name: "id".to_string(),
-                    field_type: FieldType::Scalar("ID".to_string()),
-                    description: None,
-                    is_nullable: false,
-                    is_list: false,
-                },
-                ParsedField {
-                    name: "title".to_string(),
-                    field_type: FieldType::Scalar("String".to_string()),
-                    description: None,
-                    is_nullable: false,
-                    is_list: false,
-                },
-                ParsedField {
-                    name: "content".to_string(),
-                    field_type: FieldType::Scalar("String".to_string()),
-                    description: None,
-                    is_nullable: false,
-                    is_list: false,
-                },
-                ParsedField {
-                    name: "authorId".to_string(),
-                    field_type: FieldType::Scalar("ID".to_string()),
-                    description: None,
-                    is_nullable: false,
-                    is_list: false,
-                },
-                ParsedField {
-                    name: "published".to_string(),
-                    field_type: FieldType::Scalar("Boolean".to_string()),
-                    description: None,
-                    is_nullable: false,
-                    is_list: false,
-                },
-                ParsedField {
-                    name: "tags".to_string(),
-                    field_type: FieldType::Scalar("String".to_string()),
-                    description: None,
-                    is_nullable: false,
-                    is_list: true,
-                },
-            ],
-            description: Some("Blog post".to_string()),
-            interfaces: vec![],
-        },
-    );
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        db: graphql_codegen_rust::cli::DatabaseType::Postgres,
+        output_dir: output_dir.clone(),
+        generate_migrations: true,
+        generate_entities: true,
+        format_output: true,
+        ..Default::default()
+    };
 
-    // Status enum
-    enums.insert(
-        "PostStatus".to_string(),
-        ParsedEnum {
-            name: "PostStatus".to_string(),
-            values: vec![
-                "DRAFT".to_string(),
-                "PUBLISHED".to_string(),
-                "ARCHIVED".to_string(),
-            ],
-            description: Some("Post publication status".to_string()),
-        },
-    );
+    let schema = create_complex_relationships_schema();
+    let generator_inner = graphql_codegen_rust::generator::create_generator(&config.orm);
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .expect("Code generation should succeed");
 
-    ParsedSchema {
-        types,
-        enums,
-        scalars: vec![],
+    for path in [
+        output_dir.join("mod.rs"),
+        output_dir.join("src/entities/blog_post.rs"),
+    ] {
+        let content = std::fs::read_to_string(&path).expect("Failed to read generated file");
+        assert_eq!(
+            format_rust_code(&content),
+            content,
+            "{} should already be formatted",
+            path.display()
+        );
     }
+
+    // Unparseable input is passed through untouched
+    assert_eq!(format_rust_code("fn broken( {"), "fn broken( {");
 }
 
-/// Parse the generated Diesel code to ensure it's valid Rust syntax
-fn validate_generated_diesel_code(schema_path: &std::path::Path, entity_path: &std::path::Path) {
-    // Read and parse the schema file
-    let schema_content = std::fs::read_to_string(schema_path).expect("Failed to read schema file");
-    match syn::parse_file(&schema_content) {
-        Ok(_) => println!("✓ Schema file parses successfully"),
-        Err(e) => panic!("Schema file failed to parse: {}", e),
-    }
+/// Test that a non-`id` primary key is detected and used by both generators
+#[test]
+fn test_primary_key_detection() {
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            type Account {
+                name: String!
+                uuid: ID!
+                ownerId: ID
+            }
+        "#,
+        )
+        .unwrap();
 
-    // Read and parse the entity file
-    let entity_content = std::fs::read_to_string(entity_path).expect("Failed to read entity file");
-    match syn::parse_file(&entity_content) {
-        Ok(_) => println!("✓ Entity file parses successfully"),
-        Err(e) => panic!("Entity file failed to parse: {}", e),
-    }
+    let account = &schema.types["Account"];
+    assert_eq!(account.primary_key.as_deref(), Some("uuid"));
+
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        sqlite_autoincrement: true,
+        ..Default::default()
+    };
+
+    let diesel = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let table = diesel.generate_schema(&schema, &config).unwrap();
+    assert!(table.contains("account (uuid"));
+
+    let migrations = diesel.generate_migrations(&schema, &config).unwrap();
+    let up_sql = up_sql(&migrations[0]);
+    assert!(up_sql.contains("uuid INTEGER PRIMARY KEY AUTOINCREMENT"));
+    assert!(!up_sql.contains("    id "));
+    assert_eq!(up_sql.matches("PRIMARY KEY").count(), 1);
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        ..config
+    };
+    let sea_orm = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = sea_orm.generate_entities(&schema, &config).unwrap();
+    let entity = &entities["account.rs"];
+    assert!(entity.contains(
+        "    #[sea_orm(primary_key)]\n    #[sea_orm(column_name = \"uuid\")]\n    pub uuid: i32,"
+    ));
 }
 
-/// Parse the generated Sea-ORM code to ensure it's valid Rust syntax
-fn validate_generated_sea_orm_code(mod_path: &std::path::Path, entity_path: &std::path::Path) {
-    // Read and parse the mod.rs file
-    let mod_content = std::fs::read_to_string(mod_path).expect("Failed to read mod.rs file");
-    match syn::parse_file(&mod_content) {
-        Ok(_) => println!("✓ Mod file parses successfully"),
-        Err(e) => panic!("Mod file failed to parse: {}", e),
-    }
+/// Test that feature-gated entities get a cfg attribute before their module declaration
+#[test]
+fn test_feature_gated_entity_modules() {
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        db: graphql_codegen_rust::cli::DatabaseType::Postgres,
+        feature_per_entity: true,
+        feature_map: HashMap::from([("Author".to_string(), "authors".to_string())]),
+        ..Default::default()
+    };
 
-    // Read and parse the entity file
-    let entity_content = std::fs::read_to_string(entity_path).expect("Failed to read entity file");
-    match syn::parse_file(&entity_content) {
-        Ok(_) => println!("✓ Entity file parses successfully"),
-        Err(e) => panic!("Entity file failed to parse: {}", e),
-    }
+    let schema = create_complex_relationships_schema();
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let mod_rs = generator.generate_schema(&schema, &config).unwrap();
+
+    assert!(mod_rs.contains("#[cfg(feature = \"entity_blog_post\")]\npub mod blog_post;"));
+    assert!(mod_rs.contains("#[cfg(feature = \"authors\")]\npub mod author;"));
+    assert!(mod_rs.contains("#[cfg(feature = \"entity_post_status\")]\npub mod post_status;"));
+    assert!(mod_rs.contains("#[cfg(feature = \"authors\")]\npub use author::Entity;"));
+    syn::parse_file(&mod_rs).expect("mod.rs should parse");
+
+    // Without the option, module declarations are not gated
+    let config = Config {
+        feature_per_entity: false,
+        feature_map: HashMap::new(),
+        ..config
+    };
+    let mod_rs = generator.generate_schema(&schema, &config).unwrap();
+    assert!(!mod_rs.contains("#[cfg(feature"));
+}
+
+/// Test that the Sea-ORM Model marks its key field and leaves PrimaryKey to the derive
+#[test]
+fn test_sea_orm_model_primary_key_attribute() {
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        db: graphql_codegen_rust::cli::DatabaseType::Postgres,
+        ..Default::default()
+    };
+
+    let schema = create_complex_relationships_schema();
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let entity = &entities["author.rs"];
+
+    // Postgres UUID keys are not auto-incremented
+    assert!(entity.contains(
+        "    #[sea_orm(primary_key, auto_increment = false)]\n    #[sea_orm(column_name = \"id\")]\n    pub id: uuid::Uuid,"
+    ));
+    assert_eq!(entity.matches("primary_key").count(), 1);
+    assert!(!entity.contains("impl PrimaryKeyTrait"));
+    assert!(!entity.contains("pub enum PrimaryKey"));
+    syn::parse_file(entity).expect("entity should parse");
+}
+
+/// Test that schema validation reports lossy constructs with a severity
+#[test]
+fn test_validate_schema_reports_nested_lists() {
+    use graphql_codegen_rust::validation::{Severity, validate_schema};
+
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            type Matrix {
+                id: ID!
+                cells: [[Int!]!]!
+                owner: Owner
+            }
+
+            type User {
+                id: ID!
+            }
+
+            union Owner = User | Matrix
+        "#,
+        )
+        .unwrap();
+
+    let cells = schema.types["Matrix"]
+        .fields
+        .iter()
+        .find(|f| f.name == "cells")
+        .unwrap();
+    assert_eq!(cells.list_depth, 2);
+
+    let diagnostics = validate_schema(&schema, &Config::default());
+
+    let nested = diagnostics
+        .iter()
+        .find(|d| d.field_name.as_deref() == Some("cells"))
+        .expect("nested list should be reported");
+    assert_eq!(nested.severity, Severity::Warning);
+    assert_eq!(nested.type_name, "Matrix");
+    assert!(nested.to_string().starts_with("warning: Matrix.cells:"));
+
+    let union_field = diagnostics
+        .iter()
+        .find(|d| d.field_name.as_deref() == Some("owner"))
+        .expect("union field should be reported");
+    assert_eq!(union_field.severity, Severity::Warning);
+
+    assert!(!diagnostics.iter().any(|d| d.type_name == "User"));
+}
+
+/// Test that Sea-ORM Rust migrations and their Migrator are valid Rust
+#[tokio::test]
+async fn test_sea_orm_rust_migrations_parse() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output_dir = temp_dir.path().to_path_buf();
+
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        db: graphql_codegen_rust::cli::DatabaseType::Postgres,
+        output_dir: output_dir.clone(),
+        generate_migrations: true,
+        generate_entities: true,
+        sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Rust,
+        ..Default::default()
+    };
+
+    let schema = create_complex_relationships_schema();
+    let generator_inner = graphql_codegen_rust::generator::create_generator(&config.orm);
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .expect("Code generation should succeed");
+
+    let migrations_src = output_dir.join("migrations/src");
+    let mut migration_files: Vec<_> = std::fs::read_dir(&migrations_src)
+        .expect("Rust migrations should be written to migrations/src")
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    migration_files.sort();
+    assert_eq!(migration_files.len(), 3, "two tables plus lib.rs");
+
+    for path in &migration_files {
+        let content = std::fs::read_to_string(path).unwrap();
+        syn::parse_file(&content)
+            .unwrap_or_else(|e| panic!("{} failed to parse: {}", path.display(), e));
+    }
+
+    let lib = std::fs::read_to_string(migrations_src.join("lib.rs")).unwrap();
+    assert!(lib.contains("impl MigratorTrait for Migrator"));
+    assert!(lib.contains("_create_blog_post_table::Migration"));
+
+    // The Migrator isn't one of the migrations
+    let migrations = generator_inner
+        .generate_migrations(&schema, &config)
+        .unwrap();
+    assert_eq!(migrations.len(), 2);
+    assert!(migrations.iter().all(|m| m.name().contains("_create_")));
+
+    let blog_post = migration_files
+        .iter()
+        .find(|p| p.to_string_lossy().ends_with("_create_blog_post_table.rs"))
+        .unwrap();
+    let content = std::fs::read_to_string(blog_post).unwrap();
+    assert!(content.contains("impl MigrationTrait for Migration"));
+    assert!(content.contains("manager"));
+    assert!(content.contains(".create_table("));
+    assert!(content.contains(".drop_table("));
+    assert!(
+        !output_dir
+            .join("migrations")
+            .read_dir()
+            .unwrap()
+            .any(|e| { e.unwrap().path().join("up.sql").exists() })
+    );
+}
+
+/// Test that generate_into streams every generated file to the sink
+#[test]
+fn test_generate_into_sink_emits_all_paths() {
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        generate_lib_rs: true,
+        // No timestamp in the banners, so both runs match
+        deterministic_output: true,
+        ..Default::default()
+    };
+
+    let schema = create_complex_relationships_schema();
+    let mut emitted: Vec<(String, String)> = Vec::new();
+    CodeGenerator::new(&config.orm)
+        .generate_into(&schema, &config, &mut |path, content| {
+            emitted.push((path.to_string(), content.to_string()));
+        })
+        .expect("Code generation should succeed");
+
+    let paths: Vec<&str> = emitted.iter().map(|(path, _)| path.as_str()).collect();
+    for expected in [
+        "src/lib.rs",
+        "src/schema.rs",
+        "src/entities/mod.rs",
+        "src/entities/author.rs",
+        "src/entities/blog_post.rs",
+        "src/entities/post_status.rs",
+        "migrations/create_author_table/up.sql",
+        "migrations/create_author_table/down.sql",
+        "migrations/create_blog_post_table/up.sql",
+        "migrations/create_blog_post_table/down.sql",
+    ] {
+        assert!(paths.contains(&expected), "missing {}", expected);
+    }
+    assert_eq!(paths.len(), 10);
+    assert!(emitted.iter().all(|(_, content)| !content.is_empty()));
+    assert!(paths.iter().all(|path| !path.contains('\\')));
+
+    // Entity files are streamed in file name order
+    let entities: Vec<&str> = paths
+        .iter()
+        .copied()
+        .filter(|path| path.starts_with("src/entities/") && *path != "src/entities/mod.rs")
+        .collect();
+    assert_eq!(
+        entities,
+        [
+            "src/entities/author.rs",
+            "src/entities/blog_post.rs",
+            "src/entities/post_status.rs"
+        ]
+    );
+
+    // The same files, with the same content, as the in-memory map
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+    let files: Vec<(String, String)> = files
+        .into_iter()
+        .map(|(path, content)| (path.to_string_lossy().into_owned(), content))
+        .collect();
+    emitted.sort();
+    assert_eq!(emitted, files);
+}
+
+#[test]
+fn test_generate_client_from_introspection_fixture() {
+    let fixture = include_str!("fixtures/introspection.json");
+    let response: serde_json::Value = serde_json::from_str(fixture).unwrap();
+    let introspection: graphql_codegen_rust::introspection::Schema =
+        serde_json::from_value(response["data"]["__schema"].clone()).unwrap();
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_schema(introspection)
+        .unwrap();
+
+    assert_eq!(schema.query_root.as_deref(), Some("Query"));
+    assert_eq!(schema.mutation_root.as_deref(), Some("Mutation"));
+
+    let config = Config {
+        url: "https://countries.example.com/graphql".to_string(),
+        headers: HashMap::from([
+            (
+                "Authorization".to_string(),
+                "Bearer secret-token".to_string(),
+            ),
+            ("X-API-Key".to_string(), "key456".to_string()),
+        ]),
+        generate_client: true,
+        ..Default::default()
+    };
+    let code = graphql_codegen_rust::generator::client::generate_client(&schema, &config).unwrap();
+
+    // Header values are read from the environment, never written out
+    assert!(code.contains("(\"Authorization\", \"GRAPHQL_AUTHORIZATION\"),"));
+    assert!(code.contains("(\"X-API-Key\", \"GRAPHQL_X_API_KEY\"),"));
+    assert!(!code.contains("secret-token"));
+    assert!(!code.contains("key456"));
+
+    // One function per root query field taking only scalar arguments
+    for field in &schema.types["Query"].fields {
+        let fn_decl = format!(
+            "pub async fn {}(",
+            graphql_codegen_rust::generator::to_snake_case(&field.name)
+        );
+        let scalar_args = field
+            .arguments
+            .iter()
+            .all(|arg| matches!(arg.field_type, FieldType::Scalar(_)));
+        assert_eq!(code.contains(&fn_decl), scalar_args, "{}", field.name);
+    }
+    assert!(code.contains("// Skipped query 'countries': argument 'filter' is not a scalar"));
+
+    assert!(code.contains("pub async fn country(code: String) -> anyhow::Result<Option<Country>>"));
+    assert!(code.contains("pub async fn language_count() -> anyhow::Result<i32>"));
+    assert!(code.contains(
+        "pub async fn rename_country(code: String, name: String) -> anyhow::Result<Option<Country>>"
+    ));
+    assert!(code.contains(r#"query Country($code: ID!) { country(code: $code) { code name capital phoneCode status } }"#));
+    assert!(
+        code.contains("pub const GRAPHQL_URL: &str = \"https://countries.example.com/graphql\";")
+    );
+
+    // Response structs only select leaf fields
+    assert!(code.contains("pub struct Country {"));
+    assert!(code.contains("#[serde(rename = \"phoneCode\")]\n    pub phone_code: Option<String>,"));
+    assert!(!code.contains("pub continent: "));
+    assert!(code.contains("pub status: String,"));
+
+    let parsed = syn::parse_file(&code);
+    assert!(
+        parsed.is_ok(),
+        "Generated client should parse: {:?}",
+        parsed.err()
+    );
+}
+
+#[test]
+fn test_prelude_shadowing_mappings_are_fully_qualified() {
+    use graphql_codegen_rust::validation::{Severity, validate_schema};
+
+    let mut schema = create_single_field_schema();
+    schema
+        .types
+        .get_mut("Minimal")
+        .unwrap()
+        .fields
+        .push(ParsedField {
+            graphql_type: String::new(),
+            name: "body".to_string(),
+            field_type: FieldType::Scalar("Text".to_string()),
+            description: None,
+            is_nullable: false,
+            is_list: false,
+            list_depth: 0,
+            arguments: vec![],
+            max_length: None,
+            directives: vec![],
+            default_value: None,
+            deprecation: None,
+        });
+
+    let mut type_mappings = HashMap::new();
+    type_mappings.insert("Text".to_string(), "String".to_string());
+    let config = Config {
+        type_mappings,
+        ..Default::default()
+    };
+
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let code = &entities["minimal.rs"];
+    assert!(
+        code.contains("pub body: std::string::String,"),
+        "mapped prelude type should be fully qualified:\n{}",
+        code
+    );
+
+    // Schema names that shadow prelude types are reported
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            scalar Vec
+
+            enum Option {
+                SOME
+                NONE
+            }
+
+            type Item {
+                id: ID!
+            }
+        "#,
+        )
+        .unwrap();
+    let diagnostics = validate_schema(&schema, &Config::default());
+
+    let option = diagnostics
+        .iter()
+        .find(|d| d.type_name == "Option")
+        .expect("enum shadowing Option should be reported");
+    assert_eq!(option.severity, Severity::Error);
+    let vec = diagnostics
+        .iter()
+        .find(|d| d.type_name == "Vec")
+        .expect("scalar named Vec should be reported");
+    assert_eq!(vec.severity, Severity::Warning);
+    assert!(!diagnostics.iter().any(|d| d.type_name == "Item"));
+
+    // Generation refuses them too, unless they're excluded
+    let generator = graphql_codegen_rust::generator::create_generator(
+        &graphql_codegen_rust::cli::OrmType::Diesel,
+    );
+    let error = graphql_codegen_rust::generate_to_map(&schema, &Config::default(), &*generator)
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("shadow them: Option"),
+        "{}",
+        error
+    );
+    let config = Config {
+        exclude_types: vec!["Option".to_string()],
+        ..Default::default()
+    };
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+    assert!(files.contains_key(&PathBuf::from("src/entities/item.rs")));
+}
+
+#[test]
+fn test_sdl_schema_definition_roots() {
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            schema {
+                query: Q
+                subscription: S
+            }
+
+            type Q {
+                item: Item
+            }
+
+            type S {
+                itemAdded: Item
+            }
+
+            type Mutation {
+                addItem: Item
+            }
+
+            type Item {
+                id: ID!
+            }
+        "#,
+        )
+        .unwrap();
+
+    assert_eq!(schema.query_root.as_deref(), Some("Q"));
+    assert_eq!(schema.subscription_root.as_deref(), Some("S"));
+    // An explicit schema definition replaces the conventional root names
+    assert_eq!(schema.mutation_root, None);
+
+    let schema = parser
+        .parse_from_sdl("type Query { item: Item }\ntype Item { id: ID! }")
+        .unwrap();
+    assert_eq!(schema.query_root.as_deref(), Some("Query"));
+    assert_eq!(schema.mutation_root, None);
+    assert_eq!(schema.subscription_root, None);
+}
+
+/// Test that Sea-ORM pagination helpers are only generated when enabled
+#[test]
+fn test_sea_orm_pagination_helpers() {
+    let mut config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        generate_pagination: true,
+        ..Default::default()
+    };
+
+    let schema = create_complex_relationships_schema();
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+
+    let mod_code = generator.generate_schema(&schema, &config).unwrap();
+    assert!(mod_code.contains("pub struct Paginated<T> {"));
+    assert!(mod_code.contains("pub items: Vec<T>,"));
+    syn::parse_file(&mod_code).expect("mod.rs should parse");
+
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    for filename in ["author.rs", "blog_post.rs"] {
+        let entity = &entities[filename];
+        assert!(entity.contains("pub async fn find_paginated("));
+        assert!(entity.contains("-> Result<super::Paginated<Model>, DbErr>"));
+        assert!(entity.contains("Entity::find().paginate(db, per_page)"));
+        syn::parse_file(entity).expect("entity should parse");
+    }
+    assert!(!entities["post_status.rs"].contains("find_paginated"));
+
+    config.generate_pagination = false;
+    let mod_code = generator.generate_schema(&schema, &config).unwrap();
+    assert!(!mod_code.contains("Paginated"));
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    assert!(!entities["author.rs"].contains("find_paginated"));
+}
+
+/// Test that --types/--exclude-types restrict generation to a subset of types
+#[tokio::test]
+async fn test_type_filters_limit_generated_files() {
+    use clap::Parser;
+    use graphql_codegen_rust::cli::{Cli, Commands};
+
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type User {
+                id: ID!
+                name: String!
+                role: Role!
+            }
+
+            type Post {
+                id: ID!
+                title: String!
+                author: User!
+            }
+
+            type Comment {
+                id: ID!
+                body: String!
+                post: Post!
+            }
+
+            type Tag {
+                id: ID!
+                status: Status!
+            }
+
+            enum Role { ADMIN USER }
+            enum Status { ACTIVE ARCHIVED }
+        "#,
+        )
+        .unwrap();
+
+    let cli = Cli::try_parse_from(["graphql-codegen-rust", "generate", "--types", "User"]).unwrap();
+    let Some(Commands::Generate { types, .. }) = cli.command else {
+        panic!("Expected Generate command");
+    };
+
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        output_dir: temp_dir.path().to_path_buf(),
+        include_types: types,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+
+    let mut entity_files: Vec<String> = std::fs::read_dir(temp_dir.path().join("src/entities"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    entity_files.sort();
+    // Enums used by an included type come along with it
+    assert_eq!(entity_files, vec!["mod.rs", "role.rs", "user.rs"]);
+    let user = std::fs::read_to_string(temp_dir.path().join("src/entities/user.rs")).unwrap();
+    assert!(user.contains("pub role: Role"), "{}", user);
+    let migration_count = std::fs::read_dir(temp_dir.path().join("migrations"))
+        .unwrap()
+        .count();
+    assert_eq!(migration_count, 1);
+
+    // Excluding a type also drops fields that reference it
+    let config = Config {
+        exclude_types: vec!["Post".to_string()],
+        ..Default::default()
+    };
+    let filtered = schema.filter_types(&config.include_types, &config.exclude_types);
+    assert!(!filtered.types.contains_key("Post"));
+    assert_eq!(filtered.types["User"].fields.len(), 3);
+    let comment_fields: Vec<&str> = filtered.types["Comment"]
+        .fields
+        .iter()
+        .map(|f| f.name.as_str())
+        .collect();
+    assert_eq!(comment_fields, vec!["id", "body"]);
+
+    let mut files = Vec::new();
+    graphql_codegen_rust::generate_into(&schema, &config, &*generator, &mut |path, content| {
+        files.push((path.to_string(), content.to_string()))
+    })
+    .unwrap();
+    assert!(!files.iter().any(|(path, _)| path.contains("post")));
+    let (_, comment_sql) = files
+        .iter()
+        .find(|(path, _)| path.contains("comment") && path.ends_with("up.sql"))
+        .expect("comment migration should be generated");
+    assert!(!comment_sql.contains("post"));
+}
+
+/// Test that excluding an enum drops the fields that use it
+#[test]
+fn test_exclude_enum_drops_its_fields() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            enum Status { ACTIVE ARCHIVED }
+
+            type User {
+                id: ID!
+                name: String!
+                status: Status!
+                history: [Status!]
+            }
+            "#,
+        )
+        .unwrap();
+    let config = Config {
+        exclude_types: vec!["Status".to_string()],
+        ..Default::default()
+    };
+
+    let filtered = schema.filter_types(&config.include_types, &config.exclude_types);
+    assert!(filtered.enums.is_empty());
+    let fields: Vec<&str> = filtered.types["User"]
+        .fields
+        .iter()
+        .map(|f| f.name.as_str())
+        .collect();
+    assert_eq!(fields, vec!["id", "name"]);
+
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+    assert!(!files.contains_key(&PathBuf::from("src/entities/status.rs")));
+    assert!(!files.values().any(|content| content.contains("Status")));
+    syn::parse_file(&files[&PathBuf::from("src/entities/user.rs")]).unwrap();
+}
+
+/// Test that header secret references are resolved before introspection
+#[tokio::test]
+async fn test_secret_resolver_resolves_header_references() {
+    use graphql_codegen_rust::secrets::SecretResolver;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    struct FakeResolver;
+
+    impl SecretResolver for FakeResolver {
+        fn resolve(&self, reference: &str) -> anyhow::Result<String> {
+            match reference {
+                "vault://secret/api#token" => Ok("s3cr3t".to_string()),
+                other => anyhow::bail!("unknown secret {}", other),
+            }
+        }
+    }
+
+    // Capture the introspection request and reject it
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = socket.read(&mut buf).await.unwrap();
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+        socket
+            .write_all(b"HTTP/1.1 500 Internal Server Error\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        String::from_utf8_lossy(&request).to_lowercase()
+    });
+
+    let mut headers = HashMap::new();
+    headers.insert(
+        "Authorization".to_string(),
+        "Bearer vault://secret/api#token".to_string(),
+    );
+    headers.insert("X-Plain".to_string(), "vault://not-resolved".to_string());
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        url: format!("http://{}/graphql", addr),
+        headers,
+        secret_schemes: vec!["vault".to_string()],
+        output_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    };
+
+    // The fake resolver doesn't know X-Plain's reference, so leave it out first
+    let mut config_plain = config.clone();
+    config_plain.headers.remove("X-Plain");
+    let generator = CodeGenerator::new(&config.orm).with_secret_resolver(FakeResolver);
+    let result = generator.generate_from_config(&config_plain).await;
+    assert!(result.is_err(), "server rejects the request");
+
+    let request = server.await.unwrap();
+    assert!(
+        request.contains("authorization: bearer s3cr3t"),
+        "{}",
+        request
+    );
+    assert!(!request.contains("vault://"));
+
+    // Failing resolvers surface an error naming the header
+    let err = generator.generate_from_config(&config).await.unwrap_err();
+    assert!(err.to_string().contains("X-Plain"), "{}", err);
+}
+
+/// Test that root operation types don't become entities or tables
+#[test]
+fn test_root_types_are_not_generated() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type Query {
+                user(id: ID!): User
+                users: [User!]!
+            }
+
+            type User {
+                id: ID!
+                name: String!
+            }
+        "#,
+        )
+        .unwrap();
+
+    let generate = |config: &Config| {
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+        let mut files = HashMap::new();
+        graphql_codegen_rust::generate_into(&schema, config, &*generator, &mut |path, content| {
+            files.insert(path.to_string(), content.to_string());
+        })
+        .unwrap();
+        files
+    };
+
+    let config = Config {
+        generate_client: true,
+        ..Default::default()
+    };
+    let files = generate(&config);
+    assert!(files.contains_key("src/entities/user.rs"));
+    assert!(!files.contains_key("src/entities/query.rs"));
+    assert!(
+        !files
+            .keys()
+            .any(|path| path.starts_with("migrations/") && path.contains("query"))
+    );
+    assert!(!files["src/schema.rs"].contains("query ("));
+    // The client still sees the root fields
+    assert!(files["src/client.rs"].contains("pub async fn users("));
+
+    let config = Config {
+        generate_root_types: true,
+        ..Default::default()
+    };
+    let files = generate(&config);
+    assert!(files.contains_key("src/entities/query.rs"));
+}
+
+/// Test that the primary key default strategy picks the UUID generator and extension
+#[test]
+fn test_pk_default_uuid_generate_v4() {
+    use graphql_codegen_rust::config::{PkDefault, SeaOrmMigrationStyle};
+    use graphql_codegen_rust::generator::MigrationFile;
+
+    let schema = create_single_field_schema();
+    let mut config = Config {
+        db: graphql_codegen_rust::cli::DatabaseType::Postgres,
+        pk_default: PkDefault::UuidGenerateV4,
+        ..Default::default()
+    };
+
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let sql = up_sql(&migrations[0]);
+    assert!(
+        sql.starts_with("CREATE EXTENSION IF NOT EXISTS \"uuid-ossp\";\n\nCREATE TABLE minimal")
+    );
+    assert!(sql.contains("id UUID PRIMARY KEY DEFAULT uuid_generate_v4()"));
+
+    // Sea-ORM Rust migrations enable the extension through the connection
+    config.orm = graphql_codegen_rust::cli::OrmType::SeaOrm;
+    config.sea_orm_migration_style = SeaOrmMigrationStyle::Rust;
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let MigrationFile::Rust { code, .. } = &migrations[0] else {
+        panic!("expected a Rust migration");
+    };
+    assert!(
+        code.contains(".execute_unprepared(\"CREATE EXTENSION IF NOT EXISTS \\\"uuid-ossp\\\";\")")
+    );
+    assert!(code.contains(".default(Expr::cust(\"uuid_generate_v4()\"))"));
+    syn::parse_file(code).expect("migration should parse");
+
+    // The default strategy needs no extension
+    config.orm = graphql_codegen_rust::cli::OrmType::Diesel;
+    config.pk_default = PkDefault::Auto;
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let sql = up_sql(&migrations[0]);
+    assert!(!sql.contains("EXTENSION"));
+    assert!(sql.contains("id UUID PRIMARY KEY DEFAULT gen_random_uuid()"));
+}
+
+/// Test that Diesel output includes a hub wiring schema and entities together
+#[tokio::test]
+async fn test_diesel_module_hub() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        output_dir: temp_dir.path().to_path_buf(),
+        generate_lib_rs: true,
+        ..Default::default()
+    };
+
+    let schema = create_complex_relationships_schema();
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+
+    let hub = std::fs::read_to_string(temp_dir.path().join("src/lib.rs")).unwrap();
+    assert!(hub.contains("pub mod entities;"));
+    assert!(hub.contains("pub mod schema;"));
+    assert!(
+        hub.contains("pub use super::entities::author::{Author, NewAuthor, UpdateAuthor};"),
+        "{}",
+        hub
+    );
+    assert!(hub.contains("pub use super::entities::post_status::PostStatus;"));
+    assert!(hub.contains("pub use prelude::*;"));
+    syn::parse_file(&hub).expect("hub should parse");
+
+    let entities_mod =
+        std::fs::read_to_string(temp_dir.path().join("src/entities/mod.rs")).unwrap();
+    assert!(entities_mod.contains("pub mod author;"));
+    assert!(entities_mod.contains("pub mod blog_post;"));
+    assert!(entities_mod.contains("pub mod post_status;"));
+
+    // Every entity file written next to mod.rs is declared in it
+    for entry in std::fs::read_dir(temp_dir.path().join("src/entities")).unwrap() {
+        let path = entry.unwrap().path();
+        let module = path.file_stem().unwrap().to_str().unwrap();
+        if module != "mod" {
+            assert!(
+                entities_mod.contains(&format!("pub mod {};", module)),
+                "{} is not declared in entities/mod.rs",
+                module
+            );
+        }
+    }
+}
+
+/// Test that Relay pagination types are skipped by the default skip patterns
+#[test]
+fn test_relay_pagination_types_are_skipped() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type User {
+                id: ID!
+                name: String!
+                friends: UserConnection
+            }
+
+            type UserConnection {
+                edges: [UserEdge!]!
+                pageInfo: PageInfo!
+            }
+
+            type UserEdge {
+                cursor: String!
+                node: User!
+            }
+
+            type PageInfo {
+                hasNextPage: Boolean!
+                endCursor: String
+            }
+        "#,
+        )
+        .unwrap();
+
+    let generate = |config: &Config| {
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+        let mut files = HashMap::new();
+        graphql_codegen_rust::generate_into(&schema, config, &*generator, &mut |path, content| {
+            files.insert(path.to_string(), content.to_string());
+        })
+        .map(|_| files)
+    };
+
+    let files = generate(&Config::default()).unwrap();
+    let mut entities: Vec<&str> = files
+        .keys()
+        .filter_map(|path| path.strip_prefix("src/entities/"))
+        .collect();
+    entities.sort();
+    assert_eq!(entities, vec!["mod.rs", "user.rs"]);
+    assert!(!files.keys().any(|path| path.contains("connection")
+        || path.contains("edge")
+        || path.contains("page_info")));
+    // The field pointing at the skipped connection type is dropped too
+    assert!(!files["src/entities/user.rs"].contains("friends"));
+
+    // An explicit empty list generates every type
+    let config = Config {
+        skip_patterns: Some(vec![]),
+        ..Default::default()
+    };
+    let files = generate(&config).unwrap();
+    assert!(files.contains_key("src/entities/user_connection.rs"));
+    assert!(files.contains_key("src/entities/page_info.rs"));
+
+    let config = Config {
+        skip_patterns: Some(vec!["(".to_string()]),
+        ..Default::default()
+    };
+    let err = generate(&config).unwrap_err();
+    assert!(err.to_string().contains("Invalid skip pattern"));
+}
+
+/// Test that length-limited String fields become VARCHAR columns
+#[test]
+fn test_string_length_mappings_emit_varchar() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            directive @maxLength(value: Int!) on FIELD_DEFINITION
+
+            type User {
+                id: ID!
+                email: String!
+                handle: String! @maxLength(value: 32)
+                bio: String
+            }
+        "#,
+        )
+        .unwrap();
+
+    let mut string_length_mappings = HashMap::new();
+    string_length_mappings.insert("User.email".to_string(), 255);
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Mysql,
+        string_length_mappings,
+        ..Default::default()
+    };
+
+    let diesel = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = diesel.generate_migrations(&schema, &config).unwrap();
+    let sql = up_sql(&migrations[0]);
+    assert!(sql.contains("email VARCHAR(255) NOT NULL"));
+    assert!(sql.contains("handle VARCHAR(32) NOT NULL"));
+    assert!(sql.contains("bio TEXT"));
+
+    // SQLite has no meaningful VARCHAR limit
+    let config = Config {
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        ..config
+    };
+    let migrations = diesel.generate_migrations(&schema, &config).unwrap();
+    assert!(up_sql(&migrations[0]).contains("email TEXT NOT NULL"));
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        db: graphql_codegen_rust::cli::DatabaseType::Postgres,
+        ..config
+    };
+    let sea_orm = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = sea_orm.generate_migrations(&schema, &config).unwrap();
+    let graphql_codegen_rust::generator::MigrationFile::Rust { code, .. } = &migrations[0] else {
+        panic!("Expected Rust migration");
+    };
+    assert!(code.contains("ColumnDef::new(Alias::new(\"email\")).string_len(255)"));
+}
+
+/// Test that --dump-introspection writes the raw response before parsing
+#[tokio::test]
+async fn test_dump_introspection_writes_raw_json() {
+    let fixture = include_str!("fixtures/introspection.json");
+    let (addr, server) = serve_introspection_fixture(|_| true).await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let dump_path = temp_dir.path().join("debug").join("introspection.json");
+    let config = Config {
+        url: format!("http://{}/graphql", addr),
+        output_dir: temp_dir.path().join("generated"),
+        dump_introspection: Some(dump_path.clone()),
+        ..Default::default()
+    };
+
+    let generator = CodeGenerator::new(&config.orm);
+    generator.generate_from_config(&config).await.unwrap();
+    server.await.unwrap();
+
+    // The dump is the exact response body and parses back into the same schema
+    let dumped = std::fs::read_to_string(&dump_path).unwrap();
+    assert_eq!(dumped, fixture);
+    let introspector = graphql_codegen_rust::introspection::Introspector::new();
+    let (schema, _) = introspector.parse_introspection_json(&dumped).unwrap();
+    let parsed = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_schema(schema)
+        .unwrap();
+    assert!(parsed.types.contains_key("Country"));
+    assert!(
+        temp_dir
+            .path()
+            .join("generated/src/entities/country.rs")
+            .exists()
+    );
+}
+
+/// Test that date/time scalars map to the configured crate's types
+#[test]
+fn test_datetime_crate_selects_time_types() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            scalar DateTime
+            scalar Date
+
+            type Event {
+                id: ID!
+                startsAt: DateTime!
+                day: Date
+            }
+        "#,
+        )
+        .unwrap();
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Postgres,
+        datetime_crate: graphql_codegen_rust::config::DateTimeCrate::Time,
+        ..Default::default()
+    };
+    let diesel = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = diesel.generate_entities(&schema, &config).unwrap();
+    let entity = &entities["event.rs"];
+    assert!(entity.contains("pub starts_at: time::OffsetDateTime,"));
+    assert!(entity.contains("pub day: time::Date,"));
+
+    let table = diesel.generate_schema(&schema, &config).unwrap();
+    assert!(table.contains("starts_at -> Timestamptz.not_null(),"));
+    let migrations = diesel.generate_migrations(&schema, &config).unwrap();
+    assert!(up_sql(&migrations[0]).contains("starts_at TIMESTAMPTZ NOT NULL"));
+
+    // chrono is the default
+    let config = Config {
+        datetime_crate: Default::default(),
+        ..config
+    };
+    let entities = diesel.generate_entities(&schema, &config).unwrap();
+    assert!(entities["event.rs"].contains("pub starts_at: chrono::DateTime<chrono::Utc>,"));
+
+    // Explicit mappings win over the built-in types
+    let mut type_mappings = HashMap::new();
+    type_mappings.insert("DateTime".to_string(), "i64".to_string());
+    let config = Config {
+        type_mappings,
+        ..config
+    };
+    let entities = diesel.generate_entities(&schema, &config).unwrap();
+    assert!(entities["event.rs"].contains("pub starts_at: i64,"));
+}
+
+/// Test that @unique fields and configured unique fields get UNIQUE constraints
+#[test]
+fn test_unique_directive_and_unique_fields() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            directive @unique on FIELD_DEFINITION
+
+            type User {
+                id: ID!
+                email: String! @unique
+                handle: String!
+                bio: String
+            }
+        "#,
+        )
+        .unwrap();
+    assert!(schema.types["User"].fields[1].has_directive("unique"));
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        unique_fields: vec!["User.handle".to_string()],
+        ..Default::default()
+    };
+    let diesel = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = diesel.generate_migrations(&schema, &config).unwrap();
+    let sql = up_sql(&migrations[0]);
+    assert!(sql.contains("email TEXT NOT NULL UNIQUE"));
+    assert!(sql.contains("handle TEXT NOT NULL UNIQUE"));
+    assert!(!sql.contains("bio TEXT UNIQUE"));
+    assert!(sql.contains("CREATE UNIQUE INDEX uq_user_email ON user (email);"));
+    assert!(sql.contains("CREATE UNIQUE INDEX uq_user_handle ON user (handle);"));
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        ..config
+    };
+    let sea_orm = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = sea_orm.generate_migrations(&schema, &config).unwrap();
+    let graphql_codegen_rust::generator::MigrationFile::Rust { code, .. } = &migrations[0] else {
+        panic!("Expected Rust migration");
+    };
+    assert!(code.contains("ColumnDef::new(Alias::new(\"email\")).text().not_null().unique_key()"));
+    assert!(code.contains(".name(\"uq_user_email\")"));
+    syn::parse_file(code).expect("generated migration should be valid Rust");
+}
+
+/// Test that @index fields and configured indexed fields get reversible indexes
+#[test]
+fn test_index_directive_and_indexed_fields() {
+    let long_field = "a".repeat(70);
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(&format!(
+            r#"
+            directive @index on FIELD_DEFINITION
+
+            type Post {{
+                id: ID!
+                slug: String! @index
+                publishedAt: String
+                {long_field}: Int
+                {long_field}b: Int
+            }}
+        "#
+        ))
+        .unwrap();
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Postgres,
+        indexed_fields: vec![
+            "Post.publishedAt".to_string(),
+            format!("Post.{}", long_field),
+            format!("Post.{}b", long_field),
+        ],
+        ..Default::default()
+    };
+    let diesel = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = diesel.generate_migrations(&schema, &config).unwrap();
+    let graphql_codegen_rust::generator::MigrationFile::Sql {
+        up_sql, down_sql, ..
+    } = &migrations[0]
+    else {
+        panic!("Expected SQL migration");
+    };
+    assert!(up_sql.contains("CREATE INDEX idx_post_slug ON post (slug);"));
+    assert!(up_sql.contains("CREATE INDEX idx_post_published_at ON post (published_at);"));
+    assert!(down_sql.contains("DROP INDEX idx_post_slug;"));
+    assert!(down_sql.contains("DROP INDEX idx_post_published_at;"));
+    assert!(down_sql.ends_with("DROP TABLE post;"));
+
+    // Long names are shortened to PostgreSQL's 63-byte limit and stay distinct
+    let long_names: Vec<&str> = up_sql
+        .lines()
+        .filter(|line| line.contains("aaaa"))
+        .filter_map(|line| line.strip_prefix("CREATE INDEX "))
+        .filter_map(|line| line.split_whitespace().next())
+        .collect();
+    assert_eq!(long_names.len(), 2);
+    assert!(long_names.iter().all(|name| name.len() <= 63));
+    assert_ne!(long_names[0], long_names[1]);
+    for name in &long_names {
+        assert!(down_sql.contains(&format!("DROP INDEX {};", name)));
+    }
+
+    // MySQL needs the table to drop an index; Sea-ORM emits the same statements
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        db: graphql_codegen_rust::cli::DatabaseType::Mysql,
+        sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Raw,
+        ..config
+    };
+    let sea_orm = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = sea_orm.generate_migrations(&schema, &config).unwrap();
+    let graphql_codegen_rust::generator::MigrationFile::Sql {
+        up_sql, down_sql, ..
+    } = &migrations[0]
+    else {
+        panic!("Expected SQL migration");
+    };
+    assert!(up_sql.contains("CREATE INDEX idx_post_slug ON post (slug);"));
+    assert!(down_sql.contains("DROP INDEX idx_post_slug ON post;"));
+
+    let config = Config {
+        sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Rust,
+        ..config
+    };
+    let migrations = sea_orm.generate_migrations(&schema, &config).unwrap();
+    let graphql_codegen_rust::generator::MigrationFile::Rust { code, .. } = &migrations[0] else {
+        panic!("Expected Rust migration");
+    };
+    assert!(code.contains(".name(\"idx_post_slug\")"));
+    assert!(code.contains(".drop_index(Index::drop().name(\"idx_post_slug\")"));
+    syn::parse_file(code).expect("generated migration should be valid Rust");
+}
+
+/// Test that one failing type is skipped under continue_on_error
+#[tokio::test]
+async fn test_continue_on_error_skips_failing_type() {
+    use graphql_codegen_rust::generator::{CodeGenerator, MigrationFile, diesel::DieselGenerator};
+
+    // Delegates to Diesel but panics on entities and errors on migrations for `Broken`
+    struct FlakyGenerator(DieselGenerator);
+
+    impl CodeGenerator for FlakyGenerator {
+        fn generate_schema(
+            &self,
+            schema: &ParsedSchema,
+            config: &Config,
+        ) -> anyhow::Result<String> {
+            self.0.generate_schema(schema, config)
+        }
+
+        fn generate_entities(
+            &self,
+            schema: &ParsedSchema,
+            config: &Config,
+        ) -> anyhow::Result<HashMap<String, String>> {
+            assert!(!schema.types.contains_key("Broken"), "malformed field");
+            self.0.generate_entities(schema, config)
+        }
+
+        fn generate_migrations(
+            &self,
+            schema: &ParsedSchema,
+            config: &Config,
+        ) -> anyhow::Result<Vec<MigrationFile>> {
+            self.0.generate_migrations(schema, config)
+        }
+    }
+
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type User {
+                id: ID!
+                name: String!
+                broken: Broken
+            }
+
+            type Broken {
+                id: ID!
+            }
+        "#,
+        )
+        .unwrap();
+    let generator = FlakyGenerator(DieselGenerator::new());
+
+    // Without the flag the panic is an error and types aren't generated one by one
+    let config = Config::default();
+    let err = graphql_codegen_rust::generate_into(&schema, &config, &generator, &mut |_, _| {})
+        .unwrap_err();
+    assert!(err.to_string().contains("malformed field"));
+    assert!(err.to_string().contains("--continue-on-error"));
+
+    let config = Config {
+        continue_on_error: true,
+        ..Default::default()
+    };
+    let mut files = HashMap::new();
+    graphql_codegen_rust::generate_into(&schema, &config, &generator, &mut |path, content| {
+        files.insert(path.to_string(), content.to_string());
+    })
+    .unwrap();
+    assert!(files.contains_key("src/entities/user.rs"));
+    assert!(!files.contains_key("src/entities/broken.rs"));
+    assert!(!files["src/entities/user.rs"].contains("broken"));
+
+    // The skipped type is reported in the summary
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config = Config {
+        output_dir: temp_dir.path().to_path_buf(),
+        ..config
+    };
+    let summary = graphql_codegen_rust::generate_all_code(&schema, &config, &generator)
+        .await
+        .unwrap();
+    assert_eq!(summary.skipped_types.len(), 1);
+    assert!(summary.skipped_types["Broken"].contains("malformed field"));
+
+    // The in-memory map reports it too
+    let (files, summary) =
+        graphql_codegen_rust::generate_to_map(&schema, &config, &generator).unwrap();
+    assert!(!files.contains_key(&PathBuf::from("src/entities/broken.rs")));
+    assert_eq!(
+        summary.skipped_types.keys().collect::<Vec<_>>(),
+        vec!["Broken"]
+    );
+}
+
+/// Test that Diesel down migrations drop every index the up migration creates
+#[test]
+fn test_diesel_down_migration_drops_foreign_key_indexes() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            directive @index on FIELD_DEFINITION
+
+            type Author {
+                id: ID!
+                name: String! @index
+            }
+
+            type Post {
+                id: ID!
+                title: String!
+                author: Author!
+                editor: Author
+            }
+        "#,
+        )
+        .unwrap();
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Postgres,
+        auto_fk_index: true,
+        ..Default::default()
+    };
+    let diesel = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = diesel.generate_migrations(&schema, &config).unwrap();
+
+    let mut saw_index = false;
+    for migration in &migrations {
+        let graphql_codegen_rust::generator::MigrationFile::Sql {
+            up_sql, down_sql, ..
+        } = migration
+        else {
+            panic!("Expected SQL migration");
+        };
+        let mut created: Vec<&str> = up_sql
+            .lines()
+            .filter_map(|line| line.strip_prefix("CREATE INDEX "))
+            .filter_map(|line| line.split_whitespace().next())
+            .collect();
+        let mut dropped: Vec<&str> = down_sql
+            .lines()
+            .filter_map(|line| line.strip_prefix("DROP INDEX "))
+            .map(|line| line.trim_end_matches(';'))
+            .collect();
+        created.sort();
+        dropped.sort();
+        assert_eq!(created, dropped, "{}", migration.name());
+        saw_index |= !created.is_empty();
+        assert!(
+            down_sql
+                .trim_end()
+                .lines()
+                .last()
+                .unwrap()
+                .starts_with("DROP TABLE")
+        );
+    }
+    assert!(saw_index, "the schema has foreign-key columns");
+}
+
+/// Test that auto_fk_index = false leaves foreign key columns unindexed
+#[test]
+fn test_auto_fk_index_disabled() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            directive @index on FIELD_DEFINITION
+
+            type Author { id: ID! }
+            type Post {
+                id: ID!
+                author: Author!
+                editor: Author @index
+            }
+        "#,
+        )
+        .unwrap();
+    let mut config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        auto_fk_index: true,
+        ..Default::default()
+    };
+    let diesel = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let post_up_sql =
+        |config: &Config| {
+            diesel
+                .generate_migrations(&schema, config)
+                .unwrap()
+                .into_iter()
+                .find_map(|migration| match migration {
+                    graphql_codegen_rust::generator::MigrationFile::Sql {
+                        name, up_sql, ..
+                    } if name == "create_post_table" => Some(up_sql),
+                    _ => None,
+                })
+                .unwrap()
+        };
+
+    let sql = post_up_sql(&config);
+    assert!(sql.contains("CREATE INDEX idx_post_author ON post (author);"));
+    assert!(sql.contains("CREATE INDEX idx_post_editor ON post (editor);"));
+
+    // Explicitly indexed foreign keys keep their index
+    config.auto_fk_index = false;
+    let sql = post_up_sql(&config);
+    assert!(!sql.contains("idx_post_author"));
+    assert_eq!(sql.matches("CREATE INDEX").count(), 1);
+    assert!(sql.contains("CREATE INDEX idx_post_editor ON post (editor);"));
+}
+
+/// Test that to-many relations and computed fields are ignored Sea-ORM model fields
+#[test]
+fn test_sea_orm_ignores_non_column_fields() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            directive @computed on FIELD_DEFINITION
+
+            enum Role {
+                ADMIN
+                MEMBER
+            }
+
+            type Author {
+                id: ID!
+                name: String!
+                posts: [Post!]!
+                postCount: Int! @computed
+                roles: [Role!]!
+            }
+
+            type Post {
+                id: ID!
+                title: String!
+                author: Author!
+            }
+        "#,
+        )
+        .unwrap();
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        ..Default::default()
+    };
+    let sea_orm = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = sea_orm.generate_entities(&schema, &config).unwrap();
+    let author = &entities["author.rs"];
+    assert!(author.contains("    #[sea_orm(ignore)]\n    pub posts: Vec<i32>,"));
+    assert!(author.contains("    #[sea_orm(ignore)]\n    pub post_count: i32,"));
+    assert!(!author.contains("column_name = \"posts\""));
+    assert!(!author.contains("    posts,\n"));
+    // Lists of enums are still stored
+    assert!(author.contains("column_name = \"roles\""));
+
+    let migrations = sea_orm.generate_migrations(&schema, &config).unwrap();
+    let author_migration = migrations
+        .iter()
+        .find(|m| m.name().ends_with("create_author_table"))
+        .unwrap();
+    let graphql_codegen_rust::generator::MigrationFile::Rust { code, .. } = author_migration else {
+        panic!("Expected Rust migration");
+    };
+    assert!(!code.contains("Alias::new(\"posts\")"));
+    assert!(!code.contains("Alias::new(\"post_count\")"));
+    assert!(code.contains("Alias::new(\"name\")"));
+
+    let config = Config {
+        sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Raw,
+        ..config
+    };
+    let migrations = sea_orm.generate_migrations(&schema, &config).unwrap();
+    let sql = migrations
+        .iter()
+        .find(|m| m.name().ends_with("create_author_table"))
+        .map(up_sql)
+        .unwrap();
+    assert!(!sql.contains("posts"));
+    assert!(!sql.contains("post_count"));
+}
+
+/// Test that types sharing a table name are reported
+#[test]
+fn test_validation_detects_table_collisions() {
+    use graphql_codegen_rust::validation::{
+        Severity, find_table_collisions, unmapped_scalars, validate_schema,
+    };
+
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            scalar Money
+            scalar DateTime
+
+            type UserProfile {
+                id: ID!
+                balance: Money
+                updatedAt: DateTime
+            }
+
+            type userProfile {
+                id: ID!
+            }
+
+            type Order {
+                id: ID!
+            }
+
+            enum ORDER {
+                OPEN
+            }
+        "#,
+        )
+        .unwrap();
+
+    let collisions = find_table_collisions(&schema, &Config::default());
+    assert_eq!(
+        collisions,
+        vec![
+            (
+                "order".to_string(),
+                vec!["ORDER".to_string(), "Order".to_string()]
+            ),
+            (
+                "user_profile".to_string(),
+                vec!["UserProfile".to_string(), "userProfile".to_string()]
+            ),
+        ]
+    );
+
+    let errors: Vec<_> = validate_schema(&schema, &Config::default())
+        .into_iter()
+        .filter(|d| d.severity == Severity::Error)
+        .collect();
+    assert_eq!(errors.len(), 4);
+    assert!(errors.iter().any(|d| d.type_name == "UserProfile"
+        && d.message == "maps to table 'user_profile' together with userProfile"));
+
+    // Pluralized tables can collide with names that differ in snake_case
+    let plural_schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type Status { id: ID! }
+            enum Statuses { OPEN }
+            type Result { id: ID! }
+        "#,
+        )
+        .unwrap();
+    assert!(find_table_collisions(&plural_schema, &Config::default()).is_empty());
+    let plural_config = Config {
+        pluralize_tables: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        find_table_collisions(&plural_schema, &plural_config),
+        vec![(
+            "statuses".to_string(),
+            vec!["Status".to_string(), "Statuses".to_string()]
+        )]
+    );
+
+    // Collisions are reported alongside the other errors
+    let errors: Vec<_> = validate_schema(&plural_schema, &plural_config)
+        .into_iter()
+        .filter(|d| d.severity == Severity::Error)
+        .map(|d| d.type_name)
+        .collect();
+    assert_eq!(errors, vec!["Result", "Status", "Statuses"]);
+
+    // DateTime has a built-in mapping; Money needs one
+    let config = Config::default();
+    assert_eq!(
+        unmapped_scalars(&schema, &config),
+        vec!["Money".to_string()]
+    );
+    let mut type_mappings = HashMap::new();
+    type_mappings.insert("Money".to_string(), "rust_decimal::Decimal".to_string());
+    let config = Config {
+        type_mappings,
+        ..Default::default()
+    };
+    assert!(unmapped_scalars(&schema, &config).is_empty());
+}
+
+/// Test that generated files use the configured line endings
+#[tokio::test]
+async fn test_line_endings_crlf() {
+    let schema = create_complex_relationships_schema();
+    let generator_inner = graphql_codegen_rust::generator::create_generator(
+        &graphql_codegen_rust::cli::OrmType::Diesel,
+    );
+
+    let lf_dir = TempDir::new().expect("Failed to create temp dir");
+    let crlf_dir = TempDir::new().expect("Failed to create temp dir");
+    for (dir, line_endings) in [
+        (&lf_dir, graphql_codegen_rust::config::LineEndings::Lf),
+        (&crlf_dir, graphql_codegen_rust::config::LineEndings::Crlf),
+    ] {
+        let config = Config {
+            url: "https://example.com/graphql".to_string(),
+            orm: graphql_codegen_rust::cli::OrmType::Diesel,
+            db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+            output_dir: dir.path().to_path_buf(),
+            generate_migrations: true,
+            generate_entities: true,
+            line_endings,
+            ..Default::default()
+        };
+        graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+            .await
+            .expect("Code generation should succeed");
+    }
+
+    let lf_schema = std::fs::read_to_string(lf_dir.path().join("src/schema.rs")).unwrap();
+    assert!(!lf_schema.contains('\r'));
+
+    let crlf_schema = std::fs::read_to_string(crlf_dir.path().join("src/schema.rs")).unwrap();
+    let crlf_entity =
+        std::fs::read_to_string(crlf_dir.path().join("src/entities/author.rs")).unwrap();
+    for content in [&crlf_schema, &crlf_entity] {
+        assert!(content.contains("\r\n"));
+        assert_eq!(
+            content.matches('\n').count(),
+            content.matches("\r\n").count(),
+            "every newline should be CRLF"
+        );
+    }
+}
+
+/// Test that types mapping to the same table name fail generation
+#[tokio::test]
+async fn test_table_name_collisions_fail_generation() {
+    let sdl = r#"
+        type APIKey { id: ID! }
+        type ApiKey { id: ID! }
+        type User { id: ID! }
+    "#;
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(sdl)
+        .expect("SDL should parse");
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        output_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    };
+    let generator_inner = graphql_codegen_rust::generator::create_generator(&config.orm);
+
+    let error = graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .expect_err("Colliding types should fail generation");
+    let message = error.to_string();
+    assert!(
+        message.contains("'api_key' from APIKey, ApiKey"),
+        "{}",
+        message
+    );
+    assert!(!temp_dir.path().join("src/entities").exists());
+
+    // Excluding one of them resolves the collision
+    let config = Config {
+        exclude_types: vec!["APIKey".to_string()],
+        ..config
+    };
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .expect("Generation should succeed once the collision is excluded");
+}
+
+/// Test that Int enum storage uses `@value` discriminants
+#[test]
+fn test_enum_int_storage_uses_value_directives() {
+    use graphql_codegen_rust::generator::MigrationFile;
+
+    let sdl = r#"
+        directive @value(int: Int!) on ENUM_VALUE
+        enum Status {
+            DRAFT @value(int: 1)
+            PUBLISHED
+            ARCHIVED @value(int: 10)
+        }
+        type Post { id: ID! status: Status! }
+    "#;
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(sdl)
+        .expect("SDL should parse");
+
+    for orm in [
+        graphql_codegen_rust::cli::OrmType::Diesel,
+        graphql_codegen_rust::cli::OrmType::SeaOrm,
+        graphql_codegen_rust::cli::OrmType::Plain,
+    ] {
+        let config = Config {
+            url: "https://example.com/graphql".to_string(),
+            orm: orm.clone(),
+            db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+            enum_storage: graphql_codegen_rust::config::EnumStorage::Int,
+            ..Default::default()
+        };
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+        let entities = generator.generate_entities(&schema, &config).unwrap();
+        let status = &entities["status.rs"];
+
+        syn::parse_file(status).expect("Generated enum should parse");
+        assert!(status.contains("impl TryFrom<i32> for Status"));
+        assert!(status.contains("1 => Ok(Status::DRAFT)"));
+        assert!(status.contains("2 => Ok(Status::PUBLISHED)"));
+        assert!(status.contains("10 => Ok(Status::ARCHIVED)"));
+        match orm {
+            graphql_codegen_rust::cli::OrmType::Diesel
+            | graphql_codegen_rust::cli::OrmType::Plain => {
+                assert!(status.contains("DRAFT = 1,"));
+                assert!(status.contains("ARCHIVED = 10,"));
+            }
+            graphql_codegen_rust::cli::OrmType::SeaOrm => {
+                assert!(status.contains("#[sea_orm(num_value = 10)]"));
+            }
+        }
+
+        let migrations = generator.generate_migrations(&schema, &config).unwrap();
+        let post = migrations
+            .iter()
+            .find(|m| match m {
+                MigrationFile::Sql { name, .. } | MigrationFile::Rust { name, .. } => {
+                    name.contains("post")
+                }
+            })
+            .unwrap();
+        match post {
+            MigrationFile::Sql { up_sql, .. } => assert!(up_sql.contains("status INTEGER")),
+            MigrationFile::Rust { code, .. } => assert!(code.contains(".integer()")),
+        }
+    }
+}
+
+/// Test that only_changed_tables emits migrations for new tables only
+#[tokio::test]
+async fn test_only_changed_tables_emits_new_table_migration() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let migrations_dir = temp_dir.path().join("migrations");
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        output_dir: temp_dir.path().to_path_buf(),
+        only_changed_tables: true,
+        ..Default::default()
+    };
+    let generator_inner = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let migration_names = || -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(&migrations_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    };
+
+    let schema = parser
+        .parse_from_sdl("type User { id: ID! name: String! }")
+        .unwrap();
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap();
+    assert_eq!(migration_names(), vec!["create_user_table"]);
+
+    // Existing migrations are history and must not be rewritten
+    let user_up = migrations_dir.join("create_user_table/up.sql");
+    std::fs::write(&user_up, "-- @generated by graphql-codegen-rust (applied)").unwrap();
+
+    let schema = parser
+        .parse_from_sdl("type User { id: ID! name: String! } type Post { id: ID! title: String! }")
+        .unwrap();
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap();
+    assert_eq!(
+        migration_names(),
+        vec!["create_post_table", "create_user_table"]
+    );
+    assert_eq!(
+        std::fs::read_to_string(&user_up).unwrap(),
+        "-- @generated by graphql-codegen-rust (applied)"
+    );
+
+    // A filtered out type keeps its table
+    let filtered = Config {
+        exclude_types: vec!["Post".to_string()],
+        ..config.clone()
+    };
+    graphql_codegen_rust::generate_all_code(&schema, &filtered, &*generator_inner)
+        .await
+        .unwrap();
+    assert_eq!(
+        migration_names(),
+        vec!["create_post_table", "create_user_table"]
+    );
+
+    // Adding a column alters the table instead of recreating it
+    let schema = parser
+        .parse_from_sdl(
+            "type User { id: ID! name: String! email: String } type Post { id: ID! title: String! }",
+        )
+        .unwrap();
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap();
+    let names = migration_names();
+    assert_eq!(names.len(), 3);
+    let update = names
+        .iter()
+        .find(|name| name.ends_with("_update_user_table"))
+        .expect("update migration for the changed table");
+    let read = |file: &str| {
+        graphql_codegen_rust::generator::strip_banner(
+            &std::fs::read_to_string(migrations_dir.join(update).join(file)).unwrap(),
+        )
+        .to_string()
+    };
+    assert_eq!(read("up.sql"), "ALTER TABLE user ADD COLUMN email TEXT;");
+    assert_eq!(read("down.sql"), "ALTER TABLE user DROP COLUMN email;");
+
+    // Dropping a column drops just that column
+    let schema = parser
+        .parse_from_sdl("type User { id: ID! email: String } type Post { id: ID! title: String! }")
+        .unwrap();
+    let (files, _) =
+        graphql_codegen_rust::generate_to_map(&schema, &config, &*generator_inner).unwrap();
+    let (_, up) = files
+        .iter()
+        .find(|(path, _)| {
+            path.to_string_lossy().contains("_update_user_table") && path.ends_with("up.sql")
+        })
+        .expect("update migration for the dropped column");
+    assert!(up.ends_with("ALTER TABLE user DROP COLUMN name;"), "{}", up);
+
+    // A change ALTER TABLE can't express is refused instead of losing rows
+    let schema = parser
+        .parse_from_sdl(
+            "type User { id: ID! name: Int! email: String } type Post { id: ID! title: String! }",
+        )
+        .unwrap();
+    let err = graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("column 'name' changed from `name TEXT NOT NULL` to `name INTEGER NOT NULL`"),
+        "{}",
+        err
+    );
+
+    let schema = parser
+        .parse_from_sdl(
+            "type User { id: ID! name: String! email: String age: Int! } type Post { id: ID! title: String! }",
+        )
+        .unwrap();
+    let err = graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("new column 'age' is NOT NULL without a default"),
+        "{}",
+        err
+    );
+
+    // A type gone from the schema has its table dropped
+    let schema = parser
+        .parse_from_sdl("type User { id: ID! name: String! email: String }")
+        .unwrap();
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap();
+    assert!(
+        migration_names()
+            .iter()
+            .any(|name| name.ends_with("_drop_post_table"))
+    );
+}
+
+/// Test that check mode detects out-of-date generated code without writing
+#[tokio::test]
+async fn test_check_detects_out_of_date_output() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output_dir = temp_dir.path().to_path_buf();
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        output_dir: output_dir.clone(),
+        ..Default::default()
+    };
+    let generator_inner = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let schema = create_complex_relationships_schema();
+
+    // Nothing generated yet, so every file is missing
+    let changed = graphql_codegen_rust::check_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap();
+    assert!(changed.contains(&PathBuf::from("src/schema.rs")));
+    assert!(!output_dir.join("src").exists());
+
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap();
+    let changed = graphql_codegen_rust::check_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap();
+    assert!(changed.is_empty(), "unexpected changes: {:?}", changed);
+
+    let author_path = output_dir.join("src/entities/author.rs");
+    std::fs::write(&author_path, "// edited by hand").unwrap();
+    let changed = graphql_codegen_rust::check_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap();
+    assert_eq!(changed, vec![PathBuf::from("src/entities/author.rs")]);
+    assert_eq!(
+        std::fs::read_to_string(&author_path).unwrap(),
+        "// edited by hand"
+    );
+
+    // Sea-ORM's migration names are stable, so its output checks clean too
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        output_dir: temp_dir.path().to_path_buf(),
+        ..config
+    };
+    let generator_inner = graphql_codegen_rust::generator::create_generator(&config.orm);
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap();
+    // Posts reference authors, so their table is created after
+    assert!(
+        temp_dir
+            .path()
+            .join("migrations/src/m0001_create_author_table.rs")
+            .exists()
+    );
+    assert!(
+        temp_dir
+            .path()
+            .join("migrations/src/m0002_create_blog_post_table.rs")
+            .exists()
+    );
+    let lib = std::fs::read_to_string(temp_dir.path().join("migrations/src/lib.rs")).unwrap();
+    let author = lib.find("m0001_create_author_table::Migration").unwrap();
+    let post = lib.find("m0002_create_blog_post_table::Migration").unwrap();
+    assert!(author < post, "{}", lib);
+    let changed = graphql_codegen_rust::check_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap();
+    assert!(changed.is_empty(), "unexpected changes: {:?}", changed);
+
+    // A generated file the run would remove is out of date too
+    let stale_path = temp_dir.path().join("src/entities/legacy.rs");
+    std::fs::copy(temp_dir.path().join("src/entities/author.rs"), &stale_path).unwrap();
+    let changed = graphql_codegen_rust::check_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap();
+    assert_eq!(changed, vec![PathBuf::from("src/entities/legacy.rs")]);
+    assert!(stale_path.exists());
+}
+
+/// Test that Sea-ORM entities get sea-query Iden enums when enabled
+#[test]
+fn test_sea_orm_iden_enums() {
+    let mut config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        generate_iden: true,
+        ..Default::default()
+    };
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl("type BlogPost { id: ID! title: String! publishedAt: String }")
+        .unwrap();
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let entity = &entities["blog_post.rs"];
+    assert!(entity.contains("pub enum BlogPostIden {"));
+    assert!(entity.contains("impl sea_orm::sea_query::Iden for BlogPostIden {"));
+    assert!(entity.contains("Self::Table => \"blog_post\","));
+    assert!(entity.contains("Self::Id => \"id\","));
+    assert!(entity.contains("Self::Title => \"title\","));
+    assert!(entity.contains("Self::PublishedAt => \"published_at\","));
+    syn::parse_file(entity).expect("entity should parse");
+
+    config.generate_iden = false;
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    assert!(!entities["blog_post.rs"].contains("Iden"));
+}
+
+/// Test that introspection sends an operation name and supports GET
+#[tokio::test]
+async fn test_introspection_request_envelope() {
+    use graphql_codegen_rust::config::{IntrospectionMethod, IntrospectionRequest};
+    use graphql_codegen_rust::introspection::Introspector;
+
+    // The gateway rejects anonymous operations
+    let (addr, server) = serve_introspection_fixture(|request| {
+        request.starts_with("POST ")
+            && request.contains(r#""operationName":"IntrospectionQuery""#)
+            && request.contains(r#""variables":{}"#)
+    })
+    .await;
+    let (schema, _) = Introspector::new()
+        .introspect_schema(&format!("http://{}/graphql", addr), &HashMap::new())
+        .await
+        .expect("Named introspection should be accepted");
+    assert!(
+        schema
+            .types
+            .iter()
+            .any(|t| t.name.as_deref() == Some("Country"))
+    );
+    server.await.unwrap();
+
+    let (addr, server) =
+        serve_introspection_fixture(|request| request.contains("operationName")).await;
+    let anonymous = IntrospectionRequest {
+        operation_name: String::new(),
+        send_variables: false,
+        ..Default::default()
+    };
+    let result = Introspector::new()
+        .with_request(anonymous)
+        .introspect_schema(&format!("http://{}/graphql", addr), &HashMap::new())
+        .await;
+    assert!(result.is_err(), "anonymous query should be rejected");
+    let request = server.await.unwrap();
+    assert!(!request.contains("variables"));
+
+    // GET-only servers take the query as URL parameters
+    let (addr, server) = serve_introspection_fixture(|request| {
+        request.starts_with("GET /graphql?query=")
+            && request.contains("operationName=IntrospectionQuery")
+    })
+    .await;
+    let get = IntrospectionRequest {
+        method: IntrospectionMethod::Get,
+        ..Default::default()
+    };
+    Introspector::new()
+        .with_request(get)
+        .introspect_schema(&format!("http://{}/graphql", addr), &HashMap::new())
+        .await
+        .expect("GET introspection should be accepted");
+    server.await.unwrap();
+}
+
+/// Test that the full pipeline introspects over GET when configured to
+#[tokio::test]
+async fn test_generation_with_get_introspection() {
+    use graphql_codegen_rust::config::IntrospectionMethod;
+
+    // A read-only gateway that refuses POST
+    let (addr, server) =
+        serve_introspection_fixture(|request| request.starts_with("GET /graphql?query=")).await;
+    let mut config = Config {
+        url: format!("http://{}/graphql", addr),
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        generate_lib_rs: true,
+        ..Default::default()
+    };
+    config.introspection.method = IntrospectionMethod::Get;
+
+    let (files, _) = CodeGenerator::new(&config.orm)
+        .generate_to_map(&config)
+        .await
+        .expect("generation over GET should succeed");
+    assert!(files.contains_key(&PathBuf::from("src/entities/country.rs")));
+    assert!(server.await.unwrap().starts_with("GET "));
+
+    let (addr, server) =
+        serve_introspection_fixture(|request| request.starts_with("GET /graphql?query=")).await;
+    config.url = format!("http://{}/graphql", addr);
+    config.introspection.method = IntrospectionMethod::Post;
+    let result = CodeGenerator::new(&config.orm)
+        .generate_to_map(&config)
+        .await;
+    assert!(result.is_err(), "POST should be rejected");
+    server.await.unwrap();
+}
+
+/// Test that APQ introspection falls back to the full query for unknown hashes
+/// and servers without APQ support
+#[tokio::test]
+async fn test_introspection_persisted_query_fallback() {
+    use graphql_codegen_rust::config::IntrospectionRequest;
+    use graphql_codegen_rust::introspection::Introspector;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        // The server hasn't seen the hash, then gets the document with it
+        for response in [
+            r#"{"errors":[{"message":"PersistedQueryNotFound","extensions":{"code":"PERSISTED_QUERY_NOT_FOUND"}}]}"#,
+            include_str!("fixtures/introspection.json"),
+        ] {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            requests.push(read_http_request(&mut socket).await);
+            write_json_response(&mut socket, "200 OK", response).await;
+        }
+        requests
+    });
+
+    let apq = IntrospectionRequest {
+        use_apq: true,
+        ..Default::default()
+    };
+    let (schema, _) = Introspector::new()
+        .with_request(apq)
+        .introspect_schema(&format!("http://{}/graphql", addr), &HashMap::new())
+        .await
+        .expect("APQ introspection should fall back to the full query");
+    assert!(
+        schema
+            .types
+            .iter()
+            .any(|t| t.name.as_deref() == Some("Country"))
+    );
+
+    let requests = server.await.unwrap();
+    assert!(
+        requests[0].contains(r#""persistedQuery":{"sha256Hash":""#),
+        "{}",
+        requests[0]
+    );
+    assert!(!requests[0].contains(r#""query":"#), "{}", requests[0]);
+    assert!(requests[1].contains(r#""persistedQuery":"#));
+    assert!(requests[1].contains(r#""query":"#) && requests[1].contains("__schema"));
+
+    // Servers without APQ get the plain document instead
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for response in [
+            r#"{"errors":[{"message":"PersistedQueryNotSupported","extensions":{"code":"PERSISTED_QUERY_NOT_SUPPORTED"}}]}"#,
+            include_str!("fixtures/introspection.json"),
+        ] {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            requests.push(read_http_request(&mut socket).await);
+            write_json_response(&mut socket, "200 OK", response).await;
+        }
+        requests
+    });
+
+    let apq = IntrospectionRequest {
+        use_apq: true,
+        ..Default::default()
+    };
+    Introspector::new()
+        .with_request(apq)
+        .introspect_schema(&format!("http://{}/graphql", addr), &HashMap::new())
+        .await
+        .expect("APQ introspection should fall back to a plain query");
+
+    let requests = server.await.unwrap();
+    assert!(
+        !requests[1].contains(r#""persistedQuery":"#),
+        "{}",
+        requests[1]
+    );
+    assert!(requests[1].contains(r#""query":"#) && requests[1].contains("__schema"));
+}
+
+/// Test that enum values starting with a digit get a valid variant name
+#[test]
+fn test_enum_values_with_leading_digits_are_prefixed() {
+    let mut enums = HashMap::new();
+    enums.insert(
+        "AuthMethod".to_string(),
+        ParsedEnum {
+            name: "AuthMethod".to_string(),
+            values: vec!["PASSWORD".to_string(), "2FA".to_string()],
+            description: None,
+            discriminants: HashMap::new(),
+            deprecations: HashMap::new(),
+        },
+    );
+    let schema = ParsedSchema {
+        types: HashMap::new(),
+        enums,
+        scalars: vec![],
+        query_root: None,
+        mutation_root: None,
+        subscription_root: None,
+        input_types: HashMap::new(),
+    };
+
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        prefix_enum_values: "V".to_string(),
+        ..Default::default()
+    };
+    let sea_orm = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = sea_orm.generate_entities(&schema, &config).unwrap();
+    let code = &entities["auth_method.rs"];
+    assert!(code.contains("    #[sea_orm(string_value = \"2FA\")]\n    V2FA,"));
+    assert!(code.contains("    PASSWORD,"));
+    syn::parse_file(code).expect("enum should parse");
+
+    // A custom prefix also applies to the Int storage TryFrom mapping
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        enum_storage: graphql_codegen_rust::config::EnumStorage::Int,
+        prefix_enum_values: "Value".to_string(),
+        ..config
+    };
+    let diesel = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = diesel.generate_entities(&schema, &config).unwrap();
+    let code = &entities["auth_method.rs"];
+    assert!(code.contains("Value2FA = 1,"));
+    assert!(code.contains("1 => Ok(AuthMethod::Value2FA),"));
+    syn::parse_file(code).expect("enum should parse");
+}
+
+#[test]
+fn test_enum_variant_sanitization() {
+    let mut enums = HashMap::new();
+    enums.insert(
+        "ResponseClass".to_string(),
+        ParsedEnum {
+            name: "ResponseClass".to_string(),
+            values: vec![
+                "2XX".to_string(),
+                "__INTERNAL".to_string(),
+                "text/plain".to_string(),
+            ],
+            description: None,
+            discriminants: HashMap::new(),
+            deprecations: HashMap::new(),
+        },
+    );
+    let schema = ParsedSchema {
+        types: HashMap::new(),
+        enums,
+        scalars: vec![],
+        query_root: None,
+        mutation_root: None,
+        subscription_root: None,
+        input_types: HashMap::new(),
+    };
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        prefix_enum_values: "V".to_string(),
+        ..Default::default()
+    };
+    let sea_orm = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let code = &sea_orm.generate_entities(&schema, &config).unwrap()["response_class.rs"];
+    syn::parse_file(code).expect("enum should parse");
+    assert!(code.contains("    #[sea_orm(string_value = \"2XX\")]\n    V2XX,"));
+    assert!(code.contains("    #[sea_orm(string_value = \"__INTERNAL\")]\n    __INTERNAL,"));
+    assert!(code.contains("    #[sea_orm(string_value = \"text/plain\")]\n    text_plain,"));
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        ..config
+    };
+    let diesel = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let code = &diesel.generate_entities(&schema, &config).unwrap()["response_class.rs"];
+    syn::parse_file(code).expect("enum should parse");
+    assert!(code.contains("    /// GraphQL value `2XX`\n    V2XX,"));
+    assert!(code.contains("    __INTERNAL,"));
+    assert!(code.contains("ResponseClass::text_plain => \"text/plain\","));
+    assert!(code.contains("\"text/plain\" => Ok(ResponseClass::text_plain),"));
+
+    // Values sanitized or prefixed into the same variant are refused
+    let mut schema = schema;
+    schema
+        .enums
+        .get_mut("ResponseClass")
+        .unwrap()
+        .values
+        .extend(["text_plain".to_string(), "V2XX".to_string()]);
+    let collisions = graphql_codegen_rust::validation::find_variant_collisions(&schema, &config);
+    assert_eq!(
+        collisions,
+        [
+            (
+                "ResponseClass".to_string(),
+                "V2XX".to_string(),
+                vec!["'2XX'".to_string(), "'V2XX'".to_string()]
+            ),
+            (
+                "ResponseClass".to_string(),
+                "text_plain".to_string(),
+                vec!["'text/plain'".to_string(), "'text_plain'".to_string()]
+            ),
+        ]
+    );
+    let diagnostics = graphql_codegen_rust::validation::validate_schema(&schema, &config);
+    assert!(diagnostics.iter().any(|d| d.type_name == "ResponseClass"
+        && d.message == "values 'text/plain', 'text_plain' all become the variant 'text_plain'"));
+    let error = graphql_codegen_rust::generate_to_map(&schema, &config, &*diesel).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("ResponseClass 'text/plain' and 'text_plain' as 'text_plain'"),
+        "{}",
+        error
+    );
+}
+
+/// Test that schema-only mode writes nothing but the Diesel schema file
+#[tokio::test]
+async fn test_schema_only_generates_only_schema_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        output_dir: temp_dir.path().to_path_buf(),
+        schema_only: true,
+        ..Default::default()
+    };
+    let generator_inner = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let schema = create_complex_relationships_schema();
+
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator_inner)
+        .await
+        .unwrap();
+
+    fn files(dir: &std::path::Path, root: &std::path::Path, out: &mut Vec<String>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                files(&path, root, out);
+            } else {
+                let relative = path.strip_prefix(root).unwrap();
+                out.push(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+    let mut written = Vec::new();
+    files(temp_dir.path(), temp_dir.path(), &mut written);
+    assert_eq!(written, vec!["src/schema.rs"]);
+
+    let mut emitted = Vec::new();
+    CodeGenerator::new(&config.orm)
+        .generate_into(&schema, &config, &mut |path, _| {
+            emitted.push(path.to_string())
+        })
+        .unwrap();
+    assert_eq!(emitted, vec!["src/schema.rs"]);
+}
+
+/// Test that per-field SQL type overrides win over the mapped column type
+#[test]
+fn test_sql_type_overrides_replace_column_type() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type User {
+                id: ID!
+                code: String!
+                name: String!
+            }
+        "#,
+        )
+        .unwrap();
+
+    let mut sql_type_overrides = HashMap::new();
+    sql_type_overrides.insert("User.code".to_string(), "VARCHAR(8)".to_string());
+    let mut string_length_mappings = HashMap::new();
+    string_length_mappings.insert("User.code".to_string(), 255);
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Postgres,
+        sql_type_overrides,
+        string_length_mappings,
+        ..Default::default()
+    };
+
+    let diesel = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = diesel.generate_migrations(&schema, &config).unwrap();
+    let sql = up_sql(&migrations[0]);
+    assert!(sql.contains("code VARCHAR(8) NOT NULL"));
+    assert!(sql.contains("name TEXT NOT NULL"));
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        ..config
+    };
+    let sea_orm = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = sea_orm.generate_migrations(&schema, &config).unwrap();
+    let graphql_codegen_rust::generator::MigrationFile::Rust { code, .. } = &migrations[0] else {
+        panic!("Expected Rust migration");
+    };
+    assert!(
+        code.contains("ColumnDef::new(Alias::new(\"code\")).custom(Alias::new(\"VARCHAR(8)\"))")
+    );
+    assert!(code.contains("ColumnDef::new(Alias::new(\"name\")).text()"));
+}
+
+/// Test that single_file output is one self-contained file without super:: imports
+#[test]
+fn test_single_file_output_is_self_contained() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            enum Role { ADMIN MEMBER }
+
+            type User {
+                id: ID!
+                name: String!
+                role: Role!
+            }
+
+            type Post {
+                id: ID!
+                title: String!
+                UserId: ID!
+            }
+        "#,
+        )
+        .unwrap();
+
+    for orm in [
+        graphql_codegen_rust::cli::OrmType::Diesel,
+        graphql_codegen_rust::cli::OrmType::SeaOrm,
+        graphql_codegen_rust::cli::OrmType::Plain,
+    ] {
+        let config = Config {
+            orm: orm.clone(),
+            single_file: true,
+            generate_migrations: true,
+            generate_entities: true,
+            ..Default::default()
+        };
+
+        let mut files: Vec<(String, String)> = Vec::new();
+        CodeGenerator::new(&config.orm)
+            .generate_into(&schema, &config, &mut |path, content| {
+                files.push((path.to_string(), content.to_string()));
+            })
+            .unwrap();
+
+        let paths: Vec<&str> = files.iter().map(|(path, _)| path.as_str()).collect();
+        assert!(
+            !paths.iter().any(|p| p.starts_with("src/entities/")),
+            "{:?}",
+            paths
+        );
+        assert!(!paths.contains(&"src/schema.rs") && !paths.contains(&"mod.rs"));
+        let (_, code) = files
+            .iter()
+            .find(|(path, _)| path == "src/generated.rs")
+            .expect("single file should be emitted");
+
+        syn::parse_file(code).expect("single file should parse");
+        assert!(!code.contains("use super::"), "{}", code);
+        assert!(!code.contains("extern crate"), "{}", code);
+        assert!(!code.contains("//!"), "{}", code);
+        assert!(code.contains("pub enum Role"));
+
+        match orm {
+            graphql_codegen_rust::cli::OrmType::Diesel => {
+                assert!(code.contains("table! {"));
+                assert!(code.contains("pub struct User {"));
+                assert!(code.contains("pub struct NewPost {"));
+            }
+            graphql_codegen_rust::cli::OrmType::SeaOrm => {
+                assert!(code.contains("pub mod user {"));
+                assert!(code.contains("pub mod role {"));
+                // Relations point at the sibling inline module
+                assert!(code.contains("belongs_to = \"super::user::Entity\""));
+                assert!(code.contains("pub use post::Entity;"));
+            }
+            graphql_codegen_rust::cli::OrmType::Plain => {
+                assert!(code.contains("pub struct User {"));
+                assert!(!code.contains("diesel") && !code.contains("sea_orm"));
+            }
+        }
+    }
+}
+
+/// Test that generation failures are reported as structured error kinds
+#[tokio::test]
+async fn test_generation_errors_are_structured() {
+    // Nothing listens on the discard port, so the request fails in transport
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        url: "http://127.0.0.1:9/graphql".to_string(),
+        output_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    };
+    let err = CodeGenerator::new(&config.orm)
+        .generate_from_config(&config)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, graphql_codegen_rust::CodegenError::Network(_)),
+        "{:?}",
+        err
+    );
+
+    let missing = temp_dir.path().join("missing.toml");
+    let err = graphql_codegen_rust::generate_from_config_file(&missing)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, graphql_codegen_rust::CodegenError::Config(_)),
+        "{:?}",
+        err
+    );
+    assert!(err.to_string().contains("missing.toml"), "{}", err);
+}
+
+/// Test that generate_to_map returns the generated files without writing them
+#[tokio::test]
+async fn test_generate_to_map_returns_files_in_memory() {
+    let (addr, server) = serve_introspection_fixture(|_| true).await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let output_dir = temp_dir.path().join("generated");
+    let config = Config {
+        url: format!("http://{}/graphql", addr),
+        output_dir: output_dir.clone(),
+        ..Default::default()
+    };
+
+    let (files, _) = CodeGenerator::new(&config.orm)
+        .generate_to_map(&config)
+        .await
+        .unwrap();
+    server.await.unwrap();
+
+    for expected in [
+        "src/schema.rs",
+        "src/entities/country.rs",
+        "src/entities/continent.rs",
+        "src/entities/country_status.rs",
+    ] {
+        let content = files
+            .get(std::path::Path::new(expected))
+            .unwrap_or_else(|| panic!("missing {} in {:?}", expected, files.keys()));
+        assert!(!content.is_empty(), "{} is empty", expected);
+    }
+    assert!(
+        files
+            .keys()
+            .any(|path| path.starts_with("migrations") && path.ends_with("up.sql"))
+    );
+    assert!(!output_dir.exists(), "nothing should be written");
+}
+
+/// Test that introspection can be sent as a whitelisted, registered operation
+#[tokio::test]
+async fn test_introspection_with_registered_operation_name() {
+    use graphql_codegen_rust::config::IntrospectionRequest;
+
+    // The gateway only runs its registered introspection operation
+    let (addr, server) = serve_introspection_fixture(|request| {
+        request.contains(r#""operationName":"RegisteredIntrospection""#)
+            && request.contains("query RegisteredIntrospection {")
+            && !request.contains("IntrospectionQuery")
+    })
+    .await;
+    let config = Config {
+        url: format!("http://{}/graphql", addr),
+        introspection: IntrospectionRequest {
+            operation_name: "RegisteredIntrospection".to_string(),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let (files, _) = CodeGenerator::new(&config.orm)
+        .generate_to_map(&config)
+        .await
+        .expect("Registered operation should be accepted");
+    assert!(files.contains_key(std::path::Path::new("src/entities/country.rs")));
+    server.await.unwrap();
+
+    // Names that can't appear in the document are rejected before sending
+    let introspector = graphql_codegen_rust::introspection::Introspector::new().with_request(
+        IntrospectionRequest {
+            operation_name: "registered-introspection".to_string(),
+            ..Default::default()
+        },
+    );
+    let err = introspector
+        .fetch_introspection_json("http://127.0.0.1:9/graphql", &HashMap::new())
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Invalid introspection operation name"),
+        "{}",
+        err
+    );
+}
+
+/// Test that std types named in mappings are written as full paths
+#[test]
+fn test_mapped_std_types_are_fully_qualified() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            scalar IPAddress
+            scalar Inet
+            scalar Labels
+
+            type Host {
+                id: ID!
+                address: IPAddress!
+                network: Inet!
+                labels: Labels
+            }
+        "#,
+        )
+        .unwrap();
+
+    let mut type_mappings = HashMap::new();
+    type_mappings.insert("IPAddress".to_string(), "IpAddr".to_string());
+    type_mappings.insert("Inet".to_string(), "std::net::IpAddr".to_string());
+    type_mappings.insert(
+        "Labels".to_string(),
+        "HashMap<String, Vec<IpAddr>>".to_string(),
+    );
+
+    for orm in [
+        graphql_codegen_rust::cli::OrmType::Diesel,
+        graphql_codegen_rust::cli::OrmType::SeaOrm,
+    ] {
+        let config = Config {
+            orm,
+            type_mappings: type_mappings.clone(),
+            ..Default::default()
+        };
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+        let entities = generator.generate_entities(&schema, &config).unwrap();
+        let code = &entities["host.rs"];
+
+        syn::parse_file(code).expect("entity should parse");
+        assert!(code.contains("pub address: std::net::IpAddr,"), "{}", code);
+        assert!(code.contains("pub network: std::net::IpAddr,"), "{}", code);
+        assert!(
+            code.contains(
+                "pub labels: std::collections::HashMap<std::string::String, std::vec::Vec<std::net::IpAddr>>,"
+            ),
+            "{}",
+            code
+        );
+    }
+
+    assert_eq!(
+        graphql_codegen_rust::generator::qualify_mapped_type("crate::net::IpAddr"),
+        "crate::net::IpAddr"
+    );
+}
+
+/// Test that an extra CA certificate is loaded into the introspection client
+#[tokio::test]
+async fn test_introspection_with_ca_cert() {
+    use graphql_codegen_rust::introspection::Introspector;
+
+    let ca_cert = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ca.pem");
+    Introspector::new()
+        .with_ca_cert(&ca_cert)
+        .expect("PEM certificate should load");
+
+    // The configured certificate is used end to end
+    let (addr, server) = serve_introspection_fixture(|_| true).await;
+    let config = Config {
+        url: format!("http://{}/graphql", addr),
+        ca_cert: Some(ca_cert),
+        ..Default::default()
+    };
+    let (files, _) = CodeGenerator::new(&config.orm)
+        .generate_to_map(&config)
+        .await
+        .unwrap();
+    assert!(files.contains_key(std::path::Path::new("src/schema.rs")));
+    server.await.unwrap();
+
+    // Unreadable certificates fail before any request, naming the file
+    let config = Config {
+        ca_cert: Some("missing-ca.pem".into()),
+        ..config
+    };
+    let err = CodeGenerator::new(&config.orm)
+        .generate_from_config(&config)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, graphql_codegen_rust::CodegenError::Config(_)),
+        "{:?}",
+        err
+    );
+    assert!(err.to_string().contains("missing-ca.pem"), "{}", err);
+}
+
+/// Test that SQLite primary keys only get AUTOINCREMENT when requested
+#[test]
+fn test_sqlite_autoincrement_is_opt_in() {
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    // `Tag` has an `id` field; `Note` has none, so its `ID` key is detected
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            type Tag {
+                id: ID!
+                label: String!
+            }
+            type Note {
+                body: String!
+                key: ID!
+            }
+        "#,
+        )
+        .unwrap();
+
+    for orm in [
+        graphql_codegen_rust::cli::OrmType::Diesel,
+        graphql_codegen_rust::cli::OrmType::SeaOrm,
+    ] {
+        let config = Config {
+            url: "https://example.com/graphql".to_string(),
+            orm: orm.clone(),
+            db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+            sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Raw,
+            ..Default::default()
+        };
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+
+        let migrations = generator.generate_migrations(&schema, &config).unwrap();
+        let all_sql: String = migrations.iter().map(up_sql).collect();
+        assert!(
+            all_sql.contains("    id INTEGER PRIMARY KEY,\n    label"),
+            "{orm:?}"
+        );
+        assert!(all_sql.contains("    key INTEGER PRIMARY KEY\n"), "{orm:?}");
+        assert!(!all_sql.contains("AUTOINCREMENT"), "{orm:?}");
+
+        let config = Config {
+            sqlite_autoincrement: true,
+            ..config
+        };
+        let migrations = generator.generate_migrations(&schema, &config).unwrap();
+        let all_sql: String = migrations.iter().map(up_sql).collect();
+        // Present id field
+        assert!(
+            all_sql.contains("    id INTEGER PRIMARY KEY AUTOINCREMENT,\n    label"),
+            "{orm:?}"
+        );
+        // Absent id field, detected key
+        assert!(
+            all_sql.contains("    key INTEGER PRIMARY KEY AUTOINCREMENT\n"),
+            "{orm:?}"
+        );
+    }
+
+    // Sea-ORM Rust migrations express the same choice through sea-query
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Rust,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let rust_code = |config: &Config| -> Vec<String> {
+        generator
+            .generate_migrations(&schema, config)
+            .unwrap()
+            .into_iter()
+            .filter_map(|m| match m {
+                graphql_codegen_rust::generator::MigrationFile::Rust { code, .. }
+                    if code.contains("Table::create()") =>
+                {
+                    Some(code)
+                }
+                _ => None,
+            })
+            .collect()
+    };
+    let code = rust_code(&config);
+    assert_eq!(code.len(), 2);
+    assert!(code.iter().all(|c| !c.contains(".auto_increment()")));
+    let config = Config {
+        sqlite_autoincrement: true,
+        ..config
+    };
+    let code = rust_code(&config);
+    assert_eq!(code.len(), 2);
+    assert!(code.iter().all(|c| c.contains(".auto_increment()")));
+
+    // Other databases are unaffected by the flag
+    let config = Config {
+        db: graphql_codegen_rust::cli::DatabaseType::Mysql,
+        sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Raw,
+        ..config
+    };
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let all_sql: String = migrations.iter().map(up_sql).collect();
+    assert!(all_sql.contains("    id INT UNSIGNED PRIMARY KEY AUTO_INCREMENT,"));
+    assert!(!all_sql.contains("AUTOINCREMENT"));
+}
+
+/// Test that opt-in test scaffolding is appended to each entity
+#[test]
+fn test_generate_tests_adds_round_trip_stub() {
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            type User {
+                id: ID!
+                name: String!
+            }
+        "#,
+        )
+        .unwrap();
+
+    for orm in [
+        graphql_codegen_rust::cli::OrmType::Diesel,
+        graphql_codegen_rust::cli::OrmType::SeaOrm,
+    ] {
+        let config = Config {
+            url: "https://example.com/graphql".to_string(),
+            orm: orm.clone(),
+            db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+            ..Default::default()
+        };
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+
+        let entities = generator.generate_entities(&schema, &config).unwrap();
+        assert!(!entities["user.rs"].contains("#[cfg(test)]"), "{orm:?}");
+
+        let config = Config {
+            generate_tests: true,
+            ..config
+        };
+        let entities = generator.generate_entities(&schema, &config).unwrap();
+        let entity = &entities["user.rs"];
+        assert!(entity.contains("#[cfg(test)]\nmod tests {"), "{orm:?}");
+        assert!(entity.contains("fn user_round_trip()"), "{orm:?}");
+        assert!(entity.contains("sqlite::memory:") || entity.contains("\":memory:\""));
+        syn::parse_file(entity).expect("entity with test stub should parse");
+    }
+
+    let config = Config {
+        url: "https://example.com/graphql".to_string(),
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        generate_tests: true,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    assert!(entities["user.rs"].contains("Entity::find_by_id(inserted.id.clone())"));
+}
+
+/// Test that deprecated enum values become `#[deprecated]` variants or are skipped
+#[test]
+fn test_deprecated_enum_values() {
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            enum Status {
+                ACTIVE
+                LEGACY @deprecated(reason: "Use ACTIVE")
+                OLD @deprecated
+            }
+        "#,
+        )
+        .unwrap();
+    let status = &schema.enums["Status"];
+    assert_eq!(
+        status.deprecations.get("LEGACY"),
+        Some(&Some("Use ACTIVE".to_string()))
+    );
+    assert_eq!(status.deprecations.get("OLD"), Some(&None));
+    assert!(!status.deprecations.contains_key("ACTIVE"));
+
+    for orm in [
+        graphql_codegen_rust::cli::OrmType::Diesel,
+        graphql_codegen_rust::cli::OrmType::SeaOrm,
+    ] {
+        for enum_storage in [
+            graphql_codegen_rust::config::EnumStorage::Text,
+            graphql_codegen_rust::config::EnumStorage::Int,
+        ] {
+            let config = Config {
+                orm: orm.clone(),
+                enum_storage,
+                ..Default::default()
+            };
+            let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+            let entities = generator.generate_entities(&schema, &config).unwrap();
+            let code = &entities["status.rs"];
+            assert!(
+                code.contains("    #[deprecated(note = \"Use ACTIVE\")]\n"),
+                "{orm:?}: {code}"
+            );
+            assert!(code.contains("    #[deprecated]\n"), "{orm:?}: {code}");
+            assert_eq!(code.matches("#[deprecated").count(), 2, "{orm:?}");
+            syn::parse_file(code).expect("enum should parse");
+        }
+    }
+
+    // Introspected values carry their deprecation too
+    let json = std::fs::read_to_string("tests/fixtures/introspection.json")
+        .unwrap()
+        .replace(
+            "\"name\": \"DISSOLVED\",\n              \"description\": null,\n              \"isDeprecated\": false,\n              \"deprecationReason\": null",
+            "\"name\": \"DISSOLVED\",\n              \"description\": null,\n              \"isDeprecated\": true,\n              \"deprecationReason\": \"Gone\"",
+        );
+    let (introspection, _) = graphql_codegen_rust::introspection::Introspector::new()
+        .parse_introspection_json(&json)
+        .unwrap();
+    let schema = parser.parse_schema(introspection).unwrap();
+    let country_status = &schema.enums["CountryStatus"];
+    assert_eq!(
+        country_status.deprecations.get("DISSOLVED"),
+        Some(&Some("Gone".to_string()))
+    );
+    assert_eq!(country_status.deprecations.len(), 1);
+
+    // skip_deprecated drops them from the generated enum
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            enum Status {
+                ACTIVE
+                LEGACY @deprecated(reason: "Use ACTIVE")
+            }
+        "#,
+        )
+        .unwrap();
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        skip_deprecated: true,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let schema = graphql_codegen_rust::generator::prepare_schema(&schema, &config).unwrap();
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let code = &entities["status.rs"];
+    assert!(code.contains("Active"));
+    assert!(!code.contains("Legacy") && !code.contains("LEGACY"));
+    assert!(!code.contains("#[deprecated"));
+
+    // Values stored as integers keep their discriminants
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            enum Role {
+                GUEST @deprecated
+                ADMIN
+                USER
+            }
+        "#,
+        )
+        .unwrap();
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        enum_storage: graphql_codegen_rust::config::EnumStorage::Int,
+        skip_deprecated: true,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let schema = graphql_codegen_rust::generator::prepare_schema(&schema, &config).unwrap();
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let code = &entities["role.rs"];
+    assert!(
+        !code.contains("GUEST") && !code.contains("Guest"),
+        "{}",
+        code
+    );
+    assert!(code.contains("1 => Ok(Role::"), "{}", code);
+    assert!(code.contains("2 => Ok(Role::"), "{}", code);
+    assert!(!code.contains("0 => Ok(Role::"), "{}", code);
+}
+
+/// Test that Long/BigInt scalars and `int_as_i64` produce 64-bit types for every ORM and database
+#[test]
+fn test_64_bit_integer_types() {
+    use graphql_codegen_rust::cli::{DatabaseType, OrmType};
+
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            scalar Long
+            scalar BigInt
+            type Metric {
+                id: ID!
+                views: Long!
+                total: BigInt!
+                count: Int!
+            }
+        "#,
+        )
+        .unwrap();
+
+    for orm in [OrmType::Diesel, OrmType::SeaOrm] {
+        for db in [
+            DatabaseType::Sqlite,
+            DatabaseType::Postgres,
+            DatabaseType::Mysql,
+        ] {
+            let config = Config {
+                orm: orm.clone(),
+                db: db.clone(),
+                sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Raw,
+                ..Default::default()
+            };
+            let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+            let context = format!("{orm:?}/{db:?}");
+
+            let entities = generator.generate_entities(&schema, &config).unwrap();
+            let entity = &entities["metric.rs"];
+            assert!(entity.contains("pub views: i64,"), "{context}");
+            assert!(entity.contains("pub total: i64,"), "{context}");
+            assert!(entity.contains("pub count: i32,"), "{context}");
+
+            let migrations = generator.generate_migrations(&schema, &config).unwrap();
+            let sql = up_sql(&migrations[0]);
+            assert!(sql.contains("views BIGINT NOT NULL"), "{context}: {sql}");
+            assert!(sql.contains("total BIGINT NOT NULL"), "{context}: {sql}");
+            assert!(sql.contains("count INTEGER NOT NULL"), "{context}: {sql}");
+
+            if matches!(orm, OrmType::Diesel) {
+                let table = generator.generate_schema(&schema, &config).unwrap();
+                assert!(table.contains("views -> BigInt"), "{context}");
+                assert!(table.contains("count -> Integer"), "{context}");
+            }
+
+            // Wider Int fields on request
+            let config = Config {
+                int_as_i64: true,
+                ..config
+            };
+            let entities = generator.generate_entities(&schema, &config).unwrap();
+            assert!(
+                entities["metric.rs"].contains("pub count: i64,"),
+                "{context}"
+            );
+            let migrations = generator.generate_migrations(&schema, &config).unwrap();
+            assert!(
+                up_sql(&migrations[0]).contains("count BIGINT NOT NULL"),
+                "{context}"
+            );
+            if matches!(orm, OrmType::Diesel) {
+                let table = generator.generate_schema(&schema, &config).unwrap();
+                assert!(table.contains("count -> BigInt"), "{context}");
+            }
+        }
+    }
+
+    // Sea-ORM Rust migrations use sea-query's big integer columns
+    let config = Config {
+        orm: OrmType::SeaOrm,
+        db: DatabaseType::Postgres,
+        sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Rust,
+        int_as_i64: true,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let code = migrations
+        .iter()
+        .find_map(|m| match m {
+            graphql_codegen_rust::generator::MigrationFile::Rust { code, .. }
+                if code.contains("Table::create()") =>
+            {
+                Some(code)
+            }
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(code.matches(".big_integer()").count(), 3);
+
+    // An explicit mapping still wins over the built-in one
+    let config = Config {
+        orm: OrmType::SeaOrm,
+        type_mappings: [("BigInt".to_string(), "rust_decimal::Decimal".to_string())].into(),
+        ..Default::default()
+    };
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    assert!(entities["metric.rs"].contains("pub total: rust_decimal::Decimal,"));
+}
+
+/// Test that Sea-ORM CRUD helpers take the entity's primary key type
+#[test]
+fn test_generate_crud_helpers_use_primary_key_type() {
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            type User {
+                id: ID!
+                name: String!
+            }
+            type Setting {
+                key: String!
+                value: String!
+            }
+        "#,
+        )
+        .unwrap();
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        db: graphql_codegen_rust::cli::DatabaseType::Postgres,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    assert!(!entities["user.rs"].contains("pub async fn find_by_id"));
+
+    let config = Config {
+        generate_crud: true,
+        ..config
+    };
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let user = &entities["user.rs"];
+    assert!(user.contains(
+        "pub async fn find_by_id(db: &DatabaseConnection, id: uuid::Uuid) -> Result<Option<Model>, DbErr>"
+    ));
+    assert!(user.contains("pub async fn delete(db: &DatabaseConnection, id: uuid::Uuid)"));
+    assert!(user.contains("pub async fn create(db: &DatabaseConnection, model: ActiveModel)"));
+    assert!(user.contains("pub async fn update(db: &DatabaseConnection, model: ActiveModel)"));
+    syn::parse_file(user).expect("entity with CRUD helpers should parse");
+
+    // Non-ID keys keep their own type
+    assert!(
+        entities["setting.rs"]
+            .contains("pub async fn find_by_id(db: &DatabaseConnection, id: String)")
+    );
+
+    let config = Config {
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        ..config
+    };
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    assert!(
+        entities["user.rs"].contains("pub async fn find_by_id(db: &DatabaseConnection, id: i32)")
+    );
+}
+
+/// Test that Sea-ORM models can keep the GraphQL field names for serde
+#[test]
+fn test_preserve_graphql_names_adds_serde_renames() {
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            type Post {
+                id: ID!
+                authorId: ID!
+                created_at: String!
+            }
+        "#,
+        )
+        .unwrap();
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    assert!(!entities["post.rs"].contains("#[serde(rename"));
+
+    let config = Config {
+        preserve_graphql_names: true,
+        ..config
+    };
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let post = &entities["post.rs"];
+    assert!(post.contains("    #[serde(rename = \"authorId\")]\n    pub author_id:"));
+    assert_eq!(post.matches("#[serde(rename").count(), 1);
+    syn::parse_file(post).expect("entity should parse");
+}
+
+/// Test that GraphQL descriptions become table and column comments where the database supports them
+#[test]
+fn test_descriptions_become_sql_comments() {
+    use graphql_codegen_rust::cli::{DatabaseType, OrmType};
+
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            """A blog post"""
+            type Post {
+                id: ID!
+                "The post's title"
+                title: String!
+                body: String!
+            }
+        "#,
+        )
+        .unwrap();
+
+    for orm in [OrmType::Diesel, OrmType::SeaOrm] {
+        let migration_sql = |db: DatabaseType| {
+            let config = Config {
+                orm: orm.clone(),
+                db,
+                sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Raw,
+                ..Default::default()
+            };
+            let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+            let migrations = generator.generate_migrations(&schema, &config).unwrap();
+            up_sql(&migrations[0]).to_string()
+        };
+
+        let sql = migration_sql(DatabaseType::Postgres);
+        assert!(
+            sql.contains("COMMENT ON TABLE post IS 'A blog post';"),
+            "{orm:?}: {sql}"
+        );
+        assert!(
+            sql.contains("COMMENT ON COLUMN post.title IS 'The post''s title';"),
+            "{orm:?}: {sql}"
+        );
+        assert_eq!(sql.matches("COMMENT ON").count(), 2, "{orm:?}");
+
+        let sql = migration_sql(DatabaseType::Mysql);
+        assert!(
+            sql.contains("title TEXT NOT NULL COMMENT 'The post''s title',"),
+            "{orm:?}: {sql}"
+        );
+        assert!(sql.contains("\n) COMMENT='A blog post';"), "{orm:?}: {sql}");
+
+        let sql = migration_sql(DatabaseType::Sqlite);
+        assert!(!sql.contains("COMMENT"), "{orm:?}: {sql}");
+    }
+
+    // Sea-ORM's default SchemaManager migrations keep the comments too
+    let migration_code = |db: DatabaseType| {
+        let config = Config {
+            orm: OrmType::SeaOrm,
+            db,
+            ..Default::default()
+        };
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+        let migrations = generator.generate_migrations(&schema, &config).unwrap();
+        let graphql_codegen_rust::generator::MigrationFile::Rust { code, .. } = &migrations[0]
+        else {
+            panic!("expected a Rust migration");
+        };
+        syn::parse_file(code).unwrap();
+        code.clone()
+    };
+
+    let code = migration_code(DatabaseType::Mysql);
+    assert!(
+        code.contains("Alias::new(\"title\")).text().not_null().comment(\"The post's title\")"),
+        "{code}"
+    );
+    assert!(code.contains(".comment(\"A blog post\")\n"), "{code}");
+    assert_eq!(code.matches(".comment(").count(), 2, "{code}");
+
+    let code = migration_code(DatabaseType::Postgres);
+    assert!(
+        code.contains(".execute_unprepared(\"COMMENT ON TABLE post IS 'A blog post';\")"),
+        "{code}"
+    );
+    assert!(
+        code.contains(
+            ".execute_unprepared(\"COMMENT ON COLUMN post.title IS 'The post''s title';\")"
+        ),
+        "{code}"
+    );
+    assert!(!code.contains(".comment("), "{code}");
+
+    let code = migration_code(DatabaseType::Sqlite);
+    assert!(
+        !code.contains("comment") && !code.contains("COMMENT"),
+        "{code}"
+    );
+
+    // A description with several paragraphs stays a single statement
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            """
+            A blog post.
+
+            Drafts are hidden.
+            """
+            type Post {
+                id: ID!
+            }
+        "#,
+        )
+        .unwrap();
+    let config = Config {
+        orm: OrmType::SeaOrm,
+        db: DatabaseType::Postgres,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let graphql_codegen_rust::generator::MigrationFile::Rust { code, .. } = &migrations[0] else {
+        panic!("expected a Rust migration");
+    };
+    syn::parse_file(code).unwrap();
+    assert_eq!(code.matches(".execute_unprepared(").count(), 1, "{code}");
+    assert!(
+        code.contains(
+            ".execute_unprepared(\"COMMENT ON TABLE post IS 'A blog post.\\n\\nDrafts are hidden.';\")"
+        ),
+        "{code}"
+    );
+}
+
+/// Test that type and field descriptions become doc comments on entity structs
+#[test]
+fn test_descriptions_become_entity_doc_comments() {
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            """A registered user"""
+            type User {
+                id: ID!
+                """
+                Display name.
+
+                Shown on the profile page.
+                """
+                name: String!
+                email: String!
+            }
+        "#,
+        )
+        .unwrap();
+
+    for orm in [
+        graphql_codegen_rust::cli::OrmType::Diesel,
+        graphql_codegen_rust::cli::OrmType::SeaOrm,
+    ] {
+        let config = Config {
+            orm: orm.clone(),
+            ..Default::default()
+        };
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+        let entities = generator.generate_entities(&schema, &config).unwrap();
+        let user = &entities["user.rs"];
+
+        assert!(
+            user.contains("/// A registered user\n#[derive("),
+            "{orm:?}: {user}"
+        );
+        assert!(
+            user.contains("    /// Display name.\n    ///\n    /// Shown on the profile page.\n"),
+            "{orm:?}: {user}"
+        );
+        // Undescribed fields get no doc comment
+        assert!(!user.contains("/// \n"), "{orm:?}");
+        syn::parse_file(user).expect("entity should parse");
+    }
+}
+
+/// Test that fields naming a declared enum parse as enum fields, not references
+#[test]
+fn test_parser_resolves_enum_fields() {
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let field_type = |schema: &ParsedSchema, type_name: &str, field_name: &str| {
+        schema.types[type_name]
+            .fields
+            .iter()
+            .find(|f| f.name == field_name)
+            .unwrap()
+            .field_type
+            .clone()
+    };
+
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            scalar DateTime
+            enum Status { ACTIVE ARCHIVED }
+
+            type User {
+                id: ID!
+                status: Status!
+                previous: [Status!]
+                joinedAt: DateTime
+                manager: User
+            }
+            "#,
+        )
+        .unwrap();
+    assert_eq!(
+        field_type(&schema, "User", "status"),
+        FieldType::Enum("Status".to_string())
+    );
+    assert_eq!(
+        field_type(&schema, "User", "previous"),
+        FieldType::Enum("Status".to_string())
+    );
+    assert_eq!(
+        field_type(&schema, "User", "joinedAt"),
+        FieldType::Scalar("DateTime".to_string())
+    );
+    assert_eq!(
+        field_type(&schema, "User", "manager"),
+        FieldType::Reference("User".to_string())
+    );
+
+    // Introspection results resolve the same way
+    let (schema, _) = parser
+        .parse_from_introspection_json(include_str!("fixtures/introspection.json"))
+        .unwrap();
+    assert_eq!(
+        field_type(&schema, "Country", "status"),
+        FieldType::Enum("CountryStatus".to_string())
+    );
+    assert_eq!(
+        field_type(&schema, "Country", "continent"),
+        FieldType::Reference("Continent".to_string())
+    );
+}
+
+/// Test that an introspection JSON file parses like the equivalent SDL
+#[test]
+fn test_parse_from_introspection_json_matches_sdl() {
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let (from_json, _) = parser
+        .parse_from_introspection_json(include_str!("fixtures/introspection.json"))
+        .unwrap();
+    let from_sdl = parser
+        .parse_from_sdl(
+            r#"
+            type Query {
+                "List countries matching a filter"
+                countries(filter: CountryFilterInput): [Country!]!
+                "Look up a country by code"
+                country(code: ID!): Country
+                continents: [Continent!]!
+                continent(code: ID!): Continent
+                languageCount: Int!
+            }
+
+            type Mutation {
+                renameCountry(code: ID!, name: String!): Country
+            }
+
+            "A country"
+            type Country {
+                code: ID!
+                name: String!
+                capital: String
+                phoneCode: String
+                continent: Continent!
+                status: CountryStatus!
+            }
+
+            type Continent {
+                code: ID!
+                name: String!
+                countries: [Country!]!
+            }
+
+            enum CountryStatus {
+                ACTIVE
+                DISSOLVED
+            }
+
+            input CountryFilterInput {
+                code: String
+            }
+            "#,
+        )
+        .unwrap();
+
+    let summary = |schema: &ParsedSchema| {
+        let mut types: Vec<String> = schema
+            .types
+            .values()
+            .map(|t| {
+                let fields: Vec<String> = t
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        format!(
+                            "{}: {:?} nullable={} list={} args={} doc={:?}",
+                            f.name,
+                            f.field_type,
+                            f.is_nullable,
+                            f.is_list,
+                            f.arguments.len(),
+                            f.description
+                        )
+                    })
+                    .collect();
+                format!("{} {:?} {:?}", t.name, t.description, fields)
+            })
+            .collect();
+        types.sort();
+        let mut enums: Vec<String> = schema
+            .enums
+            .values()
+            .map(|e| format!("{} {:?}", e.name, e.values))
+            .collect();
+        enums.sort();
+        (
+            types,
+            enums,
+            schema.query_root.clone(),
+            schema.mutation_root.clone(),
+        )
+    };
+    assert_eq!(summary(&from_json), summary(&from_sdl));
+    assert!(from_json.types.contains_key("Country"));
+}
+
+/// Test that introspection_file replaces the request, so no url is needed
+#[tokio::test]
+async fn test_generate_from_introspection_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config::builder()
+        .introspection_file("tests/fixtures/introspection.json")
+        .output_dir(temp_dir.path())
+        .build()
+        .unwrap();
+    assert!(config.url.is_empty());
+
+    let generator = CodeGenerator::new(&config.orm);
+    let (files, summary) = generator.generate_to_map(&config).await.unwrap();
+    assert!(files.contains_key(&PathBuf::from("src/entities/country.rs")));
+    assert!(summary.warnings.is_empty());
+
+    // Errors that came with the schema are returned as warnings
+    let mut response: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/introspection.json")).unwrap();
+    response["errors"] = serde_json::json!([{ "message": "Field 'legacy' is deprecated" }]);
+    let response_path = temp_dir.path().join("partial.json");
+    std::fs::write(&response_path, response.to_string()).unwrap();
+    let partial = Config {
+        introspection_file: Some(response_path),
+        ..config.clone()
+    };
+    let (files, summary) = generator.generate_to_map(&partial).await.unwrap();
+    assert!(files.contains_key(&PathBuf::from("src/entities/country.rs")));
+    assert_eq!(
+        summary.warnings,
+        ["Introspection returned an error along with the schema: Field 'legacy' is deprecated"]
+    );
+
+    // Without a url or a file there is nothing to generate from
+    let err = Config::builder().build().unwrap_err();
+    assert!(err.to_string().contains("introspection_file"));
+
+    // A missing file is reported as an I/O error naming the path
+    let config = Config {
+        introspection_file: Some(temp_dir.path().join("missing.json")),
+        ..Default::default()
+    };
+    let err = generator.generate_to_map(&config).await.unwrap_err();
+    assert!(err.to_string().contains("missing.json"));
+}
+
+/// Test that MySQL key columns are index-safe VARCHARs while free text stays TEXT
+#[test]
+fn test_mysql_key_columns_use_varchar() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type User { id: ID! name: String! }
+            type Post {
+                id: ID!
+                author: User!
+                slug: String! @unique
+                body: String!
+            }
+            "#,
+        )
+        .unwrap();
+    let config = Config {
+        db: graphql_codegen_rust::cli::DatabaseType::Mysql,
+        id_mapping: graphql_codegen_rust::config::IdMapping::String,
+        indexed_fields: vec!["Post.body".to_string()],
+        ..Default::default()
+    };
+
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let post = migrations
+        .iter()
+        .map(up_sql)
+        .find(|sql| sql.contains("CREATE TABLE post"))
+        .unwrap();
+    assert!(post.contains("id VARCHAR(191) PRIMARY KEY"), "{}", post);
+    assert!(post.contains("author VARCHAR(191) NOT NULL"), "{}", post);
+    assert!(
+        post.contains("slug VARCHAR(191) NOT NULL UNIQUE"),
+        "{}",
+        post
+    );
+    assert!(post.contains("body VARCHAR(191) NOT NULL"), "{}", post);
+
+    // Without an index the body is free text
+    let config = Config {
+        indexed_fields: Vec::new(),
+        ..config
+    };
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let post = migrations
+        .iter()
+        .map(up_sql)
+        .find(|sql| sql.contains("CREATE TABLE post"))
+        .unwrap();
+    assert!(post.contains("body TEXT NOT NULL"), "{}", post);
+
+    // Sea-ORM's sea-query migrations bound the key column too
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Rust,
+        ..config
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let post = migrations
+        .iter()
+        .map(|m| match m {
+            graphql_codegen_rust::generator::MigrationFile::Rust { code, .. } => code.as_str(),
+            other => panic!("Expected Rust migration, got {}", other.name()),
+        })
+        .find(|code| code.contains("Alias::new(\"slug\")"))
+        .unwrap();
+    assert!(
+        post.contains("ColumnDef::new(Alias::new(\"slug\")).string_len(191)"),
+        "{}",
+        post
+    );
+    assert!(post.contains("ColumnDef::new(Alias::new(\"body\")).text()"));
+}
+
+/// Test that Sea-ORM entities get an ActiveModel constructor for inserts
+#[test]
+fn test_sea_orm_active_model_constructor() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl("type Post { id: ID! title: String! body: String views: Int! }")
+        .unwrap();
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        ..Default::default()
+    };
+
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let post = &entities["post.rs"];
+    assert!(
+        post.contains("pub fn new_post(title: String, views: i32) -> Self {"),
+        "{}",
+        post
+    );
+    assert!(post.contains("title: ActiveValue::Set(title),"));
+    assert!(post.contains("..Default::default()"));
+    // The generated key and the optional body stay NotSet
+    assert!(!post.contains("id: ActiveValue::Set"));
+    assert!(!post.contains("body: ActiveValue::Set"));
+    syn::parse_file(post).unwrap();
+
+    // Composite keys and keys the database can't generate are parameters
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            "type PostTag { postId: ID! @id tagId: ID! @id addedAt: String } type Post { id: ID! title: String! }",
+        )
+        .unwrap();
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let post_tag = &entities["post_tag.rs"];
+    assert!(
+        post_tag.contains("pub fn new_post_tag(post_id: i32, tag_id: i32) -> Self {"),
+        "{}",
+        post_tag
+    );
+    assert!(post_tag.contains("post_id: ActiveValue::Set(post_id),"));
+    let config = Config {
+        id_mapping: graphql_codegen_rust::config::IdMapping::String,
+        ..config
+    };
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let post = &entities["post.rs"];
+    assert!(
+        post.contains("pub fn new_post(id: String, title: String) -> Self {"),
+        "{}",
+        post
+    );
+    assert!(post.contains("id: ActiveValue::Set(id),"));
+}
+
+/// Test that the plain output mode writes ORM-free structs and SQL migrations
+#[tokio::test]
+async fn test_plain_output_has_no_orm_code() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            enum Status { ACTIVE ARCHIVED }
+            type User { id: ID! name: String! bio: String status: Status! }
+            "#,
+        )
+        .unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Plain,
+        output_dir: temp_dir.path().to_path_buf(),
+        generate_lib_rs: true,
+        ..Default::default()
+    };
+
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+
+    let user = std::fs::read_to_string(temp_dir.path().join("src/entities/user.rs")).unwrap();
+    assert!(!user.contains("diesel"), "{}", user);
+    assert!(!user.contains("sea_orm"), "{}", user);
+    assert!(user.contains("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]"));
+    assert!(user.contains("pub bio: Option<String>,"));
+    syn::parse_file(&user).unwrap();
+    let status = std::fs::read_to_string(temp_dir.path().join("src/entities/status.rs")).unwrap();
+    assert!(status.contains("Serialize, Deserialize)]\npub enum Status {"));
+    syn::parse_file(&status).unwrap();
+
+    // No schema file, but the same migration SQL as the ORMs
+    assert!(!temp_dir.path().join("src/schema.rs").exists());
+    assert!(!temp_dir.path().join("mod.rs").exists());
+    let up_sql =
+        std::fs::read_to_string(temp_dir.path().join("migrations/create_user_table/up.sql"))
+            .unwrap();
+    assert!(up_sql.contains("CREATE TABLE user"));
+    let lib = std::fs::read_to_string(temp_dir.path().join("src/lib.rs")).unwrap();
+    assert!(!lib.contains("pub mod schema;"));
+    syn::parse_file(&lib).unwrap();
+}
+
+/// Test that plain structs hold lists as `Vec` and leave out relations
+#[test]
+fn test_plain_struct_lists_and_relations() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            directive @computed on FIELD_DEFINITION
+
+            type User {
+                id: ID!
+                tags: [String!]!
+                aliases: [String]
+                matrix: [[Int!]!]
+                posts: [Post!]!
+                postCount: Int! @computed
+            }
+
+            type Post { id: ID! title: String! }
+            "#,
+        )
+        .unwrap();
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Plain,
+        ..Default::default()
+    };
+
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let user = &entities["user.rs"];
+    assert!(user.contains("pub tags: Vec<String>,"), "{}", user);
+    assert!(
+        user.contains("pub aliases: Option<Vec<String>>,"),
+        "{}",
+        user
+    );
+    assert!(
+        user.contains("pub matrix: Option<Vec<Vec<i32>>>,"),
+        "{}",
+        user
+    );
+    assert!(!user.contains("pub posts:"), "{}", user);
+    assert!(!user.contains("pub post_count:"), "{}", user);
+    syn::parse_file(user).unwrap();
+}
+
+/// Test that custom generators can be registered and used by name
+#[tokio::test]
+async fn test_generator_registry_with_custom_generator() {
+    use graphql_codegen_rust::GeneratorRegistry;
+    use graphql_codegen_rust::generator::{CodeGenerator as Generate, MigrationFile};
+
+    struct TypeListGenerator;
+
+    impl Generate for TypeListGenerator {
+        fn generate_schema(&self, schema: &ParsedSchema, _: &Config) -> anyhow::Result<String> {
+            let mut names: Vec<_> = schema.types.keys().cloned().collect();
+            names.sort();
+            Ok(format!("// {}\n", names.join(", ")))
+        }
+
+        fn generate_entities(
+            &self,
+            _: &ParsedSchema,
+            _: &Config,
+        ) -> anyhow::Result<HashMap<String, String>> {
+            Ok(HashMap::from([(
+                "types.rs".to_string(),
+                "// custom\n".to_string(),
+            )]))
+        }
+
+        fn generate_migrations(
+            &self,
+            _: &ParsedSchema,
+            _: &Config,
+        ) -> anyhow::Result<Vec<MigrationFile>> {
+            Ok(Vec::new())
+        }
+    }
+
+    let mut registry = GeneratorRegistry::default();
+    registry.register("type-list", || Box::new(TypeListGenerator));
+    assert_eq!(
+        registry.names().collect::<Vec<_>>(),
+        ["diesel", "plain", "sea-orm", "type-list"]
+    );
+
+    let config = Config::builder()
+        .introspection_file("tests/fixtures/introspection.json")
+        .build()
+        .unwrap();
+    let (files, _) = CodeGenerator::from_registry(&registry, "type-list")
+        .unwrap()
+        .generate_to_map(&config)
+        .await
+        .unwrap();
+    assert_eq!(
+        graphql_codegen_rust::generator::strip_banner(
+            &files[&PathBuf::from("src/entities/types.rs")]
+        ),
+        "// custom\n"
+    );
+    assert_eq!(
+        graphql_codegen_rust::generator::strip_banner(&files[&PathBuf::from("src/schema.rs")]),
+        "// Continent, Country\n"
+    );
+    assert!(!files.keys().any(|path| path.starts_with("migrations")));
+
+    // The CLI's --generator resolves through the registry it's run with
+    use clap::Parser;
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config = Config::builder()
+        .introspection_file("tests/fixtures/introspection.json")
+        .output_dir(temp_dir.path())
+        .build()
+        .unwrap();
+    let config_path = Config::config_path(temp_dir.path());
+    config.save_to_file(&config_path).unwrap();
+    let cli = graphql_codegen_rust::cli::Cli::try_parse_from([
+        "graphql-codegen-rust",
+        "--quiet",
+        "generate",
+        "--config",
+        config_path.to_str().unwrap(),
+        "--generator",
+        "type-list",
+    ])
+    .unwrap();
+    graphql_codegen_rust::app::run(cli, &registry)
+        .await
+        .unwrap();
+    assert!(
+        std::fs::read_to_string(temp_dir.path().join("src/entities/types.rs"))
+            .unwrap()
+            .contains("// custom")
+    );
+
+    let err = CodeGenerator::from_registry(&registry, "surrealdb")
+        .err()
+        .unwrap();
+    assert!(
+        err.to_string()
+            .contains("Available generators: diesel, plain")
+    );
+}
+
+/// Test that two-column primary keys reach the table!, migration and model
+#[test]
+fn test_composite_primary_keys() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type PostTag {
+                postId: ID! @id
+                tagId: ID! @id
+                addedAt: String
+            }
+            type Membership { userId: ID! groupId: ID! role: String! }
+            "#,
+        )
+        .unwrap();
+    let config = Config {
+        composite_keys: HashMap::from([(
+            "Membership".to_string(),
+            vec!["userId".to_string(), "groupId".to_string()],
+        )]),
+        ..Default::default()
+    };
+
+    // Diesel: multi-column table! key and a table-level constraint
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let schema_rs = generator.generate_schema(&schema, &config).unwrap();
+    assert!(
+        schema_rs.contains("post_tag (post_id, tag_id\n"),
+        "{}",
+        schema_rs
+    );
+    assert!(
+        schema_rs.contains("membership (user_id, group_id\n"),
+        "{}",
+        schema_rs
+    );
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    assert!(entities["post_tag.rs"].contains("pub struct NewPostTag {\n    pub post_id: i32,"));
+
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let post_tag = migrations
+        .iter()
+        .map(up_sql)
+        .find(|sql| sql.contains("CREATE TABLE post_tag"))
+        .unwrap();
+    assert!(
+        post_tag.contains("    post_id INTEGER NOT NULL,"),
+        "{}",
+        post_tag
+    );
+    assert!(
+        post_tag.contains("    PRIMARY KEY (post_id, tag_id)\n)"),
+        "{}",
+        post_tag
+    );
+    assert!(!post_tag.contains("id INTEGER PRIMARY KEY"), "{}", post_tag);
+
+    // Sea-ORM: both fields carry the primary key attribute
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        generate_crud: true,
+        ..config
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let membership = &entities["membership.rs"];
+    assert_eq!(
+        membership
+            .matches("#[sea_orm(primary_key, auto_increment = false)]")
+            .count(),
+        2,
+        "{}",
+        membership
+    );
+    assert!(membership.contains("id: (i32, i32)"), "{}", membership);
+    syn::parse_file(membership).unwrap();
+
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let membership = migrations
+        .iter()
+        .find_map(|m| match m {
+            graphql_codegen_rust::generator::MigrationFile::Rust { code, .. }
+                if code.contains("Alias::new(\"membership\")") =>
+            {
+                Some(code.as_str())
+            }
+            _ => None,
+        })
+        .unwrap();
+    assert!(
+        membership.contains(
+            ".primary_key(Index::create().col(Alias::new(\"user_id\")).col(Alias::new(\"group_id\")))"
+        ),
+        "{}",
+        membership
+    );
+    assert!(!membership.contains(".primary_key()"), "{}", membership);
+
+    // Unknown key fields are reported instead of silently ignored
+    let config = Config {
+        composite_keys: HashMap::from([(
+            "PostTag".to_string(),
+            vec!["postId".to_string(), "missing".to_string()],
+        )]),
+        ..Default::default()
+    };
+    let err = graphql_codegen_rust::generator::prepare_schema(&schema, &config).unwrap_err();
+    assert!(err.to_string().contains("unknown field 'missing'"));
+}
+
+#[test]
+fn test_boolean_default_per_database() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type User {
+                id: ID!
+                active: Boolean!
+                nickname: String! @default(value: "it's me")
+                score: Int!
+            }
+            input CreateUserInput {
+                active: Boolean! = false
+                score: Int
+            }
+            "#,
+        )
+        .unwrap();
+    let user = &schema.types["User"];
+    let active = user.fields.iter().find(|f| f.name == "active").unwrap();
+    assert_eq!(active.default_value.as_deref(), Some("false"));
+
+    let mut default_values = std::collections::HashMap::new();
+    default_values.insert("User.score".to_string(), "10".to_string());
+    let generator = graphql_codegen_rust::generator::create_generator(
+        &graphql_codegen_rust::cli::OrmType::Diesel,
+    );
+    for (db, expected) in [
+        (graphql_codegen_rust::cli::DatabaseType::Sqlite, "DEFAULT 0"),
+        (
+            graphql_codegen_rust::cli::DatabaseType::Postgres,
+            "DEFAULT false",
+        ),
+        (graphql_codegen_rust::cli::DatabaseType::Mysql, "DEFAULT 0"),
+    ] {
+        let config = Config {
+            db,
+            default_values: default_values.clone(),
+            ..Default::default()
+        };
+        let migrations = generator.generate_migrations(&schema, &config).unwrap();
+        let sql = migrations
+            .iter()
+            .map(up_sql)
+            .find(|sql| sql.contains("CREATE TABLE user"))
+            .unwrap();
+        assert!(sql.contains(&format!(" NOT NULL {},", expected)), "{}", sql);
+        assert!(sql.contains("DEFAULT 'it''s me'"), "{}", sql);
+        assert!(sql.contains("score INTEGER NOT NULL DEFAULT 10"), "{}", sql);
+    }
+
+    // Sea-ORM's sea-query migrations take the value as a Rust literal
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let code = migrations
+        .iter()
+        .find_map(|m| match m {
+            graphql_codegen_rust::generator::MigrationFile::Rust { code, .. }
+                if code.contains("Alias::new(\"user\")") =>
+            {
+                Some(code.as_str())
+            }
+            _ => None,
+        })
+        .unwrap();
+    assert!(
+        code.contains(".boolean().not_null().default(false)"),
+        "{}",
+        code
+    );
+    syn::parse_file(code).unwrap();
+}
+
+/// Test that MySQL string columns with a default avoid TEXT, which can't have one
+#[test]
+fn test_mysql_string_defaults_use_varchar() {
+    use graphql_codegen_rust::cli::{DatabaseType, OrmType};
+
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            enum Visibility { PUBLIC PRIVATE }
+            type Post {
+                id: ID!
+                status: String! @default(value: "draft")
+                visibility: Visibility! @default(value: PUBLIC)
+                body: String!
+            }
+            "#,
+        )
+        .unwrap();
+
+    let config = Config {
+        orm: OrmType::Diesel,
+        db: DatabaseType::Mysql,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let sql = up_sql(&migrations[0]);
+    assert!(
+        sql.contains("status VARCHAR(191) NOT NULL DEFAULT 'draft'"),
+        "{}",
+        sql
+    );
+    assert!(
+        sql.contains("visibility VARCHAR(191) NOT NULL DEFAULT 'PUBLIC'"),
+        "{}",
+        sql
+    );
+    assert!(sql.contains("body TEXT NOT NULL\n"), "{}", sql);
+
+    // The default Sea-ORM migration style too
+    let config = Config {
+        orm: OrmType::SeaOrm,
+        ..config
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    let code = migrations
+        .iter()
+        .find_map(|m| match m {
+            graphql_codegen_rust::generator::MigrationFile::Rust { code, .. }
+                if code.contains("Alias::new(\"post\")") =>
+            {
+                Some(code.as_str())
+            }
+            _ => None,
+        })
+        .unwrap();
+    assert!(
+        code.contains(".string_len(191).not_null().default(\"draft\")"),
+        "{}",
+        code
+    );
+    assert!(
+        code.contains("Alias::new(\"body\")).text().not_null()"),
+        "{}",
+        code
+    );
+
+    // JSON columns have no bounded alternative, so a default is refused
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            scalar JSON
+            type Post { id: ID! meta: JSON! @default(value: "{}") }
+            "#,
+        )
+        .unwrap();
+    let err = generator
+        .generate_migrations(&schema, &config)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("MySQL JSON columns can't have a literal default"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_watcher_regenerates_once_per_schema_change() {
+    use graphql_codegen_rust::watch::{SchemaWatcher, WatchEvent};
+
+    // Stands in for the introspection endpoint: the schema changes once, and
+    // one poll fails while the server restarts
+    let first = "type User { id: ID! name: String! }";
+    let second = "type User { id: ID! name: String! email: String }";
+    let mut responses = vec![
+        Ok(first),
+        Ok(first),
+        Err("connection refused"),
+        Ok(second),
+        Ok(second),
+    ]
+    .into_iter();
+    let mut fetch = || {
+        let sdl = responses.next().unwrap().map_err(|e| anyhow::anyhow!(e))?;
+        graphql_codegen_rust::parser::GraphQLParser::new().parse_from_sdl(sdl)
+    };
+
+    let mut watcher = SchemaWatcher::new();
+    let events: Vec<WatchEvent> = (0..5).map(|_| watcher.observe(fetch())).collect();
+
+    assert!(matches!(events[0], WatchEvent::Initial(_)));
+    assert!(matches!(events[1], WatchEvent::Unchanged));
+    assert!(matches!(events[2], WatchEvent::Failed(_)));
+    assert!(matches!(events[4], WatchEvent::Unchanged));
+    let regenerated: Vec<_> = events
+        .iter()
+        .filter_map(|event| match event {
+            WatchEvent::Changed(schema) => Some(schema),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(regenerated.len(), 1);
+    assert_eq!(regenerated[0].types["User"].fields.len(), 3);
+}
+
+#[test]
+fn test_embed_as_json_columns() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            scalar JSON
+            type Address { street: String! city: String! }
+            type User {
+                id: ID!
+                address: Address!
+                settings: JSON
+            }
+            "#,
+        )
+        .unwrap();
+
+    for (db, sql_type) in [
+        (graphql_codegen_rust::cli::DatabaseType::Sqlite, "TEXT"),
+        (graphql_codegen_rust::cli::DatabaseType::Postgres, "JSONB"),
+        (graphql_codegen_rust::cli::DatabaseType::Mysql, "JSON"),
+    ] {
+        let config = Config {
+            db,
+            embed_as_json: vec!["User.address".to_string()],
+            ..Default::default()
+        };
+        let prepared = graphql_codegen_rust::generator::prepare_schema(&schema, &config).unwrap();
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+
+        let migrations = generator.generate_migrations(&prepared, &config).unwrap();
+        let user = migrations
+            .iter()
+            .map(up_sql)
+            .find(|sql| sql.contains("CREATE TABLE user"))
+            .unwrap();
+        assert!(
+            user.contains(&format!("address {} NOT NULL", sql_type)),
+            "{}",
+            user
+        );
+        assert!(user.contains(&format!("settings {}", sql_type)), "{}", user);
+        assert!(!user.contains("address_id"), "{}", user);
+
+        let entities = generator.generate_entities(&prepared, &config).unwrap();
+        let entity = &entities["user.rs"];
+        assert!(
+            entity.contains("pub address: serde_json::Value,"),
+            "{}",
+            entity
+        );
+        let settings = entity
+            .lines()
+            .find(|line| line.contains("pub settings:"))
+            .unwrap();
+        assert!(settings.contains("serde_json::Value"), "{}", entity);
+    }
+
+    let config = Config {
+        embed_as_json: vec!["User.profile".to_string()],
+        ..Default::default()
+    };
+    let err = graphql_codegen_rust::generator::prepare_schema(&schema, &config).unwrap_err();
+    assert!(err.to_string().contains("User.profile"), "{}", err);
+
+    // Entries for types filtered out of this run are ignored
+    let config = Config {
+        include_types: vec!["Address".to_string()],
+        embed_as_json: vec!["User.address".to_string()],
+        ..Default::default()
+    };
+    let prepared = graphql_codegen_rust::generator::prepare_schema(&schema, &config).unwrap();
+    assert_eq!(prepared.types.keys().collect::<Vec<_>>(), ["Address"]);
+}
+
+#[test]
+fn test_interface_traits_and_impls() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            interface Node { id: ID! }
+            type User implements Node {
+                id: ID!
+                name: String!
+            }
+            "#,
+        )
+        .unwrap();
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+
+    let node = &entities["node.rs"];
+    assert!(node.contains("pub trait Node {"), "{}", node);
+    assert!(node.contains("fn id(&self) -> &i32;"), "{}", node);
+    syn::parse_file(node).unwrap();
+
+    let user = &entities["user.rs"];
+    assert!(
+        user.contains("impl super::node::Node for Model {"),
+        "{}",
+        user
+    );
+    assert!(user.contains("&self.id"), "{}", user);
+    syn::parse_file(user).unwrap();
+
+    // The module file re-exports the trait rather than entity items
+    let module = generator.generate_schema(&schema, &config).unwrap();
+    assert!(module.contains("pub mod node;"), "{}", module);
+    assert!(module.contains("pub use node::Node;"), "{}", module);
+    assert!(!module.contains("pub use node::Entity;"), "{}", module);
+
+    // Diesel notes the interface on the struct
+    let config = Config::default();
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let user = &entities["user.rs"];
+    assert!(
+        user.contains("/// Implements the GraphQL interface `Node`\n#[derive(Queryable"),
+        "{}",
+        user
+    );
+}
+
+#[test]
+fn test_union_enum_wraps_member_models() {
+    // The union example from docs/reference.md
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            interface Node {
+              id: ID!
+            }
+
+            type User implements Node {
+              id: ID!
+              name: String!
+            }
+
+            type Post implements Node {
+              id: ID!
+              title: String!
+            }
+
+            union SearchResult = User | Post
+            "#,
+        )
+        .unwrap();
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let search_result = &entities["search_result.rs"];
+    assert!(
+        search_result.contains("#[serde(untagged)]\npub enum SearchResult {"),
+        "{}",
+        search_result
+    );
+    assert!(
+        search_result.contains("User(super::user::Model),"),
+        "{}",
+        search_result
+    );
+    assert!(
+        search_result.contains("Post(super::post::Model),"),
+        "{}",
+        search_result
+    );
+    syn::parse_file(search_result).unwrap();
+
+    let module = generator.generate_schema(&schema, &config).unwrap();
+    assert!(module.contains("pub mod search_result;"), "{}", module);
+    assert!(
+        module.contains("pub use search_result::SearchResult;"),
+        "{}",
+        module
+    );
+
+    // Unions aren't tables
+    let config = Config {
+        sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Raw,
+        ..config
+    };
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    assert_eq!(migrations.len(), 2);
+    assert!(
+        migrations
+            .iter()
+            .map(up_sql)
+            .all(|sql| !sql.contains("search_result"))
+    );
+
+    // Diesel wraps its row structs
+    let config = Config::default();
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let search_result = &entities["search_result.rs"];
+    assert!(
+        search_result.contains("User(super::user::User),"),
+        "{}",
+        search_result
+    );
+    syn::parse_file(search_result).unwrap();
+}
+
+/// Test that generated files carry a do-not-edit banner, and that
+/// deterministic_output makes repeated runs byte-identical
+#[test]
+fn test_generated_file_banner() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type User {
+              id: ID!
+              name: String!
+            }
+            "#,
+        )
+        .unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        output_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+
+    let banner = format!(
+        "@generated by graphql-codegen-rust v{} \u{2014} do not edit",
+        env!("CARGO_PKG_VERSION")
+    );
+    for (path, content) in &files {
+        let marker = if path.extension().is_some_and(|ext| ext == "sql") {
+            "--"
+        } else {
+            "//"
+        };
+        let first_line = content.lines().next().unwrap();
+        assert!(
+            first_line.starts_with(&format!("{} {}", marker, banner)),
+            "{}: {}",
+            path.display(),
+            first_line
+        );
+        // Banners are timestamped by default
+        assert_ne!(first_line, format!("{} {}", marker, banner));
+    }
+    syn::parse_file(&files[&PathBuf::from("src/entities/user.rs")]).unwrap();
+
+    let config = Config {
+        deterministic_output: true,
+        ..config
+    };
+    let (first, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+    let (second, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+    assert_eq!(first, second);
+    assert!(
+        first[&PathBuf::from("src/schema.rs")].starts_with(&format!("// {}\n", banner)),
+        "{}",
+        first[&PathBuf::from("src/schema.rs")]
+    );
+}
+
+/// Test that mod.rs and schema.rs don't depend on HashMap iteration order
+#[test]
+fn test_schema_file_order_is_deterministic() {
+    let sdl = r#"
+        type Zebra { id: ID! }
+        type Apple { id: ID! }
+        type Mango { id: ID! }
+        type Kiwi { id: ID! }
+        type Banana { id: ID! }
+        enum Size { SMALL LARGE }
+        enum Color { RED GREEN }
+        enum Mood { HAPPY SAD }
+    "#;
+    // Each parse builds fresh HashMaps with their own iteration order
+    let parse = || {
+        graphql_codegen_rust::parser::GraphQLParser::new()
+            .parse_from_sdl(sdl)
+            .unwrap()
+    };
+
+    for orm in [
+        graphql_codegen_rust::cli::OrmType::SeaOrm,
+        graphql_codegen_rust::cli::OrmType::Diesel,
+    ] {
+        let config = Config {
+            orm,
+            ..Default::default()
+        };
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+        let first = generator.generate_schema(&parse(), &config).unwrap();
+        for _ in 0..5 {
+            assert_eq!(generator.generate_schema(&parse(), &config).unwrap(), first);
+        }
+    }
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let module = generator.generate_schema(&parse(), &config).unwrap();
+    assert!(
+        module.contains(
+            "pub mod apple;\npub mod banana;\npub mod kiwi;\npub mod mango;\npub mod zebra;\n\
+             pub mod color;\npub mod mood;\npub mod size;\n"
+        ),
+        "{}",
+        module
+    );
+}
+
+/// Test that deprecated fields from SDL and introspection are reported by type
+#[test]
+fn test_deprecation_report_groups_fields_by_type() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type User {
+              id: ID!
+              email: String! @deprecated(reason: "Use contactEmail")
+              nickname: String @deprecated
+              contactEmail: String!
+            }
+
+            type Post {
+              id: ID!
+              legacyId: Int @deprecated(reason: "Ids are strings now")
+            }
+
+            type Tag {
+              id: ID!
+            }
+            "#,
+        )
+        .unwrap();
+    assert_eq!(
+        graphql_codegen_rust::validation::deprecation_report(&schema),
+        [
+            "Type 'Post' has 1 deprecated field:",
+            "  legacyId: Ids are strings now",
+            "Type 'User' has 2 deprecated fields:",
+            "  email: Use contactEmail",
+            "  nickname (no reason given)",
+        ]
+    );
+
+    // Introspection carries the same metadata
+    let mut response: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/introspection.json")).unwrap();
+    let country = response["data"]["__schema"]["types"]
+        .as_array_mut()
+        .unwrap()
+        .iter_mut()
+        .find(|t| t["name"] == "Country")
+        .unwrap();
+    let code = &mut country["fields"][0];
+    code["isDeprecated"] = true.into();
+    code["deprecationReason"] = "Use isoCode".into();
+    let (schema, _) = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_introspection_json(&response.to_string())
+        .unwrap();
+    assert_eq!(
+        graphql_codegen_rust::validation::deprecation_report(&schema),
+        [
+            "Type 'Country' has 1 deprecated field:",
+            "  code: Use isoCode"
+        ]
+    );
+}
+
+/// Test that a self-referential foreign key yields one relation to the
+/// entity's own table and a valid self-FK constraint
+#[test]
+fn test_self_referential_foreign_key() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type Category {
+              id: ID!
+              name: String!
+              parentId: ID
+              parent: Category
+            }
+            "#,
+        )
+        .unwrap();
+
+    let relationships = graphql_codegen_rust::generator::detect_relationships(&schema);
+    let category = &relationships["Category"];
+    assert_eq!(category.len(), 1);
+    assert_eq!(category[0].field_name, "parentId");
+    assert_eq!(category[0].related_type, "Category");
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        db: graphql_codegen_rust::cli::DatabaseType::Postgres,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let entity = &entities["category.rs"];
+    assert_eq!(entity.matches("pub enum Relation").count(), 1, "{}", entity);
+    assert_eq!(entity.matches("belongs_to").count(), 1, "{}", entity);
+    assert!(
+        entity.contains(
+            "    #[sea_orm(\n        belongs_to = \"Entity\",\n        \
+             from = \"Column::parent_id\",\n        to = \"Column::id\",\n        \
+             on_update = \"Cascade\",\n        on_delete = \"SetNull\"\n    )]\n    Parent,\n"
+        ),
+        "{}",
+        entity
+    );
+    syn::parse_file(entity).unwrap();
+
+    let code = generator
+        .generate_migrations(&schema, &config)
+        .unwrap()
+        .into_iter()
+        .find_map(|migration| match migration {
+            graphql_codegen_rust::generator::MigrationFile::Rust { name, code }
+                if name.ends_with("_create_category_table") =>
+            {
+                Some(code)
+            }
+            _ => None,
+        })
+        .unwrap();
+    assert!(
+        code.contains(
+            ".foreign_key(ForeignKey::create().name(\"fk_category_parent_id\")\
+             .from(Alias::new(\"category\"), Alias::new(\"parent_id\"))\
+             .to(Alias::new(\"category\"), Alias::new(\"id\"))"
+        ),
+        "{}",
+        code
+    );
+    syn::parse_file(&code).unwrap();
+
+    for orm in [
+        graphql_codegen_rust::cli::OrmType::Diesel,
+        graphql_codegen_rust::cli::OrmType::SeaOrm,
+    ] {
+        let config = Config {
+            orm,
+            sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Raw,
+            ..config.clone()
+        };
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+        let migrations = generator.generate_migrations(&schema, &config).unwrap();
+        assert_eq!(migrations.len(), 1);
+        let sql = up_sql(&migrations[0]);
+        assert!(
+            sql.contains(
+                "    parent_id UUID,\n    parent UUID,\n    CONSTRAINT fk_category_parent_id \
+                 FOREIGN KEY (parent_id) REFERENCES category (id) \
+                 ON UPDATE CASCADE ON DELETE SET NULL\n);"
+            ),
+            "{}",
+            sql
+        );
+    }
+}
+
+/// Test that type refs cut short by the introspection depth fail clearly, and
+/// that the depth of the query is configurable
+#[tokio::test]
+async fn test_introspection_depth_guard() {
+    // `Matrix.cells` is `[[[[Int!]!]!]!]!`, cut off after seven levels of ofType
+    let err = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_introspection_file(std::path::Path::new(
+            "tests/fixtures/deep_introspection.json",
+        ))
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Type nesting exceeds introspection depth 7 at 'Matrix.cells', increase --introspection-depth"
+    );
+
+    // The server only answers a query unrolling nine levels
+    let (addr, server) =
+        serve_introspection_fixture(|request| request.matches("ofType").count() == 9).await;
+    let mut config = Config::default();
+    config.introspection.depth = 9;
+    let (schema, _) = graphql_codegen_rust::parser::GraphQLParser::for_config(&config)
+        .unwrap()
+        .parse_from_introspection(&format!("http://{}/graphql", addr), &HashMap::new())
+        .await
+        .unwrap();
+    server.await.unwrap();
+    assert!(schema.types.contains_key("Country"));
+
+    use clap::Parser;
+    let cli = graphql_codegen_rust::cli::Cli::try_parse_from([
+        "graphql-codegen-rust",
+        "generate",
+        "--introspection-depth",
+        "9",
+    ])
+    .unwrap();
+    let Some(graphql_codegen_rust::cli::Commands::Generate {
+        introspection_depth,
+        ..
+    }) = cli.command
+    else {
+        panic!("Expected Generate command");
+    };
+    assert_eq!(introspection_depth, Some(9));
+}
+
+#[tokio::test]
+async fn test_custom_output_layout() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type User {
+              id: ID!
+              name: String!
+            }
+            "#,
+        )
+        .unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        output_dir: temp_dir.path().to_path_buf(),
+        schema_file_path: Some(PathBuf::from("src/db/schema.rs")),
+        entities_dir: Some(PathBuf::from("src/models")),
+        migrations_dir: Some(PathBuf::from("db/migrations")),
+        generate_lib_rs: true,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+
+    for path in [
+        "src/db/schema.rs",
+        "src/models/user.rs",
+        "src/models/mod.rs",
+    ] {
+        assert!(files.contains_key(&PathBuf::from(path)), "missing {}", path);
+    }
+    assert!(
+        files
+            .keys()
+            .any(|path| { path.starts_with("db/migrations") && path.ends_with("up.sql") })
+    );
+    assert!(
+        !files
+            .keys()
+            .any(|path| { path.starts_with("src/entities") || path.starts_with("migrations") })
+    );
+
+    // src/lib.rs points its modules at the moved files
+    let lib = &files[&PathBuf::from("src/lib.rs")];
+    assert!(lib.contains("#[path = \"models/mod.rs\"]\npub mod entities;"));
+    assert!(lib.contains("#[path = \"db/schema.rs\"]\npub mod schema;"));
+    syn::parse_file(lib).unwrap();
+
+    // The files are written where the map says
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+    assert!(temp_dir.path().join("src/models/user.rs").exists());
+    assert!(temp_dir.path().join("src/db/schema.rs").exists());
+}
+
+#[tokio::test]
+async fn test_generated_lib_rs_prelude() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type User {
+              id: ID!
+              status: Status!
+            }
+
+            enum Status {
+              ACTIVE
+              BANNED
+            }
+            "#,
+        )
+        .unwrap();
+
+    use graphql_codegen_rust::cli::OrmType;
+
+    for orm in [OrmType::Diesel, OrmType::SeaOrm, OrmType::Plain] {
+        let config = Config {
+            orm: orm.clone(),
+            generate_lib_rs: true,
+            ..Default::default()
+        };
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+        let (files, _) =
+            graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+
+        let lib = &files[&PathBuf::from("src/lib.rs")];
+        assert!(lib.contains("pub mod entities;"), "{:?}: {}", orm, lib);
+        assert_eq!(lib.contains("pub mod schema;"), orm == OrmType::Diesel);
+        assert!(lib.contains("pub mod prelude {"), "{:?}: {}", orm, lib);
+        assert!(lib.contains("pub use super::entities::status::Status;"));
+        syn::parse_file(lib).unwrap();
+
+        let entities_mod = &files[&PathBuf::from("src/entities/mod.rs")];
+        assert!(entities_mod.contains("pub mod user;"), "{:?}", orm);
+        assert!(entities_mod.contains("pub mod status;"), "{:?}", orm);
+    }
+
+    let config = Config {
+        orm: OrmType::SeaOrm,
+        generate_lib_rs: true,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+    assert!(
+        files[&PathBuf::from("src/lib.rs")]
+            .contains("pub use super::entities::user::Entity as User;")
+    );
+
+    // Turning the flag off leaves the crate entrypoint to the user
+    let config = Config {
+        generate_lib_rs: false,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+    assert!(!files.contains_key(&PathBuf::from("src/lib.rs")));
+    assert!(files.contains_key(&PathBuf::from("src/entities/mod.rs")));
+}
+
+#[test]
+fn test_generated_cargo_toml() {
+    use graphql_codegen_rust::cli::{DatabaseType, OrmType};
+
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            scalar DateTime
+
+            type User {
+              id: ID!
+              createdAt: DateTime!
+            }
+            "#,
+        )
+        .unwrap();
+
+    let manifest = |orm: OrmType, db: DatabaseType| {
+        let config = Config {
+            orm,
+            db,
+            output_dir: PathBuf::from("./my_models"),
+            generate_cargo_toml: true,
+            ..Default::default()
+        };
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+        let (files, _) =
+            graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+        let content = &files[&PathBuf::from("Cargo.toml")];
+        content.parse::<toml::Table>().unwrap()
+    };
+    let features = |manifest: &toml::Table, name: &str| -> Vec<String> {
+        manifest["dependencies"][name]["features"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|feature| feature.as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let diesel = manifest(OrmType::Diesel, DatabaseType::Sqlite);
+    assert_eq!(diesel["package"]["name"].as_str(), Some("my-models"));
+    let diesel_features = features(&diesel, "diesel");
+    assert!(
+        diesel_features.contains(&"sqlite".to_string()),
+        "{:?}",
+        diesel_features
+    );
+    assert!(diesel_features.contains(&"chrono".to_string()));
+    assert!(!diesel_features.contains(&"postgres".to_string()));
+    assert!(diesel["dependencies"].get("chrono").is_some());
+    assert!(diesel["dependencies"].get("sea-orm").is_none());
+
+    let sea_orm = manifest(OrmType::SeaOrm, DatabaseType::Postgres);
+    let sea_orm_features = features(&sea_orm, "sea-orm");
+    assert!(sea_orm_features.contains(&"sqlx-postgres".to_string()));
+    // PostgreSQL keys are UUIDs
+    assert!(sea_orm_features.contains(&"with-uuid".to_string()));
+    assert!(sea_orm["dependencies"].get("uuid").is_some());
+
+    // Mapped crates are listed, std paths aren't mistaken for crates
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            scalar Decimal
+            scalar Duration
+            type Order { id: ID! total: Decimal! timeout: Duration! }
+            "#,
+        )
+        .unwrap();
+    let mut config = Config {
+        orm: OrmType::Diesel,
+        output_dir: PathBuf::from("./my_models"),
+        generate_cargo_toml: true,
+        ..Default::default()
+    };
+    config
+        .type_mappings
+        .insert("Decimal".to_string(), "rust_decimal::Decimal".to_string());
+    config
+        .type_mappings
+        .insert("Duration".to_string(), "std::time::Duration".to_string());
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+    let mapped = files[&PathBuf::from("Cargo.toml")]
+        .parse::<toml::Table>()
+        .unwrap();
+    assert_eq!(mapped["dependencies"]["rust_decimal"].as_str(), Some("*"));
+    assert!(mapped["dependencies"].get("time").is_none());
+    assert!(!features(&mapped, "diesel").contains(&"time".to_string()));
+}
+
+#[tokio::test]
+async fn test_generation_summary_lists_required_crates() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            scalar DateTime
+
+            type Event {
+              id: ID!
+              startsAt: DateTime!
+            }
+            "#,
+        )
+        .unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let config = Config {
+        db: graphql_codegen_rust::cli::DatabaseType::Postgres,
+        output_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let summary = graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+    let mut crates: Vec<_> = summary.required_crates.iter().map(String::as_str).collect();
+    crates.sort();
+    assert_eq!(crates, ["chrono", "uuid"]);
+
+    // SQLite keys are integers and plain scalars need nothing extra
+    let config = Config {
+        output_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    };
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl("type User { id: ID! name: String! }")
+        .unwrap();
+    let summary = graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+    assert!(summary.required_crates.is_empty());
+
+    // Mapped types name their crate by the first path segment
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            scalar Decimal
+            scalar Duration
+            type Order { id: ID! total: Decimal! timeout: Duration! }
+            "#,
+        )
+        .unwrap();
+    let mut config = Config {
+        output_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    };
+    config
+        .type_mappings
+        .insert("Decimal".to_string(), "rust_decimal::Decimal".to_string());
+    config
+        .type_mappings
+        .insert("Duration".to_string(), "std::time::Duration".to_string());
+    let summary = graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+    let crates: Vec<_> = summary.required_crates.iter().map(String::as_str).collect();
+    assert_eq!(crates, ["rust_decimal"]);
+}
+
+#[test]
+fn test_sdl_type_extensions_are_merged() {
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    // The first extension comes before the type it extends
+    let schema = parser
+        .parse_from_sdl(
+            r#"
+            extend type User implements Node {
+              email: String!
+            }
+
+            interface Node {
+              id: ID!
+            }
+
+            type User {
+              id: ID!
+              name: String!
+            }
+
+            extend type User {
+              age: Int
+            }
+
+            enum Role {
+              ADMIN
+            }
+
+            extend enum Role {
+              MEMBER
+            }
+            "#,
+        )
+        .unwrap();
+
+    let user = &schema.types["User"];
+    let mut fields: Vec<&str> = user.fields.iter().map(|f| f.name.as_str()).collect();
+    fields.sort();
+    assert_eq!(fields, ["age", "email", "id", "name"]);
+    assert_eq!(user.interfaces, ["Node"]);
+    assert_eq!(user.primary_key.as_deref(), Some("id"));
+    assert_eq!(schema.enums["Role"].values, ["ADMIN", "MEMBER"]);
+
+    // An extension redefining a field is rejected
+    let error = parser
+        .parse_from_sdl("type User { id: ID! } extend type User { id: ID! }")
+        .unwrap_err();
+    assert!(error.to_string().contains("User.id"), "{}", error);
+}
+
+#[tokio::test]
+async fn test_unmapped_scalar_diagnostics() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            scalar Email
+            scalar Money
+            scalar Unused
+            scalar DateTime
+
+            type Customer {
+              id: ID!
+              email: Email!
+              balance: Money!
+              joinedAt: DateTime!
+            }
+            "#,
+        )
+        .unwrap();
+
+    // Unused and built-in scalars aren't reported, mapped ones aren't either
+    let temp_dir = TempDir::new().unwrap();
+    let mut config = Config {
+        output_dir: temp_dir.path().to_path_buf(),
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let summary = graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+    assert_eq!(summary.fallback_scalars, ["Email", "Money"]);
+
+    config
+        .scalar_mappings
+        .insert("Money".to_string(), "i64".to_string());
+    let summary = graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+    assert_eq!(summary.fallback_scalars, ["Email"]);
+
+    // Strict mode turns the fallback into an error naming the scalar
+    config.strict_scalars = true;
+    let error = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap_err();
+    assert!(error.to_string().contains("Email"), "{}", error);
+    assert!(!error.to_string().contains("Money"), "{}", error);
+
+    config
+        .type_mappings
+        .insert("Email".to_string(), "String".to_string());
+    assert!(graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).is_ok());
+}
+
+#[test]
+fn test_scalar_mappings_apply_to_custom_scalars() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            scalar Money
+            scalar Email
+
+            type Invoice {
+              id: ID!
+              total: Money!
+              contact: Email!
+            }
+            "#,
+        )
+        .unwrap();
+
+    let mut config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Plain,
+        ..Default::default()
+    };
+    config
+        .scalar_mappings
+        .insert("Money".to_string(), "i64".to_string());
+    config
+        .scalar_mappings
+        .insert("Email".to_string(), "String".to_string());
+    // type_mappings takes precedence over scalar_mappings
+    config.type_mappings.insert(
+        "Email".to_string(),
+        "email_address::EmailAddress".to_string(),
+    );
+
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+    let invoice = &files[&PathBuf::from("src/entities/invoice.rs")];
+    assert!(invoice.contains("pub total: i64,"), "{}", invoice);
+    assert!(
+        invoice.contains("pub contact: email_address::EmailAddress,"),
+        "{}",
+        invoice
+    );
+
+    // Mappings are Rust types and stay out of the column types
+    config.orm = graphql_codegen_rust::cli::OrmType::Diesel;
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+    let up_sql = &files[&PathBuf::from("migrations/create_invoice_table/up.sql")];
+    assert!(up_sql.contains("contact TEXT NOT NULL"), "{}", up_sql);
+    let schema_rs = &files[&PathBuf::from("src/schema.rs")];
+    assert!(schema_rs.contains("contact -> Text"), "{}", schema_rs);
+    assert!(!schema_rs.contains("EmailAddress"), "{}", schema_rs);
+}
+
+#[test]
+fn test_datetime_type_per_database() {
+    use graphql_codegen_rust::cli::{DatabaseType, OrmType};
+    use graphql_codegen_rust::config::SeaOrmMigrationStyle;
+
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            scalar DateTime
+
+            type Event {
+                id: ID!
+                startsAt: DateTime!
+            }
+        "#,
+        )
+        .unwrap();
+
+    for (db, rust_type, diesel_type, sql_type) in [
+        (
+            DatabaseType::Sqlite,
+            "chrono::NaiveDateTime",
+            "Timestamp",
+            "TIMESTAMP",
+        ),
+        (
+            DatabaseType::Mysql,
+            "chrono::NaiveDateTime",
+            "Datetime",
+            "DATETIME",
+        ),
+        (
+            DatabaseType::Postgres,
+            "chrono::DateTime<chrono::Utc>",
+            "Timestamptz",
+            "TIMESTAMPTZ",
+        ),
+    ] {
+        let config = Config {
+            orm: OrmType::Diesel,
+            db: db.clone(),
+            ..Default::default()
+        };
+        let diesel = graphql_codegen_rust::generator::create_generator(&config.orm);
+        let entities = diesel.generate_entities(&schema, &config).unwrap();
+        let field = format!("pub starts_at: {},", rust_type);
+        assert!(entities["event.rs"].contains(&field), "{:?}", db);
+        let table = diesel.generate_schema(&schema, &config).unwrap();
+        let column = format!("starts_at -> {}.not_null(),", diesel_type);
+        assert!(table.contains(&column), "{:?}: {}", db, table);
+        let migrations = diesel.generate_migrations(&schema, &config).unwrap();
+        let sql = format!("starts_at {} NOT NULL", sql_type);
+        assert!(up_sql(&migrations[0]).contains(&sql), "{:?}", db);
+
+        let config = Config {
+            orm: OrmType::SeaOrm,
+            sea_orm_migration_style: SeaOrmMigrationStyle::Raw,
+            ..config
+        };
+        let sea_orm = graphql_codegen_rust::generator::create_generator(&config.orm);
+        let entities = sea_orm.generate_entities(&schema, &config).unwrap();
+        assert!(entities["event.rs"].contains(&field), "{:?}", db);
+    }
+
+    // A scalar mapping overrides the per-database default
+    let mut config = Config {
+        orm: OrmType::Diesel,
+        db: DatabaseType::Sqlite,
+        ..Default::default()
+    };
+    config.scalar_mappings.insert(
+        "DateTime".to_string(),
+        "chrono::DateTime<chrono::Utc>".to_string(),
+    );
+    let diesel = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = diesel.generate_entities(&schema, &config).unwrap();
+    assert!(entities["event.rs"].contains("pub starts_at: chrono::DateTime<chrono::Utc>,"));
+
+    // The client decodes API timestamps, which keep their offset on any database
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            scalar DateTime
+
+            type Event {
+                id: ID!
+                startsAt: DateTime!
+            }
+
+            type Query {
+                event(id: ID!): Event
+            }
+        "#,
+        )
+        .unwrap();
+    for db in [DatabaseType::Sqlite, DatabaseType::Mysql] {
+        let config = Config {
+            db,
+            generate_client: true,
+            ..Default::default()
+        };
+        let client =
+            graphql_codegen_rust::generator::client::generate_client(&schema, &config).unwrap();
+        assert!(
+            client.contains("pub starts_at: chrono::DateTime<chrono::Utc>,"),
+            "{}",
+            client
+        );
+    }
+}
+
+#[test]
+fn test_diesel_identifiable_and_associations() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type Author {
+              id: ID!
+              name: String!
+            }
+
+            type Post {
+              id: ID!
+              title: String!
+              authorId: ID!
+              author: Author!
+            }
+            "#,
+        )
+        .unwrap();
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+
+    let author = &files[&PathBuf::from("src/entities/author.rs")];
+    assert!(
+        author.contains("#[derive(Queryable, Identifiable, Debug)]"),
+        "{}",
+        author
+    );
+    assert!(author.contains("#[diesel(table_name = crate::schema::author)]"));
+    assert!(!author.contains("primary_key("));
+    assert!(!author.contains("belongs_to"));
+    syn::parse_file(author).unwrap();
+
+    let post = &files[&PathBuf::from("src/entities/post.rs")];
+    assert!(
+        post.contains("#[derive(Queryable, Identifiable, Associations, Debug)]"),
+        "{}",
+        post
+    );
+    assert!(
+        post.contains("#[diesel(belongs_to(super::author::Author, foreign_key = author_id))]"),
+        "{}",
+        post
+    );
+    syn::parse_file(post).unwrap();
+}
+
+#[test]
+fn test_diesel_changeset_struct() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            directive @computed on FIELD_DEFINITION
+
+            type User {
+              id: ID!
+              name: String!
+              age: Int
+              posts: [Post!]!
+              postCount: Int! @computed
+            }
+
+            type Post {
+              id: ID!
+              title: String!
+            }
+            "#,
+        )
+        .unwrap();
+
+    let mut config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        generate_lib_rs: true,
+        generate_changesets: true,
+        format_output: false,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+
+    let user = &files[&PathBuf::from("src/entities/user.rs")];
+    // Diesel 2 attribute syntax, with the table module's path
+    assert!(
+        user.contains("#[derive(Insertable)]\n#[diesel(table_name = crate::schema::user)]\n"),
+        "{}",
+        user
+    );
+    assert!(!user.contains("#[table_name"), "{}", user);
+    let changeset = &user[user.find("#[derive(AsChangeset, Default)]").expect(user)..];
+    let changeset = &changeset[..changeset.find("}\n").unwrap()];
+    assert!(changeset.contains("#[diesel(table_name = crate::schema::user)]"));
+    assert!(changeset.contains("pub struct UpdateUser {"));
+    assert!(
+        changeset.contains("pub name: Option<String>,"),
+        "{}",
+        changeset
+    );
+    assert!(changeset.contains("pub age: Option<"), "{}", changeset);
+    assert!(!changeset.contains("pub id:"), "{}", changeset);
+    // Relations and computed fields have no column to update
+    assert!(!changeset.contains("pub posts:"), "{}", changeset);
+    assert!(!changeset.contains("pub post_count:"), "{}", changeset);
+    syn::parse_file(user).unwrap();
+
+    let lib = &files[&PathBuf::from("src/lib.rs")];
+    assert!(lib.contains("user::{User, NewUser, UpdateUser}"), "{}", lib);
+
+    config.generate_changesets = false;
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+    let user = &files[&PathBuf::from("src/entities/user.rs")];
+    assert!(!user.contains("AsChangeset"));
+    let lib = &files[&PathBuf::from("src/lib.rs")];
+    assert!(lib.contains("user::{User, NewUser}"), "{}", lib);
+}
+
+#[test]
+fn test_postgres_native_enums() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type User {
+              id: ID!
+              role: Role!
+            }
+
+            type Account {
+              id: ID!
+              role: Role!
+            }
+
+            enum Role {
+              ADMIN
+              USER
+            }
+            "#,
+        )
+        .unwrap();
+
+    use graphql_codegen_rust::cli::{DatabaseType, OrmType};
+
+    let config = Config {
+        orm: OrmType::Diesel,
+        db: DatabaseType::Postgres,
+        postgres_native_enums: true,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+    let enum_types = &files[&PathBuf::from("migrations/00000000000000_create_enum_types/up.sql")];
+    assert!(enum_types.contains("CREATE TYPE role AS ENUM ('ADMIN', 'USER');"));
+    let drop_types = &files[&PathBuf::from("migrations/00000000000000_create_enum_types/down.sql")];
+    assert!(drop_types.contains("DROP TYPE role;"));
+    let up_sql = &files[&PathBuf::from("migrations/create_user_table/up.sql")];
+    assert!(up_sql.contains("role role NOT NULL"), "{}", up_sql);
+    // Diesel runs migrations in directory order, so the types sort first
+    let first = files
+        .keys()
+        .filter_map(|path| path.strip_prefix("migrations").ok())
+        .min()
+        .unwrap();
+    assert!(
+        first.starts_with("00000000000000_create_enum_types"),
+        "{:?}",
+        first
+    );
+
+    let schema_rs = &files[&PathBuf::from("src/schema.rs")];
+    assert!(schema_rs.contains("#[diesel(postgres_type(name = \"role\"))]"));
+    assert!(
+        schema_rs.contains("use super::sql_types::Role;"),
+        "{}",
+        schema_rs
+    );
+    assert!(schema_rs.contains("role -> Role"), "{}", schema_rs);
+
+    let config = Config {
+        orm: OrmType::SeaOrm,
+        db: DatabaseType::Postgres,
+        postgres_native_enums: true,
+        sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Raw,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let migrations = generator.generate_migrations(&schema, &config).unwrap();
+    assert_eq!(migrations[0].name(), "m0000_create_enum_types");
+    let mut names: Vec<&str> = migrations.iter().map(|m| m.name()).collect();
+    names.sort();
+    assert_eq!(names[0], "m0000_create_enum_types");
+    let table = migrations
+        .iter()
+        .find_map(|m| match m {
+            graphql_codegen_rust::generator::MigrationFile::Sql { name, up_sql, .. }
+                if name.ends_with("_create_user_table") =>
+            {
+                Some(up_sql.clone())
+            }
+            _ => None,
+        })
+        .unwrap();
+    assert!(table.contains("role role NOT NULL"), "{}", table);
+    let role = &generator.generate_entities(&schema, &config).unwrap()["role.rs"];
+    assert!(
+        role.contains("db_type = \"Enum\", enum_name = \"role\""),
+        "{}",
+        role
+    );
+
+    // Other databases keep string-backed enums
+    let config = Config {
+        orm: OrmType::Diesel,
+        db: DatabaseType::Sqlite,
+        postgres_native_enums: true,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+    assert!(!files.contains_key(&PathBuf::from(
+        "migrations/00000000000000_create_enum_types/up.sql"
+    )));
+    let up_sql = &files[&PathBuf::from("migrations/create_user_table/up.sql")];
+    assert!(up_sql.contains("role TEXT NOT NULL"), "{}", up_sql);
+}
+
+#[test]
+fn test_diesel_enum_sql_impls() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type User {
+              id: ID!
+              role: Role!
+            }
+
+            enum Role {
+              ADMIN
+              USER
+            }
+            "#,
+        )
+        .unwrap();
+
+    use graphql_codegen_rust::config::EnumStorage;
+
+    let mut config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let role = &generator.generate_entities(&schema, &config).unwrap()["role.rs"];
+    syn::parse_file(role).unwrap();
+    assert!(
+        role.contains(
+            "#[derive(diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)]"
+        ),
+        "{}",
+        role
+    );
+    assert!(role.contains("#[diesel(sql_type = diesel::sql_types::Text)]"));
+    assert!(!role.contains("#[sql_type ="));
+    assert!(
+        role.contains("impl<DB> diesel::serialize::ToSql<diesel::sql_types::Text, DB> for Role")
+    );
+    assert!(role.contains("Role::ADMIN => \"ADMIN\","));
+    assert!(
+        role.contains(
+            "impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Text, DB> for Role"
+        )
+    );
+    assert!(role.contains("\"USER\" => Ok(Role::USER),"));
+
+    config.enum_storage = EnumStorage::Int;
+    let role = &generator.generate_entities(&schema, &config).unwrap()["role.rs"];
+    syn::parse_file(role).unwrap();
+    assert!(role.contains("#[diesel(sql_type = diesel::sql_types::Integer)]"));
+    assert!(
+        role.contains("impl<DB> diesel::serialize::ToSql<diesel::sql_types::Integer, DB> for Role")
+    );
+    assert!(role.contains(
+        "impl<DB> diesel::deserialize::FromSql<diesel::sql_types::Integer, DB> for Role"
+    ));
+}
+
+#[test]
+fn test_rust_keyword_identifiers() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type Item {
+              id: ID!
+              type: String!
+              match: Int
+              self: String
+              kind: Kind!
+            }
+
+            type Type {
+              id: ID!
+              name: String!
+            }
+
+            enum Kind {
+              move
+              SIMPLE
+            }
+            "#,
+        )
+        .unwrap();
+
+    use graphql_codegen_rust::cli::OrmType;
+
+    for orm in [OrmType::Diesel, OrmType::SeaOrm, OrmType::Plain] {
+        let config = Config {
+            orm: orm.clone(),
+            generate_lib_rs: true,
+            sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Raw,
+            ..Default::default()
+        };
+        let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+        let (files, _) =
+            graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+        for (path, code) in &files {
+            if path.extension().is_some_and(|ext| ext == "rs") {
+                syn::parse_file(code)
+                    .unwrap_or_else(|e| panic!("{:?} {}: {}\n{}", orm, path.display(), e, code));
+            }
+        }
+
+        let item = &files[&PathBuf::from("src/entities/item.rs")];
+        assert!(item.contains("pub r#type: String,"), "{:?}: {}", orm, item);
+        assert!(item.contains("pub r#match: "), "{:?}: {}", orm, item);
+        assert!(item.contains("pub self_: "), "{:?}: {}", orm, item);
+        let kind = &files[&PathBuf::from("src/entities/kind.rs")];
+        assert!(kind.contains("    r#move,"), "{:?}: {}", orm, kind);
+        assert!(files.contains_key(&PathBuf::from("src/entities/type.rs")));
+        let entities_mod = &files[&PathBuf::from("src/entities/mod.rs")];
+        assert!(entities_mod.contains("pub mod r#type;"), "{:?}", orm);
+
+        match orm {
+            OrmType::Diesel => {
+                let schema_rs = &files[&PathBuf::from("src/schema.rs")];
+                assert!(schema_rs.contains("r#type -> Text"), "{}", schema_rs);
+                assert!(schema_rs.contains("#[sql_name = \"self\"]\n        self_ ->"));
+            }
+            OrmType::SeaOrm => {
+                assert!(item.contains("#[sea_orm(column_name = \"self\")]"));
+                assert!(item.contains("#[serde(rename = \"self\")]"));
+            }
+            OrmType::Plain => {
+                assert!(item.contains("#[serde(rename = \"self\")]"));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_pluralized_table_names() {
+    let schema = graphql_codegen_rust::parser::GraphQLParser::new()
+        .parse_from_sdl(
+            r#"
+            type User {
+              id: ID!
+              name: String!
+            }
+
+            type Category {
+              id: ID!
+              parentId: ID
+            }
+            "#,
+        )
+        .unwrap();
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        pluralize_tables: true,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let (files, _) = graphql_codegen_rust::generate_to_map(&schema, &config, &*generator).unwrap();
+
+    let user = &files[&PathBuf::from("src/entities/user.rs")];
+    assert!(user.contains("pub struct User {"), "{}", user);
+    assert!(user.contains("pub struct NewUser {"));
+    assert!(user.contains("#[diesel(table_name = crate::schema::users)]"));
+    // The table's columns come from the schema module, not a `users` entity module
+    assert!(user.contains("use crate::schema::users::*;"), "{}", user);
+    syn::parse_file(user).unwrap();
+
+    let schema_rs = &files[&PathBuf::from("src/schema.rs")];
+    assert!(schema_rs.contains("    users (id"), "{}", schema_rs);
+    assert!(schema_rs.contains("    categories (id"));
+
+    let up_sql = &files[&PathBuf::from("migrations/create_users_table/up.sql")];
+    assert!(up_sql.contains("CREATE TABLE users ("), "{}", up_sql);
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        ..config
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let entities = generator.generate_entities(&schema, &config).unwrap();
+    let category = &entities["category.rs"];
+    assert!(
+        category.contains("#[sea_orm(table_name = \"categories\")]"),
+        "{}",
+        category
+    );
+    assert!(category.contains("pub struct Model {"));
+}
+
+/// Test that files not generated by the tool block generation unless forced
+#[tokio::test]
+async fn test_force_overwrite_protection() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output_dir = temp_dir.path().to_path_buf();
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+
+    let mut config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::Diesel,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        output_dir: output_dir.clone(),
+        generate_migrations: true,
+        generate_entities: true,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let schema = parser
+        .parse_from_sdl("type User { id: ID! name: String! } type Post { id: ID! title: String! }")
+        .unwrap();
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+
+    let helpers = output_dir.join("src/helpers.rs");
+    std::fs::write(&helpers, "pub fn helper() {}\n").unwrap();
+    let post = output_dir.join("src/entities/post.rs");
+    let post_migration = output_dir.join("migrations/create_post_table");
+    assert!(post.exists() && post_migration.exists());
+
+    // Post is gone from the schema, but the stray file stops the run
+    let schema = parser
+        .parse_from_sdl("type User { id: ID! name: String! }")
+        .unwrap();
+    let error = graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("src/helpers.rs"), "{}", error);
+    assert!(error.contains("--force"), "{}", error);
+    assert_eq!(
+        std::fs::read_to_string(&helpers).unwrap(),
+        "pub fn helper() {}\n"
+    );
+    assert!(post.exists());
+
+    // Forced, stale generated files go and the stray file stays
+    config.force = true;
+    let summary = graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+    assert_eq!(
+        summary.foreign_files,
+        [std::path::PathBuf::from("src/helpers.rs")]
+    );
+    assert_eq!(
+        std::fs::read_to_string(&helpers).unwrap(),
+        "pub fn helper() {}\n"
+    );
+    assert!(!post.exists());
+    assert!(!post_migration.exists());
+    assert!(output_dir.join("src/entities/user.rs").exists());
+    assert!(
+        output_dir
+            .join("migrations/create_user_table/up.sql")
+            .exists()
+    );
+
+    // Forced runs still keep the files of types that are only filtered out
+    let schema = parser
+        .parse_from_sdl("type User { id: ID! name: String! } type Post { id: ID! title: String! }")
+        .unwrap();
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+    let filtered = Config {
+        exclude_types: vec!["Post".to_string()],
+        ..config.clone()
+    };
+    let summary = graphql_codegen_rust::generate_all_code(&schema, &filtered, &*generator)
+        .await
+        .unwrap();
+    assert!(summary.removed_files.is_empty(), "{:?}", summary);
+    assert!(post.exists());
+    assert!(post_migration.join("up.sql").exists());
+}
+
+/// Test that entity files of types removed from the schema are deleted
+#[tokio::test]
+async fn test_stale_entity_files_are_removed() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let output_dir = temp_dir.path().to_path_buf();
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+
+    let config = Config {
+        orm: graphql_codegen_rust::cli::OrmType::SeaOrm,
+        db: graphql_codegen_rust::cli::DatabaseType::Sqlite,
+        output_dir: output_dir.clone(),
+        generate_migrations: true,
+        generate_entities: true,
+        sea_orm_migration_style: graphql_codegen_rust::config::SeaOrmMigrationStyle::Raw,
+        generate_lib_rs: true,
+        ..Default::default()
+    };
+    let generator = graphql_codegen_rust::generator::create_generator(&config.orm);
+    let schema = parser
+        .parse_from_sdl("type User { id: ID! name: String! } type Post { id: ID! title: String! }")
+        .unwrap();
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+    let post = output_dir.join("src/entities/post.rs");
+    assert!(post.exists());
+
+    // Filtering a type out of a run keeps its file
+    for filtered in [
+        Config {
+            exclude_types: vec!["Post".to_string()],
+            ..config.clone()
+        },
+        Config {
+            include_types: vec!["User".to_string()],
+            ..config.clone()
+        },
+    ] {
+        let summary = graphql_codegen_rust::generate_all_code(&schema, &filtered, &*generator)
+            .await
+            .unwrap();
+        assert!(summary.removed_files.is_empty(), "{:?}", summary);
+        assert!(post.exists());
+    }
+
+    let schema = parser
+        .parse_from_sdl("type User { id: ID! name: String! }")
+        .unwrap();
+    graphql_codegen_rust::generate_all_code(&schema, &config, &*generator)
+        .await
+        .unwrap();
+    assert!(!post.exists());
+    assert!(output_dir.join("src/entities/user.rs").exists());
+    let entities_mod = std::fs::read_to_string(output_dir.join("src/entities/mod.rs")).unwrap();
+    assert!(!entities_mod.contains("post"), "{}", entities_mod);
+
+    // Migrations are history and stay without --force
+    let migrations = std::fs::read_dir(output_dir.join("migrations"))
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    assert!(
+        migrations
+            .iter()
+            .any(|name| name.ends_with("_create_post_table")),
+        "{:?}",
+        migrations
+    );
+}
+
+/// Test that schema diffs report additions, removals and type changes
+#[test]
+fn test_schema_diff() {
+    use graphql_codegen_rust::parser::diff::{ChangeKind, diff_schemas};
+
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let old = parser
+        .parse_from_sdl(
+            r#"
+            type User {
+                id: ID!
+                age: Int
+                nickname: String
+            }
+        "#,
+        )
+        .unwrap();
+
+    // An added field isn't breaking
+    let added = parser
+        .parse_from_sdl(
+            r#"
+            type User {
+                id: ID!
+                age: Int
+                nickname: String
+                email: String!
+            }
+        "#,
+        )
+        .unwrap();
+    let changes = diff_schemas(&old, &added);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Added);
+    assert_eq!(changes[0].subject, "field User.email");
+    assert!(!changes[0].breaking);
+    assert_eq!(changes[0].to_string(), "+ field User.email: String!");
+
+    // A removed field is breaking
+    let removed = parser
+        .parse_from_sdl(
+            r#"
+            type User {
+                id: ID!
+                age: Int
+            }
+        "#,
+        )
+        .unwrap();
+    let changes = diff_schemas(&old, &removed);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Removed);
+    assert_eq!(changes[0].subject, "field User.nickname");
+    assert!(changes[0].breaking);
+
+    // A changed field type is breaking
+    let changed = parser
+        .parse_from_sdl(
+            r#"
+            type User {
+                id: ID!
+                age: String
+                nickname: String
+            }
+        "#,
+        )
+        .unwrap();
+    let changes = diff_schemas(&old, &changed);
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].kind, ChangeKind::Changed);
+    assert_eq!(changes[0].detail.as_deref(), Some("Int \u{2192} String"));
+    assert!(changes[0].breaking);
+
+    assert!(diff_schemas(&old, &old).is_empty());
+}
+
+/// Test that schema diffs classify arguments, union members, input fields and
+/// nullability by whether old requests or clients can break
+#[test]
+fn test_schema_diff_breaking_classification() {
+    use graphql_codegen_rust::parser::diff::diff_schemas;
+
+    let parser = graphql_codegen_rust::parser::GraphQLParser::new();
+    let diff = |old: &str, new: &str| -> Vec<(String, bool)> {
+        diff_schemas(
+            &parser.parse_from_sdl(old).unwrap(),
+            &parser.parse_from_sdl(new).unwrap(),
+        )
+        .into_iter()
+        .map(|change| (change.to_string(), change.breaking))
+        .collect()
+    };
+
+    // A new required argument breaks queries that don't pass it
+    assert_eq!(
+        diff(
+            "type User { id: ID! } type Query { user(id: ID!): User }",
+            "type User { id: ID! } type Query { user(id: ID!, org: ID!, limit: Int = 10, name: String): User }",
+        ),
+        [
+            ("+ argument Query.user(limit): Int".to_string(), false),
+            ("+ argument Query.user(name): String".to_string(), false),
+            (
+                "+ argument Query.user(org): ID! (breaking)".to_string(),
+                true
+            ),
+        ]
+    );
+
+    // Removed union members break clients matching on them
+    assert_eq!(
+        diff(
+            "type A { id: ID! } type B { id: ID! } union S = A | B",
+            "type A { id: ID! } type B { id: ID! } union S = A",
+        ),
+        [("- union member S.B (breaking)".to_string(), true)]
+    );
+
+    // Inputs narrowing to non-null break, relaxing to nullable doesn't
+    assert_eq!(
+        diff(
+            "input UserInput { name: String email: String! }",
+            "input UserInput { name: String! email: String }",
+        ),
+        [
+            (
+                "~ input field UserInput.email: String! \u{2192} String".to_string(),
+                false
+            ),
+            (
+                "~ input field UserInput.name: String \u{2192} String! (breaking)".to_string(),
+                true
+            ),
+        ]
+    );
+
+    // Outputs are the other way around, and list items keep their nullability
+    assert_eq!(
+        diff(
+            "type User { id: ID! name: String friends: [User!]! }",
+            "type User { id: ID! name: String! friends: [User]! }",
+        ),
+        [
+            (
+                "~ field User.friends: [User!]! \u{2192} [User]! (breaking)".to_string(),
+                true
+            ),
+            (
+                "~ field User.name: String \u{2192} String!".to_string(),
+                false
+            ),
+        ]
+    );
+}
+
+/// Test that errors next to a schema are returned as warnings, and that a
+/// null `__schema` still fails with the errors
+#[tokio::test]
+async fn test_introspection_warnings_in_summary() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let mut response: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/introspection.json")).unwrap();
+    response["errors"] = serde_json::json!([{ "message": "Field 'legacy' is deprecated" }]);
+    let response_path = temp_dir.path().join("partial.json");
+    std::fs::write(&response_path, response.to_string()).unwrap();
+
+    let config = Config {
+        output_dir: temp_dir.path().join("generated"),
+        introspection_file: Some(response_path.clone()),
+        ..Default::default()
+    };
+    let summary = CodeGenerator::new(&config.orm)
+        .generate_from_config(&config)
+        .await
+        .unwrap();
+    assert_eq!(
+        summary.warnings,
+        ["Introspection returned an error along with the schema: Field 'legacy' is deprecated"]
+    );
+
+    // Data without a schema isn't partial data
+    response["data"] = serde_json::json!({ "__schema": null });
+    std::fs::write(&response_path, response.to_string()).unwrap();
+    let err = CodeGenerator::new(&config.orm)
+        .generate_from_config(&config)
+        .await
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("GraphQL introspection failed with 1 error"),
+        "{}",
+        err
+    );
+}
+
+// Helper functions for creating test schemas
+
+fn create_empty_schema() -> ParsedSchema {
+    ParsedSchema {
+        types: HashMap::new(),
+        enums: HashMap::new(),
+        scalars: vec![],
+        query_root: None,
+        mutation_root: None,
+        subscription_root: None,
+        input_types: HashMap::new(),
+    }
+}
+
+fn create_single_field_schema() -> ParsedSchema {
+    let mut types = HashMap::new();
+
+    types.insert(
+        "Minimal".to_string(),
+        ParsedType {
+            kind: graphql_codegen_rust::parser::TypeKind::Object,
+            union_members: vec![],
+            name: "Minimal".to_string(),
+            fields: vec![ParsedField {
+                graphql_type: String::new(),
+                name: "id".to_string(),
+                field_type: FieldType::Scalar("ID".to_string()),
+                description: None,
+                is_nullable: false,
+                is_list: false,
+                list_depth: 0,
+                arguments: vec![],
+                max_length: None,
+                directives: vec![],
+                default_value: None,
+                deprecation: None,
+            }],
+            description: None,
+            interfaces: vec![],
+            primary_key: None,
+        },
+    );
+
+    ParsedSchema {
+        types,
+        enums: HashMap::new(),
+        scalars: vec![],
+        query_root: None,
+        mutation_root: None,
+        subscription_root: None,
+        input_types: HashMap::new(),
+    }
+}
+
+fn create_enum_only_schema() -> ParsedSchema {
+    let mut enums = HashMap::new();
+
+    enums.insert(
+        "Status".to_string(),
+        ParsedEnum {
+            name: "Status".to_string(),
+            values: vec![
+                "ACTIVE".to_string(),
+                "INACTIVE".to_string(),
+                "PENDING".to_string(),
+            ],
+            description: Some("Entity status".to_string()),
+            discriminants: HashMap::new(),
+            deprecations: HashMap::new(),
+        },
+    );
+
+    ParsedSchema {
+        types: HashMap::new(),
+        enums,
+        scalars: vec![],
+        query_root: None,
+        mutation_root: None,
+        subscription_root: None,
+        input_types: HashMap::new(),
+    }
+}
+
+fn create_complex_relationships_schema() -> ParsedSchema {
+    let mut types = HashMap::new();
+    let mut enums = HashMap::new();
+
+    // Author type
+    types.insert(
+        "Author".to_string(),
+        ParsedType {
+            kind: graphql_codegen_rust::parser::TypeKind::Object,
+            union_members: vec![],
+            name: "Author".to_string(),
+            fields: vec![
+                ParsedField {
+                    graphql_type: String::new(),
+                    name: "id".to_string(),
+                    field_type: FieldType::Scalar("ID".to_string()),
+                    description: None,
+                    is_nullable: false,
+                    is_list: false,
+                    list_depth: 0,
+                    arguments: vec![],
+                    max_length: None,
+                    directives: vec![],
+                    default_value: None,
+                    deprecation: None,
+                },
+                ParsedField {
+                    graphql_type: String::new(),
+                    name: "name".to_string(),
+                    field_type: FieldType::Scalar("String".to_string()),
+                    description: None,
+                    is_nullable: false,
+                    is_list: false,
+                    list_depth: 0,
+                    arguments: vec![],
+                    max_length: None,
+                    directives: vec![],
+                    default_value: None,
+                    deprecation: None,
+                },
+            ],
+            description: Some("Blog author".to_string()),
+            interfaces: vec![],
+            primary_key: None,
+        },
+    );
+
+    // Blog post type with relationships
+    types.insert(
+        "BlogPost".to_string(),
+        ParsedType {
+            kind: graphql_codegen_rust::parser::TypeKind::Object,
+            union_members: vec![],
+            name: "BlogPost".to_string(),
+            fields: vec![
+                ParsedField {
+                    graphql_type: String::new(),
+                    name: "id".to_string(),
+                    field_type: FieldType::Scalar("ID".to_string()),
+                    description: None,
+                    is_nullable: false,
+                    is_list: false,
+                    list_depth: 0,
+                    arguments: vec![],
+                    max_length: None,
+                    directives: vec![],
+                    default_value: None,
+                    deprecation: None,
+                },
+                ParsedField {
+                    graphql_type: String::new(),
+                    name: "title".to_string(),
+                    field_type: FieldType::Scalar("String".to_string()),
+                    description: None,
+                    is_nullable: false,
+                    is_list: false,
+                    list_depth: 0,
+                    arguments: vec![],
+                    max_length: None,
+                    directives: vec![],
+                    default_value: None,
+                    deprecation: None,
+                },
+                ParsedField {
+                    graphql_type: String::new(),
+                    name: "content".to_string(),
+                    field_type: FieldType::Scalar("String".to_string()),
+                    description: None,
+                    is_nullable: false,
+                    is_list: false,
+                    list_depth: 0,
+                    arguments: vec![],
+                    max_length: None,
+                    directives: vec![],
+                    default_value: None,
+                    deprecation: None,
+                },
+                ParsedField {
+                    graphql_type: String::new(),
+                    name: "authorId".to_string(),
+                    field_type: FieldType::Scalar("ID".to_string()),
+                    description: None,
+                    is_nullable: false,
+                    is_list: false,
+                    list_depth: 0,
+                    arguments: vec![],
+                    max_length: None,
+                    directives: vec![],
+                    default_value: None,
+                    deprecation: None,
+                },
+                ParsedField {
+                    graphql_type: String::new(),
+                    name: "published".to_string(),
+                    field_type: FieldType::Scalar("Boolean".to_string()),
+                    description: None,
+                    is_nullable: false,
+                    is_list: false,
+                    list_depth: 0,
+                    arguments: vec![],
+                    max_length: None,
+                    directives: vec![],
+                    default_value: None,
+                    deprecation: None,
+                },
+                ParsedField {
+                    graphql_type: String::new(),
+                    name: "tags".to_string(),
+                    field_type: FieldType::Scalar("String".to_string()),
+                    description: None,
+                    is_nullable: false,
+                    is_list: true,
+                    list_depth: 1,
+                    arguments: vec![],
+                    max_length: None,
+                    directives: vec![],
+                    default_value: None,
+                    deprecation: None,
+                },
+            ],
+            description: Some("Blog post".to_string()),
+            interfaces: vec![],
+            primary_key: None,
+        },
+    );
+
+    // Status enum
+    enums.insert(
+        "PostStatus".to_string(),
+        ParsedEnum {
+            name: "PostStatus".to_string(),
+            values: vec![
+                "DRAFT".to_string(),
+                "PUBLISHED".to_string(),
+                "ARCHIVED".to_string(),
+            ],
+            description: Some("Post publication status".to_string()),
+            discriminants: HashMap::new(),
+            deprecations: HashMap::new(),
+        },
+    );
+
+    ParsedSchema {
+        types,
+        enums,
+        scalars: vec![],
+        query_root: None,
+        mutation_root: None,
+        subscription_root: None,
+        input_types: HashMap::new(),
+    }
+}
+
+/// Get the up migration SQL, panicking for Rust migrations
+fn up_sql(migration: &graphql_codegen_rust::generator::MigrationFile) -> &str {
+    match migration {
+        graphql_codegen_rust::generator::MigrationFile::Sql { up_sql, .. } => up_sql,
+        graphql_codegen_rust::generator::MigrationFile::Rust { name, .. } => {
+            panic!("Expected SQL migration, got Rust migration '{}'", name)
+        }
+    }
+}
+
+/// Parse the generated Diesel code to ensure it's valid Rust syntax
+fn validate_generated_diesel_code(schema_path: &std::path::Path, entity_path: &std::path::Path) {
+    // Read and parse the schema file
+    let schema_content = std::fs::read_to_string(schema_path).expect("Failed to read schema file");
+    match syn::parse_file(&schema_content) {
+        Ok(_) => println!("✓ Schema file parses successfully"),
+        Err(e) => panic!("Schema file failed to parse: {}", e),
+    }
+
+    // Read and parse the entity file
+    let entity_content = std::fs::read_to_string(entity_path).expect("Failed to read entity file");
+    match syn::parse_file(&entity_content) {
+        Ok(_) => println!("✓ Entity file parses successfully"),
+        Err(e) => panic!("Entity file failed to parse: {}", e),
+    }
+}
+
+/// Parse the generated Sea-ORM code to ensure it's valid Rust syntax
+fn validate_generated_sea_orm_code(mod_path: &std::path::Path, entity_path: &std::path::Path) {
+    // Read and parse the mod.rs file
+    let mod_content = std::fs::read_to_string(mod_path).expect("Failed to read mod.rs file");
+    match syn::parse_file(&mod_content) {
+        Ok(_) => println!("✓ Mod file parses successfully"),
+        Err(e) => panic!("Mod file failed to parse: {}", e),
+    }
+
+    // Read and parse the entity file
+    let entity_content = std::fs::read_to_string(entity_path).expect("Failed to read entity file");
+    match syn::parse_file(&entity_content) {
+        Ok(_) => println!("✓ Entity file parses successfully"),
+        Err(e) => panic!("Entity file failed to parse: {}", e),
+    }
+}
+
+/// Answer a single request with the introspection fixture, or HTTP 400 when
+/// `accept` rejects it. The server task returns the raw request.
+async fn serve_introspection_fixture(
+    accept: fn(&str) -> bool,
+) -> (std::net::SocketAddr, tokio::task::JoinHandle<String>) {
+    let fixture = include_str!("fixtures/introspection.json");
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let request = read_http_request(&mut socket).await;
+        let (status, body) = if accept(&request) {
+            ("200 OK", fixture)
+        } else {
+            ("400 Bad Request", r#"{"errors":[{"message":"rejected"}]}"#)
+        };
+        write_json_response(&mut socket, status, body).await;
+        request
+    });
+    (addr, server)
+}
+
+/// Read one HTTP request, headers and body, from the socket
+async fn read_http_request(socket: &mut tokio::net::TcpStream) -> String {
+    use tokio::io::AsyncReadExt;
+
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        let n = socket.read(&mut buf).await.unwrap();
+        request.extend_from_slice(&buf[..n]);
+        let text = String::from_utf8_lossy(&request).to_lowercase();
+        if let Some(end) = text.find("\r\n\r\n") {
+            let length: usize = text
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map(|v| v.trim().parse().unwrap())
+                .unwrap_or(0);
+            if request.len() >= end + 4 + length {
+                break;
+            }
+        }
+        if n == 0 {
+            break;
+        }
+    }
+    String::from_utf8_lossy(&request).into_owned()
+}
+
+/// Answer with a JSON body and close the connection
+async fn write_json_response(socket: &mut tokio::net::TcpStream, status: &str, body: &str) {
+    use tokio::io::AsyncWriteExt;
+
+    let response = format!(
+        "HTTP/1.1 {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    socket.write_all(response.as_bytes()).await.unwrap();
 }
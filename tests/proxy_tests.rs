@@ -0,0 +1,88 @@
+//! Proxy tests run in their own binary so the proxy environment variables
+//! don't reach the introspection requests of other tests.
+
+use std::collections::HashMap;
+
+use graphql_codegen_rust::introspection::Introspector;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Test that introspection honors HTTP_PROXY and NO_PROXY
+#[tokio::test]
+async fn test_introspection_uses_proxy_from_environment() {
+    let fixture = include_str!("fixtures/introspection.json");
+
+    // A forward proxy sees the absolute target URL in the request line
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let proxy = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = stream.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request);
+            if let Some(end) = text.find("\r\n\r\n") {
+                let length = text[..end]
+                    .lines()
+                    .find_map(|line| {
+                        line.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if request.len() >= end + 4 + length {
+                    break;
+                }
+            }
+        }
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            fixture.len(),
+            fixture
+        );
+        stream.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8_lossy(&request).into_owned()
+    });
+
+    // SAFETY: this test binary has no other tests reading the environment
+    unsafe {
+        std::env::set_var("HTTP_PROXY", format!("http://{}", addr));
+        std::env::set_var("NO_PROXY", "bypassed.invalid");
+    }
+
+    // The target host doesn't resolve, so only the proxy can answer
+    let (schema, _) = Introspector::new()
+        .introspect_schema("http://graphql.invalid/graphql", &HashMap::new())
+        .await
+        .expect("request should go through the proxy");
+    assert!(
+        schema
+            .types
+            .iter()
+            .any(|t| t.name.as_deref() == Some("Country"))
+    );
+    let request = proxy.await.unwrap();
+    assert!(
+        request.starts_with("POST http://graphql.invalid/graphql HTTP/1.1"),
+        "{}",
+        request
+    );
+
+    // Hosts listed in NO_PROXY are contacted directly
+    let result = Introspector::new()
+        .introspect_schema("http://bypassed.invalid/graphql", &HashMap::new())
+        .await;
+    let err = result.expect_err("bypassed host doesn't resolve");
+    assert!(
+        err.downcast_ref::<reqwest::Error>()
+            .is_some_and(|e| e.is_connect()),
+        "{:?}",
+        err
+    );
+
+    unsafe {
+        std::env::remove_var("HTTP_PROXY");
+        std::env::remove_var("NO_PROXY");
+    }
+}